@@ -1,6 +1,12 @@
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    io::{self, IsTerminal},
+    num::NonZeroUsize,
+    path::PathBuf,
+    time::Duration,
+};
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 
 use color_eyre::eyre::{self, WrapErr};
 
@@ -8,43 +14,91 @@ use crate::{
     Repo,
     commands::{
         cd::CdCommand,
-        create::CreateCommand,
+        config::ConfigListCommand,
+        create::{CreateCommand, apply_name_template, issue_branch, last_created},
+        describe::DescribeCommand,
+        exec::ExecCommand,
         interactive,
         list::ListCommand,
-        merge_pr_github::MergePrGithubCommand,
+        merge_pr_github::{MergePrGithubCommand, MergeStrategy},
         open_editor::OpenEditorCommand,
-        pr_github::{PrGithubCommand, PrGithubOptions},
-        rm::RemoveCommand,
+        pr_github::{
+            CommandRunner, PrGithubCommand, PrGithubOptions, SystemCommandRunner,
+            VerboseCommandRunner,
+        },
+        pr_gitlab::{PrGitlabCommand, PrGitlabOptions},
+        prune::PruneCommand,
+        rename::RenameCommand,
+        rename_branch::RenameBranchCommand,
+        repair::RepairCommand,
+        rm::{CommitAge, RemoveCommand, confirm_removal_by_age, find_worktree_by_commit_age},
+        version::VersionCommand,
     },
+    error::{CommandExited, error_json},
 };
 
 #[derive(Parser, Debug)]
 #[command(name = "rsworktree", version, about = "Manage Git worktrees more easily", long_about = None)]
 pub struct Cli {
+    /// Print errors as a JSON object (`{"error": ..., "kind": ...}`) on stderr
+    #[arg(long = "json-errors", global = true)]
+    json_errors: bool,
+    /// When to color output: `auto` colors terminals only, like git
+    #[arg(long, value_enum, global = true, value_name = "when", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Create a worktree under the repo-local `.rsworktree` directory.
     Create(CreateArgs),
     /// List worktrees managed in `.rsworktree`.
-    Ls,
+    Ls(LsArgs),
     /// Open a shell in the given worktree.
     Cd(CdArgs),
+    /// Show the branch, tracking, HEAD commit and working tree state of a worktree.
+    Describe(DescribeArgs),
+    /// Run a command in every worktree, one after another.
+    Exec(ExecArgs),
     /// Interactively browse and open worktrees.
-    #[command(alias = "i")]
-    Interactive,
+    #[command(visible_aliases = ["i", "tui"])]
+    Interactive(InteractiveArgs),
     /// Worktree scoped commands.
     #[command(subcommand)]
     Worktree(WorktreeCommands),
     /// Remove a worktree tracked in `.rsworktree`.
     Rm(RmArgs),
+    /// Prune worktrees whose directory is gone, and optionally merged worktrees or stale branches.
+    Prune(PruneArgs),
+    /// Rename a worktree, moving its directory and renaming the branch named after it.
+    Rename(RenameArgs),
+    /// Rename the branch checked out in a worktree, keeping the worktree directory.
+    RenameBranch(RenameBranchArgs),
+    /// Repair worktree links broken by moving the repository or worktree directories.
+    Repair(RepairArgs),
     /// Create a GitHub pull request for the worktree's branch using the GitHub CLI.
     PrGithub(PrGithubArgs),
+    /// Create a GitLab merge request for the worktree's branch using the GitLab CLI.
+    PrGitlab(PrGitlabArgs),
     /// Merge the GitHub pull request for the current or named worktree.
+    #[command(visible_alias = "merge")]
     MergePrGithub(MergePrGithubArgs),
+    /// Inspect rsworktree configuration.
+    #[command(subcommand)]
+    Config(ConfigCommands),
+    /// Print the rsworktree version, optionally with library and tool versions for bug reports.
+    Version(VersionArgs),
+    /// Print a shell completion script, e.g. `source <(rsworktree completions bash)`.
+    Completions(CompletionsArgs),
 }
 
 #[derive(Subcommand, Debug)]
@@ -53,31 +107,238 @@ enum WorktreeCommands {
     OpenEditor(OpenEditorArgs),
 }
 
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    /// Print the effective configuration as JSON, with the source of each value.
+    List,
+}
+
 #[derive(Parser, Debug)]
 struct CreateArgs {
-    /// Name of the worktree (also used as the branch name)
-    name: String,
+    /// Name of the worktree (also the branch name unless `--branch` is given)
+    #[arg(required_unless_present = "from_issue")]
+    name: Option<String>,
+    /// Create (or reuse) the branch linked to this GitHub issue via `gh issue develop`
+    #[arg(long, value_name = "number", conflicts_with = "checkout")]
+    from_issue: Option<u64>,
+    /// Check the worktree out on this branch instead of one named after the worktree
+    #[arg(long, value_name = "branch", conflicts_with = "from_issue")]
+    branch: Option<String>,
     /// Branch to base the new worktree branch on
     #[arg(long)]
     base: Option<String>,
+    /// Stack on this worktree: branch from its current branch and record it as the parent
+    #[arg(long, value_name = "worktree", conflicts_with_all = ["base", "detach", "checkout_existing_remote", "from_issue"])]
+    on: Option<String>,
+    /// Check the worktree out detached at this ref, leaving the branch tip intact
+    #[arg(long, value_name = "ref")]
+    checkout: Option<String>,
+    /// Replace a leftover directory at the worktree path that is not a registered worktree
+    #[arg(long)]
+    force: bool,
+    /// Check the worktree out detached at `--base` (or `create.base_default`, then HEAD) without creating a branch
+    #[arg(long, conflicts_with_all = ["branch", "checkout", "checkout_existing_remote", "from_issue"])]
+    detach: bool,
+    /// Branch from and track `<remote>/<name>` when it exists and no local branch does
+    #[arg(long, visible_alias = "track", conflicts_with_all = ["base", "from_issue"])]
+    checkout_existing_remote: bool,
+    /// Only print the name of the branch checked out in the worktree, without entering it
+    #[arg(long, conflicts_with = "detach")]
+    print_branch: bool,
+    /// Do not copy the `create.copy_untracked` paths into the new worktree
+    #[arg(long)]
+    no_copy: bool,
+    /// Copy the files of this scaffolding repository (URL or path) into the new worktree
+    #[arg(long, value_name = "url-or-path")]
+    template_repo: Option<String>,
 }
 
 #[derive(Parser, Debug)]
-struct CdArgs {
-    /// Name of the worktree to enter
+struct DescribeArgs {
+    /// Name of the worktree to describe
     name: String,
+    /// Print the details as a JSON object
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Parser, Debug)]
+struct ExecArgs {
+    /// Stop at the first worktree where the command fails
+    #[arg(long)]
+    fail_fast: bool,
+    /// Run the command in up to this many worktrees at once, printing each one's output as a block
+    #[arg(long, value_name = "N", default_value = "1")]
+    max_parallel: NonZeroUsize,
+    /// Print one JSON object per worktree with its exit code; the command's output goes to stderr
+    #[arg(long)]
+    json_lines: bool,
+    /// Command to run in each worktree, after `--`
+    #[arg(last = true, required = true, value_name = "command")]
+    command: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+struct InteractiveArgs {
+    /// Initial ordering of the worktree list (press `s` in the TUI to cycle)
+    #[arg(long, value_enum, default_value_t = interactive::SortKey::Name)]
+    sort: interactive::SortKey,
+    /// Reverse the list order (press `r` in the TUI to toggle)
+    #[arg(long)]
+    reverse: bool,
+    /// Count commits ahead/behind the upstream along first parents only
+    #[arg(long = "first-parent")]
+    first_parent: bool,
+    /// Stop counting ahead/behind commits after this many (requires `--first-parent`)
+    #[arg(long, value_name = "n", requires = "first_parent")]
+    depth: Option<usize>,
+    /// Only print summaries from the PR/merge actions run after the TUI exits
+    #[arg(long)]
+    quiet: bool,
+    /// Fetch all remotes before listing branches for the Create dialog
+    #[arg(long)]
+    fetch: bool,
+}
+
+#[derive(Parser, Debug)]
+struct LsArgs {
+    /// Only list worktrees directly under `.rsworktree`
+    #[arg(long = "no-recurse")]
+    no_recurse: bool,
+    /// List local and remote branches that do not have a worktree yet
+    #[arg(long, conflicts_with = "no_recurse")]
+    candidates: bool,
+    /// Only list worktrees checked out at this branch
+    #[arg(long, value_name = "name", conflicts_with = "candidates")]
+    branch: Option<String>,
+    /// Print absolute worktree paths, one per line
+    #[arg(long, conflicts_with = "candidates")]
+    absolute: bool,
+    /// Show how far each worktree's branch has diverged from the default branch
+    #[arg(long, conflicts_with_all = ["candidates", "absolute"])]
+    since_base: bool,
+    /// Annotate worktrees whose branch has an open GitHub pull request (requires `gh`)
+    #[arg(long, conflicts_with_all = ["candidates", "absolute"])]
+    with_pr: bool,
+    /// Print the worktrees as a JSON array of `name`, `path`, `branch` and `head`, plus
+    /// `since_base` with `--since-base`
+    #[arg(long, conflicts_with_all = ["candidates", "absolute", "with_pr"])]
+    json: bool,
+    /// With `--json`, print an object with `root`, `default_branch` and `worktrees` instead
+    #[arg(long, requires = "json")]
+    json_object: bool,
+    /// Show each worktree's branch and whether it is clean, dirty or conflicted
+    #[arg(long, conflicts_with_all = ["candidates", "absolute", "json"])]
+    status: bool,
+}
+
+#[derive(Parser, Debug)]
+struct CdArgs {
+    /// Name of the worktree to enter; without one, the current worktree, or a picker in a terminal
+    name: Option<String>,
+    /// Enter the worktree most recently created by `rsworktree`
+    #[arg(long, conflicts_with_all = ["name", "create"])]
+    last: bool,
     /// Only print the resolved worktree path
     #[arg(long)]
     print: bool,
+    /// Only print `cd <path>`, shell-quoted, for a shell function to `eval`
+    #[arg(long, conflicts_with_all = ["print", "tmux"])]
+    emit_cd: bool,
+    /// Open the worktree in a new tmux window instead of a subshell
+    #[arg(long, conflicts_with = "print")]
+    tmux: bool,
+    /// Create the worktree first when it does not exist
+    #[arg(long, requires = "name")]
+    create: bool,
+    /// Branch to base the worktree on when `--create` creates it
+    #[arg(long, requires = "create")]
+    base: Option<String>,
+    /// Run this command in the worktree instead of an interactive shell, exiting with its status
+    #[arg(last = true, value_name = "command", conflicts_with_all = ["print", "emit_cd", "tmux"])]
+    command: Vec<String>,
 }
 
 #[derive(Parser, Debug)]
 struct RmArgs {
-    /// Name of the worktree to remove
-    name: String,
-    /// Force removal even if the worktree has uncommitted changes
+    /// Names of the worktrees to remove
+    #[arg(value_name = "name", required_unless_present_any = ["oldest", "newest", "all"])]
+    names: Vec<String>,
+    /// Remove every worktree under `.rsworktree`
+    #[arg(long, conflicts_with_all = ["names", "oldest", "newest"])]
+    all: bool,
+    /// Remove the worktree whose last commit is the oldest
+    #[arg(long, conflicts_with_all = ["names", "newest"])]
+    oldest: bool,
+    /// Remove the worktree whose last commit is the newest
+    #[arg(long, conflicts_with = "names")]
+    newest: bool,
+    /// Remove without asking for confirmation
+    #[arg(short = 'y', long)]
+    yes: bool,
+    /// Force removal even if the worktree has uncommitted changes or is the current directory
     #[arg(long)]
     force: bool,
+    /// Also offer to delete local branches whose upstream is gone and that have no worktree
+    #[arg(long)]
+    prune_branches: bool,
+    /// Commit uncommitted changes to the worktree's branch before removing it
+    #[arg(long)]
+    wip: bool,
+    /// Unregister the worktree but keep its files as a plain directory
+    #[arg(long = "keep-files")]
+    keep_files: bool,
+    /// Print one JSON object per worktree instead of the usual messages
+    #[arg(long, conflicts_with_all = ["oldest", "newest"])]
+    json_lines: bool,
+}
+
+#[derive(Parser, Debug)]
+struct PruneArgs {
+    /// Also delete local branches without a worktree whose last commit is older than this (e.g. `30d`, `12h`)
+    #[arg(long, value_name = "duration", value_parser = parse_expire)]
+    expire: Option<Duration>,
+    /// Also expire branches not merged into the default branch or their upstream
+    #[arg(long, requires = "expire")]
+    include_unmerged: bool,
+    /// Delete expired branches without asking for confirmation
+    #[arg(long, requires = "expire")]
+    yes: bool,
+    /// Print one JSON object per pruned worktree or deleted branch as it completes
+    #[arg(long)]
+    json_lines: bool,
+    /// Also remove clean worktrees whose branch is fully merged into the default branch
+    #[arg(long)]
+    merged: bool,
+    /// Branch to check `--merged` against instead of the default branch
+    #[arg(long, value_name = "branch", requires = "merged")]
+    into: Option<String>,
+    /// With `--merged`, only print which worktrees would be removed
+    #[arg(long, requires = "merged")]
+    dry_run: bool,
+}
+
+#[derive(Parser, Debug)]
+struct RepairArgs {
+    /// Worktree paths to repair; defaults to every worktree under the worktrees directory
+    #[arg(value_name = "path")]
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+struct RenameArgs {
+    /// Current name of the worktree
+    old: String,
+    /// New name for the worktree (and its branch, when named after the worktree)
+    new: String,
+}
+
+#[derive(Parser, Debug)]
+struct RenameBranchArgs {
+    /// Name of the worktree whose branch to rename
+    name: String,
+    /// New name for the branch
+    new_branch: String,
 }
 
 #[derive(Parser, Debug)]
@@ -88,8 +349,21 @@ struct OpenEditorArgs {
     /// Open a worktree by absolute path instead of managed name
     #[arg(long, value_name = "path", conflicts_with = "name")]
     path: Option<PathBuf>,
+    /// Create the worktree first when the name does not resolve
+    #[arg(long, conflicts_with = "path")]
+    create: bool,
+    /// Branch to base the worktree on when `--create` creates it
+    #[arg(long, requires = "create")]
+    base: Option<String>,
+    /// JSON array of extra editor arguments, passed after the configured ones
+    #[arg(long = "editor-args", value_name = "json", value_parser = parse_editor_args)]
+    editor_args: Option<EditorArgs>,
 }
 
+/// `--editor-args` parsed from its JSON array form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct EditorArgs(Vec<String>);
+
 #[derive(Parser, Debug)]
 struct PrGithubArgs {
     /// Name of the worktree to prepare a PR from (defaults to the current worktree)
@@ -97,6 +371,9 @@ struct PrGithubArgs {
     /// Skip pushing the branch before creating the PR
     #[arg(long = "no-push")]
     no_push: bool,
+    /// Skip pre-push hooks by passing `--no-verify` to `git push`
+    #[arg(long = "no-verify", conflicts_with = "no_push")]
+    no_verify: bool,
     /// Mark the PR as a draft
     #[arg(long)]
     draft: bool,
@@ -106,13 +383,90 @@ struct PrGithubArgs {
     /// Open the PR creation flow in the browser
     #[arg(long)]
     web: bool,
-    /// Remote to push the branch to before creating the PR
-    #[arg(long, default_value = "origin")]
-    remote: String,
+    /// Remote to push the branch to before creating the PR; repeat to push to several
+    #[arg(long = "remote", value_name = "remote", default_value = "origin")]
+    remotes: Vec<String>,
     /// Request reviews from the given GitHub handles
     #[arg(long = "reviewer", value_name = "login")]
     reviewers: Vec<String>,
-    /// Additional arguments passed directly to `gh pr create`
+    /// Branch to merge the PR into, e.g. the branch this one is stacked on
+    #[arg(long, value_name = "branch")]
+    base: Option<String>,
+    /// Add the PR to this milestone
+    #[arg(long, value_name = "name")]
+    milestone: Option<String>,
+    /// Add the PR to this project; repeat for several
+    #[arg(long = "project", value_name = "name")]
+    projects: Vec<String>,
+    /// Open the PR against another repository, e.g. the upstream of a fork
+    #[arg(long = "repo-slug", value_name = "owner/name", value_parser = parse_repo_slug)]
+    repo_slug: Option<String>,
+    /// Echo the raw output of every `git`/`gh` command to stderr
+    #[arg(long = "verbose-git")]
+    verbose_git: bool,
+    /// If the branch already has an open PR, push to it and apply metadata with `gh pr edit`
+    #[arg(long)]
+    update: bool,
+    /// Print the `git push` and `gh pr create` commands without running them
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+    /// Kill any `git`/`gh` command that runs longer than this many seconds
+    #[arg(long, value_name = "seconds")]
+    timeout: Option<u64>,
+    /// Additional arguments passed directly to `gh pr create` (or `gh pr edit` with `--update`)
+    #[arg(last = true, value_name = "ARG")]
+    extra: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+struct CompletionsArgs {
+    /// Shell to generate the completion script for
+    #[arg(value_enum)]
+    shell: clap_complete::Shell,
+}
+
+#[derive(Parser, Debug)]
+struct VersionArgs {
+    /// Also print the libgit2, `git2` crate, `git` and `gh` versions
+    #[arg(long, visible_alias = "build-info")]
+    verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+struct PrGitlabArgs {
+    /// Name of the worktree to prepare an MR from (defaults to the current worktree)
+    name: Option<String>,
+    /// Skip pushing the branch before creating the MR
+    #[arg(long = "no-push")]
+    no_push: bool,
+    /// Skip pre-push hooks by passing `--no-verify` to `git push`
+    #[arg(long = "no-verify", conflicts_with = "no_push")]
+    no_verify: bool,
+    /// Mark the MR as a draft
+    #[arg(long)]
+    draft: bool,
+    /// Prefill the MR title and description from commits
+    #[arg(long)]
+    fill: bool,
+    /// Open the MR creation flow in the browser
+    #[arg(long)]
+    web: bool,
+    /// Remote to push the branch to before creating the MR; repeat to push to several
+    #[arg(long = "remote", value_name = "remote", default_value = "origin")]
+    remotes: Vec<String>,
+    /// Request reviews from the given GitLab usernames
+    #[arg(long = "reviewer", value_name = "username")]
+    reviewers: Vec<String>,
+    /// Echo the raw output of every `git`/`glab` command to stderr
+    #[arg(long = "verbose-git")]
+    verbose_git: bool,
+    /// Print the `git push` and `glab mr create` commands without running them
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+    /// Kill any `git`/`glab` command that runs longer than this many seconds
+    #[arg(long, value_name = "seconds")]
+    timeout: Option<u64>,
+    /// Additional arguments passed directly to `glab mr create`
     #[arg(last = true, value_name = "ARG")]
     extra: Vec<String>,
 }
@@ -122,39 +476,279 @@ struct MergePrGithubArgs {
     /// Name of the worktree to merge the PR for (defaults to the current worktree)
     name: Option<String>,
     /// Remove the remote branch after merging
-    #[arg(long = "remove")]
+    #[arg(long = "remove", visible_alias = "rm-remote", conflicts_with = "auto")]
     remove_remote: bool,
+    /// Keep the local branch instead of deleting it after merging
+    #[arg(long = "keep-local")]
+    keep_local: bool,
+    /// Squash the PR's commits into one (`gh pr merge --squash`) instead of a merge commit
+    #[arg(long, conflicts_with = "rebase")]
+    squash: bool,
+    /// Rebase the PR's commits onto the base branch (`gh pr merge --rebase`)
+    #[arg(long)]
+    rebase: bool,
+    /// Enable auto-merge so GitHub merges the PR once required checks pass
+    #[arg(long)]
+    auto: bool,
+    /// With --auto, delete the local branch now instead of leaving cleanup for later
+    #[arg(long = "cleanup-now", requires = "auto")]
+    cleanup_now: bool,
+    /// Echo the raw output of every `git`/`gh` command to stderr
+    #[arg(long = "verbose-git")]
+    verbose_git: bool,
+    /// Kill any `git`/`gh` command that runs longer than this many seconds
+    #[arg(long, value_name = "seconds")]
+    timeout: Option<u64>,
+}
+
+impl MergePrGithubArgs {
+    fn strategy(&self) -> MergeStrategy {
+        if self.squash {
+            MergeStrategy::Squash
+        } else if self.rebase {
+            MergeStrategy::Rebase
+        } else {
+            MergeStrategy::Merge
+        }
+    }
+}
+
+impl Cli {
+    /// Whether stdout or stderr carries JSON meant for other programs.
+    fn emits_json(&self) -> bool {
+        self.json_errors
+            || matches!(
+                self.command,
+                Commands::Describe(DescribeArgs { json: true, .. })
+                    | Commands::Ls(LsArgs { json: true, .. })
+                    | Commands::Config(ConfigCommands::List)
+                    | Commands::Prune(PruneArgs {
+                        json_lines: true,
+                        ..
+                    })
+                    | Commands::Exec(ExecArgs {
+                        json_lines: true,
+                        ..
+                    })
+                    | Commands::Rm(RmArgs {
+                        json_lines: true,
+                        ..
+                    })
+            )
+    }
+
+    /// Forced color setting, or `None` to detect per stream.
+    fn color_override(&self) -> Option<bool> {
+        if self.emits_json() {
+            // Keep ANSI escapes out of machine-readable output even when colors are forced.
+            return Some(false);
+        }
+        match self.color {
+            ColorChoice::Auto => None,
+            ColorChoice::Always => Some(true),
+            ColorChoice::Never => Some(false),
+        }
+    }
 }
 
 pub fn run() -> color_eyre::Result<()> {
     let cli = Cli::parse();
+    let json_errors = cli.json_errors;
+    if let Some(enabled) = cli.color_override() {
+        owo_colors::set_override(enabled);
+    }
+
+    match execute(cli) {
+        // The command already reported its own failure; only pass its exit code on.
+        Err(err) if err.downcast_ref::<CommandExited>().is_some() => {
+            let code = err
+                .downcast_ref::<CommandExited>()
+                .map_or(1, |exit| exit.code);
+            std::process::exit(code);
+        }
+        Err(err) if json_errors => {
+            eprintln!("{}", error_json(&err));
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+fn execute(cli: Cli) -> color_eyre::Result<()> {
+    // Version reports are most useful when something is wrong, so they must not need a repo.
+    if let Commands::Version(args) = &cli.command {
+        return VersionCommand::new().with_verbose(args.verbose).execute();
+    }
+    if let Commands::Completions(args) = &cli.command {
+        let mut command = Cli::command();
+        let name = command.get_name().to_owned();
+        clap_complete::generate(args.shell, &mut command, name, &mut io::stdout());
+        return Ok(());
+    }
+
     let repo = Repo::discover()?;
 
     match cli.command {
         Commands::Create(args) => {
-            let command = CreateCommand::new(args.name, args.base);
+            let (name, base) = match args.from_issue {
+                Some(issue) => {
+                    let mut runner = SystemCommandRunner::default();
+                    let branch = issue_branch(
+                        &mut runner,
+                        &repo,
+                        issue,
+                        args.name.as_deref(),
+                        args.base.as_deref(),
+                    )?;
+                    (branch, None)
+                }
+                None => {
+                    let name = args
+                        .name
+                        .expect("clap requires a name without --from-issue");
+                    (apply_name_template(&repo, &name)?, args.base)
+                }
+            };
+            let command = CreateCommand::new(name, base)
+                .with_branch(args.branch)
+                .with_on(args.on)
+                .with_checkout(args.checkout)
+                .with_force(args.force)
+                .with_detach(args.detach)
+                .with_checkout_existing_remote(args.checkout_existing_remote)
+                .with_print_branch(args.print_branch)
+                .with_no_copy(args.no_copy)
+                .with_template_repo(args.template_repo);
             command.execute(&repo)?;
         }
-        Commands::Ls => {
-            let command = ListCommand;
+        Commands::Ls(args) => {
+            let command = ListCommand::default()
+                .with_recurse(!args.no_recurse)
+                .with_candidates(args.candidates)
+                .with_branch(args.branch)
+                .with_absolute(args.absolute)
+                .with_since_base(args.since_base)
+                .with_pr(args.with_pr)
+                .with_json(args.json)
+                .with_json_object(args.json_object)
+                .with_status(args.status);
             command.execute(&repo)?;
         }
         Commands::Cd(args) => {
-            let command = CdCommand::new(args.name, args.print);
+            let name = match args.name {
+                Some(name) => name,
+                None if args.last => last_created(&repo)?.ok_or_else(|| {
+                    eyre::eyre!(
+                        "no worktree has been created with `rsworktree create` yet; pass a name instead of `--last`"
+                    )
+                })?,
+                // Outside a worktree the empty name makes `cd` offer a picker.
+                None => match cwd_worktree(&repo)? {
+                    CwdWorktree::Named(name) => name,
+                    CwdWorktree::WorktreesRoot | CwdWorktree::Outside { .. } => String::new(),
+                },
+            };
+            let mut command = CdCommand::new(name, args.print)
+                .with_interactive(io::stdin().is_terminal() && io::stderr().is_terminal())
+                .with_emit_cd(args.emit_cd)
+                .with_tmux(args.tmux)
+                .with_create_missing(args.create, args.base)
+                .with_command(args.command);
             command.execute(&repo)?;
         }
-        Commands::Interactive => {
-            interactive::run(&repo)?;
+        Commands::Describe(args) => {
+            DescribeCommand::new(args.name)
+                .with_json(args.json)
+                .execute(&repo)?;
+        }
+        Commands::Exec(args) => {
+            ExecCommand::new(args.command)
+                .with_fail_fast(args.fail_fast)
+                .with_max_parallel(args.max_parallel.get())
+                .with_json_lines(args.json_lines)
+                .execute(&repo)?;
+        }
+        Commands::Interactive(args) => {
+            let ahead_behind = interactive::AheadBehind {
+                first_parent: args.first_parent,
+                depth: args.depth,
+            };
+            interactive::run(
+                &repo,
+                args.sort,
+                args.reverse,
+                ahead_behind,
+                args.quiet,
+                args.fetch,
+            )?;
         }
         Commands::Worktree(command) => match command {
             WorktreeCommands::OpenEditor(args) => {
-                let command = OpenEditorCommand::new(args.name, args.path);
+                let command = OpenEditorCommand::new(args.name, args.path)
+                    .with_create_missing(args.create, args.base)
+                    .with_editor_args(args.editor_args.map(|args| args.0).unwrap_or_default());
                 command.execute(&repo)?;
             }
         },
+        Commands::Config(ConfigCommands::List) => {
+            ConfigListCommand::new().execute(&repo)?;
+        }
         Commands::Rm(args) => {
-            let command = RemoveCommand::new(args.name, args.force);
-            let _ = command.execute(&repo)?;
+            let age = if args.oldest {
+                Some(CommitAge::Oldest)
+            } else if args.newest {
+                Some(CommitAge::Newest)
+            } else {
+                None
+            };
+            let mut names = args.names;
+            if let Some(age) = age {
+                let Some(name) = find_worktree_by_commit_age(&repo, age)? else {
+                    println!("No worktrees to remove.");
+                    return Ok(());
+                };
+                if !args.yes && !confirm_removal_by_age(&name, age)? {
+                    println!("Kept worktree `{name}`.");
+                    return Ok(());
+                }
+                names.push(name);
+            }
+            let command = |name: String| {
+                RemoveCommand::new(name, args.force)
+                    .with_prompt(io::stdin().is_terminal())
+                    .with_assume_yes(args.yes || age.is_some())
+                    .with_prune_branches(args.prune_branches)
+                    .with_wip(args.wip)
+                    .with_keep_files(args.keep_files)
+                    .with_json_lines(args.json_lines)
+            };
+            if args.all {
+                command(String::new()).execute_all(&repo)?;
+            } else if names.len() == 1 && !args.json_lines {
+                let _ = command(names.remove(0)).execute(&repo)?;
+            } else {
+                command(String::new()).execute_many(&repo, &names)?;
+            }
+        }
+        Commands::Prune(args) => {
+            let command = PruneCommand::new()
+                .with_expire(args.expire)
+                .with_include_unmerged(args.include_unmerged)
+                .with_yes(args.yes)
+                .with_json_lines(args.json_lines)
+                .with_merged(args.merged)
+                .with_into(args.into)
+                .with_dry_run(args.dry_run);
+            command.execute(&repo)?;
+        }
+        Commands::Rename(args) => {
+            RenameCommand::new(args.old, args.new).execute(&repo)?;
+        }
+        Commands::RenameBranch(args) => {
+            RenameBranchCommand::new(args.name, args.new_branch).execute(&repo)?;
+        }
+        Commands::Repair(args) => {
+            RepairCommand::new(args.paths).execute(&repo)?;
         }
         Commands::PrGithub(args) => {
             let worktree_name = resolve_worktree_name(args.name, &repo, "pr-github")?;
@@ -164,26 +758,108 @@ pub fn run() -> color_eyre::Result<()> {
                 draft: args.draft,
                 fill: args.fill,
                 web: args.web,
-                remote: args.remote,
+                remotes: args.remotes,
                 reviewers: args.reviewers,
                 extra_args: args.extra,
+                base: args.base,
             };
-            let mut command = PrGithubCommand::new(options);
-            command.execute(&repo)?;
+            let system = SystemCommandRunner::default().with_timeout(command_timeout(args.timeout));
+            if args.verbose_git {
+                let runner = VerboseCommandRunner::new(system);
+                PrGithubCommand::with_runner(options, runner)
+                    .with_dry_run(args.dry_run)
+                    .with_no_verify(args.no_verify)
+                    .with_repo_slug(args.repo_slug)
+                    .with_milestone(args.milestone)
+                    .with_projects(args.projects)
+                    .with_update(args.update)
+                    .execute(&repo)?;
+            } else {
+                PrGithubCommand::with_runner(options, system)
+                    .with_dry_run(args.dry_run)
+                    .with_no_verify(args.no_verify)
+                    .with_repo_slug(args.repo_slug)
+                    .with_milestone(args.milestone)
+                    .with_projects(args.projects)
+                    .with_update(args.update)
+                    .execute(&repo)?;
+            }
+        }
+        Commands::PrGitlab(args) => {
+            let worktree_name = resolve_worktree_name(args.name, &repo, "pr-gitlab")?;
+            let options = PrGitlabOptions {
+                name: worktree_name,
+                push: !args.no_push,
+                draft: args.draft,
+                fill: args.fill,
+                web: args.web,
+                remotes: args.remotes,
+                reviewers: args.reviewers,
+                extra_args: args.extra,
+            };
+            let system = SystemCommandRunner::default().with_timeout(command_timeout(args.timeout));
+            if args.verbose_git {
+                let runner = VerboseCommandRunner::new(system);
+                PrGitlabCommand::with_runner(options, runner)
+                    .with_dry_run(args.dry_run)
+                    .with_no_verify(args.no_verify)
+                    .execute(&repo)?;
+            } else {
+                PrGitlabCommand::with_runner(options, system)
+                    .with_dry_run(args.dry_run)
+                    .with_no_verify(args.no_verify)
+                    .execute(&repo)?;
+            }
+        }
+        Commands::Version(_) => unreachable!("`version` runs before repository discovery"),
+        Commands::Completions(_) => {
+            unreachable!("`completions` runs before repository discovery")
         }
         Commands::MergePrGithub(args) => {
+            let strategy = args.strategy();
             let worktree_name = resolve_worktree_name(args.name, &repo, "merge-pr-github")?;
-            let mut command = MergePrGithubCommand::new(worktree_name);
-            if args.remove_remote {
-                command.enable_remove_remote();
+            let system = SystemCommandRunner::default().with_timeout(command_timeout(args.timeout));
+            if args.verbose_git {
+                let runner = VerboseCommandRunner::new(system);
+                let command = MergePrGithubCommand::with_runner(worktree_name, runner)
+                    .with_strategy(strategy)
+                    .with_auto(args.auto)
+                    .with_cleanup_now(args.cleanup_now);
+                merge_pr_github(command, args.remove_remote, args.keep_local, &repo)?;
+            } else {
+                let command = MergePrGithubCommand::with_runner(worktree_name, system)
+                    .with_strategy(strategy)
+                    .with_auto(args.auto)
+                    .with_cleanup_now(args.cleanup_now);
+                merge_pr_github(command, args.remove_remote, args.keep_local, &repo)?;
             }
-            command.execute(&repo)?;
         }
     }
 
     Ok(())
 }
 
+fn command_timeout(seconds: Option<u64>) -> Option<Duration> {
+    seconds
+        .filter(|seconds| *seconds > 0)
+        .map(Duration::from_secs)
+}
+
+fn merge_pr_github<R: CommandRunner>(
+    mut command: MergePrGithubCommand<R>,
+    remove_remote: bool,
+    keep_local: bool,
+    repo: &Repo,
+) -> color_eyre::Result<()> {
+    if remove_remote {
+        command.enable_remove_remote();
+    }
+    if keep_local {
+        command.disable_remove_local();
+    }
+    command.execute(repo)
+}
+
 fn resolve_worktree_name(
     name: Option<String>,
     repo: &Repo,
@@ -193,6 +869,32 @@ fn resolve_worktree_name(
         return Ok(name);
     }
 
+    match cwd_worktree(repo)? {
+        CwdWorktree::Named(name) => Ok(name),
+        CwdWorktree::Outside { worktrees_dir, cwd } => Err(eyre::eyre!(
+            "`rsworktree {}` without <name> must be run from inside `{}`. Current directory: `{}`.",
+            command_label,
+            worktrees_dir.display(),
+            cwd.display()
+        )),
+        CwdWorktree::WorktreesRoot => Err(eyre::eyre!(
+            "Run `rsworktree {}` from inside a specific worktree (e.g. `.rsworktree/<name>`).",
+            command_label
+        )),
+    }
+}
+
+/// Where the current directory sits relative to the worktrees directory.
+enum CwdWorktree {
+    Named(String),
+    WorktreesRoot,
+    Outside {
+        worktrees_dir: PathBuf,
+        cwd: PathBuf,
+    },
+}
+
+fn cwd_worktree(repo: &Repo) -> color_eyre::Result<CwdWorktree> {
     let cwd = env::current_dir().wrap_err("failed to read current directory")?;
     let canonical_cwd = cwd.canonicalize().unwrap_or(cwd);
 
@@ -202,12 +904,10 @@ fn resolve_worktree_name(
         .unwrap_or_else(|_| worktrees_dir.clone());
 
     if !canonical_cwd.starts_with(&canonical_worktrees_dir) {
-        return Err(eyre::eyre!(
-            "`rsworktree {}` without <name> must be run from inside `{}`. Current directory: `{}`.",
-            command_label,
-            worktrees_dir.display(),
-            canonical_cwd.display()
-        ));
+        return Ok(CwdWorktree::Outside {
+            worktrees_dir,
+            cwd: canonical_cwd,
+        });
     }
 
     let relative = canonical_cwd
@@ -220,13 +920,53 @@ fn resolve_worktree_name(
         .collect::<Vec<_>>();
 
     if components.is_empty() {
-        return Err(eyre::eyre!(
-            "Run `rsworktree {}` from inside a specific worktree (e.g. `.rsworktree/<name>`).",
-            command_label
-        ));
+        return Ok(CwdWorktree::WorktreesRoot);
+    }
+
+    Ok(CwdWorktree::Named(components.join("/")))
+}
+
+fn parse_editor_args(value: &str) -> Result<EditorArgs, String> {
+    serde_json::from_str(value)
+        .map(EditorArgs)
+        .map_err(|err| format!("expected a JSON array of strings like `[\"--wait\"]`: {err}"))
+}
+
+fn parse_repo_slug(value: &str) -> Result<String, String> {
+    let valid = value.split_once('/').is_some_and(|(owner, name)| {
+        let segment_ok = |segment: &str| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+        };
+        segment_ok(owner) && segment_ok(name)
+    });
+
+    if valid {
+        Ok(value.to_owned())
+    } else {
+        Err(format!("expected `owner/name`, got `{value}`"))
     }
+}
+
+fn parse_expire(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("expected a duration such as `30d`, `12h` or `2w`, got `{value}`");
+    let unit_at = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(unit_at);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let seconds_per_unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
 
-    Ok(components.join("/"))
+    Ok(Duration::from_secs(amount.saturating_mul(seconds_per_unit)))
 }
 
 #[cfg(test)]
@@ -235,14 +975,14 @@ mod tests {
     use std::{
         env, fs,
         path::{Path, PathBuf},
-        process::Command as StdCommand,
     };
 
     use clap::Parser;
     use color_eyre::eyre::{self, WrapErr};
-
     use tempfile::TempDir;
 
+    use crate::test_support::init_repo;
+
     struct DirGuard {
         original: std::path::PathBuf,
     }
@@ -271,47 +1011,10 @@ mod tests {
         }
     }
 
-    fn init_git_repo(dir: &TempDir) -> color_eyre::Result<()> {
-        run(dir, ["git", "init"])?;
-        fs::write(dir.path().join("README.md"), "test")?;
-        run(dir, ["git", "add", "README.md"])?;
-        run(
-            dir,
-            [
-                "git",
-                "-c",
-                "user.name=Test",
-                "-c",
-                "user.email=test@example.com",
-                "commit",
-                "-m",
-                "Initial commit",
-            ],
-        )?;
-        Ok(())
-    }
-
-    fn run(dir: &TempDir, cmd: impl IntoIterator<Item = &'static str>) -> color_eyre::Result<()> {
-        let mut iter = cmd.into_iter();
-        let program = iter.next().expect("command must not be empty");
-        let status = StdCommand::new(program)
-            .current_dir(dir.path())
-            .args(iter)
-            .status()
-            .wrap_err_with(|| eyre::eyre!("failed to run `{program}`"))?;
-
-        if !status.success() {
-            return Err(eyre::eyre!("`{program}` exited with status {status}"));
-        }
-
-        Ok(())
-    }
-
     #[test]
     fn resolve_worktree_name_returns_cli_argument_when_present() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
 
         let resolved = resolve_worktree_name(Some("feature/test".into()), &repo, "pr-github")?;
         assert_eq!(resolved, "feature/test");
@@ -323,20 +1026,49 @@ mod tests {
     fn parses_interactive_command_and_alias() -> color_eyre::Result<()> {
         let interactive = Cli::try_parse_from(["rsworktree", "interactive"])
             .expect("interactive subcommand should parse");
-        assert!(matches!(interactive.command, Commands::Interactive));
+        assert!(matches!(interactive.command, Commands::Interactive(_)));
 
-        let alias =
-            Cli::try_parse_from(["rsworktree", "i"]).expect("interactive alias should parse");
-        assert!(matches!(alias.command, Commands::Interactive));
+        for alias in ["i", "tui"] {
+            let cli =
+                Cli::try_parse_from(["rsworktree", alias]).expect("interactive alias should parse");
+            assert!(matches!(cli.command, Commands::Interactive(_)));
+        }
 
         Ok(())
     }
 
+    #[test]
+    fn parses_interactive_sort_flags() {
+        let cli = Cli::try_parse_from(["rsworktree", "i", "--sort", "recent", "--reverse"])
+            .expect("interactive sort flags should parse");
+        match cli.command {
+            Commands::Interactive(args) => {
+                assert_eq!(args.sort, interactive::SortKey::Recent);
+                assert!(args.reverse);
+            }
+            _ => panic!("expected Interactive command"),
+        }
+    }
+
+    #[test]
+    fn parses_interactive_first_parent_flags() {
+        let cli = Cli::try_parse_from(["rsworktree", "i", "--first-parent", "--depth", "50"])
+            .expect("interactive first-parent flags should parse");
+        match cli.command {
+            Commands::Interactive(args) => {
+                assert!(args.first_parent);
+                assert_eq!(args.depth, Some(50));
+            }
+            _ => panic!("expected Interactive command"),
+        }
+
+        assert!(Cli::try_parse_from(["rsworktree", "i", "--depth", "50"]).is_err());
+    }
+
     #[test]
     fn resolve_worktree_name_infers_from_cwd_inside_worktree() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_dir = repo.ensure_worktrees_dir()?.join("feature/nested");
         fs::create_dir_all(&worktree_dir)?;
 
@@ -350,8 +1082,7 @@ mod tests {
     #[test]
     fn resolve_worktree_name_requires_running_inside_worktree() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let _guard = DirGuard::change_to(repo.root())?;
 
         let err = resolve_worktree_name(None, &repo, "pr-github").unwrap_err();
@@ -363,8 +1094,7 @@ mod tests {
     #[test]
     fn resolve_worktree_name_rejects_rsworktree_root() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktrees_dir = repo.ensure_worktrees_dir()?;
         let _guard = DirGuard::change_to(&worktrees_dir)?;
 
@@ -377,13 +1107,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn cwd_worktree_treats_outside_and_root_as_unnamed_but_propagates_errors()
+    -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktrees_dir = repo.ensure_worktrees_dir()?;
+
+        let guard = DirGuard::change_to(repo.root())?;
+        assert!(matches!(cwd_worktree(&repo)?, CwdWorktree::Outside { .. }));
+        drop(guard);
+        let guard = DirGuard::change_to(&worktrees_dir)?;
+        assert!(matches!(cwd_worktree(&repo)?, CwdWorktree::WorktreesRoot));
+        drop(guard);
+
+        let inner_path = worktrees_dir.join("feature/test/inner");
+        git2::Repository::init(&inner_path)?;
+        let inner = Repo::discover_from(&inner_path)?;
+        let _guard = DirGuard::change_to(&inner_path)?;
+        assert!(cwd_worktree(&inner).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn parses_create_command_with_base() {
-        let cli = Cli::try_parse_from(["rsworktree", "create", "feature/test", "--base", "develop"])
-            .expect("create with base should parse");
+        let cli =
+            Cli::try_parse_from(["rsworktree", "create", "feature/test", "--base", "develop"])
+                .expect("create with base should parse");
         match cli.command {
             Commands::Create(args) => {
-                assert_eq!(args.name, "feature/test");
+                assert_eq!(args.name, Some("feature/test".into()));
                 assert_eq!(args.base, Some("develop".into()));
             }
             _ => panic!("expected Create command"),
@@ -396,26 +1150,101 @@ mod tests {
             .expect("cd with print should parse");
         match cli.command {
             Commands::Cd(args) => {
-                assert_eq!(args.name, "my-worktree");
+                assert_eq!(args.name, Some("my-worktree".into()));
                 assert!(args.print);
             }
             _ => panic!("expected Cd command"),
         }
     }
 
+    #[test]
+    fn parses_cd_command_with_tmux_flag() {
+        let cli = Cli::try_parse_from(["rsworktree", "cd", "my-worktree", "--tmux"])
+            .expect("cd with tmux should parse");
+        match cli.command {
+            Commands::Cd(args) => {
+                assert!(args.tmux);
+                assert!(!args.print);
+            }
+            _ => panic!("expected Cd command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["rsworktree", "cd", "my-worktree", "--tmux", "--print"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_pr_github_repo_slug() {
+        let cli = Cli::try_parse_from([
+            "rsworktree",
+            "pr-github",
+            "my-feature",
+            "--repo-slug",
+            "upstream-org/project.rs",
+        ])
+        .expect("pr-github with repo slug should parse");
+        match cli.command {
+            Commands::PrGithub(args) => {
+                assert_eq!(args.repo_slug.as_deref(), Some("upstream-org/project.rs"));
+            }
+            _ => panic!("expected PrGithub command"),
+        }
+
+        for invalid in [
+            "project",
+            "org/",
+            "/project",
+            "org/project/extra",
+            "org/pro ject",
+        ] {
+            assert!(
+                Cli::try_parse_from(["rsworktree", "pr-github", "--repo-slug", invalid]).is_err(),
+                "`{invalid}` should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn parses_rm_command_with_force_flag() {
         let cli = Cli::try_parse_from(["rsworktree", "rm", "old-worktree", "--force"])
             .expect("rm with force should parse");
         match cli.command {
             Commands::Rm(args) => {
-                assert_eq!(args.name, "old-worktree");
+                assert_eq!(args.names, ["old-worktree"]);
                 assert!(args.force);
             }
             _ => panic!("expected Rm command"),
         }
     }
 
+    #[test]
+    fn parses_rm_command_with_short_yes() {
+        let cli = Cli::try_parse_from(["rsworktree", "rm", "feature", "-y"])
+            .expect("rm with -y should parse");
+        match cli.command {
+            Commands::Rm(args) => assert!(args.yes),
+            _ => panic!("expected Rm command"),
+        }
+    }
+
+    #[test]
+    fn parses_rm_command_with_several_names_or_all() {
+        let cli = Cli::try_parse_from(["rsworktree", "rm", "one", "two"])
+            .expect("rm with several names should parse");
+        match cli.command {
+            Commands::Rm(args) => assert_eq!(args.names, ["one", "two"]),
+            _ => panic!("expected Rm command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["rsworktree", "rm", "--all"]).expect("rm --all should parse");
+        assert!(matches!(cli.command, Commands::Rm(args) if args.all && args.names.is_empty()));
+
+        assert!(Cli::try_parse_from(["rsworktree", "rm", "--all", "one"]).is_err());
+        assert!(Cli::try_parse_from(["rsworktree", "rm"]).is_err());
+    }
+
     #[test]
     fn parses_pr_github_with_all_flags() {
         let cli = Cli::try_parse_from([
@@ -432,6 +1261,16 @@ mod tests {
             "alice",
             "--reviewer",
             "bob",
+            "--base",
+            "feature/parent",
+            "--milestone",
+            "v1.2",
+            "--project",
+            "Roadmap",
+            "--project",
+            "Triage",
+            "--verbose-git",
+            "--dry-run",
             "--",
             "--label",
             "bug",
@@ -444,14 +1283,53 @@ mod tests {
                 assert!(args.draft);
                 assert!(args.fill);
                 assert!(args.web);
-                assert_eq!(args.remote, "upstream");
+                assert_eq!(args.remotes, vec!["upstream"]);
                 assert_eq!(args.reviewers, vec!["alice", "bob"]);
+                assert_eq!(args.base.as_deref(), Some("feature/parent"));
+                assert_eq!(args.milestone.as_deref(), Some("v1.2"));
+                assert_eq!(args.projects, vec!["Roadmap", "Triage"]);
+                assert!(args.verbose_git);
+                assert!(args.dry_run);
                 assert_eq!(args.extra, vec!["--label", "bug"]);
             }
             _ => panic!("expected PrGithub command"),
         }
     }
 
+    #[test]
+    fn parses_pr_gitlab_with_flags() {
+        let cli = Cli::try_parse_from([
+            "rsworktree",
+            "pr-gitlab",
+            "my-feature",
+            "--draft",
+            "--fill",
+            "--remote",
+            "gitlab",
+            "--reviewer",
+            "alice",
+            "--dry-run",
+            "--",
+            "--label",
+            "bug",
+        ])
+        .expect("pr-gitlab with flags should parse");
+        match cli.command {
+            Commands::PrGitlab(args) => {
+                assert_eq!(args.name, Some("my-feature".into()));
+                assert!(!args.no_push);
+                assert!(args.draft);
+                assert!(args.fill);
+                assert!(!args.web);
+                assert_eq!(args.remotes, vec!["gitlab"]);
+                assert_eq!(args.reviewers, vec!["alice"]);
+                assert!(args.dry_run);
+                assert_eq!(args.extra, vec!["--label", "bug"]);
+            }
+            _ => panic!("expected PrGitlab command"),
+        }
+    }
+
     #[test]
     fn parses_merge_pr_github_with_remove_flag() {
         let cli = Cli::try_parse_from(["rsworktree", "merge-pr-github", "feature", "--remove"])
@@ -465,6 +1343,130 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_merge_pr_github_auto_flags() {
+        let cli =
+            Cli::try_parse_from(["rsworktree", "merge", "feature", "--auto", "--cleanup-now"])
+                .expect("merge with --auto should parse");
+        match cli.command {
+            Commands::MergePrGithub(args) => {
+                assert!(args.auto);
+                assert!(args.cleanup_now);
+            }
+            _ => panic!("expected MergePrGithub command"),
+        }
+
+        assert!(Cli::try_parse_from(["rsworktree", "merge", "--cleanup-now"]).is_err());
+        assert!(Cli::try_parse_from(["rsworktree", "merge", "--auto", "--remove"]).is_err());
+    }
+
+    #[test]
+    fn parses_merge_pr_github_strategy_flags() {
+        for (flag, strategy) in [
+            (None, MergeStrategy::Merge),
+            (Some("--squash"), MergeStrategy::Squash),
+            (Some("--rebase"), MergeStrategy::Rebase),
+        ] {
+            let mut argv = vec!["rsworktree", "merge", "feature"];
+            argv.extend(flag);
+            match Cli::try_parse_from(argv)
+                .expect("merge strategy should parse")
+                .command
+            {
+                Commands::MergePrGithub(args) => assert_eq!(args.strategy(), strategy),
+                _ => panic!("expected MergePrGithub command"),
+            }
+        }
+
+        assert!(
+            Cli::try_parse_from(["rsworktree", "merge", "feature", "--squash", "--rebase"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_merge_alias_with_cleanup_flags() {
+        let cli = Cli::try_parse_from([
+            "rsworktree",
+            "merge",
+            "feature",
+            "--rm-remote",
+            "--keep-local",
+        ])
+        .expect("merge alias with cleanup flags should parse");
+        match cli.command {
+            Commands::MergePrGithub(args) => {
+                assert_eq!(args.name, Some("feature".into()));
+                assert!(args.remove_remote);
+                assert!(args.keep_local);
+            }
+            _ => panic!("expected MergePrGithub command"),
+        }
+
+        let cli = Cli::try_parse_from(["rsworktree", "merge-pr-github"])
+            .expect("merge-pr-github without flags should parse");
+        match cli.command {
+            Commands::MergePrGithub(args) => {
+                assert_eq!(args.name, None);
+                assert!(!args.remove_remote);
+                assert!(!args.keep_local);
+            }
+            _ => panic!("expected MergePrGithub command"),
+        }
+    }
+
+    #[test]
+    fn prune_into_and_dry_run_require_merged() {
+        assert!(Cli::try_parse_from(["rsworktree", "prune", "--dry-run"]).is_err());
+        assert!(Cli::try_parse_from(["rsworktree", "prune", "--into", "main"]).is_err());
+        let cli = Cli::try_parse_from([
+            "rsworktree",
+            "prune",
+            "--merged",
+            "--into",
+            "main",
+            "--dry-run",
+        ])
+        .expect("prune --merged should parse");
+        match cli.command {
+            Commands::Prune(args) => {
+                assert!(args.merged && args.dry_run);
+                assert_eq!(args.into.as_deref(), Some("main"));
+            }
+            _ => panic!("expected Prune command"),
+        }
+    }
+
+    #[test]
+    fn parses_prune_expire_durations() {
+        assert_eq!(
+            parse_expire("30d"),
+            Ok(Duration::from_secs(30 * 24 * 60 * 60))
+        );
+        assert_eq!(parse_expire("12h"), Ok(Duration::from_secs(12 * 60 * 60)));
+        assert_eq!(
+            parse_expire("2w"),
+            Ok(Duration::from_secs(14 * 24 * 60 * 60))
+        );
+        for invalid in ["", "30", "d", "30 days", "-1d"] {
+            assert!(
+                parse_expire(invalid).is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+
+        assert!(Cli::try_parse_from(["rsworktree", "prune", "--yes"]).is_err());
+        let cli = Cli::try_parse_from(["rsworktree", "prune", "--expire", "30d", "--yes"])
+            .expect("prune with expire should parse");
+        match cli.command {
+            Commands::Prune(args) => {
+                assert_eq!(args.expire, Some(Duration::from_secs(30 * 24 * 60 * 60)));
+                assert!(args.yes);
+            }
+            _ => panic!("expected Prune command"),
+        }
+    }
+
     #[test]
     fn parses_worktree_open_editor_by_name() {
         let cli = Cli::try_parse_from(["rsworktree", "worktree", "open-editor", "feature/test"])
@@ -480,9 +1482,14 @@ mod tests {
 
     #[test]
     fn parses_worktree_open_editor_by_path() {
-        let cli =
-            Cli::try_parse_from(["rsworktree", "worktree", "open-editor", "--path", "/some/path"])
-                .expect("worktree open-editor by path should parse");
+        let cli = Cli::try_parse_from([
+            "rsworktree",
+            "worktree",
+            "open-editor",
+            "--path",
+            "/some/path",
+        ])
+        .expect("worktree open-editor by path should parse");
         match cli.command {
             Commands::Worktree(WorktreeCommands::OpenEditor(args)) => {
                 assert!(args.name.is_none());
@@ -492,9 +1499,140 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_worktree_open_editor_with_create_and_base() {
+        let cli = Cli::try_parse_from([
+            "rsworktree",
+            "worktree",
+            "open-editor",
+            "feature/new",
+            "--create",
+            "--base",
+            "main",
+        ])
+        .expect("worktree open-editor with create should parse");
+        match cli.command {
+            Commands::Worktree(WorktreeCommands::OpenEditor(args)) => {
+                assert!(args.create);
+                assert_eq!(args.base, Some("main".into()));
+            }
+            _ => panic!("expected Worktree OpenEditor command"),
+        }
+
+        let err = Cli::try_parse_from([
+            "rsworktree",
+            "worktree",
+            "open-editor",
+            "feature/new",
+            "--base",
+            "main",
+        ]);
+        assert!(err.is_err(), "--base should require --create");
+    }
+
+    #[test]
+    fn parses_open_editor_json_editor_args() {
+        let cli = Cli::try_parse_from([
+            "rsworktree",
+            "worktree",
+            "open-editor",
+            "feature/test",
+            "--editor-args",
+            r#"["--profile","work","--wait"]"#,
+        ])
+        .expect("valid --editor-args should parse");
+        match cli.command {
+            Commands::Worktree(WorktreeCommands::OpenEditor(args)) => {
+                assert_eq!(
+                    args.editor_args,
+                    Some(EditorArgs(vec![
+                        "--profile".into(),
+                        "work".into(),
+                        "--wait".into()
+                    ]))
+                );
+            }
+            _ => panic!("expected Worktree OpenEditor command"),
+        }
+
+        for invalid in [r#"["--wait""#, r#"{"wait":true}"#, "[1, 2]", "wait"] {
+            let err = Cli::try_parse_from([
+                "rsworktree",
+                "worktree",
+                "open-editor",
+                "feature/test",
+                "--editor-args",
+                invalid,
+            ])
+            .expect_err("malformed --editor-args should be rejected");
+            assert!(
+                err.to_string().contains("expected a JSON array of strings"),
+                "unexpected error for `{invalid}`: {err}"
+            );
+        }
+    }
+
     #[test]
     fn parses_ls_command() {
         let cli = Cli::try_parse_from(["rsworktree", "ls"]).expect("ls should parse");
-        assert!(matches!(cli.command, Commands::Ls));
+        assert!(matches!(cli.command, Commands::Ls(_)));
+    }
+
+    #[test]
+    fn json_output_modes_are_detected() {
+        let emits_json = |args: &[&str]| {
+            Cli::try_parse_from(args)
+                .expect("arguments should parse")
+                .emits_json()
+        };
+
+        assert!(emits_json(&["rsworktree", "describe", "feature", "--json"]));
+        assert!(emits_json(&["rsworktree", "config", "list"]));
+        assert!(emits_json(&["rsworktree", "ls", "--json-errors"]));
+        assert!(!emits_json(&["rsworktree", "describe", "feature"]));
+        assert!(!emits_json(&["rsworktree", "ls"]));
+    }
+
+    #[test]
+    fn color_flag_overrides_detection_except_for_json_output() {
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from(args)
+                .expect("color flag should parse")
+                .color_override()
+        };
+
+        assert_eq!(parse(&["rsworktree", "ls"]), None);
+        assert_eq!(parse(&["rsworktree", "ls", "--color", "auto"]), None);
+        assert_eq!(
+            parse(&["rsworktree", "--color", "always", "ls"]),
+            Some(true)
+        );
+        assert_eq!(parse(&["rsworktree", "ls", "--color=never"]), Some(false));
+        assert_eq!(
+            parse(&["rsworktree", "describe", "x", "--json", "--color", "always"]),
+            Some(false)
+        );
+        assert!(Cli::try_parse_from(["rsworktree", "ls", "--color", "sometimes"]).is_err());
+    }
+
+    #[test]
+    fn parses_global_json_errors_flag() {
+        let cli = Cli::try_parse_from(["rsworktree", "cd", "feature", "--json-errors"])
+            .expect("--json-errors should be accepted after the subcommand");
+        assert!(cli.json_errors);
+
+        let cli = Cli::try_parse_from(["rsworktree", "--json-errors", "ls"])
+            .expect("--json-errors should be accepted before the subcommand");
+        assert!(cli.json_errors);
+    }
+
+    #[test]
+    fn parses_ls_no_recurse() {
+        let cli = Cli::try_parse_from(["rsworktree", "ls", "--no-recurse"])
+            .expect("ls --no-recurse should parse");
+        match cli.command {
+            Commands::Ls(args) => assert!(args.no_recurse),
+            _ => panic!("expected Ls command"),
+        }
     }
 }
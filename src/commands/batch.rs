@@ -0,0 +1,242 @@
+use std::fmt::Display;
+
+use owo_colors::{OwoColorize, Stream};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BatchOutcome {
+    Succeeded,
+    Skipped,
+    Failed,
+}
+
+impl BatchOutcome {
+    fn status(self) -> &'static str {
+        match self {
+            BatchOutcome::Succeeded => "ok",
+            BatchOutcome::Skipped => "skipped",
+            BatchOutcome::Failed => "failed",
+        }
+    }
+}
+
+/// Tallies per-item results of a batch operation and prints them as one summary line.
+#[derive(Debug)]
+pub(crate) struct BatchReporter {
+    /// Key naming each item in JSON lines, e.g. `worktree`.
+    item: &'static str,
+    /// Past-tense verb for successful items, e.g. `removed`.
+    verb: &'static str,
+    json_lines: bool,
+    succeeded: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+impl BatchReporter {
+    pub(crate) fn new(item: &'static str, verb: &'static str) -> Self {
+        Self {
+            item,
+            verb,
+            json_lines: false,
+            succeeded: 0,
+            skipped: 0,
+            failed: 0,
+        }
+    }
+
+    /// Print one JSON object per item on stdout instead of messages and a summary.
+    pub(crate) fn with_json_lines(mut self, json_lines: bool) -> Self {
+        self.json_lines = json_lines;
+        self
+    }
+
+    /// Counts `outcome` for `name` and prints `message`, on stderr for failures. In JSON-lines
+    /// mode stdout gets `{"<item>": name, "status": ...}` instead; failure messages still go to
+    /// stderr.
+    pub(crate) fn record(&mut self, name: &str, outcome: BatchOutcome, message: impl Display) {
+        self.record_event(name, outcome, message, None);
+    }
+
+    /// Like [`BatchReporter::record`] for a command run in the item; its JSON line also carries
+    /// `"exit"`, the exit code or `null` when there is none.
+    pub(crate) fn record_exit(
+        &mut self,
+        name: &str,
+        outcome: BatchOutcome,
+        exit: Option<i32>,
+        message: impl Display,
+    ) {
+        self.record_event(name, outcome, message, Some(exit));
+    }
+
+    fn record_event(
+        &mut self,
+        name: &str,
+        outcome: BatchOutcome,
+        message: impl Display,
+        exit: Option<Option<i32>>,
+    ) {
+        self.tally(outcome);
+
+        if outcome == BatchOutcome::Failed {
+            eprintln!("{message}");
+        } else if !self.json_lines {
+            println!("{message}");
+        }
+        if self.json_lines {
+            println!("{}", self.event(name, outcome, exit));
+        }
+    }
+
+    /// Counts `outcome` for an item that already reported itself; only its JSON line is printed.
+    pub(crate) fn count(&mut self, name: &str, outcome: BatchOutcome) {
+        self.tally(outcome);
+        if self.json_lines {
+            println!("{}", self.event(name, outcome, None));
+        }
+    }
+
+    fn tally(&mut self, outcome: BatchOutcome) {
+        match outcome {
+            BatchOutcome::Succeeded => self.succeeded += 1,
+            BatchOutcome::Skipped => self.skipped += 1,
+            BatchOutcome::Failed => self.failed += 1,
+        }
+    }
+
+    fn event(
+        &self,
+        name: &str,
+        outcome: BatchOutcome,
+        exit: Option<Option<i32>>,
+    ) -> serde_json::Value {
+        let mut event = serde_json::Map::new();
+        event.insert(self.item.to_owned(), name.into());
+        event.insert("status".to_owned(), outcome.status().into());
+        if let Some(exit) = exit {
+            event.insert("exit".to_owned(), exit.into());
+        }
+        event.into()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.succeeded + self.skipped + self.failed == 0
+    }
+
+    pub(crate) fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Counts such as `3 removed, 1 skipped, 1 failed`; zero skipped or failed counts are left out.
+    pub(crate) fn summary(&self) -> String {
+        self.render(|text, _| text)
+    }
+
+    /// Prints `<title>: <summary>.` with each count coloured by its outcome; silent in JSON-lines
+    /// mode.
+    pub(crate) fn print_summary(&self, title: &str) {
+        if self.json_lines {
+            return;
+        }
+        let summary = self.render(|text, outcome| {
+            format!(
+                "{}",
+                text.as_str()
+                    .if_supports_color(Stream::Stdout, |text| match outcome {
+                        BatchOutcome::Succeeded => format!("{}", text.green().bold()),
+                        BatchOutcome::Skipped => format!("{}", text.yellow().bold()),
+                        BatchOutcome::Failed => format!("{}", text.red().bold()),
+                    })
+            )
+        });
+        println!("{title}: {summary}.");
+    }
+
+    fn render(&self, paint: impl Fn(String, BatchOutcome) -> String) -> String {
+        let mut parts = vec![paint(
+            format!("{} {}", self.succeeded, self.verb),
+            BatchOutcome::Succeeded,
+        )];
+        if self.skipped > 0 {
+            parts.push(paint(
+                format!("{} skipped", self.skipped),
+                BatchOutcome::Skipped,
+            ));
+        }
+        if self.failed > 0 {
+            parts.push(paint(
+                format!("{} failed", self.failed),
+                BatchOutcome::Failed,
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reporter(outcomes: &[BatchOutcome]) -> BatchReporter {
+        let mut reporter = BatchReporter::new("worktree", "removed");
+        for outcome in outcomes {
+            reporter.record("feat/x", *outcome, "");
+        }
+        reporter
+    }
+
+    #[test]
+    fn summary_lists_every_outcome_in_order() {
+        use BatchOutcome::*;
+        let reporter = reporter(&[Succeeded, Failed, Succeeded, Skipped, Succeeded]);
+
+        assert_eq!(reporter.summary(), "3 removed, 1 skipped, 1 failed");
+        assert_eq!(reporter.failed(), 1);
+    }
+
+    #[test]
+    fn summary_omits_zero_skipped_and_failed_counts() {
+        use BatchOutcome::*;
+
+        assert_eq!(reporter(&[Succeeded, Succeeded]).summary(), "2 removed");
+        assert_eq!(reporter(&[Failed]).summary(), "0 removed, 1 failed");
+        assert_eq!(
+            reporter(&[Skipped, Skipped]).summary(),
+            "0 removed, 2 skipped"
+        );
+    }
+
+    #[test]
+    fn empty_reporter_reports_nothing_done() {
+        let reporter = reporter(&[]);
+
+        assert!(reporter.is_empty());
+        assert_eq!(reporter.summary(), "0 removed");
+    }
+
+    #[test]
+    fn events_name_the_item_and_its_status() {
+        let reporter = BatchReporter::new("worktree", "removed").with_json_lines(true);
+
+        assert_eq!(
+            reporter
+                .event("feat/x", BatchOutcome::Succeeded, None)
+                .to_string(),
+            r#"{"status":"ok","worktree":"feat/x"}"#
+        );
+        assert_eq!(
+            reporter.event("feat/y", BatchOutcome::Failed, None)["status"],
+            "failed"
+        );
+        assert_eq!(
+            reporter
+                .event("feat/z", BatchOutcome::Failed, Some(Some(2)))
+                .to_string(),
+            r#"{"exit":2,"status":"failed","worktree":"feat/z"}"#
+        );
+        assert_eq!(
+            reporter.event("feat/z", BatchOutcome::Failed, Some(None))["exit"],
+            serde_json::Value::Null
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::{fs, io, path::Path};
+
+use color_eyre::eyre::{self, WrapErr};
+use serde::Deserialize;
+
+use crate::Repo;
+
+/// Env file read from the worktree root unless `cd.env_file` names another one.
+pub(crate) const DEFAULT_ENV_FILE: &str = ".rsworktree.env";
+
+/// The `cd` section of the preferences file.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct CdPreferences {
+    /// Path of the env file, relative to the worktree root.
+    #[serde(default)]
+    pub(crate) env_file: Option<String>,
+    /// Shell snippet run when entering a worktree.
+    #[serde(default)]
+    pub(crate) init: Option<String>,
+}
+
+/// Variables from the worktree's env file, in file order; empty when there is no such file.
+pub(crate) fn load_worktree_env(
+    repo: &Repo,
+    worktree: &Path,
+) -> color_eyre::Result<Vec<(String, String)>> {
+    let path = worktree.join(env_file_name(repo)?);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).wrap_err_with(|| eyre::eyre!("failed to read `{}`", path.display()));
+        }
+    };
+    parse_env(&text).wrap_err_with(|| eyre::eyre!("failed to parse `{}`", path.display()))
+}
+
+fn env_file_name(repo: &Repo) -> color_eyre::Result<String> {
+    Ok(configured_env_file(repo)?.unwrap_or_else(|| DEFAULT_ENV_FILE.to_owned()))
+}
+
+/// The `cd.env_file` preference, if set to a non-empty path.
+pub(crate) fn configured_env_file(repo: &Repo) -> color_eyre::Result<Option<String>> {
+    Ok(load_cd_preferences(repo)?
+        .env_file
+        .filter(|file| !file.trim().is_empty()))
+}
+
+/// The `cd.init` snippet, if one is configured.
+pub(crate) fn load_cd_init(repo: &Repo) -> color_eyre::Result<Option<String>> {
+    Ok(load_cd_preferences(repo)?
+        .init
+        .filter(|init| !init.trim().is_empty()))
+}
+
+fn load_cd_preferences(repo: &Repo) -> color_eyre::Result<CdPreferences> {
+    Ok(repo.preferences()?.cd)
+}
+
+/// Parses `KEY=VALUE` lines. Blank lines and `#` comments are skipped, an `export ` prefix is
+/// allowed, and one pair of matching quotes around the value is removed.
+fn parse_env(text: &str) -> color_eyre::Result<Vec<(String, String)>> {
+    let mut vars = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(eyre::eyre!(
+                "line {}: expected `KEY=VALUE`, found `{line}`",
+                index + 1
+            ));
+        };
+        let key = key.trim();
+        let valid_key = !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            return Err(eyre::eyre!(
+                "line {}: `{key}` is not a valid variable name",
+                index + 1
+            ));
+        }
+
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)
+                    .and_then(|rest| rest.strip_suffix(*quote))
+            })
+            .unwrap_or(value);
+        vars.push((key.to_owned(), value.to_owned()));
+    }
+    Ok(vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_pairs_skipping_comments_and_quotes() -> color_eyre::Result<()> {
+        let vars = parse_env(
+            "# local settings\n\nDATABASE_URL=postgres://localhost/app\nexport PORT = 4000\nGREETING=\"hello world\"\nEMPTY=\nEQUALS='a=b'\n",
+        )?;
+
+        let expected = [
+            ("DATABASE_URL", "postgres://localhost/app"),
+            ("PORT", "4000"),
+            ("GREETING", "hello world"),
+            ("EMPTY", ""),
+            ("EQUALS", "a=b"),
+        ]
+        .map(|(key, value)| (key.to_owned(), value.to_owned()));
+        assert_eq!(vars, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_lines_without_a_valid_key() {
+        let err = parse_env("OK=1\nnot a pair\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected `KEY=VALUE`, found `not a pair`"
+        );
+
+        let err = parse_env("1ST=x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1: `1ST` is not a valid variable name"
+        );
+    }
+
+    #[test]
+    fn env_file_location_comes_from_preferences() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        git2::Repository::init(dir.path())?;
+        let repo = Repo::discover_from(dir.path())?;
+        let worktree = repo.ensure_worktrees_dir()?.join("feature");
+        fs::create_dir_all(&worktree)?;
+        assert!(load_worktree_env(&repo, &worktree)?.is_empty());
+
+        fs::write(worktree.join(DEFAULT_ENV_FILE), "SOURCE=default\n")?;
+        fs::write(worktree.join(".env.local"), "SOURCE=preferences\n")?;
+        assert_eq!(
+            load_worktree_env(&repo, &worktree)?,
+            [("SOURCE".to_owned(), "default".to_owned())]
+        );
+
+        fs::write(
+            repo.preferences_path(),
+            r#"{ "cd": { "env_file": ".env.local" } }"#,
+        )?;
+        assert_eq!(
+            load_worktree_env(&repo, &worktree)?,
+            [("SOURCE".to_owned(), "preferences".to_owned())]
+        );
+        assert_eq!(load_cd_init(&repo)?, None);
+
+        fs::write(
+            repo.preferences_path(),
+            r#"{ "cd": { "init": "source .venv/bin/activate" } }"#,
+        )?;
+        assert_eq!(
+            load_cd_init(&repo)?.as_deref(),
+            Some("source .venv/bin/activate")
+        );
+        Ok(())
+    }
+}
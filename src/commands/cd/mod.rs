@@ -1,39 +1,172 @@
-use std::process::Command;
+use std::{env, ffi::OsStr, path::Path, process::Command};
 
 use color_eyre::eyre::{self, WrapErr};
 use owo_colors::{OwoColorize, Stream};
 
 pub(crate) const SHELL_OVERRIDE_ENV: &str = "RSWORKTREE_SHELL";
-
-use crate::Repo;
+/// Holds the `cd.init` snippet for shells it cannot be run in directly, so their rc file can
+/// `eval` it.
+pub(crate) const CD_INIT_ENV: &str = "RSWORKTREE_CD_INIT";
+
+mod env_file;
+mod picker;
+
+use env_file::load_worktree_env;
+pub(crate) use env_file::{CdPreferences, DEFAULT_ENV_FILE, configured_env_file, load_cd_init};
+pub use picker::{PromptPicker, WorktreePicker};
+
+use crate::{
+    Repo,
+    commands::{
+        create::CreateCommand,
+        list::{ambiguous_worktree_name, find_worktrees, format_worktree, worktree_name_matches},
+        pr_github::{CommandRunner, SystemCommandRunner, quote_arg},
+    },
+    error::{CommandExited, WorktreeNotFound},
+};
 
 #[derive(Debug)]
-pub struct CdCommand {
+pub struct CdCommand<R = SystemCommandRunner, P = PromptPicker> {
     name: String,
     print_only: bool,
+    emit_cd: bool,
+    tmux: bool,
+    create_missing: bool,
+    base: Option<String>,
+    command: Vec<String>,
+    interactive: bool,
+    runner: R,
+    picker: P,
 }
 
 impl CdCommand {
     pub fn new(name: String, print_only: bool) -> Self {
-        Self { name, print_only }
+        Self::with_runner(name, print_only, SystemCommandRunner::default())
+    }
+}
+
+impl<R> CdCommand<R>
+where
+    R: CommandRunner,
+{
+    pub fn with_runner(name: String, print_only: bool, runner: R) -> Self {
+        Self {
+            name,
+            print_only,
+            emit_cd: false,
+            tmux: false,
+            create_missing: false,
+            base: None,
+            command: Vec::new(),
+            interactive: false,
+            runner,
+            picker: PromptPicker,
+        }
+    }
+}
+
+impl<R, P> CdCommand<R, P>
+where
+    R: CommandRunner,
+    P: WorktreePicker,
+{
+    /// Let the user pick the worktree when the name is empty or matches several worktrees,
+    /// instead of failing. Only meant for a terminal session.
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_picker<Q: WorktreePicker>(self, picker: Q) -> CdCommand<R, Q> {
+        CdCommand {
+            name: self.name,
+            print_only: self.print_only,
+            emit_cd: self.emit_cd,
+            tmux: self.tmux,
+            create_missing: self.create_missing,
+            base: self.base,
+            command: self.command,
+            interactive: self.interactive,
+            runner: self.runner,
+            picker,
+        }
+    }
+
+    /// Print only a shell-quoted `cd <path>` line for the calling shell to `eval`.
+    pub fn with_emit_cd(mut self, emit_cd: bool) -> Self {
+        self.emit_cd = emit_cd;
+        self
+    }
+
+    /// Open the worktree in a new tmux window instead of a subshell.
+    pub fn with_tmux(mut self, tmux: bool) -> Self {
+        self.tmux = tmux;
+        self
     }
 
-    pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
+    /// Create the worktree (from `base`, if given) instead of failing when it does not exist.
+    pub fn with_create_missing(mut self, create: bool, base: Option<String>) -> Self {
+        self.create_missing = create;
+        self.base = base;
+        self
+    }
+
+    /// Run `command` in the worktree instead of an interactive shell; a non-zero exit is
+    /// reported as [`CommandExited`].
+    pub fn with_command(mut self, command: Vec<String>) -> Self {
+        self.command = command;
+        self
+    }
+
+    pub fn execute(&mut self, repo: &Repo) -> color_eyre::Result<()> {
         let worktrees_dir = repo.ensure_worktrees_dir()?;
-        let worktree_path = worktrees_dir.join(&self.name);
+        let mut worktree_path = worktrees_dir.join(&self.name);
+        if self.name.is_empty() {
+            let candidates = find_worktrees(&worktrees_dir)?
+                .iter()
+                .map(|worktree| format_worktree(worktree))
+                .collect::<Vec<_>>();
+            if !self.interactive {
+                return Err(eyre::eyre!(
+                    "no worktree name given; pass one, or run `rsworktree cd` in a terminal to pick one"
+                ));
+            }
+            if candidates.is_empty() {
+                return Err(eyre::eyre!(
+                    "no worktrees to pick from under `{}`",
+                    worktrees_dir.display()
+                ));
+            }
+            worktree_path = worktrees_dir.join(self.pick(&candidates)?);
+        } else if !worktree_path.exists() && !self.create_missing {
+            let matches = worktree_name_matches(&worktrees_dir, &self.name)?;
+            match matches.as_slice() {
+                [] => {}
+                [only] => worktree_path = worktrees_dir.join(only),
+                _ if self.interactive => worktree_path = worktrees_dir.join(self.pick(&matches)?),
+                _ => return Err(ambiguous_worktree_name(&self.name, &matches)),
+            }
+        }
+
+        if !worktree_path.exists() && self.create_missing {
+            CreateCommand::new(self.name.clone(), self.base.clone())
+                .create_without_enter(repo, self.print_only || self.emit_cd)?;
+        }
 
         if !worktree_path.exists() {
-            return Err(eyre::eyre!(
-                "worktree `{}` does not exist under `{}`",
-                self.name,
-                worktrees_dir.display()
-            ));
+            return Err(WorktreeNotFound::new(&self.name, &worktrees_dir).into());
         }
 
         let canonical = worktree_path
             .canonicalize()
             .wrap_err_with(|| eyre::eyre!("failed to resolve `{}`", worktree_path.display()))?;
 
+        if self.emit_cd {
+            println!("cd {}", quote_arg(&canonical.to_string_lossy()));
+            return Ok(());
+        }
+
         if self.print_only {
             let path_raw = format!("{}", canonical.display());
             let path = format!(
@@ -46,17 +179,135 @@ impl CdCommand {
             return Ok(());
         }
 
-        let (program, args) = shell_command();
+        if self.tmux {
+            return self.open_in_tmux(&canonical, inside_tmux());
+        }
+
+        let env = load_worktree_env(repo, &canonical)?;
+        let init = load_cd_init(repo)?;
+        if let Some((program, args)) = self.command.split_first() {
+            let mut command = match &init {
+                Some(init) => {
+                    let mut command = Command::new("sh");
+                    command
+                        .arg("-c")
+                        .arg(format!("{init}\nexec \"$@\""))
+                        .arg("sh")
+                        .arg(program);
+                    command
+                }
+                None => Command::new(program),
+            };
+            let status = command
+                .args(args)
+                .current_dir(&canonical)
+                .env("PWD", canonical.as_os_str())
+                .envs(env)
+                .status()
+                .wrap_err_with(|| eyre::eyre!("failed to run `{program}`"))?;
+            if !status.success() {
+                return Err(CommandExited {
+                    program: program.clone(),
+                    code: status.code().unwrap_or(1),
+                }
+                .into());
+            }
+            return Ok(());
+        }
+
+        run_interactive_shell(&canonical, canonical.as_os_str(), &env, init.as_deref())
+    }
+
+    fn pick(&mut self, candidates: &[String]) -> color_eyre::Result<String> {
+        self.picker
+            .pick(candidates)?
+            .ok_or_else(|| eyre::eyre!("no worktree selected"))
+    }
+
+    fn open_in_tmux(&mut self, path: &Path, inside_tmux: bool) -> color_eyre::Result<()> {
+        if !inside_tmux {
+            return Err(eyre::eyre!(
+                "`--tmux` requires running inside a tmux session (`$TMUX` is not set)"
+            ));
+        }
+
+        let args = vec![
+            "new-window".to_owned(),
+            "-c".to_owned(),
+            path.display().to_string(),
+        ];
+        let output = self
+            .runner
+            .run("tmux", path, &args)
+            .wrap_err("failed to run `tmux new-window`")?;
+
+        if !output.success {
+            let stderr = output.stderr.trim();
+            return Err(if stderr.is_empty() {
+                eyre::eyre!("`tmux new-window` exited with a non-zero status")
+            } else {
+                eyre::eyre!("`tmux new-window` failed: {stderr}")
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn inside_tmux() -> bool {
+    env::var_os("TMUX").is_some_and(|value| !value.is_empty())
+}
 
-        let mut cmd = Command::new(&program);
-        cmd.args(args);
-        cmd.current_dir(&canonical);
-        cmd.env("PWD", canonical.as_os_str());
-        cmd.status()
-            .wrap_err("failed to spawn subshell")?
-            .success()
-            .then_some(())
-            .ok_or_else(|| eyre::eyre!("subshell exited with a non-zero status"))
+/// Runs the user's shell in `dir` until they leave it. The shell's exit status is whatever the
+/// last command typed there returned, so only a failure to start it is an error.
+pub(crate) fn run_interactive_shell(
+    dir: &Path,
+    pwd: &OsStr,
+    env: &[(String, String)],
+    init: Option<&str>,
+) -> color_eyre::Result<()> {
+    let (program, mut args) = shell_command();
+    let mut command = Command::new(&program);
+    if let Some(init) = init {
+        match shell_args_with_init(&program, &args, init) {
+            Some(wrapped) => args = wrapped,
+            None => {
+                command.env(CD_INIT_ENV, init);
+            }
+        }
+    }
+    command
+        .args(args)
+        .current_dir(dir)
+        .env("PWD", pwd)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .status()
+        .wrap_err_with(|| eyre::eyre!("failed to spawn subshell `{program}`"))?;
+    Ok(())
+}
+
+/// Arguments that make `program` run `init` before going interactive, or `None` when the shell
+/// is not known to support it. POSIX shells run the snippet with `-c` and then `exec` themselves,
+/// which keeps exported variables but drops aliases, functions and unexported settings; fish
+/// keeps everything through `--init-command`.
+fn shell_args_with_init(program: &str, args: &[String], init: &str) -> Option<Vec<String>> {
+    let name = Path::new(program).file_name()?.to_str()?;
+    match name {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "mksh" | "ash" => {
+            let mut wrapped = vec![
+                "-c".to_owned(),
+                format!("{init}\nexec \"$0\" \"$@\""),
+                program.to_owned(),
+            ];
+            wrapped.extend(args.iter().cloned());
+            Some(wrapped)
+        }
+        "fish" => {
+            let mut wrapped = vec!["--init-command".to_owned(), init.to_owned()];
+            wrapped.extend(args.iter().cloned());
+            Some(wrapped)
+        }
+        _ => None,
     }
 }
 
@@ -80,56 +331,22 @@ pub(crate) fn shell_command() -> (String, Vec<String>) {
 mod tests {
     use super::*;
     use std::{
-        env, fs,
-        process::Command as StdCommand,
+        env,
         sync::{Mutex, OnceLock},
     };
 
     use tempfile::TempDir;
 
-    use crate::{Repo, commands::create::CreateCommand};
-
-    fn init_git_repo(dir: &TempDir) -> color_eyre::Result<()> {
-        run(dir, ["git", "init"])?;
-        fs::write(dir.path().join("README.md"), "test")?;
-        run(dir, ["git", "add", "README.md"])?;
-        run(
-            dir,
-            [
-                "git",
-                "-c",
-                "user.name=Test",
-                "-c",
-                "user.email=test@example.com",
-                "commit",
-                "-m",
-                "Initial commit",
-            ],
-        )?;
-        Ok(())
-    }
-
-    fn run(dir: &TempDir, cmd: impl IntoIterator<Item = &'static str>) -> color_eyre::Result<()> {
-        let mut iter = cmd.into_iter();
-        let program = iter.next().expect("command must not be empty");
-        let status = StdCommand::new(program)
-            .current_dir(dir.path())
-            .args(iter)
-            .status()
-            .wrap_err_with(|| eyre::eyre!("failed to run `{program}`"))?;
-
-        if !status.success() {
-            return Err(eyre::eyre!("`{program}` exited with status {status}"));
-        }
-
-        Ok(())
-    }
+    use crate::{
+        Repo,
+        commands::create::CreateCommand,
+        test_support::{MockCommandRunner, RecordedCall, init_repo},
+    };
 
     #[test]
     fn prints_canonical_path_when_worktree_exists() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
-        let repo = Repo::discover_from(dir.path())?;
+        let repo = init_repo(&dir)?;
 
         let create = CreateCommand::new("feature/test".into(), None);
         unsafe {
@@ -137,21 +354,164 @@ mod tests {
         }
         create.execute(&repo)?;
 
-        let command = CdCommand::new("feature/test".into(), true);
+        let mut command = CdCommand::new("feature/test".into(), true);
         command.execute(&repo)?;
 
         Ok(())
     }
 
+    #[derive(Debug)]
+    struct StubPicker {
+        choice: Option<&'static str>,
+        offered: Vec<Vec<String>>,
+    }
+
+    impl WorktreePicker for &mut StubPicker {
+        fn pick(&mut self, candidates: &[String]) -> color_eyre::Result<Option<String>> {
+            self.offered.push(candidates.to_vec());
+            Ok(self.choice.map(str::to_owned))
+        }
+    }
+
+    fn repo_with_worktrees(dir: &TempDir, names: &[&str]) -> color_eyre::Result<Repo> {
+        let repo = init_repo(dir)?;
+        for name in names {
+            CreateCommand::new((*name).into(), None).create_without_enter(&repo, true)?;
+        }
+        Ok(repo)
+    }
+
+    fn touch_in_picked(name: &str) -> CdCommand {
+        CdCommand::new(name.into(), false).with_command(vec!["touch".into(), "picked".into()])
+    }
+
+    #[test]
+    fn ambiguous_name_offers_matches_to_picker_when_interactive() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktrees(&dir, &["feature/api-v1", "bugfix/api-crash", "docs"])?;
+        let mut picker = StubPicker {
+            choice: Some("bugfix/api-crash"),
+            offered: Vec::new(),
+        };
+
+        touch_in_picked("api")
+            .with_interactive(true)
+            .with_picker(&mut picker)
+            .execute(&repo)?;
+
+        assert_eq!(picker.offered, [["bugfix/api-crash", "feature/api-v1"]]);
+        assert!(
+            repo.worktrees_dir()
+                .join("bugfix/api-crash/picked")
+                .exists()
+        );
+        assert!(!repo.worktrees_dir().join("feature/api-v1/picked").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn ambiguous_or_missing_name_errors_without_a_terminal() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktrees(&dir, &["feature/api-v1", "bugfix/api-crash"])?;
+        let mut picker = StubPicker {
+            choice: Some("feature/api-v1"),
+            offered: Vec::new(),
+        };
+
+        let err = touch_in_picked("api")
+            .with_picker(&mut picker)
+            .execute(&repo)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "worktree identifier `api` is ambiguous. Matches: bugfix/api-crash, feature/api-v1"
+        );
+
+        let err = touch_in_picked("")
+            .with_picker(&mut picker)
+            .execute(&repo)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("no worktree name given"));
+        assert!(picker.offered.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn omitted_name_offers_every_worktree_and_honours_cancel() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktrees(&dir, &["alpha", "beta"])?;
+        let mut picker = StubPicker {
+            choice: None,
+            offered: Vec::new(),
+        };
+
+        let err = touch_in_picked("")
+            .with_interactive(true)
+            .with_picker(&mut picker)
+            .execute(&repo)
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "no worktree selected");
+        assert_eq!(picker.offered, [["alpha", "beta"]]);
+        Ok(())
+    }
+
     #[test]
     fn errors_when_missing_worktree() {
         let dir = TempDir::new().unwrap();
-        init_git_repo(&dir).unwrap();
-        let repo = Repo::discover_from(dir.path()).unwrap();
-        let command = CdCommand::new("missing".into(), true);
+        let repo = init_repo(&dir).unwrap();
+        let mut command = CdCommand::new("missing".into(), true);
         assert!(command.execute(&repo).is_err());
     }
 
+    #[test]
+    fn tmux_opens_new_window_in_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature/tmux".into(), None).create_without_enter(&repo, true)?;
+        let expected = repo.worktrees_dir().join("feature/tmux").canonicalize()?;
+
+        let _lock = env_lock().lock().unwrap();
+        let _tmux_guard = EnvGuard::set("TMUX", "/tmp/tmux-1000/default,1234,0");
+
+        let mut command = CdCommand::with_runner(
+            "feature/tmux".into(),
+            false,
+            MockCommandRunner::succeeding(),
+        )
+        .with_tmux(true);
+        command.execute(&repo)?;
+
+        assert_eq!(
+            command.runner.calls,
+            vec![RecordedCall {
+                program: "tmux".to_owned(),
+                dir: expected.clone(),
+                args: vec![
+                    "new-window".to_owned(),
+                    "-c".to_owned(),
+                    expected.display().to_string(),
+                ],
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tmux_errors_outside_tmux_session() {
+        let mut command =
+            CdCommand::with_runner("feature/tmux".into(), false, MockCommandRunner::default())
+                .with_tmux(true);
+
+        let err = command
+            .open_in_tmux(Path::new("/tmp/worktree"), false)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("`$TMUX` is not set"));
+        assert!(command.runner.calls.is_empty());
+    }
+
     struct EnvGuard {
         key: &'static str,
         previous: Option<std::ffi::OsString>,
@@ -235,4 +595,30 @@ mod tests {
         assert_eq!(program, "/bin/sh");
         assert_eq!(args, vec![String::from("-i")]);
     }
+
+    #[test]
+    fn init_snippet_wraps_posix_and_fish_shells_only() {
+        let interactive = vec![String::from("-i")];
+        assert_eq!(
+            shell_args_with_init("/bin/zsh", &interactive, "export A=1"),
+            Some(vec![
+                "-c".to_owned(),
+                "export A=1\nexec \"$0\" \"$@\"".to_owned(),
+                "/bin/zsh".to_owned(),
+                "-i".to_owned(),
+            ])
+        );
+        assert_eq!(
+            shell_args_with_init("/usr/bin/fish", &interactive, "set -x A 1"),
+            Some(vec![
+                "--init-command".to_owned(),
+                "set -x A 1".to_owned(),
+                "-i".to_owned(),
+            ])
+        );
+        assert_eq!(
+            shell_args_with_init("/usr/bin/nu", &interactive, "$env.A = 1"),
+            None
+        );
+    }
 }
@@ -0,0 +1,78 @@
+use std::io::{self, BufRead, Write};
+
+use color_eyre::eyre::WrapErr;
+
+/// Chooses one worktree out of several candidates for `cd`.
+pub trait WorktreePicker {
+    /// The chosen worktree name, or `None` when the user cancelled.
+    fn pick(&mut self, candidates: &[String]) -> color_eyre::Result<Option<String>>;
+}
+
+/// Lists the candidates on stderr and reads a number or a fuzzy filter from stdin until one
+/// worktree is left. Everything goes to stderr so `--print`/`--emit-cd` output stays clean.
+#[derive(Debug, Default)]
+pub struct PromptPicker;
+
+impl WorktreePicker for PromptPicker {
+    fn pick(&mut self, candidates: &[String]) -> color_eyre::Result<Option<String>> {
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        let mut shown = candidates.to_vec();
+        loop {
+            for (index, name) in shown.iter().enumerate() {
+                eprintln!("{:>3}) {name}", index + 1);
+            }
+            eprint!("Pick a worktree (number or filter, empty to cancel): ");
+            io::stderr().flush().wrap_err("failed to flush stderr")?;
+
+            let mut answer = String::new();
+            let read = input
+                .read_line(&mut answer)
+                .wrap_err("failed to read worktree choice")?;
+            let answer = answer.trim();
+            if read == 0 || answer.is_empty() {
+                return Ok(None);
+            }
+
+            if let Ok(number) = answer.parse::<usize>()
+                && let Some(name) = number.checked_sub(1).and_then(|index| shown.get(index))
+            {
+                return Ok(Some(name.clone()));
+            }
+
+            let filtered = shown
+                .iter()
+                .filter(|name| fuzzy_matches(answer, name))
+                .cloned()
+                .collect::<Vec<_>>();
+            match filtered.as_slice() {
+                [] => eprintln!("No worktree matches `{answer}`."),
+                [only] => return Ok(Some(only.clone())),
+                _ => shown = filtered,
+            }
+        }
+    }
+}
+
+/// Whether the characters of `query` appear in `candidate` in order, ignoring case.
+pub(crate) fn fuzzy_matches(query: &str, candidate: &str) -> bool {
+    let mut chars = candidate.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|wanted| chars.any(|found| found == wanted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_characters_in_order_ignoring_case() {
+        assert!(fuzzy_matches("fln", "feature/long-name"));
+        assert!(fuzzy_matches("LONG", "feature/long-name"));
+        assert!(fuzzy_matches("", "anything"));
+        assert!(!fuzzy_matches("nlf", "feature/long-name"));
+        assert!(!fuzzy_matches("featurex", "feature/long-name"));
+    }
+}
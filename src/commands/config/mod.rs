@@ -0,0 +1,195 @@
+use std::env;
+
+use color_eyre::eyre::{self, WrapErr};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    Repo,
+    commands::{
+        cd::{DEFAULT_ENV_FILE, configured_env_file, load_cd_init},
+        create::{
+            default_base, load_copy_untracked, load_name_template, load_post_create_hook,
+            load_post_create_when_quiet,
+        },
+        interactive::Theme,
+        pr_github::{COMMAND_TIMEOUT_ENV, SystemCommandRunner},
+        rm::load_remove_hooks,
+    },
+    editor::{
+        EditorPreferenceResolution, EditorPreferenceSource, PreferenceMissingReason,
+        resolve_editor_preference,
+    },
+    repo::DEFAULT_WORKTREES_DIR,
+};
+
+const SOURCE_DEFAULT: &str = "default";
+const SOURCE_PREFERENCES: &str = "preferences";
+
+#[derive(Debug, Default)]
+pub struct ConfigListCommand;
+
+impl ConfigListCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
+        let config = effective_config(repo)?;
+        let text =
+            serde_json::to_string_pretty(&config).wrap_err("failed to serialize configuration")?;
+        println!("{text}");
+        Ok(())
+    }
+}
+
+/// Every setting rsworktree reads, with its resolved value and where that value came from.
+fn effective_config(repo: &Repo) -> color_eyre::Result<Value> {
+    let mut settings = Map::new();
+
+    settings.insert("editor".into(), editor_entry(repo)?);
+
+    let worktrees_dir = repo
+        .worktrees_dir()
+        .strip_prefix(repo.root())
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    let worktrees_dir_source = if worktrees_dir == DEFAULT_WORKTREES_DIR {
+        SOURCE_DEFAULT
+    } else {
+        SOURCE_PREFERENCES
+    };
+    settings.insert(
+        "worktrees_dir".into(),
+        entry(worktrees_dir, worktrees_dir_source),
+    );
+
+    settings.insert(
+        "create.name_template".into(),
+        optional_entry(load_name_template(repo)?),
+    );
+    settings.insert(
+        "create.base_default".into(),
+        match default_base(repo)? {
+            Some(base) => entry(base, SOURCE_PREFERENCES),
+            None => entry("HEAD", SOURCE_DEFAULT),
+        },
+    );
+    settings.insert(
+        "create.post_create".into(),
+        optional_entry(load_post_create_hook(repo, false)?),
+    );
+    let when_quiet = load_post_create_when_quiet(repo)?;
+    settings.insert(
+        "create.post_create_when_quiet".into(),
+        entry(
+            when_quiet,
+            if when_quiet {
+                SOURCE_PREFERENCES
+            } else {
+                SOURCE_DEFAULT
+            },
+        ),
+    );
+    let copy_untracked = load_copy_untracked(repo)?;
+    let copy_source = if copy_untracked.is_empty() {
+        SOURCE_DEFAULT
+    } else {
+        SOURCE_PREFERENCES
+    };
+    settings.insert(
+        "create.copy_untracked".into(),
+        entry(copy_untracked, copy_source),
+    );
+
+    let hooks = load_remove_hooks(repo)?;
+    settings.insert("remove.pre_remove".into(), optional_entry(hooks.pre_remove));
+    settings.insert(
+        "remove.post_remove".into(),
+        optional_entry(hooks.post_remove),
+    );
+
+    settings.insert(
+        "cd.env_file".into(),
+        match configured_env_file(repo)? {
+            Some(file) => entry(file, SOURCE_PREFERENCES),
+            None => entry(DEFAULT_ENV_FILE, SOURCE_DEFAULT),
+        },
+    );
+    settings.insert("cd.init".into(), optional_entry(load_cd_init(repo)?));
+
+    for (role, color, configured) in Theme::load_roles(repo)? {
+        let source = if configured {
+            SOURCE_PREFERENCES
+        } else {
+            SOURCE_DEFAULT
+        };
+        settings.insert(
+            format!("tui.theme.{role}"),
+            entry(color.to_string(), source),
+        );
+    }
+
+    let timeout = SystemCommandRunner::default().effective_timeout()?;
+    let timeout_source = match env::var(COMMAND_TIMEOUT_ENV) {
+        Ok(value) if !value.trim().is_empty() => format!("env:{COMMAND_TIMEOUT_ENV}"),
+        _ => SOURCE_DEFAULT.to_owned(),
+    };
+    settings.insert(
+        "command_timeout_secs".into(),
+        entry(
+            timeout.map_or(Value::Null, |timeout| timeout.as_secs().into()),
+            timeout_source,
+        ),
+    );
+
+    Ok(json!({
+        "preferences_file": repo.preferences_path().display().to_string(),
+        "settings": settings,
+    }))
+}
+
+fn editor_entry(repo: &Repo) -> color_eyre::Result<Value> {
+    let resolution = resolve_editor_preference(repo)?;
+    Ok(match resolution {
+        EditorPreferenceResolution::Found(preference) => {
+            let command_line = std::iter::once(&preference.command)
+                .chain(&preference.args)
+                .map(|part| part.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            let source = match preference.source {
+                EditorPreferenceSource::ConfigFile(_) => SOURCE_PREFERENCES.to_owned(),
+                EditorPreferenceSource::Environment { variable } => {
+                    format!("env:{}", variable.name())
+                }
+            };
+            entry(shell_words::join(command_line), source)
+        }
+        EditorPreferenceResolution::Missing(PreferenceMissingReason::NotConfigured) => {
+            entry(Value::Null, SOURCE_DEFAULT)
+        }
+        EditorPreferenceResolution::Missing(PreferenceMissingReason::ConfigInvalid {
+            error,
+            ..
+        }) => {
+            return Err(eyre::eyre!("invalid `editor` preference: {error}"));
+        }
+        EditorPreferenceResolution::Missing(PreferenceMissingReason::EnvInvalid {
+            variable,
+            error,
+        }) => {
+            return Err(eyre::eyre!("invalid `{}` value: {error}", variable.name()));
+        }
+    })
+}
+
+/// A preference with no default: `null` unless the preferences file sets it.
+fn optional_entry(value: Option<String>) -> Value {
+    match value {
+        Some(value) => entry(value, SOURCE_PREFERENCES),
+        None => entry(Value::Null, SOURCE_DEFAULT),
+    }
+}
+
+fn entry(value: impl Into<Value>, source: impl Into<String>) -> Value {
+    json!({ "value": value.into(), "source": source.into() })
+}
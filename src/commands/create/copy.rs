@@ -0,0 +1,269 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use color_eyre::eyre::{self, WrapErr};
+
+use crate::{Repo, repo::DEFAULT_WORKTREES_DIR};
+
+use super::template::load_create_preferences;
+
+/// The `create.copy_untracked` paths, relative to the repository root.
+///
+/// Entries that would copy the whole repository, git's own data, or any worktree (including the
+/// new one, which would then be copied into itself) are rejected.
+pub(crate) fn load_copy_untracked(repo: &Repo) -> color_eyre::Result<Vec<String>> {
+    let entries = load_create_preferences(repo)?.copy_untracked;
+    let worktrees_dir = repo
+        .worktrees_dir()
+        .strip_prefix(repo.root())
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    for entry in &entries {
+        let inside_repo = Path::new(entry)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if entry.trim().is_empty() || !inside_repo {
+            return Err(eyre::eyre!(
+                "invalid `create.copy_untracked` entry `{entry}`; expected a path relative to the repository root"
+            ));
+        }
+
+        let normalized: PathBuf = Path::new(entry)
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .collect();
+        let reason = if normalized.as_os_str().is_empty() {
+            Some("it names the whole repository".to_owned())
+        } else if normalized.starts_with(".git") {
+            Some("git's own data cannot be copied".to_owned())
+        } else if normalized.starts_with(DEFAULT_WORKTREES_DIR) {
+            Some(format!(
+                "`{DEFAULT_WORKTREES_DIR}` holds rsworktree's own state"
+            ))
+        } else if normalized.starts_with(&worktrees_dir) || worktrees_dir.starts_with(&normalized) {
+            Some(format!(
+                "it overlaps the worktrees directory `{}`",
+                worktrees_dir.display()
+            ))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(eyre::eyre!(
+                "invalid `create.copy_untracked` entry `{entry}`; {reason}"
+            ));
+        }
+    }
+    Ok(entries)
+}
+
+/// Copies each of `entries` from `root` into `worktree_path`, keeping their relative location.
+///
+/// Entries missing from `root` are skipped, and files already present in the worktree are left
+/// alone so tracked files are never overwritten.
+pub(crate) fn copy_untracked(
+    root: &Path,
+    worktree_path: &Path,
+    entries: &[String],
+    quiet: bool,
+) -> color_eyre::Result<()> {
+    for entry in entries {
+        let source = root.join(entry);
+        if !source.exists() {
+            if !quiet {
+                eprintln!(
+                    "Skipping `{entry}` from `create.copy_untracked`: not found in `{}`.",
+                    root.display()
+                );
+            }
+            continue;
+        }
+        if worktree_path.starts_with(&source) {
+            return Err(eyre::eyre!(
+                "refusing to copy `{entry}` into the new worktree, which lies inside it"
+            ));
+        }
+        copy_path(&source, &worktree_path.join(entry))
+            .wrap_err_with(|| eyre::eyre!("failed to copy `{entry}` into the new worktree"))?;
+    }
+    Ok(())
+}
+
+/// Copies a file or directory tree, keeping any file that already exists at the destination.
+/// Symlinks are copied as links rather than followed.
+pub(super) fn copy_path(source: &Path, destination: &Path) -> color_eyre::Result<()> {
+    let metadata = fs::symlink_metadata(source)
+        .wrap_err_with(|| eyre::eyre!("failed to read `{}`", source.display()))?;
+    if metadata.is_dir() {
+        fs::create_dir_all(destination)
+            .wrap_err_with(|| eyre::eyre!("failed to create `{}`", destination.display()))?;
+        let children = fs::read_dir(source)
+            .wrap_err_with(|| eyre::eyre!("failed to read `{}`", source.display()))?;
+        for child in children {
+            let child =
+                child.wrap_err_with(|| eyre::eyre!("failed to read `{}`", source.display()))?;
+            copy_path(&child.path(), &destination.join(child.file_name()))?;
+        }
+        return Ok(());
+    }
+
+    if fs::symlink_metadata(destination).is_ok() {
+        return Ok(());
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .wrap_err_with(|| eyre::eyre!("failed to create `{}`", parent.display()))?;
+    }
+    if metadata.is_symlink() {
+        return copy_symlink(source, destination);
+    }
+    fs::copy(source, destination).wrap_err_with(|| {
+        eyre::eyre!(
+            "failed to copy `{}` to `{}`",
+            source.display(),
+            destination.display()
+        )
+    })?;
+    Ok(())
+}
+
+fn copy_symlink(source: &Path, destination: &Path) -> color_eyre::Result<()> {
+    let target = fs::read_link(source)
+        .wrap_err_with(|| eyre::eyre!("failed to read link `{}`", source.display()))?;
+    #[cfg(unix)]
+    let linked = std::os::unix::fs::symlink(&target, destination);
+    #[cfg(windows)]
+    let linked = if source.is_dir() {
+        std::os::windows::fs::symlink_dir(&target, destination)
+    } else {
+        std::os::windows::fs::symlink_file(&target, destination)
+    };
+    linked.wrap_err_with(|| eyre::eyre!("failed to create link `{}`", destination.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn copies_files_and_directories_keeping_structure() -> color_eyre::Result<()> {
+        let root = TempDir::new()?;
+        let worktree = TempDir::new()?;
+        fs::write(root.path().join(".env"), "SECRET=1")?;
+        fs::create_dir_all(root.path().join(".vscode/nested"))?;
+        fs::write(root.path().join(".vscode/settings.json"), "{}")?;
+        fs::write(root.path().join(".vscode/nested/launch.json"), "[]")?;
+        fs::create_dir_all(root.path().join("config"))?;
+        fs::write(root.path().join("config/local.toml"), "a = 1")?;
+
+        let entries = [".env", ".vscode", "config/local.toml", "missing.txt"].map(String::from);
+        copy_untracked(root.path(), worktree.path(), &entries, true)?;
+
+        assert_eq!(
+            fs::read_to_string(worktree.path().join(".env"))?,
+            "SECRET=1"
+        );
+        assert_eq!(
+            fs::read_to_string(worktree.path().join(".vscode/nested/launch.json"))?,
+            "[]"
+        );
+        assert_eq!(
+            fs::read_to_string(worktree.path().join("config/local.toml"))?,
+            "a = 1"
+        );
+        assert!(!worktree.path().join("missing.txt").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn leaves_existing_worktree_files_alone() -> color_eyre::Result<()> {
+        let root = TempDir::new()?;
+        let worktree = TempDir::new()?;
+        fs::write(root.path().join("settings.toml"), "main")?;
+        fs::write(worktree.path().join("settings.toml"), "tracked")?;
+
+        copy_untracked(
+            root.path(),
+            worktree.path(),
+            &["settings.toml".to_owned()],
+            true,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(worktree.path().join("settings.toml"))?,
+            "tracked"
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copies_symlinks_as_links() -> color_eyre::Result<()> {
+        let root = TempDir::new()?;
+        let worktree = TempDir::new()?;
+        fs::create_dir_all(root.path().join("cache"))?;
+        std::os::unix::fs::symlink("..", root.path().join("cache/loop"))?;
+
+        copy_untracked(root.path(), worktree.path(), &["cache".to_owned()], true)?;
+
+        let copied = worktree.path().join("cache/loop");
+        assert!(fs::symlink_metadata(&copied)?.is_symlink());
+        assert_eq!(fs::read_link(&copied)?, Path::new(".."));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_entries_covering_the_repository_or_worktrees() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        git2::Repository::init(dir.path())?;
+        let repo = Repo::discover_from(dir.path())?;
+        repo.ensure_worktrees_dir()?;
+
+        for (entry, reason) in [
+            (".", "it names the whole repository"),
+            ("./.git/config", "git's own data cannot be copied"),
+            (".rsworktree", "`.rsworktree` holds rsworktree's own state"),
+        ] {
+            fs::write(
+                repo.preferences_path(),
+                format!(r#"{{ "create": {{ "copy_untracked": ["{entry}"] }} }}"#),
+            )?;
+            let err = load_copy_untracked(&repo).expect_err(entry);
+            assert_eq!(
+                err.to_string(),
+                format!("invalid `create.copy_untracked` entry `{entry}`; {reason}")
+            );
+        }
+
+        let worktree = dir.path().join("cache/wt");
+        fs::create_dir_all(&worktree)?;
+        let err = copy_untracked(dir.path(), &worktree, &["cache".to_owned()], true)
+            .expect_err("the worktree lies inside `cache`");
+        assert!(err.to_string().contains("lies inside it"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_entries_outside_the_repository() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        git2::Repository::init(dir.path())?;
+        let repo = Repo::discover_from(dir.path())?;
+        repo.ensure_worktrees_dir()?;
+
+        fs::write(
+            repo.preferences_path(),
+            r#"{ "create": { "copy_untracked": [".env", "../secrets"] } }"#,
+        )?;
+
+        let err = load_copy_untracked(&repo).expect_err("`..` escapes the repository");
+        assert!(
+            err.to_string()
+                .starts_with("invalid `create.copy_untracked` entry `../secrets`")
+        );
+        Ok(())
+    }
+}
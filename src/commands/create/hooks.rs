@@ -0,0 +1,68 @@
+use crate::Repo;
+
+use super::template::load_create_preferences;
+
+/// Environment variable carrying the new worktree's name to the `post_create` hook.
+pub(crate) const WORKTREE_NAME_ENV: &str = "RSWORKTREE_NAME";
+
+/// The `create.post_create` hook to run for this creation, if any.
+///
+/// Quiet creations (the TUI, `create --print-branch`) skip it unless
+/// `create.post_create_when_quiet` is set, since its output would interleave with theirs.
+pub(crate) fn load_post_create_hook(
+    repo: &Repo,
+    quiet: bool,
+) -> color_eyre::Result<Option<String>> {
+    let preferences = load_create_preferences(repo)?;
+    if quiet && !preferences.post_create_when_quiet {
+        return Ok(None);
+    }
+
+    Ok(preferences
+        .post_create
+        .filter(|hook| !hook.trim().is_empty()))
+}
+
+/// The `create.post_create_when_quiet` preference.
+pub(crate) fn load_post_create_when_quiet(repo: &Repo) -> color_eyre::Result<bool> {
+    Ok(load_create_preferences(repo)?.post_create_when_quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::test_support::init_repo;
+
+    #[test]
+    fn quiet_creation_skips_hook_unless_opted_in() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        repo.ensure_worktrees_dir()?;
+        assert_eq!(load_post_create_hook(&repo, false)?, None);
+
+        fs::write(
+            repo.preferences_path(),
+            r#"{ "create": { "post_create": "./setup.sh" } }"#,
+        )?;
+        assert_eq!(
+            load_post_create_hook(&repo, false)?.as_deref(),
+            Some("./setup.sh")
+        );
+        assert_eq!(load_post_create_hook(&repo, true)?, None);
+
+        fs::write(
+            repo.preferences_path(),
+            r#"{ "create": { "post_create": "./setup.sh", "post_create_when_quiet": true } }"#,
+        )?;
+        assert_eq!(
+            load_post_create_hook(&repo, true)?.as_deref(),
+            Some("./setup.sh")
+        );
+        Ok(())
+    }
+}
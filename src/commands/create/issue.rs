@@ -0,0 +1,175 @@
+use color_eyre::eyre::{self, WrapErr};
+use git2::BranchType;
+
+use crate::{
+    Repo,
+    commands::pr_github::{CommandOutput, CommandRunner},
+};
+
+const ISSUE_REMOTE: &str = "origin";
+
+/// Resolves the branch linked to GitHub issue `issue`, creating one with `gh issue develop` when
+/// the issue has none, and makes sure it exists locally.
+pub fn issue_branch<R: CommandRunner>(
+    runner: &mut R,
+    repo: &Repo,
+    issue: u64,
+    name: Option<&str>,
+    base: Option<&str>,
+) -> color_eyre::Result<String> {
+    let issue_arg = issue.to_string();
+    let listed = run_checked(
+        runner,
+        repo,
+        "gh",
+        vec![
+            "issue".into(),
+            "develop".into(),
+            "--list".into(),
+            issue_arg.clone(),
+        ],
+    )?;
+
+    let branch = match parse_linked_branch(&listed.stdout) {
+        Some(branch) => {
+            println!("Issue #{issue} already has linked branch `{branch}`; reusing it.");
+            branch
+        }
+        None => {
+            let mut args = vec!["issue".into(), "develop".into(), issue_arg];
+            if let Some(name) = name {
+                args.extend(["--name".into(), name.to_owned()]);
+            }
+            if let Some(base) = base {
+                args.extend(["--base".into(), base.to_owned()]);
+            }
+            let created = run_checked(runner, repo, "gh", args)?;
+            parse_created_branch(&created.stdout).ok_or_else(|| {
+                eyre::eyre!(
+                    "could not determine the branch `gh issue develop` created for issue #{issue}"
+                )
+            })?
+        }
+    };
+
+    if repo.git().find_branch(&branch, BranchType::Local).is_err() {
+        run_checked(
+            runner,
+            repo,
+            "git",
+            vec![
+                "fetch".into(),
+                ISSUE_REMOTE.into(),
+                format!("refs/heads/{branch}:refs/heads/{branch}"),
+            ],
+        )?;
+    }
+
+    Ok(branch)
+}
+
+fn run_checked<R: CommandRunner>(
+    runner: &mut R,
+    repo: &Repo,
+    program: &str,
+    args: Vec<String>,
+) -> color_eyre::Result<CommandOutput> {
+    let output = runner
+        .run(program, repo.root(), &args)
+        .wrap_err_with(|| eyre::eyre!("failed to run `{program}`"))?;
+    if !output.success {
+        return Err(eyre::eyre!(
+            "`{program} {}` failed: {}",
+            args.join(" "),
+            output.stderr.trim()
+        ));
+    }
+    Ok(output)
+}
+
+/// First branch in `gh issue develop --list` output (`<branch>\t<url>` per line).
+fn parse_linked_branch(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .next()
+        .map(str::to_owned)
+}
+
+/// Branch name from the `.../tree/<branch>` URL printed by `gh issue develop`.
+fn parse_created_branch(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().split_once("/tree/"))
+        .map(|(_, branch)| branch.to_owned())
+        .filter(|branch| !branch.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::{
+        commands::create::CreateCommand,
+        test_support::{MockCommandRunner, init_repo, output},
+    };
+
+    #[test]
+    fn creates_linked_branch_and_worktree_from_issue() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+
+        let mut runner = MockCommandRunner::with_outputs([
+            output("", true),
+            output("https://github.com/owner/repo/tree/123-fix-login\n", true),
+            output("", true),
+        ]);
+
+        let branch = issue_branch(&mut runner, &repo, 123, None, Some("main"))?;
+        assert_eq!(branch, "123-fix-login");
+        let calls = runner
+            .calls
+            .iter()
+            .map(|call| (call.program.as_str(), call.args.join(" ")))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            calls,
+            vec![
+                ("gh", "issue develop --list 123".to_owned()),
+                ("gh", "issue develop 123 --base main".to_owned()),
+                (
+                    "git",
+                    "fetch origin refs/heads/123-fix-login:refs/heads/123-fix-login".to_owned()
+                ),
+            ]
+        );
+
+        CreateCommand::new(branch.clone(), None).create_without_enter(&repo, true)?;
+        let worktree = git2::Repository::open(repo.worktrees_dir().join(&branch))?;
+        assert_eq!(worktree.head()?.shorthand(), Some("123-fix-login"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reuses_branch_already_linked_to_issue() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let head = repo.git().head()?.peel_to_commit()?;
+        repo.git().branch("42-existing", &head, false)?;
+
+        let mut runner = MockCommandRunner::with_outputs([output(
+            "42-existing\thttps://github.com/owner/repo/tree/42-existing\n",
+            true,
+        )]);
+
+        let branch = issue_branch(&mut runner, &repo, 42, Some("ignored"), None)?;
+        assert_eq!(branch, "42-existing");
+        assert_eq!(runner.calls.len(), 1, "only the linked branch lookup runs");
+
+        Ok(())
+    }
+}
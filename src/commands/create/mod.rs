@@ -1,17 +1,54 @@
-use std::fs;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use color_eyre::eyre::{self, Context};
 
 use owo_colors::{OwoColorize, Stream};
 
-use git2::{ErrorCode, WorktreeAddOptions};
+use git2::{BranchType, ErrorCode, WorktreeAddOptions};
 
-use crate::{Repo, commands::cd::CdCommand};
+use crate::{
+    Repo,
+    commands::{
+        cd::CdCommand, hooks::run_hook, list::worktree_branch, pr_github::SystemCommandRunner,
+    },
+};
+
+mod copy;
+mod hooks;
+mod issue;
+mod scaffold;
+mod stack;
+mod template;
+
+use copy::copy_untracked;
+pub(crate) use copy::load_copy_untracked;
+use hooks::WORKTREE_NAME_ENV;
+pub(crate) use hooks::{load_post_create_hook, load_post_create_when_quiet};
+pub use issue::issue_branch;
+use scaffold::apply_template_repo;
+pub(crate) use stack::{forget_stack_worktree, rename_stack_worktree};
+use stack::{record_stack_parent, resolve_stack_base};
+pub(crate) use template::{CreatePreferences, load_name_template};
+pub use template::{apply_name_template, default_base};
 
 #[derive(Debug)]
 pub struct CreateCommand {
     name: String,
+    /// Branch to check out when it differs from `name`.
+    branch: Option<String>,
     base: Option<String>,
+    /// Worktree whose branch this one is stacked on.
+    on: Option<String>,
+    checkout: Option<String>,
+    force: bool,
+    track_remote: bool,
+    detach: bool,
+    print_branch: bool,
+    no_copy: bool,
+    template_repo: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,10 +59,86 @@ pub enum CreateOutcome {
 
 impl CreateCommand {
     pub fn new(name: String, base: Option<String>) -> Self {
-        Self { name, base }
+        Self {
+            name,
+            branch: None,
+            base,
+            on: None,
+            checkout: None,
+            force: false,
+            track_remote: false,
+            detach: false,
+            print_branch: false,
+            no_copy: false,
+            template_repo: None,
+        }
+    }
+
+    /// Check the worktree out on `branch` instead of a branch named after the worktree.
+    pub fn with_branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Base the new branch on the branch checked out in worktree `on` and record it as the parent.
+    pub fn with_on(mut self, on: Option<String>) -> Self {
+        self.on = on;
+        self
+    }
+
+    /// Replace a leftover directory at the worktree path that is not a registered worktree.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Check the new worktree out detached at `reference`, leaving the branch tip untouched.
+    pub fn with_checkout(mut self, reference: Option<String>) -> Self {
+        self.checkout = reference;
+        self
+    }
+
+    /// When no local branch exists but `<remote>/<name>` does, branch from it and track it.
+    pub fn with_checkout_existing_remote(mut self, track_remote: bool) -> Self {
+        self.track_remote = track_remote;
+        self
+    }
+
+    /// Add the worktree detached at the base (or HEAD) without creating a branch.
+    pub fn with_detach(mut self, detach: bool) -> Self {
+        self.detach = detach;
+        self
+    }
+
+    /// Print only the branch checked out in the worktree instead of reporting and entering it.
+    pub fn with_print_branch(mut self, print_branch: bool) -> Self {
+        self.print_branch = print_branch;
+        self
+    }
+
+    /// Skip copying the `create.copy_untracked` paths into the new worktree.
+    pub fn with_no_copy(mut self, no_copy: bool) -> Self {
+        self.no_copy = no_copy;
+        self
+    }
+
+    /// Copy the files of this scaffolding repository (URL or path) into the new worktree.
+    pub fn with_template_repo(mut self, template_repo: Option<String>) -> Self {
+        self.template_repo = template_repo;
+        self
     }
 
     pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
+        if self.print_branch {
+            self.create_internal(repo, true)?;
+            let worktree_path = repo.worktrees_dir().join(&self.name);
+            let branch = worktree_branch(&worktree_path).ok_or_else(|| {
+                eyre::eyre!("worktree `{}` is not checked out on a branch", self.name)
+            })?;
+            println!("{branch}");
+            return Ok(());
+        }
+
         let outcome = self.create_internal(repo, false)?;
         match outcome {
             CreateOutcome::Created | CreateOutcome::AlreadyExists => self.enter_worktree(repo),
@@ -47,8 +160,34 @@ impl CreateCommand {
     fn create_internal(&self, repo: &Repo, quiet: bool) -> color_eyre::Result<CreateOutcome> {
         let worktrees_dir = repo.ensure_worktrees_dir()?;
         let worktree_path = worktrees_dir.join(&self.name);
-        let target_branch = self.name.as_str();
-        let base_branch = self.base.as_deref();
+        let target_branch = self.branch.as_deref().unwrap_or(&self.name);
+        let git_repo = repo.git();
+        let upstream = if self.track_remote {
+            remote_branch_to_track(git_repo, target_branch)
+        } else {
+            None
+        };
+        let base = match (&upstream, &self.on, &self.base) {
+            (Some(upstream), _, _) => Some(upstream.clone()),
+            (None, Some(parent), _) => Some(resolve_stack_base(repo, parent)?),
+            (None, None, Some(base)) => Some(base.clone()),
+            (None, None, None) => default_base(repo)?,
+        };
+        let base_branch = base.as_deref();
+
+        if worktree_path.exists() && self.force && !is_valid_worktree(git_repo, &worktree_path)? {
+            ensure_leftover_worktree_dir(git_repo, &self.name, &worktree_path)?;
+            if !self.detach {
+                ensure_branch_creatable(git_repo, target_branch)?;
+            }
+            remove_stale_worktree(git_repo, &self.name, &worktree_path)?;
+            if !quiet {
+                println!(
+                    "Removed stale directory `{}`; it was not a registered worktree.",
+                    worktree_path.display()
+                );
+            }
+        }
 
         if worktree_path.exists() {
             if !quiet {
@@ -69,26 +208,76 @@ impl CreateCommand {
             return Ok(CreateOutcome::AlreadyExists);
         }
 
-        if let Some(parent) = worktree_path.parent() {
-            fs::create_dir_all(parent).wrap_err_with(|| {
-                eyre::eyre!("failed to prepare directory `{}`", parent.display())
-            })?;
+        let metadata_name = worktree_metadata_name(&self.name);
+        let metadata_existed = ensure_metadata_name_free(git_repo, &metadata_name, &self.name)?;
+
+        // Directories are only created once every check has passed, by `add_worktree`.
+        let created_dir = worktree_path
+            .parent()
+            .and_then(|parent| first_missing_ancestor(parent, &worktrees_dir));
+        if self.detach {
+            let cleanup = PartialCreate {
+                metadata_name: &metadata_name,
+                metadata_existed,
+                branch: None,
+                worktree_path: &worktree_path,
+                created_dir: created_dir.as_deref(),
+            };
+            return self.add_detached(repo, base_branch, cleanup, quiet);
         }
 
-        let git_repo = repo.git();
-        let reference = prepare_branch(git_repo, target_branch, base_branch)?;
-        let metadata_name = worktree_metadata_name(&self.name);
-        let mut opts = WorktreeAddOptions::new();
-        opts.reference(Some(&reference));
-        git_repo
-            .worktree(&metadata_name, &worktree_path, Some(&opts))
-            .wrap_err_with(|| {
-                eyre::eyre!(
-                    "failed to add worktree `{}` at `{}`",
-                    target_branch,
-                    worktree_path.display()
-                )
-            })?;
+        if let Some(location) = find_branch_checkout(git_repo, repo.root(), target_branch)? {
+            return Err(eyre::eyre!(
+                "branch `{}` is already checked out at `{}`; switch that checkout to another branch or remove it before creating worktree `{}`",
+                target_branch,
+                location.display(),
+                self.name
+            ));
+        }
+
+        let detached_commit = self
+            .checkout
+            .as_deref()
+            .map(|spec| resolve_commit(git_repo, spec))
+            .transpose()?;
+
+        let (reference, created_branch) = prepare_branch(git_repo, target_branch, base_branch)?;
+        let added = add_worktree(
+            git_repo,
+            &metadata_name,
+            &reference,
+            &worktree_path,
+            detached_commit,
+        )
+        .wrap_err_with(|| {
+            eyre::eyre!(
+                "failed to add worktree `{}` at `{}`",
+                target_branch,
+                worktree_path.display()
+            )
+        });
+        drop(reference);
+
+        if let Err(err) = added {
+            let cleanup = PartialCreate {
+                metadata_name: &metadata_name,
+                metadata_existed,
+                branch: created_branch.then_some(target_branch),
+                worktree_path: &worktree_path,
+                created_dir: created_dir.as_deref(),
+            };
+            cleanup.roll_back(git_repo);
+            return Err(err);
+        }
+
+        if let Some(upstream) = upstream.as_deref() {
+            git_repo
+                .find_branch(target_branch, BranchType::Local)
+                .and_then(|mut branch| branch.set_upstream(Some(upstream)))
+                .wrap_err_with(|| {
+                    eyre::eyre!("failed to set upstream of `{target_branch}` to `{upstream}`")
+                })?;
+        }
 
         if !quiet {
             let name = format!(
@@ -115,20 +304,403 @@ impl CreateCommand {
             } else {
                 println!("Created worktree `{}` at `{}`.", name, path);
             }
+            if target_branch != self.name {
+                println!(
+                    "Worktree directory `{}` is checked out on branch `{}`.",
+                    self.name, target_branch
+                );
+            }
+            if let Some(upstream) = upstream.as_deref() {
+                println!(
+                    "Created tracking branch `{}` from `{}`.",
+                    target_branch, upstream
+                );
+            } else if created_branch {
+                println!(
+                    "Created new branch `{}` from `{}`.",
+                    target_branch,
+                    base_branch.unwrap_or("HEAD")
+                );
+            }
+            if let (Some(spec), Some(commit)) = (self.checkout.as_deref(), detached_commit) {
+                let short = commit.to_string();
+                println!(
+                    "Checked out `{}` ({}) detached; branch `{}` was left at its tip.",
+                    spec,
+                    &short[..7],
+                    target_branch
+                );
+            }
+        }
+
+        if let Some(parent) = self.on.as_deref() {
+            record_stack_parent(repo, &self.name, parent)?;
+            if !quiet {
+                println!("Stacked worktree `{}` on `{}`.", self.name, parent);
+            }
+        }
+
+        record_last_created(repo, &self.name)?;
+        self.copy_untracked(repo, &worktree_path, quiet)?;
+        self.apply_template_repo(&worktree_path);
+        self.run_post_create_hook(repo, &worktree_path, quiet)?;
+
+        Ok(CreateOutcome::Created)
+    }
+
+    /// Adds the worktree with HEAD detached at `--base` (or HEAD), leaving no branch behind.
+    fn add_detached(
+        &self,
+        repo: &Repo,
+        base: Option<&str>,
+        cleanup: PartialCreate<'_>,
+        quiet: bool,
+    ) -> color_eyre::Result<CreateOutcome> {
+        let git_repo = repo.git();
+        let worktree_path = cleanup.worktree_path;
+        let spec = base.unwrap_or("HEAD");
+        let commit = git_repo
+            .revparse_single(spec)
+            .wrap_err_with(|| eyre::eyre!("failed to resolve base reference `{spec}`"))?
+            .peel_to_commit()
+            .wrap_err_with(|| eyre::eyre!("base reference `{spec}` does not point to a commit"))?;
+
+        // libgit2 only adds worktrees on a branch, so use a scratch branch and detach from it.
+        let metadata_name = cleanup.metadata_name;
+        let scratch = format!("rsworktree-detach/{metadata_name}");
+        let branch = git_repo
+            .branch(&scratch, &commit, false)
+            .wrap_err_with(|| eyre::eyre!("failed to create scratch branch `{scratch}`"))?;
+        let added = add_worktree(
+            git_repo,
+            metadata_name,
+            branch.get(),
+            worktree_path,
+            Some(commit.id()),
+        )
+        .wrap_err_with(|| {
+            eyre::eyre!(
+                "failed to add worktree `{}` at `{}`",
+                self.name,
+                worktree_path.display()
+            )
+        });
+        drop(branch);
+
+        let cleanup = PartialCreate {
+            branch: Some(&scratch),
+            ..cleanup
+        };
+        if let Err(err) = added {
+            cleanup.roll_back(git_repo);
+            return Err(err);
         }
+        git_repo
+            .find_branch(&scratch, BranchType::Local)
+            .and_then(|mut branch| branch.delete())
+            .wrap_err_with(|| eyre::eyre!("failed to delete scratch branch `{scratch}`"))?;
+
+        if !quiet {
+            let name = format!(
+                "{}",
+                self.name
+                    .as_str()
+                    .if_supports_color(Stream::Stdout, |text| {
+                        format!("{}", text.green().bold())
+                    })
+            );
+            let short = commit.id().to_string();
+            println!(
+                "Created worktree `{}` at `{}` detached at `{}` ({}).",
+                name,
+                worktree_path.display(),
+                spec,
+                &short[..7]
+            );
+        }
+
+        record_last_created(repo, &self.name)?;
+        self.copy_untracked(repo, worktree_path, quiet)?;
+        self.apply_template_repo(worktree_path);
+        self.run_post_create_hook(repo, worktree_path, quiet)?;
 
         Ok(CreateOutcome::Created)
     }
+
+    /// Copies the `create.copy_untracked` paths from the main working tree; like the hook, a
+    /// failure only warns.
+    fn copy_untracked(
+        &self,
+        repo: &Repo,
+        worktree_path: &Path,
+        quiet: bool,
+    ) -> color_eyre::Result<()> {
+        if self.no_copy {
+            return Ok(());
+        }
+        let copied = load_copy_untracked(repo)
+            .and_then(|entries| copy_untracked(repo.root(), worktree_path, &entries, quiet));
+        if let Err(err) = copied {
+            eprintln!("Warning: {err:#}");
+        }
+        Ok(())
+    }
+
+    /// Scaffolds the new worktree from `--template-repo`; a failed clone only warns.
+    fn apply_template_repo(&self, worktree_path: &Path) {
+        if let Some(source) = self.template_repo.as_deref()
+            && let Err(err) = apply_template_repo(source, worktree_path)
+        {
+            eprintln!("Warning: {err:#}");
+        }
+    }
+
+    /// Runs the `create.post_create` hook; the worktree already exists, so a failure only warns.
+    fn run_post_create_hook(
+        &self,
+        repo: &Repo,
+        worktree_path: &Path,
+        quiet: bool,
+    ) -> color_eyre::Result<()> {
+        if let Some(hook) = load_post_create_hook(repo, quiet)?
+            && let Err(err) = run_hook(
+                &mut SystemCommandRunner::default(),
+                "post-create",
+                &hook,
+                worktree_path,
+                &[(WORKTREE_NAME_ENV, &self.name)],
+                quiet,
+            )
+        {
+            eprintln!("Warning: {err:#}");
+        }
+        Ok(())
+    }
+}
+
+/// File next to the preferences holding the name of the most recently created worktree.
+const LAST_CREATED_FILE: &str = "last-created";
+
+fn last_created_path(repo: &Repo) -> PathBuf {
+    repo.preferences_path().with_file_name(LAST_CREATED_FILE)
+}
+
+fn record_last_created(repo: &Repo, name: &str) -> color_eyre::Result<()> {
+    let path = last_created_path(repo);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .wrap_err_with(|| eyre::eyre!("failed to prepare directory `{}`", parent.display()))?;
+    }
+    fs::write(&path, format!("{name}\n")).wrap_err_with(|| {
+        eyre::eyre!(
+            "failed to record last created worktree in `{}`",
+            path.display()
+        )
+    })
+}
+
+/// Name of the worktree most recently created by `rsworktree`, if any was recorded.
+pub(crate) fn last_created(repo: &Repo) -> color_eyre::Result<Option<String>> {
+    let path = last_created_path(repo);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let text = fs::read_to_string(&path)
+        .wrap_err_with(|| eyre::eyre!("failed to read `{}`", path.display()))?;
+    let name = text.trim();
+    Ok((!name.is_empty()).then(|| name.to_owned()))
+}
+
+/// Whether `path` is checked out as a registered worktree that git can still open.
+fn is_valid_worktree(repo: &git2::Repository, path: &Path) -> color_eyre::Result<bool> {
+    let Some(metadata_name) = find_worktree_metadata_name(repo, path)? else {
+        return Ok(false);
+    };
+
+    let registered = repo
+        .find_worktree(&metadata_name)
+        .is_ok_and(|worktree| worktree.validate().is_ok());
+    Ok(registered && git2::Repository::open(path).is_ok())
+}
+
+/// Refuses to let `--force` delete anything but a leftover worktree directory: one holding no
+/// registered worktree or git repository, and that is empty, has a `.git` file pointing into this
+/// repository's worktree metadata, or still has metadata registered for `name`.
+fn ensure_leftover_worktree_dir(
+    repo: &git2::Repository,
+    name: &str,
+    path: &Path,
+) -> color_eyre::Result<()> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let names = repo
+        .worktrees()
+        .wrap_err("failed to list repository worktrees")?;
+    for worktree_name in names.iter().flatten() {
+        let Ok(worktree) = repo.find_worktree(worktree_name) else {
+            continue;
+        };
+        let worktree_path = worktree
+            .path()
+            .canonicalize()
+            .unwrap_or_else(|_| worktree.path().to_path_buf());
+        if worktree_path.starts_with(&canonical) && worktree.validate().is_ok() {
+            return Err(eyre::eyre!(
+                "refusing to replace `{}`: it contains the worktree at `{}`; remove that worktree first",
+                path.display(),
+                worktree_path.display()
+            ));
+        }
+    }
+
+    if let Some(nested) = find_nested_git(path, path)? {
+        return Err(eyre::eyre!(
+            "refusing to replace `{}`: it contains a git repository at `{}`",
+            path.display(),
+            nested.display()
+        ));
+    }
+
+    let metadata_dir = repo
+        .path()
+        .join("worktrees")
+        .join(worktree_metadata_name(name));
+    let is_empty = fs::read_dir(path)
+        .wrap_err_with(|| eyre::eyre!("failed to read `{}`", path.display()))?
+        .next()
+        .is_none();
+    let points_into_repo = fs::read_to_string(path.join(".git")).is_ok_and(|gitfile| {
+        gitfile
+            .strip_prefix("gitdir:")
+            .map(|gitdir| Path::new(gitdir.trim()))
+            .is_some_and(|gitdir| gitdir.starts_with(repo.path().join("worktrees")))
+    });
+    if is_empty || points_into_repo || metadata_dir.exists() {
+        return Ok(());
+    }
+
+    Err(eyre::eyre!(
+        "refusing to replace `{}`: it does not look like a leftover worktree directory; move it away before creating worktree `{}`",
+        path.display(),
+        name
+    ))
+}
+
+/// The first `.git` below `dir`, other than the `.git` file of `root` itself. Symlinks are not
+/// followed.
+fn find_nested_git(dir: &Path, root: &Path) -> color_eyre::Result<Option<PathBuf>> {
+    let entries =
+        fs::read_dir(dir).wrap_err_with(|| eyre::eyre!("failed to read `{}`", dir.display()))?;
+    for entry in entries {
+        let entry = entry.wrap_err_with(|| eyre::eyre!("failed to read `{}`", dir.display()))?;
+        let path = entry.path();
+        if entry.file_name() == ".git" {
+            if dir != root || path.is_dir() {
+                return Ok(Some(path));
+            }
+            continue;
+        }
+        if entry.file_type().is_ok_and(|kind| kind.is_dir())
+            && let Some(nested) = find_nested_git(&path, root)?
+        {
+            return Ok(Some(nested));
+        }
+    }
+    Ok(None)
+}
+
+/// Fails when `branch` does not exist and cannot be created, e.g. because `feature/x` exists
+/// while creating `feature`, so callers can check before changing anything on disk.
+pub(crate) fn ensure_branch_creatable(
+    repo: &git2::Repository,
+    branch: &str,
+) -> color_eyre::Result<()> {
+    let full_ref = format!("refs/heads/{branch}");
+    if repo.find_reference(&full_ref).is_ok() {
+        return Ok(());
+    }
+    if !git2::Reference::is_valid_name(&full_ref) {
+        return Err(eyre::eyre!("`{branch}` is not a valid branch name"));
+    }
+
+    let conflict = branch
+        .match_indices('/')
+        .map(|(index, _)| &branch[..index])
+        .find(|parent| repo.find_reference(&format!("refs/heads/{parent}")).is_ok())
+        .map(str::to_owned)
+        .or_else(|| {
+            repo.references_glob(&format!("{full_ref}/*"))
+                .ok()?
+                .names()
+                .flatten()
+                .next()
+                .map(|name| name.trim_start_matches("refs/heads/").to_owned())
+        });
+    match conflict {
+        Some(existing) => Err(eyre::eyre!(
+            "cannot create branch `{branch}` because branch `{existing}` exists"
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Deletes a stale worktree directory along with any broken metadata left for `name`.
+fn remove_stale_worktree(
+    repo: &git2::Repository,
+    name: &str,
+    worktree_path: &Path,
+) -> color_eyre::Result<()> {
+    fs::remove_dir_all(worktree_path).wrap_err_with(|| {
+        eyre::eyre!(
+            "failed to remove stale directory `{}`",
+            worktree_path.display()
+        )
+    })?;
+
+    let metadata_name = worktree_metadata_name(name);
+    let metadata_dir = repo.path().join("worktrees").join(&metadata_name);
+    if !metadata_dir.exists() {
+        return Ok(());
+    }
+
+    match repo.find_worktree(&metadata_name) {
+        Ok(worktree) if worktree.validate().is_ok() => Ok(()),
+        Ok(worktree) => worktree.prune(None).wrap_err_with(|| {
+            eyre::eyre!("failed to prune stale worktree metadata `{metadata_name}`")
+        }),
+        Err(_) => fs::remove_dir_all(&metadata_dir).wrap_err_with(|| {
+            eyre::eyre!(
+                "failed to remove stale worktree metadata `{}`",
+                metadata_dir.display()
+            )
+        }),
+    }
 }
 
+/// `<remote>/<branch>` when no local `branch` exists yet, preferring `origin` over other remotes.
+fn remote_branch_to_track(repo: &git2::Repository, branch: &str) -> Option<String> {
+    if repo.find_branch(branch, BranchType::Local).is_ok() {
+        return None;
+    }
+
+    let remotes = repo.remotes().ok()?;
+    let mut names: Vec<&str> = remotes.iter().flatten().collect();
+    names.sort_by_key(|name| (*name != "origin", *name));
+    names
+        .into_iter()
+        .map(|remote| format!("{remote}/{branch}"))
+        .find(|candidate| repo.find_branch(candidate, BranchType::Remote).is_ok())
+}
+
+/// Returns the branch reference and whether it was created by this call.
 fn prepare_branch<'repo>(
     repo: &'repo git2::Repository,
     branch: &str,
     base: Option<&str>,
-) -> color_eyre::Result<git2::Reference<'repo>> {
+) -> color_eyre::Result<(git2::Reference<'repo>, bool)> {
     let full_ref = format!("refs/heads/{branch}");
     match repo.find_reference(&full_ref) {
-        Ok(reference) => Ok(reference),
+        Ok(reference) => Ok((reference, false)),
         Err(err) if err.code() == ErrorCode::NotFound => {
             let base_name = base.unwrap_or("HEAD");
             let object = repo
@@ -140,16 +712,169 @@ fn prepare_branch<'repo>(
             let branch = repo.branch(branch, &commit, false).wrap_err_with(|| {
                 eyre::eyre!("failed to create branch `{branch}` from `{base_name}`")
             })?;
-            Ok(branch.into_reference())
+            Ok((branch.into_reference(), true))
         }
         Err(err) => Err(eyre::eyre!("failed to look up branch `{branch}`: {err}")),
     }
 }
 
-fn worktree_metadata_name(name: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::Hasher;
+fn add_worktree(
+    repo: &git2::Repository,
+    metadata_name: &str,
+    reference: &git2::Reference<'_>,
+    worktree_path: &Path,
+    detached_commit: Option<git2::Oid>,
+) -> color_eyre::Result<()> {
+    if let Some(parent) = worktree_path.parent() {
+        fs::create_dir_all(parent)
+            .wrap_err_with(|| eyre::eyre!("failed to prepare directory `{}`", parent.display()))?;
+    }
+
+    let mut opts = WorktreeAddOptions::new();
+    opts.reference(Some(reference));
+    repo.worktree(metadata_name, worktree_path, Some(&opts))?;
+
+    if let Some(commit) = detached_commit {
+        detach_worktree_head(worktree_path, commit)?;
+    }
+
+    Ok(())
+}
+
+/// State created by a `create` that failed part-way, undone in reverse order.
+struct PartialCreate<'a> {
+    metadata_name: &'a str,
+    /// Whether `.git/worktrees/<metadata_name>` was already there before the add.
+    metadata_existed: bool,
+    /// Only set when the branch was created by this run, never for reused branches.
+    branch: Option<&'a str>,
+    worktree_path: &'a Path,
+    /// Topmost directory created for the worktree's parent, if any.
+    created_dir: Option<&'a Path>,
+}
+
+impl PartialCreate<'_> {
+    // Best effort: the original error is what the user needs to see.
+    // Only what this run created is removed; never prune, which could reach a registered
+    // worktree that happens to share the metadata name.
+    fn roll_back(&self, repo: &git2::Repository) {
+        if !self.metadata_existed {
+            let _ = fs::remove_dir_all(repo.path().join("worktrees").join(self.metadata_name));
+        }
+
+        if let Some(branch) = self.branch
+            && let Ok(mut branch) = repo.find_branch(branch, BranchType::Local)
+        {
+            let _ = branch.delete();
+        }
+
+        let _ = fs::remove_dir_all(self.worktree_path);
+        if let Some(dir) = self.created_dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Refuses a metadata name already used by a registered worktree that still exists, e.g. one
+/// moved elsewhere with `git worktree move`. Returns whether the metadata directory exists, so a
+/// failed add leaves a stale one alone.
+fn ensure_metadata_name_free(
+    repo: &git2::Repository,
+    metadata_name: &str,
+    name: &str,
+) -> color_eyre::Result<bool> {
+    if !repo.path().join("worktrees").join(metadata_name).exists() {
+        return Ok(false);
+    }
+
+    if let Ok(worktree) = repo.find_worktree(metadata_name)
+        && worktree.validate().is_ok()
+    {
+        return Err(eyre::eyre!(
+            "cannot create worktree `{}`: its git metadata `{}` belongs to the worktree at `{}`; remove or repair that worktree first",
+            name,
+            metadata_name,
+            worktree.path().display()
+        ));
+    }
+    Ok(true)
+}
+
+/// Returns the outermost ancestor of `path` (below `stop`) that does not exist yet.
+fn first_missing_ancestor(path: &Path, stop: &Path) -> Option<PathBuf> {
+    let mut missing = None;
+    for ancestor in path.ancestors() {
+        if ancestor == stop || ancestor.exists() {
+            break;
+        }
+        missing = Some(ancestor.to_path_buf());
+    }
+    missing
+}
+
+fn resolve_commit(repo: &git2::Repository, spec: &str) -> color_eyre::Result<git2::Oid> {
+    let object = repo
+        .revparse_single(spec)
+        .wrap_err_with(|| eyre::eyre!("failed to resolve checkout reference `{spec}`"))?;
+    let commit = object
+        .peel_to_commit()
+        .wrap_err_with(|| eyre::eyre!("checkout reference `{spec}` does not point to a commit"))?;
+    Ok(commit.id())
+}
+
+fn detach_worktree_head(path: &Path, commit: git2::Oid) -> color_eyre::Result<()> {
+    let worktree_repo = git2::Repository::open(path)
+        .wrap_err_with(|| eyre::eyre!("failed to open worktree at `{}`", path.display()))?;
+    worktree_repo
+        .set_head_detached(commit)
+        .wrap_err_with(|| eyre::eyre!("failed to detach worktree HEAD at `{commit}`"))?;
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    worktree_repo
+        .checkout_head(Some(&mut checkout))
+        .wrap_err_with(|| eyre::eyre!("failed to check out `{commit}` in `{}`", path.display()))?;
+    Ok(())
+}
+
+pub(crate) fn find_branch_checkout(
+    repo: &git2::Repository,
+    root: &Path,
+    branch: &str,
+) -> color_eyre::Result<Option<PathBuf>> {
+    let full_ref = format!("refs/heads/{branch}");
+
+    if let Ok(head) = repo.head()
+        && head.name() == Some(full_ref.as_str())
+    {
+        return Ok(Some(root.to_path_buf()));
+    }
+
+    let names = repo
+        .worktrees()
+        .wrap_err("failed to list repository worktrees")?;
+
+    for name in names.iter().flatten() {
+        let Ok(worktree) = repo.find_worktree(name) else {
+            continue;
+        };
+        let Ok(worktree_repo) = git2::Repository::open_from_worktree(&worktree) else {
+            continue;
+        };
+        if let Ok(head) = worktree_repo.head()
+            && head.name() == Some(full_ref.as_str())
+        {
+            return Ok(Some(worktree.path().to_path_buf()));
+        }
+    }
+
+    Ok(None)
+}
 
+/// Git metadata name for the managed worktree `name`.
+///
+/// The result only depends on `name` (FNV-1a, not the std hasher), so it stays the same across
+/// runs and toolchains.
+pub(crate) fn worktree_metadata_name(name: &str) -> String {
     let sanitized: String = name
         .chars()
         .map(|ch| match ch {
@@ -159,9 +884,7 @@ fn worktree_metadata_name(name: &str) -> String {
         })
         .collect();
 
-    let mut hasher = DefaultHasher::new();
-    hasher.write(name.as_bytes());
-    let hash = hasher.finish();
+    let hash = fnv1a64(name.as_bytes());
 
     let base = sanitized.trim_matches('-');
     let trimmed: String = if base.is_empty() {
@@ -173,6 +896,52 @@ fn worktree_metadata_name(name: &str) -> String {
     format!("rsworktree-{trimmed}-{hash:016x}")
 }
 
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Looks up the git metadata name of the worktree checked out at `worktree_path`.
+///
+/// Worktrees created by older versions may use a different naming scheme, so this matches by
+/// path rather than recomputing [`worktree_metadata_name`].
+pub(crate) fn find_worktree_metadata_name(
+    repo: &git2::Repository,
+    worktree_path: &Path,
+) -> color_eyre::Result<Option<String>> {
+    let target = worktree_path
+        .canonicalize()
+        .unwrap_or_else(|_| worktree_path.to_path_buf());
+
+    let names = repo
+        .worktrees()
+        .wrap_err("failed to list repository worktrees")?;
+
+    for name in names.iter().flatten() {
+        let worktree = match repo.find_worktree(name) {
+            Ok(worktree) => worktree,
+            Err(err) if err.code() == ErrorCode::NotFound => continue,
+            Err(err) => {
+                return Err(eyre::eyre!("failed to open git worktree `{name}`: {err}"));
+            }
+        };
+
+        let path = worktree
+            .path()
+            .canonicalize()
+            .unwrap_or_else(|_| worktree.path().to_path_buf());
+        if path == target {
+            return Ok(Some(name.to_owned()));
+        }
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,27 +949,7 @@ mod tests {
 
     use tempfile::TempDir;
 
-    use crate::{Repo, commands::cd::SHELL_OVERRIDE_ENV};
-
-    fn init_git_repo(dir: &TempDir) -> color_eyre::Result<()> {
-        run(dir, ["git", "init"])?;
-        fs::write(dir.path().join("README.md"), "test")?;
-        run(dir, ["git", "add", "README.md"])?;
-        run(
-            dir,
-            [
-                "git",
-                "-c",
-                "user.name=Test",
-                "-c",
-                "user.email=test@example.com",
-                "commit",
-                "-m",
-                "Initial commit",
-            ],
-        )?;
-        Ok(())
-    }
+    use crate::{Repo, commands::cd::SHELL_OVERRIDE_ENV, test_support::init_repo};
 
     fn run(dir: &TempDir, cmd: impl IntoIterator<Item = &'static str>) -> color_eyre::Result<()> {
         let mut iter = cmd.into_iter();
@@ -221,9 +970,7 @@ mod tests {
     #[test]
     fn creates_new_worktree_under_rsworktree_directory() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
-
-        let repo = Repo::discover_from(dir.path())?;
+        let repo = init_repo(&dir)?;
         unsafe {
             std::env::set_var(SHELL_OVERRIDE_ENV, "env");
         }
@@ -289,18 +1036,369 @@ mod tests {
         assert!(sanitized.chars().all(|c| c == 'a'));
     }
 
+    #[test]
+    fn metadata_name_is_stable_for_the_same_input() {
+        assert_eq!(
+            worktree_metadata_name("feature/test"),
+            worktree_metadata_name("feature/test")
+        );
+        assert_ne!(
+            worktree_metadata_name("feature/test"),
+            worktree_metadata_name("feature-test")
+        );
+        assert_eq!(
+            worktree_metadata_name("feature/test"),
+            format!("rsworktree-feature-test-{:016x}", fnv1a64(b"feature/test"))
+        );
+        assert_eq!(fnv1a64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a64(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn finds_metadata_name_for_worktree_path() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+
+        CreateCommand::new("feature/lookup".into(), None).create_without_enter(&repo, true)?;
+        let worktree_path = repo.worktrees_dir().join("feature/lookup");
+
+        let found = find_worktree_metadata_name(repo.git(), &worktree_path)?;
+        assert_eq!(found, Some(worktree_metadata_name("feature/lookup")));
+
+        let missing = find_worktree_metadata_name(repo.git(), &repo.worktrees_dir().join("nope"))?;
+        assert_eq!(missing, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_branch_checked_out_in_main_repository() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let head = repo.git().head()?;
+        let current = head
+            .shorthand()
+            .expect("HEAD should be a branch")
+            .to_owned();
+        drop(head);
+
+        let command = CreateCommand::new(current.clone(), None);
+        let err = command.create_without_enter(&repo, true).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains(&format!("branch `{current}` is already checked out")));
+        assert!(message.contains(&repo.root().display().to_string()));
+        assert!(!repo.worktrees_dir().join(&current).exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_branch_checked_out_in_another_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        init_repo(&dir)?;
+        let outside = TempDir::new()?;
+        let outside_path = outside.path().join("elsewhere");
+        let outside_arg = outside_path.to_string_lossy().into_owned();
+
+        let status = StdCommand::new("git")
+            .current_dir(dir.path())
+            .args(["worktree", "add", "-b", "feature/busy", &outside_arg])
+            .status()?;
+        assert!(status.success(), "git worktree add should succeed");
+
+        let repo = Repo::discover_from(dir.path())?;
+        let command = CreateCommand::new("feature/busy".into(), None);
+        let err = command.create_without_enter(&repo, true).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("branch `feature/busy` is already checked out at"),
+            "unexpected error: {err}"
+        );
+        assert!(err.to_string().contains("elsewhere"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_detaches_worktree_and_leaves_branch_tip() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let older = repo.git().head()?.peel_to_commit()?.id();
+
+        fs::write(dir.path().join("NEXT.md"), "next")?;
+        run(&dir, ["git", "add", "NEXT.md"])?;
+        run(
+            &dir,
+            [
+                "git",
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "Second commit",
+            ],
+        )?;
+        let tip = repo.git().head()?.peel_to_commit()?.id();
+
+        let command = CreateCommand::new("feature/bisect".into(), None)
+            .with_checkout(Some(older.to_string()));
+        command.create_without_enter(&repo, true)?;
+
+        let worktree_path = repo.worktrees_dir().join("feature/bisect");
+        let worktree_repo = git2::Repository::open(&worktree_path)?;
+        assert!(worktree_repo.head_detached()?);
+        assert_eq!(worktree_repo.head()?.peel_to_commit()?.id(), older);
+        assert!(!worktree_path.join("NEXT.md").exists());
+
+        let branch = repo
+            .git()
+            .find_branch("feature/bisect", git2::BranchType::Local)?;
+        assert_eq!(branch.get().peel_to_commit()?.id(), tip);
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_rejects_unknown_reference() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+
+        let command = CreateCommand::new("feature/missing".into(), None)
+            .with_checkout(Some("does-not-exist".into()));
+        let err = command.create_without_enter(&repo, true).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("failed to resolve checkout reference `does-not-exist`")
+        );
+        assert!(!repo.worktrees_dir().join("feature/missing").exists());
+
+        Ok(())
+    }
+
+    fn block_worktree_metadata(repo: &Repo, name: &str) -> color_eyre::Result<()> {
+        // libgit2 refuses to add a worktree whose metadata directory already exists.
+        let metadata_dir = repo
+            .git()
+            .path()
+            .join("worktrees")
+            .join(worktree_metadata_name(name));
+        fs::create_dir_all(&metadata_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn failed_worktree_add_removes_new_branch_and_directories() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        block_worktree_metadata(&repo, "feature/nested/broken")?;
+
+        let command = CreateCommand::new("feature/nested/broken".into(), None);
+        let err = command.create_without_enter(&repo, true).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("failed to add worktree `feature/nested/broken`")
+        );
+
+        let branch = repo
+            .git()
+            .find_branch("feature/nested/broken", git2::BranchType::Local);
+        assert!(matches!(branch, Err(err) if err.code() == ErrorCode::NotFound));
+        assert!(!repo.worktrees_dir().join("feature").exists());
+        assert!(repo.worktrees_dir().exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_checks_leave_no_directories() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        repo.ensure_worktrees_dir()?;
+
+        let bad_base = CreateCommand::new("deep/nested/wt".into(), Some("nope".into()));
+        assert!(bad_base.create_without_enter(&repo, true).is_err());
+        let bad_checkout =
+            CreateCommand::new("deep2/wt".into(), None).with_checkout(Some("nope".into()));
+        assert!(bad_checkout.create_without_enter(&repo, true).is_err());
+        let detached = CreateCommand::new("deep3/wt".into(), Some("nope".into())).with_detach(true);
+        assert!(detached.create_without_enter(&repo, true).is_err());
+        let head = repo.git().head()?;
+        let checked_out = head.shorthand().expect("branch name").to_owned();
+        let taken = CreateCommand::new("deep4/wt".into(), None).with_branch(Some(checked_out));
+        assert!(taken.create_without_enter(&repo, true).is_err());
+
+        for name in ["deep", "deep2", "deep3", "deep4"] {
+            assert!(
+                !repo.worktrees_dir().join(name).exists(),
+                "{name} left behind"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn failed_worktree_add_keeps_reused_branch() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let head = repo.git().head()?.peel_to_commit()?;
+        repo.git().branch("feature/existing", &head, false)?;
+        block_worktree_metadata(&repo, "feature/existing")?;
+
+        let command = CreateCommand::new("feature/existing".into(), None);
+        assert!(command.create_without_enter(&repo, true).is_err());
+
+        assert!(
+            repo.git()
+                .find_branch("feature/existing", git2::BranchType::Local)
+                .is_ok(),
+            "pre-existing branch must survive a failed create"
+        );
+        assert!(!repo.worktrees_dir().join("feature").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_metadata_name_of_moved_worktree_and_keeps_it() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feat".into(), None).create_without_enter(&repo, true)?;
+        let moved = dir.path().join("moved");
+        fs::write(repo.worktrees_dir().join("feat/note.txt"), "uncommitted")?;
+        let status = StdCommand::new("git")
+            .current_dir(dir.path())
+            .args(["worktree", "move", ".rsworktree/feat"])
+            .arg(&moved)
+            .status()?;
+        assert!(status.success());
+
+        let err = CreateCommand::new("feat".into(), None)
+            .create_without_enter(&repo, true)
+            .expect_err("metadata name is taken by the moved worktree");
+        assert!(
+            err.to_string().contains("belongs to the worktree at"),
+            "{err}"
+        );
+
+        assert_eq!(fs::read_to_string(moved.join("note.txt"))?, "uncommitted");
+        assert!(is_valid_worktree(repo.git(), &moved)?);
+        assert!(!repo.worktrees_dir().join("feat").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn force_replaces_stale_worktree_directory() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let stale = repo.worktrees_dir().join("feature/stale");
+        fs::create_dir_all(&stale)?;
+        block_worktree_metadata(&repo, "feature/stale")?;
+
+        let outcome =
+            CreateCommand::new("feature/stale".into(), None).create_without_enter(&repo, true)?;
+        assert_eq!(outcome, CreateOutcome::AlreadyExists);
+
+        let outcome = CreateCommand::new("feature/stale".into(), None)
+            .with_force(true)
+            .create_without_enter(&repo, true)?;
+        assert_eq!(outcome, CreateOutcome::Created);
+        assert!(is_valid_worktree(repo.git(), &stale)?);
+        assert!(stale.join("README.md").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn force_refuses_directory_holding_other_worktrees() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature/x".into(), None).create_without_enter(&repo, true)?;
+        let note = repo.worktrees_dir().join("feature/x/note.txt");
+        fs::write(&note, "uncommitted")?;
+
+        let err = CreateCommand::new("feature".into(), None)
+            .with_force(true)
+            .create_without_enter(&repo, true)
+            .expect_err("`feature` holds the `feature/x` worktree");
+        assert!(
+            err.to_string().contains("contains the worktree at"),
+            "{err}"
+        );
+        assert_eq!(fs::read_to_string(&note)?, "uncommitted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn force_refuses_unrelated_directory_and_impossible_branch() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let unrelated = repo.worktrees_dir().join("notes");
+        fs::create_dir_all(&unrelated)?;
+        fs::write(unrelated.join("todo.txt"), "keep me")?;
+
+        let err = CreateCommand::new("notes".into(), None)
+            .with_force(true)
+            .create_without_enter(&repo, true)
+            .expect_err("`notes` is not a leftover worktree");
+        assert!(
+            err.to_string().contains("does not look like a leftover"),
+            "{err}"
+        );
+        assert!(unrelated.join("todo.txt").exists());
+
+        let head = repo.git().head()?.peel_to_commit()?;
+        repo.git().branch("stale/child", &head, false)?;
+        let stale = repo.worktrees_dir().join("stale");
+        fs::create_dir_all(&stale)?;
+        block_worktree_metadata(&repo, "stale")?;
+        let err = CreateCommand::new("stale".into(), None)
+            .with_force(true)
+            .create_without_enter(&repo, true)
+            .expect_err("branch `stale` conflicts with `stale/child`");
+        assert_eq!(
+            err.to_string(),
+            "cannot create branch `stale` because branch `stale/child` exists"
+        );
+        assert!(stale.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn force_keeps_valid_worktree_with_changes() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature/live".into(), None).create_without_enter(&repo, true)?;
+        let note = repo.worktrees_dir().join("feature/live").join("note.txt");
+        fs::write(&note, "uncommitted")?;
+
+        let outcome = CreateCommand::new("feature/live".into(), None)
+            .with_force(true)
+            .create_without_enter(&repo, true)?;
+
+        assert_eq!(outcome, CreateOutcome::AlreadyExists);
+        assert_eq!(fs::read_to_string(&note)?, "uncommitted");
+
+        Ok(())
+    }
+
     #[test]
     fn prepare_branch_reuses_existing_reference() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
+        init_repo(&dir)?;
 
         let repo = git2::Repository::open(dir.path())?;
         let commit = repo.head()?.peel_to_commit()?;
         repo.branch("feature/test", &commit, false)?;
 
-        let reference = prepare_branch(&repo, "feature/test", Some("HEAD"))?;
+        let (reference, created) = prepare_branch(&repo, "feature/test", Some("HEAD"))?;
 
         assert_eq!(reference.name(), Some("refs/heads/feature/test"));
+        assert!(!created);
 
         Ok(())
     }
@@ -308,14 +1406,15 @@ mod tests {
     #[test]
     fn prepare_branch_creates_branch_from_base() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
+        init_repo(&dir)?;
 
         let repo = git2::Repository::open(dir.path())?;
         let head = repo.head()?.peel_to_commit()?;
 
-        let reference = prepare_branch(&repo, "feature/new", Some("HEAD"))?;
+        let (reference, was_created) = prepare_branch(&repo, "feature/new", Some("HEAD"))?;
         let created = repo.find_reference("refs/heads/feature/new")?;
 
+        assert!(was_created);
         assert_eq!(reference.name(), created.name());
         assert_eq!(created.peel_to_commit()?.id(), head.id());
 
@@ -0,0 +1,143 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use color_eyre::eyre::{self, WrapErr};
+
+use super::copy::copy_path;
+
+/// Copies the files of the template repository `source` (a URL or path `git clone` accepts)
+/// into `worktree_path`, leaving files the worktree already has untouched.
+pub(crate) fn apply_template_repo(source: &str, worktree_path: &Path) -> color_eyre::Result<()> {
+    let checkout = TemplateCheckout::clone(source)?;
+    let entries = fs::read_dir(&checkout.path)
+        .wrap_err_with(|| eyre::eyre!("failed to read `{}`", checkout.path.display()))?;
+    for entry in entries {
+        let entry =
+            entry.wrap_err_with(|| eyre::eyre!("failed to read `{}`", checkout.path.display()))?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        copy_path(&entry.path(), &worktree_path.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+/// Distinguishes scratch clones made by the same process.
+static CLONE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Shallow clone of a template repository in a scratch directory, removed on drop.
+struct TemplateCheckout {
+    path: PathBuf,
+}
+
+impl TemplateCheckout {
+    fn clone(source: &str) -> color_eyre::Result<Self> {
+        let path = env::temp_dir().join(format!(
+            "rsworktree-template-{}-{}",
+            process::id(),
+            CLONE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if path.exists() {
+            fs::remove_dir_all(&path)
+                .wrap_err_with(|| eyre::eyre!("failed to remove `{}`", path.display()))?;
+        }
+
+        let output = Command::new("git")
+            .args(["clone", "--quiet", "--depth", "1", "--no-local", "--"])
+            .arg(source)
+            .arg(&path)
+            .output()
+            .wrap_err("failed to run `git clone`")?;
+        let checkout = Self { path };
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let mut message = format!("failed to clone template repository `{source}`");
+            if !stderr.trim().is_empty() {
+                message.push('\n');
+                message.push_str(stderr.trim());
+            }
+            return Err(eyre::eyre!(message));
+        }
+        Ok(checkout)
+    }
+}
+
+impl Drop for TemplateCheckout {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> color_eyre::Result<()> {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()?;
+        if !status.success() {
+            return Err(eyre::eyre!("`git {}` failed", args.join(" ")));
+        }
+        Ok(())
+    }
+
+    /// A bare repository holding `Makefile` and `ci/pipeline.yml`.
+    fn template_repo(dir: &TempDir) -> color_eyre::Result<PathBuf> {
+        let work = dir.path().join("work");
+        fs::create_dir_all(work.join("ci"))?;
+        fs::write(work.join("Makefile"), "all:\n")?;
+        fs::write(work.join("ci/pipeline.yml"), "steps: []\n")?;
+        git(&work, &["init", "--quiet"])?;
+        git(&work, &["add", "."])?;
+        git(&work, &["commit", "--quiet", "-m", "scaffold"])?;
+
+        let bare = dir.path().join("template.git");
+        git(
+            dir.path(),
+            &["clone", "--quiet", "--bare", "work", "template.git"],
+        )?;
+        Ok(bare)
+    }
+
+    #[test]
+    fn copies_template_files_without_overwriting_existing_ones() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let bare = template_repo(&dir)?;
+        let worktree = dir.path().join("worktree");
+        fs::create_dir_all(&worktree)?;
+        fs::write(worktree.join("Makefile"), "tracked\n")?;
+
+        apply_template_repo(&bare.to_string_lossy(), &worktree)?;
+
+        assert_eq!(fs::read_to_string(worktree.join("Makefile"))?, "tracked\n");
+        assert_eq!(
+            fs::read_to_string(worktree.join("ci/pipeline.yml"))?,
+            "steps: []\n"
+        );
+        assert!(!worktree.join(".git").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn reports_clone_failures() {
+        let dir = TempDir::new().expect("tempdir");
+        let missing = dir.path().join("missing.git");
+
+        let err = apply_template_repo(&missing.to_string_lossy(), dir.path())
+            .expect_err("clone should fail");
+
+        assert!(
+            err.to_string()
+                .starts_with("failed to clone template repository")
+        );
+    }
+}
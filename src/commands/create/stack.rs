@@ -0,0 +1,214 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use color_eyre::eyre::{self, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::{Repo, commands::list::worktree_branch};
+
+/// File next to the preferences recording which worktree each stacked worktree was created on.
+const STACKS_FILE: &str = "stacks.json";
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct StacksFile {
+    /// Worktree name to the name of the worktree it was stacked on.
+    #[serde(default)]
+    parents: BTreeMap<String, String>,
+}
+
+fn stacks_path(repo: &Repo) -> PathBuf {
+    repo.preferences_path().with_file_name(STACKS_FILE)
+}
+
+fn load_stacks(repo: &Repo) -> color_eyre::Result<StacksFile> {
+    let path = stacks_path(repo);
+    if !path.exists() {
+        return Ok(StacksFile::default());
+    }
+
+    let text = fs::read_to_string(&path)
+        .wrap_err_with(|| eyre::eyre!("failed to read `{}`", path.display()))?;
+    serde_json::from_str(&text)
+        .wrap_err_with(|| eyre::eyre!("failed to parse `{}`", path.display()))
+}
+
+fn save_stacks(repo: &Repo, stacks: &StacksFile) -> color_eyre::Result<()> {
+    let path = stacks_path(repo);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .wrap_err_with(|| eyre::eyre!("failed to prepare directory `{}`", parent.display()))?;
+    }
+    let text = serde_json::to_string_pretty(stacks).wrap_err("failed to serialize stacks")?;
+    fs::write(&path, format!("{text}\n"))
+        .wrap_err_with(|| eyre::eyre!("failed to write `{}`", path.display()))
+}
+
+fn update_stacks(repo: &Repo, update: impl FnOnce(&mut StacksFile)) -> color_eyre::Result<()> {
+    if !stacks_path(repo).exists() {
+        return Ok(());
+    }
+    let mut stacks = load_stacks(repo)?;
+    update(&mut stacks);
+    save_stacks(repo, &stacks)
+}
+
+/// Branch checked out in the worktree `parent`, used as the base of a stacked worktree.
+pub(crate) fn resolve_stack_base(repo: &Repo, parent: &str) -> color_eyre::Result<String> {
+    let path = repo.worktrees_dir().join(parent);
+    if !path.exists() {
+        return Err(eyre::eyre!(
+            "worktree `{parent}` does not exist at `{}`",
+            path.display()
+        ));
+    }
+
+    worktree_branch(&path).ok_or_else(|| {
+        eyre::eyre!("worktree `{parent}` is not checked out on a branch to stack on")
+    })
+}
+
+/// Records that worktree `name` was stacked on worktree `parent`.
+pub(crate) fn record_stack_parent(repo: &Repo, name: &str, parent: &str) -> color_eyre::Result<()> {
+    let mut stacks = load_stacks(repo)?;
+    stacks.parents.insert(name.to_owned(), parent.to_owned());
+    save_stacks(repo, &stacks)
+}
+
+/// Drops the record of worktree `name` and of the worktrees stacked on it.
+pub(crate) fn forget_stack_worktree(repo: &Repo, name: &str) -> color_eyre::Result<()> {
+    update_stacks(repo, |stacks| {
+        stacks
+            .parents
+            .retain(|child, parent| child != name && parent != name);
+    })
+}
+
+/// Follows worktree `old` being renamed to `new` in both directions of the stack records.
+pub(crate) fn rename_stack_worktree(repo: &Repo, old: &str, new: &str) -> color_eyre::Result<()> {
+    update_stacks(repo, |stacks| {
+        if let Some(parent) = stacks.parents.remove(old) {
+            stacks.parents.insert(new.to_owned(), parent);
+        }
+        for parent in stacks.parents.values_mut() {
+            if parent == old {
+                *parent = new.to_owned();
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::{commands::create::CreateCommand, test_support};
+
+    fn init_repo(dir: &TempDir) -> color_eyre::Result<Repo> {
+        let repo = test_support::init_repo(dir)?;
+        repo.ensure_worktrees_dir()?;
+        Ok(repo)
+    }
+
+    #[test]
+    fn resolves_base_from_parent_worktree_branch() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("part1".into(), None)
+            .with_branch(Some("feat/x-part1".into()))
+            .create_without_enter(&repo, true)?;
+
+        assert_eq!(resolve_stack_base(&repo, "part1")?, "feat/x-part1");
+
+        let err = resolve_stack_base(&repo, "missing").expect_err("missing worktree");
+        assert!(
+            err.to_string()
+                .starts_with("worktree `missing` does not exist")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn records_parents_without_dropping_earlier_entries() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+
+        record_stack_parent(&repo, "part2", "part1")?;
+        record_stack_parent(&repo, "part3", "part2")?;
+
+        let stacks = load_stacks(&repo)?;
+        assert_eq!(stacks.parents.len(), 2);
+        assert_eq!(stacks.parents["part2"], "part1");
+        assert_eq!(stacks.parents["part3"], "part2");
+        Ok(())
+    }
+
+    #[test]
+    fn create_on_stacks_new_branch_on_parent_tip() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feat/x-part1".into(), None).create_without_enter(&repo, true)?;
+
+        let parent_path = repo.worktrees_dir().join("feat/x-part1");
+        let parent = git2::Repository::open(&parent_path)?;
+        let signature = git2::Signature::now("Test", "test@example.com")?;
+        let head = parent.head()?.peel_to_commit()?;
+        let tip = parent.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "part1 work",
+            &head.tree()?,
+            &[&head],
+        )?;
+
+        CreateCommand::new("feat/x-part2".into(), None)
+            .with_on(Some("feat/x-part1".into()))
+            .create_without_enter(&repo, true)?;
+
+        let child = repo
+            .git()
+            .find_branch("feat/x-part2", git2::BranchType::Local)?;
+        assert_eq!(child.get().target(), Some(tip));
+        assert_eq!(load_stacks(&repo)?.parents["feat/x-part2"], "feat/x-part1");
+        Ok(())
+    }
+
+    #[test]
+    fn follows_renames_and_removals() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        record_stack_parent(&repo, "part2", "part1")?;
+        record_stack_parent(&repo, "part3", "part2")?;
+        record_stack_parent(&repo, "other", "base")?;
+
+        rename_stack_worktree(&repo, "part2", "middle")?;
+        let stacks = load_stacks(&repo)?;
+        assert_eq!(stacks.parents["middle"], "part1");
+        assert_eq!(stacks.parents["part3"], "middle");
+        assert!(!stacks.parents.contains_key("part2"));
+
+        forget_stack_worktree(&repo, "middle")?;
+        let stacks = load_stacks(&repo)?;
+        assert_eq!(stacks.parents.len(), 1);
+        assert_eq!(stacks.parents["other"], "base");
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_stacks_next_to_preferences() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        fs::create_dir_all(dir.path().join(".rsworktree"))?;
+        fs::write(
+            dir.path().join(".rsworktree/preferences.json"),
+            r#"{"worktrees_dir": "trees"}"#,
+        )?;
+        let repo = init_repo(&dir)?;
+
+        record_stack_parent(&repo, "part2", "part1")?;
+
+        assert!(dir.path().join(".rsworktree").join(STACKS_FILE).exists());
+        assert!(!repo.worktrees_dir().join(STACKS_FILE).exists());
+        Ok(())
+    }
+}
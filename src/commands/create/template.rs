@@ -0,0 +1,171 @@
+use std::env;
+
+use color_eyre::eyre;
+use serde::Deserialize;
+
+use crate::Repo;
+
+const NAME_PLACEHOLDER: &str = "{name}";
+const USER_PLACEHOLDER: &str = "{user}";
+
+/// The `create` section of the preferences file.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct CreatePreferences {
+    #[serde(default)]
+    name_template: Option<String>,
+    #[serde(default)]
+    base_default: Option<String>,
+    #[serde(default)]
+    pub(crate) post_create: Option<String>,
+    #[serde(default)]
+    pub(crate) post_create_when_quiet: bool,
+    #[serde(default)]
+    pub(crate) copy_untracked: Vec<String>,
+}
+
+/// Expands `name` with the `create.name_template` preference, if one is configured.
+pub fn apply_name_template(repo: &Repo, name: &str) -> color_eyre::Result<String> {
+    let Some(template) = load_name_template(repo)? else {
+        return Ok(name.to_owned());
+    };
+
+    let user = if template.contains(USER_PLACEHOLDER) {
+        Some(resolve_user(repo).ok_or_else(|| {
+            eyre::eyre!(
+                "`create.name_template` uses `{USER_PLACEHOLDER}` but neither `$USER` nor git `user.name` is set"
+            )
+        })?)
+    } else {
+        None
+    };
+
+    expand_name_template(&template, name, user.as_deref())
+}
+
+/// The `create.base_default` preference, used as the base when `--base` is not given.
+pub fn default_base(repo: &Repo) -> color_eyre::Result<Option<String>> {
+    Ok(load_create_preferences(repo)?
+        .base_default
+        .map(|base| base.trim().to_owned())
+        .filter(|base| !base.is_empty()))
+}
+
+/// The `create.name_template` preference, if set to a non-empty template.
+pub(crate) fn load_name_template(repo: &Repo) -> color_eyre::Result<Option<String>> {
+    Ok(load_create_preferences(repo)?
+        .name_template
+        .filter(|template| !template.trim().is_empty()))
+}
+
+pub(super) fn load_create_preferences(repo: &Repo) -> color_eyre::Result<CreatePreferences> {
+    Ok(repo.preferences()?.create)
+}
+
+fn expand_name_template(
+    template: &str,
+    name: &str,
+    user: Option<&str>,
+) -> color_eyre::Result<String> {
+    if !template.contains(NAME_PLACEHOLDER) {
+        return Err(eyre::eyre!(
+            "`create.name_template` must contain `{NAME_PLACEHOLDER}`"
+        ));
+    }
+
+    let mut expanded = template.replace(NAME_PLACEHOLDER, name);
+    if let Some(user) = user {
+        expanded = expanded.replace(USER_PLACEHOLDER, user);
+    }
+
+    Ok(expanded)
+}
+
+fn resolve_user(repo: &Repo) -> Option<String> {
+    if let Ok(user) = env::var("USER")
+        && !user.trim().is_empty()
+    {
+        return Some(user.trim().to_owned());
+    }
+
+    let config = repo.git().config().ok()?;
+    let name = config.get_string("user.name").ok()?;
+    let slug = name.split_whitespace().collect::<Vec<_>>().join("-");
+    (!slug.is_empty()).then_some(slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::test_support::{init_repo, write_preferences};
+
+    #[test]
+    fn expands_user_and_name_placeholders() {
+        let expanded = expand_name_template("{user}/{name}", "feat-x", Some("alice")).unwrap();
+        assert_eq!(expanded, "alice/feat-x");
+
+        let ticket = expand_name_template("tickets/{name}", "ABC-12", None).unwrap();
+        assert_eq!(ticket, "tickets/ABC-12");
+    }
+
+    #[test]
+    fn template_without_name_placeholder_is_rejected() {
+        let err = expand_name_template("{user}/fixed", "feat-x", Some("alice")).unwrap_err();
+        assert!(err.to_string().contains("must contain `{name}`"));
+    }
+
+    #[test]
+    fn name_is_unchanged_without_template() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = init_repo(&dir).expect("repo");
+        assert_eq!(apply_name_template(&repo, "feat-x").unwrap(), "feat-x");
+
+        write_preferences(&repo, serde_json::json!({ "editor": { "command": "vim" } }));
+        assert_eq!(apply_name_template(&repo, "feat-x").unwrap(), "feat-x");
+    }
+
+    #[test]
+    fn applies_template_from_preferences() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = init_repo(&dir).expect("repo");
+        write_preferences(
+            &repo,
+            serde_json::json!({ "create": { "name_template": "team/{name}" } }),
+        );
+
+        assert_eq!(apply_name_template(&repo, "feat-x").unwrap(), "team/feat-x");
+    }
+
+    #[test]
+    fn reads_base_default_from_preferences() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = init_repo(&dir).expect("repo");
+        assert_eq!(default_base(&repo).unwrap(), None);
+
+        write_preferences(
+            &repo,
+            serde_json::json!({ "create": { "base_default": " develop " } }),
+        );
+        assert_eq!(default_base(&repo).unwrap().as_deref(), Some("develop"));
+
+        write_preferences(
+            &repo,
+            serde_json::json!({ "create": { "base_default": "" } }),
+        );
+        assert_eq!(default_base(&repo).unwrap(), None);
+    }
+
+    #[test]
+    fn malformed_preferences_are_reported() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = init_repo(&dir).expect("repo");
+        repo.ensure_worktrees_dir().expect("worktrees dir");
+        fs::write(repo.preferences_path(), "{ nope").expect("write config");
+
+        let err = apply_name_template(&repo, "feat-x").unwrap_err();
+        assert!(err.to_string().contains("failed to parse"));
+    }
+}
@@ -0,0 +1,51 @@
+use color_eyre::eyre::WrapErr;
+
+use crate::{
+    Repo,
+    commands::interactive::{AheadBehind, Theme, collect_details, detail_lines},
+    error::WorktreeNotFound,
+};
+
+#[derive(Debug)]
+pub struct DescribeCommand {
+    name: String,
+    json: bool,
+}
+
+impl DescribeCommand {
+    pub fn new(name: String) -> Self {
+        Self { name, json: false }
+    }
+
+    /// Print the details as a JSON object instead of text.
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
+        let worktrees_dir = repo.worktrees_dir();
+        let path = worktrees_dir.join(&self.name);
+        if !path.exists() {
+            return Err(WorktreeNotFound::new(&self.name, &worktrees_dir).into());
+        }
+
+        let details = collect_details(&self.name, &path, AheadBehind::default());
+        if self.json {
+            let text = serde_json::to_string_pretty(&details)
+                .wrap_err("failed to serialize worktree details")?;
+            println!("{text}");
+            return Ok(());
+        }
+
+        for line in detail_lines(&details, &Theme::default(), AheadBehind::default()) {
+            let text: String = line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect();
+            println!("{}", text.trim_end());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use color_eyre::eyre;
+use owo_colors::{OwoColorize, Stream};
+
+use crate::{
+    Repo,
+    commands::{
+        batch::{BatchOutcome, BatchReporter},
+        list::{find_worktrees, format_worktree},
+        pr_github::{CommandOutput, CommandRunner, SystemCommandRunner},
+    },
+};
+
+#[derive(Debug)]
+pub struct ExecCommand<R = SystemCommandRunner> {
+    command: Vec<String>,
+    fail_fast: bool,
+    max_parallel: usize,
+    json_lines: bool,
+    runner: R,
+}
+
+impl ExecCommand {
+    pub fn new(command: Vec<String>) -> Self {
+        Self::with_runner(command, SystemCommandRunner::default())
+    }
+}
+
+impl<R> ExecCommand<R>
+where
+    R: CommandRunner + Clone + Send,
+{
+    pub fn with_runner(command: Vec<String>, runner: R) -> Self {
+        Self {
+            command,
+            fail_fast: false,
+            max_parallel: 1,
+            json_lines: false,
+            runner,
+        }
+    }
+
+    /// Stop at the first worktree where the command fails instead of running it everywhere.
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Run the command in up to `max_parallel` worktrees at once. Above one, each worktree's
+    /// output is captured and printed as a block, in worktree order, once it finishes.
+    pub fn with_max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel.max(1);
+        self
+    }
+
+    /// Print one JSON object per worktree on stdout, with the command's exit code, instead of
+    /// headers and a summary. The command's own output goes to stderr.
+    pub fn with_json_lines(mut self, json_lines: bool) -> Self {
+        self.json_lines = json_lines;
+        self
+    }
+
+    pub fn execute(&mut self, repo: &Repo) -> color_eyre::Result<()> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err(eyre::eyre!("no command given; pass one after `--`"));
+        };
+
+        let worktrees_dir = repo.worktrees_dir();
+        let worktrees = if worktrees_dir.exists() {
+            find_worktrees(&worktrees_dir)?
+        } else {
+            Vec::new()
+        };
+        if worktrees.is_empty() {
+            if !self.json_lines {
+                println!("No worktrees to run `{program}` in.");
+            }
+            return Ok(());
+        }
+
+        let json_lines = self.json_lines;
+        let mut reporter = BatchReporter::new("worktree", "succeeded").with_json_lines(json_lines);
+        let mut failed = Vec::new();
+        // Reports one worktree's result and returns whether the command failed there.
+        let mut report = |name: String, result: &color_eyre::Result<CommandOutput>| {
+            let exit = result.as_ref().ok().and_then(|output| output.status_code);
+            match failure_reason(result) {
+                // The empty message leaves a blank line before the next worktree's header.
+                None => {
+                    reporter.record_exit(&name, BatchOutcome::Succeeded, exit, "");
+                    false
+                }
+                Some(reason) => {
+                    reporter.record_exit(
+                        &name,
+                        BatchOutcome::Failed,
+                        exit,
+                        format!("`{program}` {reason} in `{name}`."),
+                    );
+                    failed.push(name);
+                    true
+                }
+            }
+        };
+
+        if self.max_parallel > 1 {
+            run_parallel(
+                &self.runner,
+                &worktrees_dir,
+                &worktrees,
+                (program, args),
+                self.max_parallel,
+                self.fail_fast,
+                |worktree, result| {
+                    let name = format_worktree(worktree);
+                    if !json_lines {
+                        print_header(&name);
+                    }
+                    if let Ok(output) = result {
+                        print_output(output, json_lines);
+                    }
+                    report(name, result);
+                },
+            );
+        } else {
+            for worktree in &worktrees {
+                let name = format_worktree(worktree);
+                let path = worktrees_dir.join(worktree);
+                let result = if json_lines {
+                    let result = self.runner.run(program, &path, args);
+                    if let Ok(output) = &result {
+                        print_output(output, true);
+                    }
+                    result
+                } else {
+                    print_header(&name);
+                    self.runner.run_streamed(program, &path, args)
+                };
+                if report(name, &result) && self.fail_fast {
+                    break;
+                }
+            }
+        }
+
+        reporter.print_summary("Worktrees");
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre::eyre!(
+                "`{program}` failed in {}: {}",
+                if failed.len() == 1 {
+                    "1 worktree".to_owned()
+                } else {
+                    format!("{} worktrees", failed.len())
+                },
+                failed.join(", ")
+            ))
+        }
+    }
+}
+
+fn print_header(name: &str) {
+    let header = format!("==> {name}");
+    println!(
+        "{}",
+        header
+            .as_str()
+            .if_supports_color(Stream::Stdout, |text| format!("{}", text.cyan().bold()))
+    );
+}
+
+/// Prints a captured command's output; on stderr only in JSON-lines mode, so stdout stays JSON.
+fn print_output(output: &CommandOutput, json_lines: bool) {
+    if json_lines {
+        eprint!("{}", output.stdout);
+    } else {
+        print!("{}", output.stdout);
+    }
+    eprint!("{}", output.stderr);
+    let _ = io::stdout().flush();
+}
+
+fn failure_reason(result: &color_eyre::Result<CommandOutput>) -> Option<String> {
+    match result {
+        Ok(output) if output.success => None,
+        Ok(output) => Some(match output.status_code {
+            Some(code) => format!("exited with status {code}"),
+            None => "was terminated by a signal".to_owned(),
+        }),
+        Err(err) => Some(format!("could not be run: {err:#}")),
+    }
+}
+
+/// Runs the command in `worktrees` on up to `max_parallel` threads, each with its own clone of
+/// `runner`, and hands every result to `on_result` on the calling thread in worktree order. With
+/// `fail_fast`, no further worktrees are started once one fails.
+fn run_parallel<R>(
+    runner: &R,
+    worktrees_dir: &Path,
+    worktrees: &[PathBuf],
+    (program, args): (&str, &[String]),
+    max_parallel: usize,
+    fail_fast: bool,
+    mut on_result: impl FnMut(&Path, &color_eyre::Result<CommandOutput>),
+) where
+    R: CommandRunner + Clone + Send,
+{
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..max_parallel.min(worktrees.len()) {
+            let mut runner = runner.clone();
+            let sender = sender.clone();
+            let (next, stop) = (&next, &stop);
+            scope.spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(worktree) = worktrees.get(index) else {
+                        break;
+                    };
+                    let result = runner.run(program, &worktrees_dir.join(worktree), args);
+                    if fail_fast && failure_reason(&result).is_some() {
+                        stop.store(true, Ordering::SeqCst);
+                    }
+                    if sender.send((index, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Worktrees are handed out in order, so every started index eventually arrives.
+        let mut pending = BTreeMap::new();
+        let mut next_to_report = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_to_report) {
+                on_result(&worktrees[next_to_report], &result);
+                next_to_report += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use tempfile::TempDir;
+
+    use crate::test_support::{MockCommandRunner, output};
+
+    fn repo_with_worktrees(dir: &TempDir, names: &[&str]) -> color_eyre::Result<Repo> {
+        git2::Repository::init(dir.path())?;
+        let repo = Repo::discover_from(dir.path())?;
+        let worktrees_dir = repo.ensure_worktrees_dir()?;
+        for name in names {
+            let path = worktrees_dir.join(name);
+            fs::create_dir_all(&path)?;
+            fs::write(
+                path.join(".git"),
+                format!("gitdir: ../../.git/worktrees/{}\n", name.replace('/', "-")),
+            )?;
+        }
+        Ok(repo)
+    }
+
+    fn command(args: &[&str], status_codes: &[i32]) -> ExecCommand<MockCommandRunner> {
+        let runner = MockCommandRunner {
+            fallback: Some(output("", true)),
+            ..MockCommandRunner::with_outputs(status_codes.iter().map(|&code| CommandOutput {
+                status_code: Some(code),
+                ..output("", code == 0)
+            }))
+        };
+        ExecCommand::with_runner(args.iter().map(|arg| arg.to_string()).collect(), runner)
+    }
+
+    #[test]
+    fn runs_command_in_every_worktree_and_reports_failures() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktrees(&dir, &["alpha", "beta", "nested/gamma"])?;
+        let mut exec = command(&["cargo", "test"], &[0, 2, 0]);
+
+        let err = exec.execute(&repo).unwrap_err();
+
+        let worktrees_dir = repo.worktrees_dir();
+        let dirs: Vec<_> = exec.runner.calls.iter().map(|call| &call.dir).collect();
+        assert_eq!(
+            dirs,
+            [
+                &worktrees_dir.join("alpha"),
+                &worktrees_dir.join("beta"),
+                &worktrees_dir.join("nested/gamma"),
+            ]
+        );
+        assert!(
+            exec.runner
+                .calls
+                .iter()
+                .all(|call| call.program == "cargo" && call.args == ["test"])
+        );
+        assert_eq!(err.to_string(), "`cargo` failed in 1 worktree: beta");
+        Ok(())
+    }
+
+    #[test]
+    fn fail_fast_stops_after_first_failure() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktrees(&dir, &["alpha", "beta", "gamma"])?;
+        let mut exec = command(&["false"], &[1, 1, 1]).with_fail_fast(true);
+
+        let err = exec.execute(&repo).unwrap_err();
+
+        assert_eq!(exec.runner.calls.len(), 1);
+        assert_eq!(err.to_string(), "`false` failed in 1 worktree: alpha");
+        Ok(())
+    }
+
+    /// Shares its call log between clones and finishes worktrees in reverse order, so results
+    /// arrive out of order.
+    #[derive(Clone, Debug, Default)]
+    struct ParallelRunner {
+        calls: Arc<Mutex<Vec<PathBuf>>>,
+    }
+
+    impl CommandRunner for ParallelRunner {
+        fn run(
+            &mut self,
+            _program: &str,
+            current_dir: &Path,
+            _args: &[String],
+        ) -> color_eyre::Result<CommandOutput> {
+            let name = current_dir
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned();
+            let delay = match name.as_str() {
+                "alpha" => 200,
+                "beta" => 100,
+                _ => 0,
+            };
+            thread::sleep(Duration::from_millis(delay));
+            self.calls.lock().unwrap().push(current_dir.to_path_buf());
+            let success = name != "beta";
+            Ok(CommandOutput {
+                stdout: format!("ran in {name}\n"),
+                stderr: String::new(),
+                success,
+                status_code: Some(if success { 0 } else { 3 }),
+            })
+        }
+    }
+
+    #[test]
+    fn parallel_run_reports_every_worktree_in_order() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktrees(&dir, &["alpha", "beta", "gamma"])?;
+        let runner = ParallelRunner::default();
+        let mut exec =
+            ExecCommand::with_runner(vec!["make".to_owned()], runner.clone()).with_max_parallel(3);
+
+        let err = exec.execute(&repo).unwrap_err();
+
+        assert_eq!(err.to_string(), "`make` failed in 1 worktree: beta");
+        let calls = runner.calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(
+            calls.last(),
+            Some(&repo.worktrees_dir().join("alpha")),
+            "worktrees should have run concurrently"
+        );
+        drop(calls);
+
+        let mut seen = Vec::new();
+        run_parallel(
+            &runner,
+            &repo.worktrees_dir(),
+            &[
+                PathBuf::from("alpha"),
+                PathBuf::from("beta"),
+                PathBuf::from("gamma"),
+            ],
+            ("make", &[]),
+            2,
+            false,
+            |worktree, result| {
+                seen.push((
+                    worktree.to_path_buf(),
+                    result.as_ref().unwrap().stdout.clone(),
+                ))
+            },
+        );
+        assert_eq!(
+            seen,
+            [
+                (PathBuf::from("alpha"), "ran in alpha\n".to_owned()),
+                (PathBuf::from("beta"), "ran in beta\n".to_owned()),
+                (PathBuf::from("gamma"), "ran in gamma\n".to_owned()),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::Path;
+
+use color_eyre::eyre::{self, WrapErr};
+
+use crate::commands::pr_github::CommandRunner;
+
+/// Runs `hook` with `sh -c` in `dir`, streaming its output unless `quiet`.
+///
+/// `env` is passed through `env(1)` so the hook still goes through the runner.
+pub(crate) fn run_hook<R: CommandRunner>(
+    runner: &mut R,
+    phase: &str,
+    hook: &str,
+    dir: &Path,
+    env: &[(&str, &str)],
+    quiet: bool,
+) -> color_eyre::Result<()> {
+    let mut args: Vec<String> = env
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    let program = if args.is_empty() {
+        "sh"
+    } else {
+        args.push("sh".to_owned());
+        "env"
+    };
+    args.extend(["-c".to_owned(), hook.to_owned()]);
+
+    let output = if quiet {
+        runner.run(program, dir, &args)
+    } else {
+        runner.run_streamed(program, dir, &args)
+    }
+    .wrap_err_with(|| eyre::eyre!("failed to run {phase} hook `{hook}`"))?;
+
+    if output.success {
+        return Ok(());
+    }
+
+    let status = match output.status_code {
+        Some(code) => format!("exit status {code}"),
+        None => "termination by signal".to_owned(),
+    };
+    let mut message = format!("{phase} hook `{hook}` failed with {status}");
+    let stderr = output.stderr.trim();
+    if !stderr.is_empty() {
+        message.push('\n');
+        message.push_str(stderr);
+    }
+    Err(eyre::eyre!(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::{
+        commands::pr_github::{CommandOutput, SystemCommandRunner},
+        test_support::MockCommandRunner,
+    };
+
+    #[test]
+    fn runs_hook_through_sh_and_streams_unless_quiet() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let mut runner = MockCommandRunner::succeeding();
+
+        run_hook(
+            &mut runner,
+            "pre-remove",
+            "make down",
+            dir.path(),
+            &[],
+            false,
+        )?;
+        assert_eq!(runner.streamed_calls, 1);
+        run_hook(
+            &mut runner,
+            "pre-remove",
+            "make down",
+            dir.path(),
+            &[],
+            true,
+        )?;
+        assert_eq!(runner.streamed_calls, 1, "quiet hooks are captured");
+
+        assert_eq!(runner.calls.len(), 2);
+        for call in &runner.calls {
+            assert_eq!(call.program, "sh");
+            assert_eq!(call.args, ["-c", "make down"]);
+            assert_eq!(call.dir, dir.path());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn passes_env_through_env_command() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let mut runner = MockCommandRunner::succeeding();
+
+        run_hook(
+            &mut runner,
+            "post-create",
+            "./setup.sh",
+            dir.path(),
+            &[("RSWORKTREE_NAME", "feature/x")],
+            true,
+        )?;
+
+        assert_eq!(runner.calls.len(), 1);
+        assert_eq!(runner.calls[0].program, "env");
+        assert_eq!(
+            runner.calls[0].args,
+            ["RSWORKTREE_NAME=feature/x", "sh", "-c", "./setup.sh"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hook_runs_in_dir_with_env() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let mut runner = SystemCommandRunner::default();
+
+        run_hook(
+            &mut runner,
+            "post-create",
+            "printf '%s' \"$RSWORKTREE_NAME\" > hook.out",
+            dir.path(),
+            &[("RSWORKTREE_NAME", "feature/x")],
+            true,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hook.out"))?,
+            "feature/x"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn failing_hook_reports_phase_status_and_stderr() {
+        let dir = TempDir::new().expect("tempdir");
+        let mut runner = MockCommandRunner::answering(CommandOutput {
+            stdout: String::new(),
+            stderr: "boom".into(),
+            success: false,
+            status_code: Some(3),
+        });
+
+        let err = run_hook(&mut runner, "post-remove", "false", dir.path(), &[], true)
+            .expect_err("hook should fail");
+
+        assert_eq!(
+            err.to_string(),
+            "post-remove hook `false` failed with exit status 3\nboom"
+        );
+    }
+}
@@ -1,14 +1,11 @@
-use std::path::{Path, PathBuf};
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+};
 
 use color_eyre::{Result, eyre::WrapErr};
-use crossterm::{
-    event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
-use git2::{
-    Branch, BranchType, Commit, ErrorCode, Oid, Repository, RepositoryState, Status, StatusOptions,
-};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use git2::{Repository, StatusOptions};
 use ratatui::{
     Terminal,
     backend::Backend,
@@ -19,15 +16,24 @@ use ratatui::{
 };
 
 use super::{
-    Action, EventSource, Focus, Selection, StatusMessage, WorktreeEntry,
+    Action, AheadBehind, EventSource, Focus, ScreenSuspender, Selection, SortKey, StatusMessage,
+    Theme, UncommittedChanges, WorktreeEntry,
+    details::{
+        CommitDetails, HeadDetails, HeadKind, Tracking, WorkingTree, WorktreeDetails,
+        collect_details,
+    },
     dialog::{
         CreateDialog, CreateDialogFocus, Dialog, InfoDialogKind, MergeDialog, MergeDialogFocus,
         RemoveDialog, RemoveDialogFocus,
     },
-    view::{DetailData, DialogView, Snapshot},
+    runtime::CrosstermScreen,
+    view::{DetailData, DialogView, Snapshot, TitleBar, split_title},
 };
 use crate::{
-    commands::rm::{LocalBranchStatus, RemoveOutcome},
+    commands::{
+        pr_github::{CommandRunner, SystemCommandRunner},
+        rm::{LocalBranchStatus, RemoteBranchStatus, RemoveOutcome},
+    },
     editor::LaunchOutcome,
     telemetry::{EditorLaunchStatus, log_editor_launch_attempt},
 };
@@ -116,10 +122,18 @@ where
     pub(crate) action_panel: ActionPanelState,
     pub(crate) global_action_selected: usize,
     pub(crate) branches: Vec<String>,
+    pub(crate) remote_branches: Vec<String>,
     pub(crate) default_branch: Option<String>,
     pub(crate) status: Option<StatusMessage>,
     pub(crate) dialog: Option<Dialog>,
     editor_logs: Vec<EditorLaunchLog>,
+    screen: Box<dyn ScreenSuspender>,
+    diff_runner: Box<dyn CommandRunner>,
+    pub(crate) sort: SortKey,
+    pub(crate) reverse: bool,
+    theme: Theme,
+    ahead_behind: AheadBehind,
+    title: Option<TitleBar>,
 }
 
 impl<B, E> InteractiveCommand<B, E>
@@ -150,13 +164,65 @@ where
             action_panel: ActionPanelState::vertical(),
             global_action_selected: 0,
             branches,
+            remote_branches: Vec::new(),
             default_branch,
             status: None,
             dialog: None,
             editor_logs: Vec::new(),
+            screen: Box::new(CrosstermScreen),
+            diff_runner: Box::new(SystemCommandRunner::default()),
+            sort: SortKey::Name,
+            reverse: false,
+            theme: Theme::default(),
+            ahead_behind: AheadBehind::default(),
+            title: None,
         }
     }
 
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Show `root` and the main checkout's `branch` in a title bar above the columns.
+    pub fn with_title_bar(mut self, root: &Path, branch: Option<String>) -> Self {
+        self.title = Some(TitleBar {
+            root: root.display().to_string(),
+            branch,
+        });
+        self
+    }
+
+    /// Remote-tracking branches (`origin/main`) offered as bases in the Create dialog.
+    pub fn with_remote_branches(mut self, remote_branches: Vec<String>) -> Self {
+        self.remote_branches = remote_branches;
+        self
+    }
+
+    pub fn with_ahead_behind(mut self, ahead_behind: AheadBehind) -> Self {
+        self.ahead_behind = ahead_behind;
+        self
+    }
+
+    pub fn with_sort(mut self, sort: SortKey, reverse: bool) -> Self {
+        self.sort = sort;
+        self.reverse = reverse;
+        sort_entries(&mut self.worktrees, sort, reverse);
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_screen(mut self, screen: impl ScreenSuspender + 'static) -> Self {
+        self.screen = Box::new(screen);
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_diff_runner(mut self, runner: impl CommandRunner + 'static) -> Self {
+        self.diff_runner = Box::new(runner);
+        self
+    }
+
     pub fn run<F, G, H>(
         mut self,
         mut on_remove: F,
@@ -164,7 +230,7 @@ where
         mut on_open_editor: H,
     ) -> Result<Option<Selection>>
     where
-        F: FnMut(&str, bool) -> Result<RemoveOutcome>,
+        F: FnMut(&str, bool, bool, UncommittedChanges) -> Result<RemoveOutcome>,
         G: FnMut(&str, Option<&str>) -> Result<()>,
         H: FnMut(&str, &Path) -> color_eyre::Result<LaunchOutcome>,
     {
@@ -195,7 +261,7 @@ where
         on_open_editor: &mut H,
     ) -> Result<Option<Selection>>
     where
-        F: FnMut(&str, bool) -> Result<RemoveOutcome>,
+        F: FnMut(&str, bool, bool, UncommittedChanges) -> Result<RemoveOutcome>,
         G: FnMut(&str, Option<&str>) -> Result<()>,
         H: FnMut(&str, &Path) -> color_eyre::Result<LaunchOutcome>,
     {
@@ -224,41 +290,42 @@ where
         on_open_editor: &mut H,
     ) -> Result<LoopControl>
     where
-        F: FnMut(&str, bool) -> Result<RemoveOutcome>,
+        F: FnMut(&str, bool, bool, UncommittedChanges) -> Result<RemoveOutcome>,
         G: FnMut(&str, Option<&str>) -> Result<()>,
         H: FnMut(&str, &Path) -> color_eyre::Result<LaunchOutcome>,
     {
         if let Some(dialog) = self.dialog.clone() {
             match dialog {
                 Dialog::Remove(_) => {
-                    if let Event::Key(key) = event {
-                        if key.kind == KeyEventKind::Press {
-                            return self.handle_remove_dialog_key(key, state, on_remove);
-                        }
+                    if let Event::Key(key) = event
+                        && key.kind == KeyEventKind::Press
+                    {
+                        return self.handle_remove_dialog_key(key, state, on_remove);
                     }
                     return Ok(LoopControl::Continue);
                 }
                 Dialog::Info { .. } => {
-                    if let Event::Key(key) = event {
-                        if key.kind == KeyEventKind::Press && key.code == KeyCode::Enter {
-                            self.dialog = None;
-                        }
+                    if let Event::Key(key) = event
+                        && key.kind == KeyEventKind::Press
+                        && key.code == KeyCode::Enter
+                    {
+                        self.dialog = None;
                     }
                     return Ok(LoopControl::Continue);
                 }
                 Dialog::Create(_) => {
-                    if let Event::Key(key) = event {
-                        if key.kind == KeyEventKind::Press {
-                            self.handle_create_key(key, state, on_create)?;
-                        }
+                    if let Event::Key(key) = event
+                        && key.kind == KeyEventKind::Press
+                    {
+                        self.handle_create_key(key, state, on_create)?;
                     }
                     return Ok(LoopControl::Continue);
                 }
                 Dialog::Merge(_) => {
-                    if let Event::Key(key) = event {
-                        if key.kind == KeyEventKind::Press {
-                            return self.handle_merge_dialog_key(key);
-                        }
+                    if let Event::Key(key) = event
+                        && key.kind == KeyEventKind::Press
+                    {
+                        return self.handle_merge_dialog_key(key);
                     }
                     return Ok(LoopControl::Continue);
                 }
@@ -291,6 +358,22 @@ where
                 self.handle_down(state);
                 Ok(LoopControl::Continue)
             }
+            KeyCode::Char('s') | KeyCode::Char('r') => {
+                if key.code == KeyCode::Char('s') {
+                    self.sort = self.sort.next();
+                } else {
+                    self.reverse = !self.reverse;
+                }
+                self.sort_worktrees();
+                self.sync_selection(state);
+                let direction = if self.reverse { " (reversed)" } else { "" };
+                self.status = Some(StatusMessage::info(format!(
+                    "Sorted by {}{}.",
+                    self.sort.label(),
+                    direction
+                )));
+                Ok(LoopControl::Continue)
+            }
             KeyCode::Char('e') | KeyCode::Char('E') => match self.focus {
                 Focus::Worktrees => {
                     if let Some(entry) = self.current_entry().cloned() {
@@ -406,6 +489,14 @@ where
                         }
                         return Ok(LoopControl::Continue);
                     }
+                    Action::Diff => {
+                        if let Some(entry) = self.current_entry().cloned() {
+                            self.show_diff(&entry.name, &entry.path);
+                        } else {
+                            self.status = Some(StatusMessage::info("No worktree selected."));
+                        }
+                        return Ok(LoopControl::Continue);
+                    }
                     Action::Remove => {
                         if let Some(index) = self.selected {
                             self.dialog = Some(Dialog::Remove(RemoveDialog::new(index)));
@@ -444,8 +535,12 @@ where
                         return Ok(LoopControl::Continue);
                     }
 
-                    let dialog =
-                        CreateDialog::new(&self.branches, &self.worktrees, self.default_branch());
+                    let dialog = CreateDialog::new(
+                        &self.branches,
+                        &self.remote_branches,
+                        &self.worktrees,
+                        self.default_branch(),
+                    );
                     self.dialog = Some(Dialog::Create(dialog));
                 }
                 1 => {
@@ -465,7 +560,7 @@ where
         on_remove: &mut F,
     ) -> Result<LoopControl>
     where
-        F: FnMut(&str, bool) -> Result<RemoveOutcome>,
+        F: FnMut(&str, bool, bool, UncommittedChanges) -> Result<RemoveOutcome>,
     {
         let dialog_state = self.dialog.take();
         let Some(Dialog::Remove(mut dialog)) = dialog_state else {
@@ -486,6 +581,8 @@ where
                 if let Some(selection) = self.perform_remove(
                     dialog.index,
                     dialog.remove_local_branch(),
+                    dialog.remove_remote_branch(),
+                    dialog.uncommitted(),
                     state,
                     on_remove,
                 )? {
@@ -502,20 +599,14 @@ where
                 RemoveDialogFocus::Options => dialog.move_option(1),
                 RemoveDialogFocus::Buttons => {}
             },
-            KeyCode::Left => {
-                if dialog.focus == RemoveDialogFocus::Buttons {
-                    dialog.move_button(-1);
-                }
+            KeyCode::Left if dialog.focus == RemoveDialogFocus::Buttons => {
+                dialog.move_button(-1);
             }
-            KeyCode::Right => {
-                if dialog.focus == RemoveDialogFocus::Buttons {
-                    dialog.move_button(1);
-                }
+            KeyCode::Right if dialog.focus == RemoveDialogFocus::Buttons => {
+                dialog.move_button(1);
             }
-            KeyCode::Char(' ') => {
-                if dialog.focus == RemoveDialogFocus::Options {
-                    dialog.toggle_selected_option();
-                }
+            KeyCode::Char(' ') if dialog.focus == RemoveDialogFocus::Options => {
+                dialog.toggle_selected_option();
             }
             KeyCode::Enter => match dialog.focus {
                 RemoveDialogFocus::Options => dialog.toggle_selected_option(),
@@ -528,6 +619,8 @@ where
                         if let Some(selection) = self.perform_remove(
                             dialog.index,
                             dialog.remove_local_branch(),
+                            dialog.remove_remote_branch(),
+                            dialog.uncommitted(),
                             state,
                             on_remove,
                         )? {
@@ -550,14 +643,21 @@ where
         &mut self,
         index: usize,
         remove_local_branch: bool,
+        remove_remote_branch: bool,
+        uncommitted: UncommittedChanges,
         state: &mut ListState,
         on_remove: &mut F,
     ) -> Result<Option<Selection>>
     where
-        F: FnMut(&str, bool) -> Result<RemoveOutcome>,
+        F: FnMut(&str, bool, bool, UncommittedChanges) -> Result<RemoveOutcome>,
     {
         if let Some(entry) = self.worktrees.get(index).cloned() {
-            match on_remove(&entry.name, remove_local_branch) {
+            match on_remove(
+                &entry.name,
+                remove_local_branch,
+                remove_remote_branch,
+                uncommitted,
+            ) {
                 Ok(outcome) => {
                     self.worktrees.remove(index);
                     let removal_dir = entry
@@ -576,6 +676,16 @@ where
                         }
                         None => {}
                     }
+                    match outcome.remote_branch {
+                        Some(RemoteBranchStatus::Deleted) => {
+                            message.push_str(&format!(" Deleted remote branch `{}`.", entry.name));
+                        }
+                        Some(RemoteBranchStatus::NotFound) => {
+                            message
+                                .push_str(&format!(" Remote branch `{}` not found.", entry.name));
+                        }
+                        None => {}
+                    }
                     self.selected = None;
                     self.focus = Focus::Worktrees;
                     self.sync_selection(state);
@@ -649,6 +759,7 @@ where
         let mut close_dialog = false;
         let mut status_message: Option<StatusMessage> = None;
         let mut submit_requested = false;
+        let mut keep_open = false;
 
         {
             let Some(dialog) = self.dialog.as_mut().and_then(|dialog| {
@@ -709,38 +820,40 @@ where
                         _ => {}
                     },
                     CreateDialogFocus::Buttons => match key.code {
-                        KeyCode::Left => {
-                            if dialog.buttons_selected > 0 {
-                                dialog.buttons_selected -= 1;
-                            }
+                        KeyCode::Left if dialog.buttons_selected > 0 => {
+                            dialog.buttons_selected -= 1;
                         }
-                        KeyCode::Right => {
-                            if dialog.buttons_selected < 1 {
-                                dialog.buttons_selected += 1;
-                            }
+                        KeyCode::Right if dialog.buttons_selected < 2 => {
+                            dialog.buttons_selected += 1;
                         }
-                        KeyCode::Enter => {
-                            if dialog.buttons_selected == 0 {
+                        KeyCode::Enter => match dialog.buttons_selected {
+                            0 => submit_requested = true,
+                            1 => {
                                 submit_requested = true;
-                            } else {
+                                keep_open = true;
+                            }
+                            _ => {
                                 close_dialog = true;
                                 status_message = Some(StatusMessage::info("Creation cancelled."));
                                 self.focus = Focus::Worktrees;
                             }
-                        }
+                        },
                         _ => {}
                     },
                 }
             }
         }
 
-        if submit_requested {
-            if let Some((name, base_label)) = self.perform_create_submission(state, on_create)? {
+        if submit_requested
+            && let Some((name, base_label)) =
+                self.perform_create_submission(state, on_create, keep_open)?
+        {
+            let message = StatusMessage::info(format!("Created `{}` from {}", name, base_label));
+            if keep_open {
+                self.status = Some(message);
+            } else {
                 close_dialog = true;
-                status_message = Some(StatusMessage::info(format!(
-                    "Created `{}` from {}",
-                    name, base_label
-                )));
+                status_message = Some(message);
             }
         }
 
@@ -760,6 +873,10 @@ where
             return Ok(LoopControl::Continue);
         };
 
+        if dialog.confirming {
+            return Ok(self.handle_merge_confirmation_key(key, dialog));
+        }
+
         let mut reinstate = true;
         let mut outcome = LoopControl::Continue;
 
@@ -778,20 +895,14 @@ where
                 MergeDialogFocus::Options => dialog.move_option(1),
                 MergeDialogFocus::Buttons => {}
             },
-            KeyCode::Left => {
-                if dialog.focus == MergeDialogFocus::Buttons {
-                    dialog.move_button(-1);
-                }
+            KeyCode::Left if dialog.focus == MergeDialogFocus::Buttons => {
+                dialog.move_button(-1);
             }
-            KeyCode::Right => {
-                if dialog.focus == MergeDialogFocus::Buttons {
-                    dialog.move_button(1);
-                }
+            KeyCode::Right if dialog.focus == MergeDialogFocus::Buttons => {
+                dialog.move_button(1);
             }
-            KeyCode::Char(' ') => {
-                if dialog.focus == MergeDialogFocus::Options {
-                    dialog.toggle_selected_option();
-                }
+            KeyCode::Char(' ') if dialog.focus == MergeDialogFocus::Options => {
+                dialog.toggle_selected_option();
             }
             KeyCode::Enter => match dialog.focus {
                 MergeDialogFocus::Options => dialog.toggle_selected_option(),
@@ -799,6 +910,9 @@ where
                     if dialog.buttons_selected == 0 {
                         reinstate = false;
                         self.status = Some(StatusMessage::info("Merge cancelled."));
+                    } else if dialog.remove_worktree() {
+                        dialog.confirming = true;
+                        dialog.buttons_selected = 1;
                     } else {
                         match self.build_merge_selection(&dialog) {
                             Some(selection) => {
@@ -825,6 +939,43 @@ where
         Ok(outcome)
     }
 
+    /// Final gate before a merge that also removes the worktree: `y` runs every selected step,
+    /// `n` merges but keeps the worktree, and `Esc` returns to the cleanup options.
+    fn handle_merge_confirmation_key(
+        &mut self,
+        key: KeyEvent,
+        mut dialog: MergeDialog,
+    ) -> LoopControl {
+        let remove_worktree = match key.code {
+            KeyCode::Esc => {
+                dialog.confirming = false;
+                self.dialog = Some(Dialog::Merge(dialog));
+                return LoopControl::Continue;
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab => {
+                dialog.move_button(1);
+                self.dialog = Some(Dialog::Merge(dialog));
+                return LoopControl::Continue;
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') => true,
+            KeyCode::Char('n') | KeyCode::Char('N') => false,
+            KeyCode::Enter => dialog.buttons_selected == 1,
+            _ => {
+                self.dialog = Some(Dialog::Merge(dialog));
+                return LoopControl::Continue;
+            }
+        };
+
+        dialog.remove_worktree = remove_worktree;
+        match self.build_merge_selection(&dialog) {
+            Some(selection) => LoopControl::Exit(Some(selection)),
+            None => {
+                self.status = Some(StatusMessage::error("Selected worktree no longer exists."));
+                LoopControl::Continue
+            }
+        }
+    }
+
     fn build_merge_selection(&self, dialog: &MergeDialog) -> Option<Selection> {
         self.worktrees
             .get(dialog.index)
@@ -833,6 +984,7 @@ where
                 remove_local_branch: dialog.remove_local_branch(),
                 remove_remote_branch: dialog.remove_remote_branch(),
                 remove_worktree: dialog.remove_worktree(),
+                strategy: dialog.strategy(),
             })
     }
 
@@ -887,7 +1039,7 @@ where
         let path = self.worktrees_dir.join(&name_owned);
         self.worktrees
             .push(WorktreeEntry::new(name_owned.clone(), path));
-        self.worktrees.sort_by(|a, b| a.name.cmp(&b.name));
+        sort_entries(&mut self.worktrees, self.sort, self.reverse);
         self.selected = self
             .worktrees
             .iter()
@@ -899,10 +1051,13 @@ where
         Ok(Some((name_owned, base_label)))
     }
 
+    /// With `keep_open`, a successful create leaves the dialog up with the name cleared and the
+    /// base kept, ready for the next worktree.
     fn perform_create_submission<G>(
         &mut self,
         state: &mut ListState,
         on_create: &mut G,
+        keep_open: bool,
     ) -> Result<Option<(String, String)>>
     where
         G: FnMut(&str, Option<&str>) -> Result<()>,
@@ -912,6 +1067,10 @@ where
 
             if outcome.is_none() {
                 self.dialog = Some(Dialog::Create(dialog));
+            } else if keep_open {
+                dialog.name_input.clear();
+                dialog.focus = CreateDialogFocus::Name;
+                self.dialog = Some(Dialog::Create(dialog));
             }
 
             Ok(outcome)
@@ -1020,24 +1179,18 @@ where
         }
     }
 
-    fn suspend_terminal(&mut self) -> Result<()> {
-        // Show cursor, leave alternate screen, and disable raw mode
-        // In test environments, these operations may fail, which is okay
+    fn suspend_terminal(&mut self) {
+        // Show cursor, then leave alternate screen and disable raw mode
         let _ = self.terminal.show_cursor();
-        let _ = std::io::stdout().execute(LeaveAlternateScreen);
-        let _ = disable_raw_mode();
-        Ok(())
+        self.screen.suspend();
     }
 
-    fn restore_terminal(&mut self) -> Result<()> {
-        // Re-enable raw mode, enter alternate screen, and hide cursor
-        // In test environments, these operations may fail, which is okay
-        let _ = enable_raw_mode();
-        let _ = std::io::stdout().execute(EnterAlternateScreen);
+    fn restore_terminal(&mut self) {
+        // Re-enable raw mode and alternate screen, then hide cursor
+        self.screen.resume();
         let _ = self.terminal.hide_cursor();
         // Clear the terminal to ensure a clean redraw
         let _ = self.terminal.clear();
-        Ok(())
     }
 
     fn trigger_open_in_editor<H>(
@@ -1050,13 +1203,13 @@ where
         H: FnMut(&str, &Path) -> color_eyre::Result<LaunchOutcome>,
     {
         // Suspend the terminal before launching the editor
-        self.suspend_terminal()?;
+        self.suspend_terminal();
 
         // Launch the editor and wait for it to complete
         let result = on_open_editor(name, path);
 
         // Restore the terminal after the editor exits
-        self.restore_terminal()?;
+        self.restore_terminal();
 
         match result {
             Ok(outcome) => {
@@ -1094,6 +1247,29 @@ where
         Ok(())
     }
 
+    fn show_diff(&mut self, name: &str, path: &Path) {
+        self.suspend_terminal();
+        let result = self.diff_runner.run_streamed("git", path, &diff_args(path));
+        self.restore_terminal();
+
+        match result {
+            Ok(output) if output.success => {
+                self.status = Some(StatusMessage::info(format!("Closed diff for `{name}`.")));
+            }
+            Ok(output) => {
+                let code = output
+                    .status_code
+                    .map_or_else(|| String::from("unknown"), |code| code.to_string());
+                self.show_error_popup(format!(
+                    "`git diff` for `{name}` exited with status {code}."
+                ));
+            }
+            Err(error) => {
+                self.show_error_popup(format!("Failed to show diff for `{name}`: {error}"));
+            }
+        }
+    }
+
     fn show_info_popup(&mut self, message: String) {
         self.status = None;
         self.dialog = Some(Dialog::Info {
@@ -1125,10 +1301,11 @@ where
             return Action::ALL.len();
         };
 
+        let (_, body) = split_title(size, self.title.is_some());
         let columns = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
-            .split(size);
+            .split(body);
 
         let panel_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -1142,6 +1319,15 @@ where
         usize::from(panel_layout[1].height.saturating_sub(2))
     }
 
+    /// Re-sorts the list while keeping the highlighted worktree selected.
+    fn sort_worktrees(&mut self) {
+        let selected_name = self.current_entry().map(|entry| entry.name.clone());
+        sort_entries(&mut self.worktrees, self.sort, self.reverse);
+        if let Some(name) = selected_name {
+            self.selected = self.worktrees.iter().position(|entry| entry.name == name);
+        }
+    }
+
     fn current_entry(&self) -> Option<&WorktreeEntry> {
         self.selected.and_then(|idx| self.worktrees.get(idx))
     }
@@ -1173,7 +1359,9 @@ where
             .map(|entry| entry.name.clone())
             .collect::<Vec<_>>();
 
-        let detail = self.current_entry().map(build_detail_data);
+        let detail = self
+            .current_entry()
+            .map(|entry| build_detail_data(entry, &self.theme, self.ahead_behind));
 
         let dialog = match self.dialog.clone() {
             Some(Dialog::Remove(dialog)) => {
@@ -1206,7 +1394,9 @@ where
             self.status.clone(),
             dialog,
             !self.worktrees.is_empty(),
+            self.theme,
         )
+        .with_title(self.title.clone())
     }
 }
 
@@ -1215,45 +1405,111 @@ enum LoopControl {
     Exit(Option<Selection>),
 }
 
-fn build_detail_data(entry: &WorktreeEntry) -> DetailData {
+/// Arguments for `git diff HEAD` in `path`, paged through the user's configured git pager.
+pub(crate) fn diff_args(path: &Path) -> Vec<String> {
+    vec![
+        "-C".to_owned(),
+        path.display().to_string(),
+        "--paginate".to_owned(),
+        "diff".to_owned(),
+        "HEAD".to_owned(),
+    ]
+}
+
+pub(crate) fn sort_entries(entries: &mut [WorktreeEntry], sort: SortKey, reverse: bool) {
+    match sort {
+        SortKey::Name => entries.sort_by(|a, b| a.name.cmp(&b.name)),
+        SortKey::Recent => entries.sort_by_cached_key(|entry| {
+            (Reverse(last_commit_time(&entry.path)), entry.name.clone())
+        }),
+        SortKey::Dirty => {
+            entries.sort_by_cached_key(|entry| (!has_changes(&entry.path), entry.name.clone()))
+        }
+    }
+
+    if reverse {
+        entries.reverse();
+    }
+}
+
+pub(crate) fn last_commit_time(path: &Path) -> Option<i64> {
+    let repo = Repository::open(path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.time().seconds())
+}
+
+fn has_changes(path: &Path) -> bool {
+    let Ok(repo) = Repository::open(path) else {
+        return false;
+    };
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).include_ignored(false);
+    repo.statuses(Some(&mut options))
+        .map(|statuses| !statuses.is_empty())
+        .unwrap_or(false)
+}
+
+fn build_detail_data(
+    entry: &WorktreeEntry,
+    theme: &Theme,
+    ahead_behind: AheadBehind,
+) -> DetailData {
+    let details = collect_details(&entry.name, &entry.path, ahead_behind);
+    DetailData {
+        lines: detail_lines(&details, theme, ahead_behind),
+    }
+}
+
+/// Renders collected worktree details as the lines shown in the details pane.
+pub(crate) fn detail_lines(
+    details: &WorktreeDetails,
+    theme: &Theme,
+    ahead_behind: AheadBehind,
+) -> Vec<Line<'static>> {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
-    lines.push(section_header("Repository"));
+    lines.push(section_header(theme, "Repository"));
     lines.push(kv_line(
+        theme,
         "Path",
-        entry.path.display().to_string(),
-        muted_style(),
+        details.path.clone(),
+        muted_style(theme),
     ));
 
-    if !entry.path.exists() {
+    if !details.exists {
         lines.push(Line::default());
         lines.push(message_line(
             "Worktree directory not found.",
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.destructive),
         ));
-        return DetailData { lines };
+        return lines;
     }
 
-    match Repository::open(&entry.path) {
-        Ok(repo) => append_repository_details(&mut lines, &repo),
-        Err(err) => {
-            lines.push(Line::default());
-            lines.push(message_line(
-                "Unable to open worktree repo.",
-                Style::default().fg(Color::Red),
-            ));
-            lines.push(message_line(err.message().to_string(), muted_style()));
-        }
+    if let Some(error) = &details.error {
+        lines.push(Line::default());
+        lines.push(message_line(
+            "Unable to open worktree repo.",
+            Style::default().fg(theme.destructive),
+        ));
+        lines.push(message_line(error.clone(), muted_style(theme)));
+        return lines;
     }
 
-    DetailData { lines }
-}
-
-fn append_repository_details(lines: &mut Vec<Line<'static>>, repo: &Repository) {
-    let mut repo_lines = describe_head(repo);
-
-    if let Some(state_line) = describe_repository_state(repo) {
-        repo_lines.push(state_line);
+    let mut repo_lines = details
+        .head
+        .as_ref()
+        .map(|head| head_lines(head, theme, ahead_behind))
+        .unwrap_or_default();
+
+    if let Some(state) = &details.state {
+        repo_lines.push(kv_line(
+            theme,
+            "Git State",
+            state.clone(),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
     }
 
     if !repo_lines.is_empty() {
@@ -1261,150 +1517,141 @@ fn append_repository_details(lines: &mut Vec<Line<'static>>, repo: &Repository)
         lines.append(&mut repo_lines);
     }
 
-    if let Some(status_line) = summarize_worktree(repo) {
-        lines.push(Line::default());
-        lines.push(section_header("Working Tree"));
-        lines.push(status_line);
-    }
+    lines.push(Line::default());
+    lines.push(section_header(theme, "Working Tree"));
+    lines.push(working_tree_line(details.working_tree.as_ref(), theme));
+
+    lines
 }
 
-fn describe_head(repo: &Repository) -> Vec<Line<'static>> {
+fn head_lines(head: &HeadDetails, theme: &Theme, ahead_behind: AheadBehind) -> Vec<Line<'static>> {
     let mut lines: Vec<Line<'static>> = Vec::new();
-
-    match repo.head() {
-        Ok(head) => {
-            if head.is_branch() {
-                let branch_name = head.shorthand().unwrap_or("(unnamed)").to_string();
-                lines.push(kv_line(
-                    "Branch",
-                    branch_name.clone(),
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                ));
-
-                if let Ok(branch) = repo.find_branch(&branch_name, BranchType::Local) {
-                    match branch.upstream() {
-                        Ok(upstream) => lines.push(build_tracking_line(repo, &branch, &upstream)),
-                        Err(err) => {
-                            if err.code() == ErrorCode::NotFound {
-                                lines.push(kv_line(
-                                    "Tracking",
-                                    "(none)",
-                                    Style::default().fg(Color::DarkGray),
-                                ));
-                            } else {
-                                lines.push(kv_line(
-                                    "Tracking",
-                                    "Unavailable",
-                                    Style::default().fg(Color::Red),
-                                ));
-                                lines.push(message_line(err.message().to_string(), muted_style()));
-                            }
-                        }
-                    }
-                }
-            } else if head.is_tag() {
-                let tag_name = head.shorthand().unwrap_or("(tag)");
-                lines.push(kv_line(
-                    "Branch",
-                    format!("tag {tag_name}"),
-                    Style::default().fg(Color::Magenta),
-                ));
-            } else {
-                lines.push(kv_line(
-                    "Branch",
-                    "(detached)",
-                    Style::default().fg(Color::Yellow),
-                ));
-            }
-
-            match head.peel_to_commit() {
-                Ok(commit) => lines.extend(describe_commit(&commit)),
-                Err(err) => lines.push(message_line(
-                    format!("HEAD is not a commit ({})", err.message()),
-                    Style::default().fg(Color::Red),
-                )),
+    let name = head.name.clone().unwrap_or_default();
+
+    match head.kind {
+        HeadKind::Branch => {
+            lines.push(kv_line(
+                theme,
+                "Branch",
+                name,
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            if let Some(tracking) = &head.tracking {
+                lines.extend(tracking_lines(tracking, theme, ahead_behind));
             }
         }
-        Err(err) => {
-            if err.code() == ErrorCode::UnbornBranch {
-                lines.push(kv_line(
-                    "Branch",
-                    "(unborn)",
-                    Style::default().fg(Color::Yellow),
-                ));
-            } else {
-                lines.push(kv_line(
-                    "Branch",
-                    "Unavailable",
-                    Style::default().fg(Color::Red),
-                ));
-                lines.push(message_line(err.message().to_string(), muted_style()));
+        HeadKind::Tag => lines.push(kv_line(
+            theme,
+            "Branch",
+            format!("tag {name}"),
+            Style::default().fg(Color::Magenta),
+        )),
+        HeadKind::Detached => lines.push(kv_line(
+            theme,
+            "Branch",
+            "(detached)",
+            Style::default().fg(theme.warning),
+        )),
+        HeadKind::Unborn => {
+            lines.push(kv_line(
+                theme,
+                "Branch",
+                "(unborn)",
+                Style::default().fg(theme.warning),
+            ));
+            return lines;
+        }
+        HeadKind::Unavailable => {
+            lines.push(kv_line(
+                theme,
+                "Branch",
+                "Unavailable",
+                Style::default().fg(theme.destructive),
+            ));
+            if let Some(error) = &head.error {
+                lines.push(message_line(error.clone(), muted_style(theme)));
             }
+            return lines;
         }
     }
 
+    if let Some(commit) = &head.commit {
+        lines.extend(commit_lines(commit, theme));
+    } else if let Some(error) = &head.commit_error {
+        lines.push(message_line(
+            format!("HEAD is not a commit ({error})"),
+            Style::default().fg(theme.destructive),
+        ));
+    }
+
     lines
 }
 
-fn build_tracking_line(
-    repo: &Repository,
-    branch: &Branch<'_>,
-    upstream: &Branch<'_>,
-) -> Line<'static> {
-    let upstream_name = match upstream.name() {
-        Ok(Some(name)) => name.to_string(),
-        Ok(None) => String::from("(non-UTF8)"),
-        Err(_) => upstream
-            .get()
-            .shorthand()
-            .map(|name| name.to_string())
-            .unwrap_or_else(|| String::from("(unknown)")),
+fn tracking_lines(tracking: &Tracking, theme: &Theme, mode: AheadBehind) -> Vec<Line<'static>> {
+    let Some(upstream) = &tracking.upstream else {
+        return match &tracking.error {
+            Some(error) => vec![
+                kv_line(
+                    theme,
+                    "Tracking",
+                    "Unavailable",
+                    Style::default().fg(theme.destructive),
+                ),
+                message_line(error.clone(), muted_style(theme)),
+            ],
+            None => vec![kv_line(
+                theme,
+                "Tracking",
+                "(none)",
+                Style::default().fg(theme.muted),
+            )],
+        };
     };
 
-    let ahead_behind = branch
-        .get()
-        .target()
-        .zip(upstream.get().target())
-        .and_then(|(local, remote)| repo.graph_ahead_behind(local, remote).ok());
-
-    let mut text = upstream_name;
-    if let Some((ahead, behind)) = ahead_behind {
+    let mut text = upstream.clone();
+    if let (Some(ahead), Some(behind)) = (tracking.ahead, tracking.behind) {
+        let count = |count: usize| match mode.depth {
+            Some(depth) if mode.first_parent && count > depth => format!("{depth}+"),
+            _ => count.to_string(),
+        };
         let mut parts = Vec::new();
         if ahead > 0 {
-            parts.push(format!("ahead {ahead}"));
+            parts.push(format!("ahead {}", count(ahead)));
         }
         if behind > 0 {
-            parts.push(format!("behind {behind}"));
+            parts.push(format!("behind {}", count(behind)));
         }
         if !parts.is_empty() {
             text.push_str(&format!(" ({})", parts.join(", ")));
         }
     }
 
-    kv_line("Tracking", text, Style::default().fg(Color::LightBlue))
+    vec![kv_line(
+        theme,
+        "Tracking",
+        text,
+        Style::default().fg(Color::LightBlue),
+    )]
 }
 
-fn describe_commit(commit: &Commit<'_>) -> Vec<Line<'static>> {
+fn commit_lines(commit: &CommitDetails, theme: &Theme) -> Vec<Line<'static>> {
     let mut lines: Vec<Line<'static>> = Vec::new();
-    let summary = commit.summary().unwrap_or("(no summary)");
-    let summary = summary.lines().next().unwrap_or(summary).trim();
 
     let mut head_value = vec![Span::styled(
-        short_id(commit.id()),
+        short_id(&commit.id),
         Style::default()
             .fg(Color::Magenta)
             .add_modifier(Modifier::BOLD),
     )];
-    if !summary.is_empty() {
-        head_value.push(Span::raw(format!("  {summary}")));
+    if !commit.summary.is_empty() {
+        head_value.push(Span::raw(format!("  {}", commit.summary)));
     }
-    lines.push(kv_line_spans("HEAD", head_value));
+    lines.push(kv_line_spans(theme, "HEAD", head_value));
 
-    let author = commit.author();
-    let author_name = author.name().unwrap_or("Unknown").trim();
-    let author_email = author.email().unwrap_or("").trim();
+    let author_name = commit.author_name.as_str();
+    let author_email = commit.author_email.as_str();
 
     if !author_name.is_empty() || !author_email.is_empty() {
         let mut author_text = String::new();
@@ -1420,113 +1667,51 @@ fn describe_commit(commit: &Commit<'_>) -> Vec<Line<'static>> {
             author_text.push('>');
         }
 
-        lines.push(kv_line("Author", author_text, muted_style()));
+        lines.push(kv_line(theme, "Author", author_text, muted_style(theme)));
     }
 
     lines
 }
 
-fn describe_repository_state(repo: &Repository) -> Option<Line<'static>> {
-    let state = repo.state();
-    if state == RepositoryState::Clean {
-        return None;
-    }
-
-    let label = match state {
-        RepositoryState::Merge => "MERGING",
-        RepositoryState::Revert => "REVERTING",
-        RepositoryState::RevertSequence => "REVERTING",
-        RepositoryState::CherryPick => "CHERRY-PICKING",
-        RepositoryState::CherryPickSequence => "CHERRY-PICKING",
-        RepositoryState::Bisect => "BISECTING",
-        RepositoryState::Rebase => "REBASING",
-        RepositoryState::RebaseInteractive => "REBASING",
-        RepositoryState::RebaseMerge => "REBASING",
-        RepositoryState::ApplyMailbox => "APPLYING MAILBOX",
-        RepositoryState::ApplyMailboxOrRebase => "APPLYING",
-        _ => "PENDING",
-    };
-
-    Some(kv_line(
-        "Git State",
-        label,
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD),
-    ))
-}
-
-fn summarize_worktree(repo: &Repository) -> Option<Line<'static>> {
-    let mut options = StatusOptions::new();
-    options
-        .include_untracked(true)
-        .recurse_untracked_dirs(true)
-        .renames_head_to_index(true)
-        .renames_index_to_workdir(true);
-
-    let Ok(statuses) = repo.statuses(Some(&mut options)) else {
-        return Some(kv_line(
+fn working_tree_line(summary: Option<&WorkingTree>, theme: &Theme) -> Line<'static> {
+    let Some(summary) = summary else {
+        return kv_line(
+            theme,
             "State",
             "Unable to read status",
-            Style::default().fg(Color::Red),
-        ));
+            Style::default().fg(theme.destructive),
+        );
     };
 
-    let mut staged = 0usize;
-    let mut unstaged = 0usize;
-    let mut untracked = 0usize;
-    let mut conflicts = 0usize;
-
-    for entry in statuses.iter() {
-        let status = entry.status();
-        if status.intersects(
-            Status::INDEX_NEW
-                | Status::INDEX_MODIFIED
-                | Status::INDEX_DELETED
-                | Status::INDEX_RENAMED
-                | Status::INDEX_TYPECHANGE,
-        ) {
-            staged += 1;
-        }
-
-        if status.intersects(
-            Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
-        ) {
-            unstaged += 1;
-        }
-
-        if status.contains(Status::WT_NEW) {
-            untracked += 1;
-        }
-
-        if status.contains(Status::CONFLICTED) {
-            conflicts += 1;
-        }
-    }
-
-    let clean = staged == 0 && unstaged == 0 && untracked == 0 && conflicts == 0;
-
-    if clean {
-        return Some(kv_line("State", "Clean", Style::default().fg(Color::Green)));
+    if summary.clean {
+        return kv_line(theme, "State", "Clean", Style::default().fg(theme.success));
     }
 
     let mut parts = Vec::new();
-    if staged > 0 {
-        parts.push(pluralize(staged, "staged change", "staged changes"));
+    if summary.staged > 0 {
+        parts.push(pluralize(summary.staged, "staged change", "staged changes"));
     }
-    if unstaged > 0 {
-        parts.push(pluralize(unstaged, "unstaged change", "unstaged changes"));
+    if summary.unstaged > 0 {
+        parts.push(pluralize(
+            summary.unstaged,
+            "unstaged change",
+            "unstaged changes",
+        ));
     }
-    if untracked > 0 {
-        parts.push(pluralize(untracked, "untracked file", "untracked files"));
+    if summary.untracked > 0 {
+        parts.push(pluralize(
+            summary.untracked,
+            "untracked file",
+            "untracked files",
+        ));
     }
-    if conflicts > 0 {
-        parts.push(pluralize(conflicts, "conflict", "conflicts"));
+    if summary.conflicts > 0 {
+        parts.push(pluralize(summary.conflicts, "conflict", "conflicts"));
     }
 
-    let mut style = Style::default().fg(Color::Yellow);
-    if conflicts > 0 {
-        style = style.fg(Color::Red).add_modifier(Modifier::BOLD);
+    let mut style = Style::default().fg(theme.warning);
+    if summary.conflicts > 0 {
+        style = style.fg(theme.destructive).add_modifier(Modifier::BOLD);
     }
 
     let text = if parts.is_empty() {
@@ -1535,28 +1720,33 @@ fn summarize_worktree(repo: &Repository) -> Option<Line<'static>> {
         parts.join(" | ")
     };
 
-    Some(kv_line("State", text, style))
+    kv_line(theme, "State", text, style)
 }
 
-fn section_header(title: &str) -> Line<'static> {
+fn section_header(theme: &Theme, title: &str) -> Line<'static> {
     Line::from(vec![Span::styled(
         format!("> {}", title.to_uppercase()),
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.header)
             .add_modifier(Modifier::BOLD),
     )])
 }
 
-fn kv_line(label: &str, value: impl Into<String>, value_style: Style) -> Line<'static> {
-    kv_line_spans(label, vec![Span::styled(value.into(), value_style)])
+fn kv_line(
+    theme: &Theme,
+    label: &str,
+    value: impl Into<String>,
+    value_style: Style,
+) -> Line<'static> {
+    kv_line_spans(theme, label, vec![Span::styled(value.into(), value_style)])
 }
 
-fn kv_line_spans(label: &str, mut value_spans: Vec<Span<'static>>) -> Line<'static> {
+fn kv_line_spans(theme: &Theme, label: &str, mut value_spans: Vec<Span<'static>>) -> Line<'static> {
     let mut spans = Vec::with_capacity(value_spans.len() + 3);
     spans.push(Span::raw("  "));
     spans.push(Span::styled(
         format!("{:<11}", format!("{label}:")),
-        label_style(),
+        label_style(theme),
     ));
     spans.push(Span::raw(" "));
     spans.append(&mut value_spans);
@@ -1567,18 +1757,15 @@ fn message_line(text: impl Into<String>, style: Style) -> Line<'static> {
     Line::from(vec![Span::raw("  "), Span::styled(text.into(), style)])
 }
 
-fn label_style() -> Style {
-    Style::default()
-        .fg(Color::Gray)
-        .add_modifier(Modifier::BOLD)
+fn label_style(theme: &Theme) -> Style {
+    muted_style(theme).add_modifier(Modifier::BOLD)
 }
 
-fn muted_style() -> Style {
-    Style::default().fg(Color::Gray)
+fn muted_style(theme: &Theme) -> Style {
+    Style::default().fg(theme.muted)
 }
 
-fn short_id(oid: Oid) -> String {
-    let id = oid.to_string();
+fn short_id(id: &str) -> String {
     id.chars().take(7).collect()
 }
 
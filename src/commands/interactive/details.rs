@@ -0,0 +1,288 @@
+use std::path::Path;
+
+use git2::{
+    Branch, BranchType, Commit, ErrorCode, Oid, Repository, RepositoryState, Sort, Status,
+    StatusOptions,
+};
+use serde::Serialize;
+
+use super::AheadBehind;
+use crate::repo::open_worktree_at;
+
+/// What the details pane and `describe` report about a worktree, independent of presentation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct WorktreeDetails {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) exists: bool,
+    /// Why the worktree repository could not be opened.
+    pub(crate) error: Option<String>,
+    pub(crate) head: Option<HeadDetails>,
+    /// In-progress git operation such as `REBASING`; `None` when the repository is clean.
+    pub(crate) state: Option<String>,
+    /// `None` when the status could not be read.
+    pub(crate) working_tree: Option<WorkingTree>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HeadKind {
+    Branch,
+    Tag,
+    Detached,
+    Unborn,
+    Unavailable,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct HeadDetails {
+    pub(crate) kind: HeadKind,
+    /// Branch or tag name.
+    pub(crate) name: Option<String>,
+    pub(crate) error: Option<String>,
+    pub(crate) tracking: Option<Tracking>,
+    pub(crate) commit: Option<CommitDetails>,
+    pub(crate) commit_error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct Tracking {
+    /// `None` when the branch has no upstream.
+    pub(crate) upstream: Option<String>,
+    pub(crate) ahead: Option<usize>,
+    pub(crate) behind: Option<usize>,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct CommitDetails {
+    pub(crate) id: String,
+    pub(crate) summary: String,
+    pub(crate) author_name: String,
+    pub(crate) author_email: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct WorkingTree {
+    pub(crate) clean: bool,
+    pub(crate) staged: usize,
+    pub(crate) unstaged: usize,
+    pub(crate) untracked: usize,
+    pub(crate) conflicts: usize,
+}
+
+pub(crate) fn collect_details(name: &str, path: &Path, mode: AheadBehind) -> WorktreeDetails {
+    let mut details = WorktreeDetails {
+        name: name.to_owned(),
+        path: path.display().to_string(),
+        exists: path.exists(),
+        error: None,
+        head: None,
+        state: None,
+        working_tree: None,
+    };
+
+    if !details.exists {
+        return details;
+    }
+
+    match open_worktree_at(name, path) {
+        Ok(repo) => {
+            details.head = Some(describe_head(&repo, mode));
+            details.state = describe_repository_state(&repo).map(str::to_owned);
+            details.working_tree = summarize_worktree(&repo);
+        }
+        Err(err) => details.error = Some(err.to_string()),
+    }
+
+    details
+}
+
+fn describe_head(repo: &Repository, mode: AheadBehind) -> HeadDetails {
+    let mut details = HeadDetails {
+        kind: HeadKind::Unavailable,
+        name: None,
+        error: None,
+        tracking: None,
+        commit: None,
+        commit_error: None,
+    };
+
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(err) => {
+            if err.code() == ErrorCode::UnbornBranch {
+                details.kind = HeadKind::Unborn;
+            } else {
+                details.error = Some(err.message().to_string());
+            }
+            return details;
+        }
+    };
+
+    if head.is_branch() {
+        let branch_name = head.shorthand().unwrap_or("(unnamed)").to_string();
+        details.kind = HeadKind::Branch;
+        if let Ok(branch) = repo.find_branch(&branch_name, BranchType::Local) {
+            details.tracking = Some(match branch.upstream() {
+                Ok(upstream) => describe_tracking(repo, &branch, &upstream, mode),
+                Err(err) => Tracking {
+                    upstream: None,
+                    ahead: None,
+                    behind: None,
+                    error: (err.code() != ErrorCode::NotFound).then(|| err.message().to_string()),
+                },
+            });
+        }
+        details.name = Some(branch_name);
+    } else if head.is_tag() {
+        details.kind = HeadKind::Tag;
+        details.name = Some(head.shorthand().unwrap_or("(tag)").to_string());
+    } else {
+        details.kind = HeadKind::Detached;
+    }
+
+    match head.peel_to_commit() {
+        Ok(commit) => details.commit = Some(describe_commit(&commit)),
+        Err(err) => details.commit_error = Some(err.message().to_string()),
+    }
+
+    details
+}
+
+fn describe_tracking(
+    repo: &Repository,
+    branch: &Branch<'_>,
+    upstream: &Branch<'_>,
+    mode: AheadBehind,
+) -> Tracking {
+    let upstream_name = match upstream.name() {
+        Ok(Some(name)) => name.to_string(),
+        Ok(None) => String::from("(non-UTF8)"),
+        Err(_) => upstream
+            .get()
+            .shorthand()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| String::from("(unknown)")),
+    };
+
+    let ahead_behind = branch
+        .get()
+        .target()
+        .zip(upstream.get().target())
+        .and_then(|(local, remote)| count_ahead_behind(repo, local, remote, mode).ok());
+
+    Tracking {
+        upstream: Some(upstream_name),
+        ahead: ahead_behind.map(|(ahead, _)| ahead),
+        behind: ahead_behind.map(|(_, behind)| behind),
+        error: None,
+    }
+}
+
+/// Commits `local` is ahead of and behind `remote`.
+///
+/// In first-parent mode each side is counted with a revwalk that only follows first parents and,
+/// when `depth` is set, stops after `depth + 1` commits so callers can tell the count was cut off.
+pub(crate) fn count_ahead_behind(
+    repo: &Repository,
+    local: Oid,
+    remote: Oid,
+    mode: AheadBehind,
+) -> std::result::Result<(usize, usize), git2::Error> {
+    if !mode.first_parent {
+        return repo.graph_ahead_behind(local, remote);
+    }
+
+    let count = |from: Oid, hide: Oid| -> std::result::Result<usize, git2::Error> {
+        let mut walk = repo.revwalk()?;
+        walk.set_sorting(Sort::TOPOLOGICAL)?;
+        walk.simplify_first_parent()?;
+        walk.push(from)?;
+        walk.hide(hide)?;
+        let limit = mode
+            .depth
+            .map_or(usize::MAX, |depth| depth.saturating_add(1));
+        walk.take(limit)
+            .try_fold(0, |count, oid| oid.map(|_| count + 1))
+    };
+
+    Ok((count(local, remote)?, count(remote, local)?))
+}
+
+fn describe_commit(commit: &Commit<'_>) -> CommitDetails {
+    let summary = commit.summary().unwrap_or("(no summary)");
+    let summary = summary.lines().next().unwrap_or(summary).trim();
+    let author = commit.author();
+
+    CommitDetails {
+        id: commit.id().to_string(),
+        summary: summary.to_owned(),
+        author_name: author.name().unwrap_or("Unknown").trim().to_owned(),
+        author_email: author.email().unwrap_or("").trim().to_owned(),
+    }
+}
+
+fn describe_repository_state(repo: &Repository) -> Option<&'static str> {
+    let label = match repo.state() {
+        RepositoryState::Clean => return None,
+        RepositoryState::Merge => "MERGING",
+        RepositoryState::Revert => "REVERTING",
+        RepositoryState::RevertSequence => "REVERTING",
+        RepositoryState::CherryPick => "CHERRY-PICKING",
+        RepositoryState::CherryPickSequence => "CHERRY-PICKING",
+        RepositoryState::Bisect => "BISECTING",
+        RepositoryState::Rebase => "REBASING",
+        RepositoryState::RebaseInteractive => "REBASING",
+        RepositoryState::RebaseMerge => "REBASING",
+        RepositoryState::ApplyMailbox => "APPLYING MAILBOX",
+        RepositoryState::ApplyMailboxOrRebase => "APPLYING",
+    };
+
+    Some(label)
+}
+
+pub(crate) fn summarize_worktree(repo: &Repository) -> Option<WorkingTree> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true);
+
+    let statuses = repo.statuses(Some(&mut options)).ok()?;
+
+    let mut summary = WorkingTree::default();
+    for entry in statuses.iter() {
+        let status = entry.status();
+        if status.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            summary.staged += 1;
+        }
+
+        if status.intersects(
+            Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+        ) {
+            summary.unstaged += 1;
+        }
+
+        if status.contains(Status::WT_NEW) {
+            summary.untracked += 1;
+        }
+
+        if status.contains(Status::CONFLICTED) {
+            summary.conflicts += 1;
+        }
+    }
+
+    summary.clean = summary.staged == 0
+        && summary.unstaged == 0
+        && summary.untracked == 0
+        && summary.conflicts == 0;
+    Some(summary)
+}
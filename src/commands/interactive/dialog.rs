@@ -1,4 +1,5 @@
-use super::WorktreeEntry;
+use super::{UncommittedChanges, WorktreeEntry};
+use crate::commands::merge_pr_github::MergeStrategy;
 
 /// Calculates initial scroll position to center default branch
 fn calculate_initial_scroll(
@@ -76,6 +77,7 @@ pub(crate) struct CreateDialog {
 impl CreateDialog {
     pub(crate) fn new(
         branches: &[String],
+        remote_branches: &[String],
         worktrees: &[WorktreeEntry],
         default_branch: Option<&str>,
     ) -> Self {
@@ -95,6 +97,20 @@ impl CreateDialog {
             });
         }
 
+        if !remote_branches.is_empty() {
+            let options = remote_branches
+                .iter()
+                .map(|branch| BaseOption {
+                    label: format!("remote: {branch}"),
+                    value: Some(branch.clone()),
+                })
+                .collect();
+            groups.push(BaseOptionGroup {
+                title: "Remotes".into(),
+                options,
+            });
+        }
+
         let mut worktree_options = worktrees
             .iter()
             .map(|entry| BaseOption {
@@ -129,20 +145,16 @@ impl CreateDialog {
         }
 
         let mut base_selected = 0;
-        if let Some(default) = default_branch {
-            if let Some((idx, _)) =
+        if let Some(default) = default_branch
+            && let Some((idx, _)) =
                 base_indices
                     .iter()
                     .enumerate()
                     .find(|(_, (group_idx, option_idx))| {
-                        groups[*group_idx].options[*option_idx]
-                            .value
-                            .as_deref()
-                            .map_or(false, |value| value == default)
+                        groups[*group_idx].options[*option_idx].value.as_deref() == Some(default)
                     })
-            {
-                base_selected = idx;
-            }
+        {
+            base_selected = idx;
         }
 
         if base_indices.is_empty() {
@@ -340,10 +352,12 @@ pub(crate) struct RemoveDialog {
     pub(crate) options_selected: usize,
     pub(crate) buttons_selected: usize,
     pub(crate) remove_local_branch: bool,
+    pub(crate) remove_remote_branch: bool,
+    pub(crate) uncommitted: UncommittedChanges,
 }
 
 impl RemoveDialog {
-    const OPTION_COUNT: usize = 1;
+    const OPTION_COUNT: usize = 3;
     const BUTTON_COUNT: usize = 2;
 
     pub(crate) fn new(index: usize) -> Self {
@@ -353,6 +367,8 @@ impl RemoveDialog {
             options_selected: 0,
             buttons_selected: 1,
             remove_local_branch: true,
+            remove_remote_branch: false,
+            uncommitted: UncommittedChanges::default(),
         }
     }
 
@@ -382,14 +398,25 @@ impl RemoveDialog {
     }
 
     pub(crate) fn toggle_selected_option(&mut self) {
-        if self.options_selected == 0 {
-            self.remove_local_branch = !self.remove_local_branch;
+        match self.options_selected {
+            0 => self.remove_local_branch = !self.remove_local_branch,
+            1 => self.remove_remote_branch = !self.remove_remote_branch,
+            2 => self.uncommitted = self.uncommitted.next(),
+            _ => {}
         }
     }
 
     pub(crate) fn remove_local_branch(&self) -> bool {
         self.remove_local_branch
     }
+
+    pub(crate) fn remove_remote_branch(&self) -> bool {
+        self.remove_remote_branch
+    }
+
+    pub(crate) fn uncommitted(&self) -> UncommittedChanges {
+        self.uncommitted
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -398,6 +425,8 @@ pub(crate) struct RemoveDialogView {
     pub(crate) options_selected: usize,
     pub(crate) buttons_selected: usize,
     pub(crate) remove_local_branch: bool,
+    pub(crate) remove_remote_branch: bool,
+    pub(crate) uncommitted: UncommittedChanges,
 }
 
 impl From<&RemoveDialog> for RemoveDialogView {
@@ -407,6 +436,8 @@ impl From<&RemoveDialog> for RemoveDialogView {
             options_selected: dialog.options_selected,
             buttons_selected: dialog.buttons_selected,
             remove_local_branch: dialog.remove_local_branch,
+            remove_remote_branch: dialog.remove_remote_branch,
+            uncommitted: dialog.uncommitted,
         }
     }
 }
@@ -432,10 +463,13 @@ pub(crate) struct MergeDialog {
     pub(crate) remove_local_branch: bool,
     pub(crate) remove_remote_branch: bool,
     pub(crate) remove_worktree: bool,
+    pub(crate) strategy: MergeStrategy,
+    /// Showing the final summary because the selected cleanup removes the worktree.
+    pub(crate) confirming: bool,
 }
 
 impl MergeDialog {
-    const OPTION_COUNT: usize = 3;
+    const OPTION_COUNT: usize = 4;
     const BUTTON_COUNT: usize = 2;
 
     pub(crate) fn new(index: usize) -> Self {
@@ -447,6 +481,8 @@ impl MergeDialog {
             remove_local_branch: true,
             remove_remote_branch: false,
             remove_worktree: false,
+            strategy: MergeStrategy::default(),
+            confirming: false,
         }
     }
 
@@ -480,6 +516,7 @@ impl MergeDialog {
             0 => self.remove_local_branch = !self.remove_local_branch,
             1 => self.remove_remote_branch = !self.remove_remote_branch,
             2 => self.remove_worktree = !self.remove_worktree,
+            3 => self.strategy = self.strategy.next(),
             _ => {}
         }
     }
@@ -495,6 +532,29 @@ impl MergeDialog {
     pub(crate) fn remove_worktree(&self) -> bool {
         self.remove_worktree
     }
+
+    pub(crate) fn strategy(&self) -> MergeStrategy {
+        self.strategy
+    }
+
+    /// The steps the merge will run, in order, for the confirmation summary.
+    pub(crate) fn cleanup_steps(&self) -> Vec<&'static str> {
+        let mut steps = vec![match self.strategy {
+            MergeStrategy::Merge => "Merge the pull request",
+            MergeStrategy::Squash => "Squash and merge the pull request",
+            MergeStrategy::Rebase => "Rebase and merge the pull request",
+        }];
+        if self.remove_local_branch {
+            steps.push("Delete the local branch");
+        }
+        if self.remove_remote_branch {
+            steps.push("Delete the remote branch");
+        }
+        if self.remove_worktree {
+            steps.push("Remove the worktree");
+        }
+        steps
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -505,6 +565,9 @@ pub(crate) struct MergeDialogView {
     pub(crate) remove_local_branch: bool,
     pub(crate) remove_remote_branch: bool,
     pub(crate) remove_worktree: bool,
+    pub(crate) strategy: MergeStrategy,
+    pub(crate) confirming: bool,
+    pub(crate) cleanup_steps: Vec<&'static str>,
 }
 
 impl From<&MergeDialog> for MergeDialogView {
@@ -516,6 +579,9 @@ impl From<&MergeDialog> for MergeDialogView {
             remove_local_branch: dialog.remove_local_branch,
             remove_remote_branch: dialog.remove_remote_branch,
             remove_worktree: dialog.remove_worktree,
+            strategy: dialog.strategy,
+            confirming: dialog.confirming,
+            cleanup_steps: dialog.cleanup_steps(),
         }
     }
 }
@@ -1,22 +1,37 @@
 mod command;
+mod details;
 mod dialog;
 mod runtime;
+mod theme;
 mod view;
 
 #[allow(unused_imports)]
 pub use command::InteractiveCommand;
 #[allow(unused_imports)]
-pub use runtime::{CrosstermEvents, run};
+pub use runtime::{CrosstermEvents, CrosstermScreen, run};
+pub use theme::Theme;
+pub(crate) use theme::TuiPreferences;
+
+pub(crate) use command::{detail_lines, last_commit_time};
+pub(crate) use details::{WorkingTree, collect_details, summarize_worktree};
 
 use std::path::PathBuf;
 
 use crossterm::event::Event;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
+
+use crate::commands::merge_pr_github::MergeStrategy;
 
 pub trait EventSource {
     fn next(&mut self) -> color_eyre::Result<Event>;
 }
 
+/// Hands the terminal back to a child process (such as an editor) and takes it back afterwards.
+pub trait ScreenSuspender {
+    fn suspend(&mut self);
+    fn resume(&mut self);
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct WorktreeEntry {
     pub(crate) name: String,
@@ -29,6 +44,74 @@ impl WorktreeEntry {
     }
 }
 
+/// Ordering applied to the worktree list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortKey {
+    #[default]
+    Name,
+    /// Most recent commit first.
+    Recent,
+    /// Worktrees with uncommitted changes first.
+    Dirty,
+}
+
+impl SortKey {
+    pub(crate) fn next(self) -> Self {
+        match self {
+            SortKey::Name => SortKey::Recent,
+            SortKey::Recent => SortKey::Dirty,
+            SortKey::Dirty => SortKey::Name,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Recent => "last commit",
+            SortKey::Dirty => "dirty first",
+        }
+    }
+}
+
+/// What the Remove dialog does with uncommitted changes in the worktree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UncommittedChanges {
+    /// Refuse to remove a dirty worktree.
+    #[default]
+    Keep,
+    /// Commit them to the worktree's branch first, like `rm --wip`.
+    Commit,
+    /// Remove the worktree anyway, like `rm --force`.
+    Discard,
+}
+
+impl UncommittedChanges {
+    pub(crate) fn next(self) -> Self {
+        match self {
+            UncommittedChanges::Keep => UncommittedChanges::Commit,
+            UncommittedChanges::Commit => UncommittedChanges::Discard,
+            UncommittedChanges::Discard => UncommittedChanges::Keep,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            UncommittedChanges::Keep => "refuse",
+            UncommittedChanges::Commit => "commit",
+            UncommittedChanges::Discard => "discard",
+        }
+    }
+}
+
+/// How the detail pane counts commits ahead of and behind the upstream.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AheadBehind {
+    /// Only follow first parents, like `git rev-list --first-parent --count`.
+    pub first_parent: bool,
+    /// Stop counting after this many commits per side (first-parent mode only).
+    pub depth: Option<usize>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum Focus {
     Worktrees,
@@ -47,6 +130,7 @@ pub(crate) enum Selection {
         remove_local_branch: bool,
         remove_remote_branch: bool,
         remove_worktree: bool,
+        strategy: MergeStrategy,
     },
     RepoRoot,
 }
@@ -58,15 +142,17 @@ pub(crate) enum Action {
     Remove,
     PrGithub,
     MergePrGithub,
+    Diff,
 }
 
 impl Action {
-    pub(crate) const ALL: [Action; 5] = [
+    pub(crate) const ALL: [Action; 6] = [
         Action::Open,
         Action::OpenInEditor,
         Action::Remove,
         Action::PrGithub,
         Action::MergePrGithub,
+        Action::Diff,
     ];
 
     pub(crate) fn label(self) -> &'static str {
@@ -76,6 +162,7 @@ impl Action {
             Action::Remove => "Remove",
             Action::PrGithub => "PR (GitHub)",
             Action::MergePrGithub => "Merge PR (GitHub)",
+            Action::Diff => "Diff",
         }
     }
 
@@ -87,6 +174,7 @@ impl Action {
                 | Action::Remove
                 | Action::PrGithub
                 | Action::MergePrGithub
+                | Action::Diff
         )
     }
 
@@ -122,10 +210,12 @@ impl StatusMessage {
         }
     }
 
-    pub(crate) fn style(&self) -> Style {
+    pub(crate) fn style(&self, theme: &Theme) -> Style {
         match self.kind {
-            StatusKind::Info => Style::default().fg(Color::Gray),
-            StatusKind::Error => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            StatusKind::Info => Style::default().fg(theme.muted),
+            StatusKind::Error => Style::default()
+                .fg(theme.destructive)
+                .add_modifier(Modifier::BOLD),
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::{io, process::Command};
+use std::io::{self, Write};
 
 use color_eyre::{Result, eyre::WrapErr};
 use crossterm::{
@@ -11,17 +11,20 @@ use ratatui::{Terminal, backend::CrosstermBackend};
 use crate::{
     Repo,
     commands::{
-        cd::{CdCommand, shell_command},
+        cd::{CdCommand, run_interactive_shell},
         create::{CreateCommand, CreateOutcome},
         list::{find_worktrees, format_worktree},
         merge_pr_github::MergePrGithubCommand,
-        pr_github::{PrGithubCommand, PrGithubOptions},
-        rm::RemoveCommand,
+        pr_github::{CommandRunner, PrGithubCommand, PrGithubOptions, SystemCommandRunner},
+        rm::{RemoveCommand, RemoveOutcome},
     },
     editor::launch_worktree,
 };
 
-use super::{EventSource, Selection, WorktreeEntry, command::InteractiveCommand};
+use super::{
+    AheadBehind, EventSource, ScreenSuspender, Selection, SortKey, Theme, UncommittedChanges,
+    WorktreeEntry, command::InteractiveCommand,
+};
 
 pub struct CrosstermEvents;
 
@@ -37,7 +40,30 @@ impl EventSource for CrosstermEvents {
     }
 }
 
-pub fn run(repo: &Repo) -> Result<()> {
+pub struct CrosstermScreen;
+
+impl ScreenSuspender for CrosstermScreen {
+    fn suspend(&mut self) {
+        // Failures are ignored so a broken terminal never blocks the child process.
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+        let _ = io::stdout().flush();
+    }
+
+    fn resume(&mut self) {
+        let _ = enable_raw_mode();
+        let _ = execute!(io::stdout(), EnterAlternateScreen);
+    }
+}
+
+pub fn run(
+    repo: &Repo,
+    sort: SortKey,
+    reverse: bool,
+    ahead_behind: AheadBehind,
+    quiet: bool,
+    fetch: bool,
+) -> Result<()> {
     let worktrees_dir = repo.ensure_worktrees_dir()?;
     let raw_entries = find_worktrees(&worktrees_dir)?;
     let worktrees = raw_entries
@@ -48,30 +74,44 @@ pub fn run(repo: &Repo) -> Result<()> {
         })
         .collect::<Vec<_>>();
 
-    let (branches, default_branch) = load_branches(repo)?;
+    let branches = load_branch_choices(repo, &mut SystemCommandRunner::default(), fetch)?;
+    let theme = Theme::load(repo)?;
+    let main_branch = repo
+        .git()
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(str::to_owned));
 
     enable_raw_mode().wrap_err("failed to enable raw mode")?;
     execute!(io::stdout(), EnterAlternateScreen).wrap_err("failed to enter alternate screen")?;
 
     let backend = CrosstermBackend::new(io::stdout());
     let terminal = Terminal::new(backend).wrap_err("failed to initialize terminal")?;
-    let events = CrosstermEvents::default();
+    let events = CrosstermEvents;
 
     let command = InteractiveCommand::new(
         terminal,
         events,
         worktrees_dir.clone(),
         worktrees,
-        branches,
-        default_branch,
-    );
+        branches.local,
+        branches.default_branch,
+    )
+    .with_remote_branches(branches.remote)
+    .with_sort(sort, reverse)
+    .with_theme(theme)
+    .with_ahead_behind(ahead_behind)
+    .with_title_bar(repo.root(), main_branch);
     let result = command.run(
-        |name, remove_local_branch| {
-            let command = RemoveCommand::new(name.to_owned(), false)
-                .with_quiet(true)
-                .with_remove_local_branch(remove_local_branch)
-                .with_spawn_shell(false);
-            command.execute(repo)
+        |name, remove_local_branch, remove_remote_branch, uncommitted| {
+            remove_from_dialog(
+                repo,
+                name,
+                remove_local_branch,
+                remove_remote_branch,
+                uncommitted,
+            )
         },
         |name, base| {
             let command = CreateCommand::new(name.to_owned(), base.map(|b| b.to_owned()));
@@ -83,7 +123,7 @@ pub fn run(repo: &Repo) -> Result<()> {
                 )),
             }
         },
-        |name, path| launch_worktree(repo, name, path, true),
+        |name, path| launch_worktree(repo, name, path, true, &[]),
     );
     let cleanup_result = cleanup_terminal();
 
@@ -104,40 +144,52 @@ pub fn run(repo: &Repo) -> Result<()> {
                 cd_repo_root(repo)?;
             }
             Selection::Worktree(name) => {
-                let command = CdCommand::new(name, false);
+                let mut command = CdCommand::new(name, false);
                 command.execute(repo)?;
             }
             Selection::PrGithub(name) => {
+                let label = format!("`gh pr create` for `{name}`");
                 let options = PrGithubOptions {
                     name,
                     push: true,
                     draft: false,
                     fill: false,
                     web: false,
-                    remote: String::from("origin"),
+                    remotes: vec![String::from("origin")],
                     reviewers: Vec::new(),
                     extra_args: Vec::new(),
+                    base: None,
                 };
-                let mut command = PrGithubCommand::new(options);
-                command.execute(repo)?;
+                let mut command = PrGithubCommand::new(options).with_quiet(quiet);
+                run_with_progress(&mut io::stdout(), &label, || command.execute(repo))?;
             }
             Selection::MergePrGithub {
                 name,
                 remove_local_branch,
                 remove_remote_branch,
                 remove_worktree,
+                strategy,
             } => {
-                let mut command = MergePrGithubCommand::new(name.clone());
+                let mut command = MergePrGithubCommand::new(name.clone())
+                    .with_strategy(strategy)
+                    .with_quiet(quiet);
                 if !remove_local_branch {
                     command.disable_remove_local();
                 }
                 if remove_remote_branch {
                     command.enable_remove_remote();
                 }
-                command.execute(repo)?;
+                let label = format!("`gh pr merge` for `{name}`");
+                run_with_progress(&mut io::stdout(), &label, || command.execute(repo))?;
 
                 if remove_worktree {
-                    let remove_command = RemoveCommand::new(name, false);
+                    let hint = format!(
+                        "the worktree was kept; run `rsworktree rm {name} --force` to remove it anyway, or add `--wip` to commit them first"
+                    );
+                    let remove_command = RemoveCommand::new(name, false)
+                        .with_confirmed(true)
+                        .with_quiet(quiet)
+                        .with_dirty_hint(hint);
                     let _ = remove_command.execute(repo)?;
                 }
             }
@@ -147,6 +199,40 @@ pub fn run(repo: &Repo) -> Result<()> {
     Ok(())
 }
 
+/// Brackets an action run after the TUI has closed with `Running ...` and a result line, so the
+/// wait on `gh` is not silent.
+pub(crate) fn remove_from_dialog(
+    repo: &Repo,
+    name: &str,
+    remove_local_branch: bool,
+    remove_remote_branch: bool,
+    uncommitted: UncommittedChanges,
+) -> Result<RemoveOutcome> {
+    RemoveCommand::new(name.to_owned(), uncommitted == UncommittedChanges::Discard)
+        .with_confirmed(true)
+        .with_quiet(true)
+        .with_remove_local_branch(remove_local_branch)
+        .with_remove_remote_branch(remove_remote_branch)
+        .with_wip(uncommitted == UncommittedChanges::Commit)
+        .with_dirty_hint("set Uncommitted changes to commit or discard in the Remove dialog")
+        .with_spawn_shell(false)
+        .execute(repo)
+}
+
+pub(crate) fn run_with_progress<W, F>(out: &mut W, label: &str, action: F) -> Result<()>
+where
+    W: Write,
+    F: FnOnce() -> Result<()>,
+{
+    writeln!(out, "Running {label}...").wrap_err("failed to write progress")?;
+    out.flush().wrap_err("failed to flush progress")?;
+
+    let result = action();
+    let outcome = if result.is_ok() { "Finished" } else { "Failed" };
+    writeln!(out, "{outcome} {label}.").wrap_err("failed to write progress")?;
+    result
+}
+
 fn cleanup_terminal() -> Result<()> {
     disable_raw_mode().wrap_err("failed to disable raw mode")?;
     execute!(io::stdout(), LeaveAlternateScreen).wrap_err("failed to leave alternate screen")?;
@@ -164,21 +250,46 @@ fn cd_repo_root(repo: &Repo) -> Result<()> {
 
     let canonical = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
 
-    let (program, args) = shell_command();
+    run_interactive_shell(&canonical, canonical.as_os_str(), &[], None)
+}
 
-    let mut cmd = Command::new(&program);
-    cmd.args(&args);
-    cmd.current_dir(&canonical);
-    cmd.env("PWD", canonical.as_os_str());
+/// Branches offered as bases in the Create dialog.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct BranchChoices {
+    pub(crate) local: Vec<String>,
+    pub(crate) remote: Vec<String>,
+    pub(crate) default_branch: Option<String>,
+}
 
-    cmd.status()
-        .wrap_err("failed to spawn subshell")?
-        .success()
-        .then_some(())
-        .ok_or_else(|| color_eyre::eyre::eyre!("subshell exited with a non-zero status"))
+/// Enumerates the branch choices, first running `git fetch --all` when `fetch` is set so remote
+/// branches are current.
+pub(crate) fn load_branch_choices<R: CommandRunner>(
+    repo: &Repo,
+    runner: &mut R,
+    fetch: bool,
+) -> Result<BranchChoices> {
+    if fetch {
+        fetch_all_remotes(repo, runner)?;
+    }
+    load_branches(repo)
 }
 
-fn load_branches(repo: &Repo) -> Result<(Vec<String>, Option<String>)> {
+fn fetch_all_remotes<R: CommandRunner>(repo: &Repo, runner: &mut R) -> Result<()> {
+    println!("Fetching all remotes...");
+    let args = vec!["fetch".to_owned(), "--all".to_owned(), "--prune".to_owned()];
+    let output = runner
+        .run_streamed("git", repo.root(), &args)
+        .wrap_err("failed to run `git fetch --all`")?;
+    if !output.success {
+        return Err(color_eyre::eyre::eyre!(
+            "`git fetch --all` failed: {}",
+            output.stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+fn load_branches(repo: &Repo) -> Result<BranchChoices> {
     use std::collections::BTreeSet;
 
     use git2::BranchType;
@@ -187,23 +298,32 @@ fn load_branches(repo: &Repo) -> Result<(Vec<String>, Option<String>)> {
     let mut set = BTreeSet::new();
     let mut default_branch = None;
 
-    if let Ok(head) = git_repo.head() {
-        if head.is_branch() {
-            if let Some(name) = head.shorthand() {
-                let branch = name.to_string();
-                set.insert(branch.clone());
-                default_branch = Some(branch);
-            }
+    if let Ok(head) = git_repo.head()
+        && head.is_branch()
+        && let Some(name) = head.shorthand()
+    {
+        let branch = name.to_string();
+        set.insert(branch.clone());
+        default_branch = Some(branch);
+    }
+
+    let iter = git_repo.branches(Some(BranchType::Local))?;
+    for branch_result in iter {
+        let (branch, _) = branch_result?;
+        if let Some(name) = branch.name()?
+            && !name.is_empty()
+        {
+            set.insert(name.to_string());
         }
     }
 
-    let mut iter = git_repo.branches(Some(BranchType::Local))?;
-    while let Some(branch_result) = iter.next() {
+    let mut remote = BTreeSet::new();
+    for branch_result in git_repo.branches(Some(BranchType::Remote))? {
         let (branch, _) = branch_result?;
-        if let Some(name) = branch.name()? {
-            if !name.is_empty() {
-                set.insert(name.to_string());
-            }
+        if let Some(name) = branch.name()?
+            && !name.ends_with("/HEAD")
+        {
+            remote.insert(name.to_string());
         }
     }
 
@@ -216,5 +336,9 @@ fn load_branches(repo: &Repo) -> Result<(Vec<String>, Option<String>)> {
         }
     });
 
-    Ok((branches, default_branch))
+    Ok(BranchChoices {
+        local: branches,
+        remote: remote.into_iter().collect(),
+        default_branch,
+    })
 }
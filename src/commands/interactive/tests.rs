@@ -1,13 +1,16 @@
-use super::command::ActionPanelState;
 use super::*;
-use std::{collections::VecDeque, path::PathBuf};
+use super::{command::ActionPanelState, details::count_ahead_behind};
+use std::{cell::RefCell, collections::VecDeque, path::PathBuf, rc::Rc};
 
 use color_eyre::{Result, eyre};
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{Terminal, backend::TestBackend};
 
 use crate::{
-    commands::rm::{LocalBranchStatus, RemoveOutcome},
+    commands::{
+        pr_github::{CommandOutput, CommandRunner},
+        rm::{LocalBranchStatus, RemoteBranchStatus, RemoveOutcome},
+    },
     editor::LaunchOutcome,
     telemetry::EditorLaunchStatus,
 };
@@ -47,6 +50,51 @@ fn entries(names: &[&str]) -> Vec<WorktreeEntry> {
         .collect()
 }
 
+#[derive(Clone, Default)]
+struct RecordingScreen {
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl ScreenSuspender for RecordingScreen {
+    fn suspend(&mut self) {
+        self.log.borrow_mut().push("suspend");
+    }
+
+    fn resume(&mut self) {
+        self.log.borrow_mut().push("resume");
+    }
+}
+
+type RecordedCalls = Rc<RefCell<Vec<(String, PathBuf, Vec<String>)>>>;
+
+struct RecordingDiffRunner {
+    log: Rc<RefCell<Vec<&'static str>>>,
+    calls: RecordedCalls,
+    success: bool,
+}
+
+impl CommandRunner for RecordingDiffRunner {
+    fn run(
+        &mut self,
+        program: &str,
+        current_dir: &std::path::Path,
+        args: &[String],
+    ) -> Result<CommandOutput> {
+        self.log.borrow_mut().push("diff");
+        self.calls.borrow_mut().push((
+            program.to_owned(),
+            current_dir.to_path_buf(),
+            args.to_vec(),
+        ));
+        Ok(CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            success: self.success,
+            status_code: Some(if self.success { 0 } else { 128 }),
+        })
+    }
+}
+
 fn noop_open_editor() -> impl FnMut(&str, &std::path::Path) -> Result<LaunchOutcome> {
     move |_, _| {
         Ok(LaunchOutcome {
@@ -73,10 +121,12 @@ fn returns_first_worktree_when_enter_pressed_immediately() -> Result<()> {
 
     let selection = command
         .run(
-            |_, _| {
+            |_, _, _, _| {
                 Ok(RemoveOutcome {
                     local_branch: None,
+                    remote_branch: None,
                     repositioned: false,
+                    removed: true,
                 })
             },
             |_, _| panic!("create should not be called"),
@@ -105,10 +155,12 @@ fn navigates_down_before_selecting() -> Result<()> {
 
     let selection = command
         .run(
-            |_, _| {
+            |_, _, _, _| {
                 Ok(RemoveOutcome {
                     local_branch: None,
+                    remote_branch: None,
                     repositioned: false,
+                    removed: true,
                 })
             },
             |_, _| panic!("create should not be called"),
@@ -143,11 +195,13 @@ fn selecting_pr_github_action_exits_with_pr_variant() -> Result<()> {
 
     let mut removed = Vec::new();
     let result = command.run(
-        |name, _remove_branch| {
+        |name, _remove_branch, _remove_remote, _| {
             removed.push(name.to_owned());
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -168,6 +222,7 @@ fn action_panel_wraps_when_navigating_up() -> Result<()> {
         key(KeyCode::Tab),
         key(KeyCode::Up),
         key(KeyCode::Up),
+        key(KeyCode::Up),
         key(KeyCode::Enter),
     ]);
     let worktrees = entries(&["alpha"]);
@@ -181,10 +236,12 @@ fn action_panel_wraps_when_navigating_up() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -239,6 +296,7 @@ fn selecting_merge_action_collects_cleanup_choices() -> Result<()> {
         char_key(' '),
         key(KeyCode::Tab),
         key(KeyCode::Enter),
+        char_key('y'),
     ]);
     let worktrees = entries(&["alpha"]);
     let command = InteractiveCommand::new(
@@ -251,10 +309,12 @@ fn selecting_merge_action_collects_cleanup_choices() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -267,11 +327,64 @@ fn selecting_merge_action_collects_cleanup_choices() -> Result<()> {
             remove_local_branch,
             remove_remote_branch,
             remove_worktree,
+            strategy,
         }) => {
             assert_eq!(name, "alpha");
             assert!(remove_local_branch);
             assert!(remove_remote_branch);
             assert!(remove_worktree);
+            assert_eq!(strategy, MergeStrategy::Merge);
+        }
+        other => panic!("unexpected selection: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn declining_merge_confirmation_merges_but_keeps_worktree() -> Result<()> {
+    let backend = TestBackend::new(60, 20);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![
+        key(KeyCode::Tab),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Enter),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        char_key(' '),
+        key(KeyCode::Tab),
+        key(KeyCode::Enter),
+        char_key('n'),
+    ]);
+    let worktrees = entries(&["alpha"]);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    );
+
+    let result = command.run(
+        |_, _, _, _| panic!("worktree removal should not run inside the TUI"),
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    match result {
+        Some(Selection::MergePrGithub {
+            name,
+            remove_local_branch,
+            remove_worktree,
+            ..
+        }) => {
+            assert_eq!(name, "alpha");
+            assert!(remove_local_branch);
+            assert!(!remove_worktree);
         }
         other => panic!("unexpected selection: {other:?}"),
     }
@@ -305,10 +418,12 @@ fn merge_dialog_allows_disabling_local_branch_removal() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -321,11 +436,63 @@ fn merge_dialog_allows_disabling_local_branch_removal() -> Result<()> {
             remove_local_branch,
             remove_remote_branch,
             remove_worktree,
+            strategy,
         }) => {
             assert_eq!(name, "alpha");
             assert!(!remove_local_branch);
             assert!(!remove_remote_branch);
             assert!(!remove_worktree);
+            assert_eq!(strategy, MergeStrategy::Merge);
+        }
+        other => panic!("unexpected selection: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn merge_dialog_cycles_merge_strategy() -> Result<()> {
+    let backend = TestBackend::new(40, 12);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![
+        key(KeyCode::Tab),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Enter),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        char_key(' '),
+        char_key(' '),
+        key(KeyCode::Tab),
+        key(KeyCode::Enter),
+    ]);
+    let worktrees = entries(&["alpha"]);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    );
+
+    let result = command.run(
+        |_, _, _, _| panic!("remove should not be called"),
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    match result {
+        Some(Selection::MergePrGithub {
+            remove_local_branch,
+            strategy,
+            ..
+        }) => {
+            assert!(remove_local_branch);
+            assert_eq!(strategy, MergeStrategy::Rebase);
         }
         other => panic!("unexpected selection: {other:?}"),
     }
@@ -359,11 +526,13 @@ fn tabbing_to_actions_removes_selected_worktree() -> Result<()> {
 
     let mut removed = Vec::new();
     let result = command.run(
-        |name, remove_local_branch| {
-            removed.push((name.to_owned(), remove_local_branch));
+        |name, remove_local_branch, remove_remote_branch, _| {
+            removed.push((name.to_owned(), remove_local_branch, remove_remote_branch));
             Ok(RemoveOutcome {
                 local_branch: remove_local_branch.then_some(LocalBranchStatus::Deleted),
+                remote_branch: remove_remote_branch.then_some(RemoteBranchStatus::Deleted),
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -374,7 +543,7 @@ fn tabbing_to_actions_removes_selected_worktree() -> Result<()> {
         result.is_none(),
         "expected interactive session to exit without opening"
     );
-    assert_eq!(removed, vec![(String::from("beta"), true)]);
+    assert_eq!(removed, vec![(String::from("beta"), true, false)]);
 
     Ok(())
 }
@@ -406,11 +575,13 @@ fn remove_dialog_allows_disabling_local_branch_removal() -> Result<()> {
 
     let mut removed = Vec::new();
     let result = command.run(
-        |name, remove_local_branch| {
-            removed.push((name.to_owned(), remove_local_branch));
+        |name, remove_local_branch, remove_remote_branch, _| {
+            removed.push((name.to_owned(), remove_local_branch, remove_remote_branch));
             Ok(RemoveOutcome {
                 local_branch: remove_local_branch.then_some(LocalBranchStatus::Deleted),
+                remote_branch: remove_remote_branch.then_some(RemoteBranchStatus::Deleted),
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -418,7 +589,53 @@ fn remove_dialog_allows_disabling_local_branch_removal() -> Result<()> {
     )?;
 
     assert!(result.is_none());
-    assert_eq!(removed, vec![(String::from("alpha"), false)]);
+    assert_eq!(removed, vec![(String::from("alpha"), false, false)]);
+
+    Ok(())
+}
+
+#[test]
+fn remove_dialog_requests_remote_branch_removal_when_checked() -> Result<()> {
+    let backend = TestBackend::new(40, 12);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![
+        key(KeyCode::Tab),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Enter),
+        key(KeyCode::Down),
+        char_key(' '),
+        char_key('y'),
+        key(KeyCode::Enter),
+        key(KeyCode::Esc),
+    ]);
+    let worktrees = entries(&["alpha"]);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    );
+
+    let mut removed = Vec::new();
+    let result = command.run(
+        |name, remove_local_branch, remove_remote_branch, _| {
+            removed.push((name.to_owned(), remove_local_branch, remove_remote_branch));
+            Ok(RemoveOutcome {
+                local_branch: remove_local_branch.then_some(LocalBranchStatus::Deleted),
+                remote_branch: remove_remote_branch.then_some(RemoteBranchStatus::Deleted),
+                repositioned: false,
+                removed: true,
+            })
+        },
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    assert!(result.is_none());
+    assert_eq!(removed, vec![(String::from("alpha"), true, true)]);
 
     Ok(())
 }
@@ -447,18 +664,20 @@ fn removing_current_worktree_requests_root_exit() -> Result<()> {
 
     let mut removed = Vec::new();
     let result = command.run(
-        |name, remove_local_branch| {
-            removed.push((name.to_owned(), remove_local_branch));
+        |name, remove_local_branch, remove_remote_branch, _| {
+            removed.push((name.to_owned(), remove_local_branch, remove_remote_branch));
             Ok(RemoveOutcome {
                 local_branch: remove_local_branch.then_some(LocalBranchStatus::Deleted),
+                remote_branch: remove_remote_branch.then_some(RemoteBranchStatus::Deleted),
                 repositioned: true,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
         noop_open_editor(),
     )?;
 
-    assert_eq!(removed, vec![(String::from("alpha"), true)]);
+    assert_eq!(removed, vec![(String::from("alpha"), true, false)]);
     assert_eq!(result, Some(Selection::RepoRoot));
 
     Ok(())
@@ -487,11 +706,13 @@ fn cancelling_remove_keeps_worktree() -> Result<()> {
 
     let mut removed = Vec::new();
     let result = command.run(
-        |name, remove_local_branch| {
-            removed.push((name.to_owned(), remove_local_branch));
+        |name, remove_local_branch, remove_remote_branch, _| {
+            removed.push((name.to_owned(), remove_local_branch, remove_remote_branch));
             Ok(RemoveOutcome {
                 local_branch: remove_local_branch.then_some(LocalBranchStatus::Deleted),
+                remote_branch: remove_remote_branch.then_some(RemoteBranchStatus::Deleted),
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -533,10 +754,12 @@ fn create_action_adds_new_worktree() -> Result<()> {
 
     let mut created = Vec::new();
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |name, base| {
@@ -555,6 +778,70 @@ fn create_action_adds_new_worktree() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn create_and_continue_keeps_dialog_open_for_next_worktree() -> Result<()> {
+    let backend = TestBackend::new(60, 18);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![
+        key(KeyCode::Up),
+        key(KeyCode::Up),
+        key(KeyCode::Enter),
+        char_key('o'),
+        char_key('n'),
+        char_key('e'),
+        key(KeyCode::Tab),
+        key(KeyCode::Tab),
+        key(KeyCode::Right),
+        key(KeyCode::Enter),
+        char_key('t'),
+        char_key('w'),
+        char_key('o'),
+        key(KeyCode::Tab),
+        key(KeyCode::Tab),
+        key(KeyCode::Enter),
+        key(KeyCode::Esc),
+        key(KeyCode::Enter),
+    ]);
+
+    let worktrees = entries(&["alpha"]);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    );
+
+    let mut created = Vec::new();
+    let result = command.run(
+        |_, _, _, _| {
+            Ok(RemoveOutcome {
+                local_branch: None,
+                remote_branch: None,
+                repositioned: false,
+                removed: true,
+            })
+        },
+        |name, base| {
+            created.push((name.to_string(), base.map(|b| b.to_string())));
+            Ok(())
+        },
+        noop_open_editor(),
+    )?;
+
+    assert_eq!(result, Some(Selection::Worktree(String::from("two"))));
+    assert_eq!(
+        created,
+        vec![
+            (String::from("one"), Some(String::from("main"))),
+            (String::from("two"), Some(String::from("main"))),
+        ]
+    );
+
+    Ok(())
+}
+
 #[test]
 fn cancelling_create_leaves_state_unchanged() -> Result<()> {
     let backend = TestBackend::new(60, 18);
@@ -578,10 +865,12 @@ fn cancelling_create_leaves_state_unchanged() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -610,10 +899,12 @@ fn cd_to_root_global_action_exits() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -642,10 +933,12 @@ fn up_from_top_moves_to_global_actions() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -679,10 +972,12 @@ fn up_from_top_after_tabbing_picks_last_global_action() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -711,10 +1006,12 @@ fn up_with_no_worktrees_moves_to_global_actions() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -754,7 +1051,7 @@ fn down_with_no_worktrees_opens_create_dialog() -> Result<()> {
 
     let mut created = Vec::new();
     let result = command.run(
-        |_, _| panic!("remove should not be called"),
+        |_, _, _, _| panic!("remove should not be called"),
         |name, base| {
             created.push((name.to_string(), base.map(|b| b.to_string())));
             Ok(())
@@ -780,7 +1077,7 @@ fn find_selected_line_locates_branch_in_flat_lines() {
         "develop".to_string(),
         "feature".to_string(),
     ];
-    let dialog = dialog::CreateDialog::new(&branches, &[], Some("develop"));
+    let dialog = dialog::CreateDialog::new(&branches, &[], &[], Some("develop"));
 
     let selected_line = dialog.find_selected_line();
 
@@ -791,7 +1088,7 @@ fn find_selected_line_locates_branch_in_flat_lines() {
 #[test]
 fn ensure_visible_scrolls_down_when_selection_below_viewport() {
     let branches: Vec<String> = (0..50).map(|i| format!("branch-{i}")).collect();
-    let mut dialog = dialog::CreateDialog::new(&branches, &[], None);
+    let mut dialog = dialog::CreateDialog::new(&branches, &[], &[], None);
 
     // Navigate to branch 40
     for _ in 0..40 {
@@ -816,7 +1113,7 @@ fn ensure_visible_scrolls_down_when_selection_below_viewport() {
 #[test]
 fn ensure_visible_scrolls_up_when_selection_above_viewport() {
     let branches: Vec<String> = (0..50).map(|i| format!("branch-{i}")).collect();
-    let mut dialog = dialog::CreateDialog::new(&branches, &[], None);
+    let mut dialog = dialog::CreateDialog::new(&branches, &[], &[], None);
 
     // Navigate to end
     for _ in 0..45 {
@@ -839,7 +1136,7 @@ fn ensure_visible_scrolls_up_when_selection_above_viewport() {
 #[test]
 fn initial_scroll_centers_default_branch() {
     let branches: Vec<String> = (0..50).map(|i| format!("branch-{:02}", i)).collect();
-    let dialog = dialog::CreateDialog::new(&branches, &[], Some("branch-25"));
+    let dialog = dialog::CreateDialog::new(&branches, &[], &[], Some("branch-25"));
 
     let selected_line = dialog.find_selected_line().unwrap();
 
@@ -853,7 +1150,7 @@ fn initial_scroll_centers_default_branch() {
     // Verify selection is within reasonable center range
     let relative_pos = selected_line - dialog.scroll_offset;
     assert!(
-        relative_pos >= 3 && relative_pos <= 7,
+        (3..=7).contains(&relative_pos),
         "selected branch should be near center of viewport"
     );
 }
@@ -861,7 +1158,7 @@ fn initial_scroll_centers_default_branch() {
 #[test]
 fn scroll_offset_never_exceeds_content_bounds() {
     let branches: Vec<String> = (0..10).map(|i| format!("branch-{i}")).collect();
-    let mut dialog = dialog::CreateDialog::new(&branches, &[], None);
+    let mut dialog = dialog::CreateDialog::new(&branches, &[], &[], None);
 
     // Simulate large viewport (larger than content)
     let visible_height = 100;
@@ -878,7 +1175,7 @@ fn scroll_offset_never_exceeds_content_bounds() {
 #[test]
 fn move_base_updates_scroll_position() {
     let branches: Vec<String> = (0..30).map(|i| format!("branch-{i}")).collect();
-    let mut dialog = dialog::CreateDialog::new(&branches, &[], None);
+    let mut dialog = dialog::CreateDialog::new(&branches, &[], &[], None);
 
     let initial_offset = dialog.scroll_offset;
 
@@ -901,7 +1198,7 @@ fn move_base_updates_scroll_position() {
 #[test]
 fn wrap_around_from_last_to_first_adjusts_scroll() {
     let branches: Vec<String> = (0..50).map(|i| format!("branch-{i}")).collect();
-    let mut dialog = dialog::CreateDialog::new(&branches, &[], None);
+    let mut dialog = dialog::CreateDialog::new(&branches, &[], &[], None);
 
     // Navigate to last branch
     for _ in 0..49 {
@@ -928,7 +1225,7 @@ fn scroll_with_multiple_groups() {
         WorktreeEntry::new("wt2".into(), PathBuf::from("/tmp/wt2")),
     ];
 
-    let dialog = dialog::CreateDialog::new(&branches, &worktrees, Some("main"));
+    let dialog = dialog::CreateDialog::new(&branches, &[], &worktrees, Some("main"));
 
     // Verify flat_lines includes both groups
     let has_branch_header = dialog
@@ -970,10 +1267,12 @@ fn down_from_last_worktree_moves_to_global_actions() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -991,9 +1290,9 @@ fn up_from_first_global_action_moves_to_last_worktree() -> Result<()> {
     let terminal = Terminal::new(backend)?;
     // Up to GlobalActions (lands on last), Up to first GlobalAction, Up again to last worktree
     let events = StubEvents::new(vec![
-        key(KeyCode::Up),   // From first worktree to GlobalActions (Cd to root dir)
-        key(KeyCode::Up),   // To Create worktree
-        key(KeyCode::Up),   // Back to last worktree (gamma)
+        key(KeyCode::Up), // From first worktree to GlobalActions (Cd to root dir)
+        key(KeyCode::Up), // To Create worktree
+        key(KeyCode::Up), // Back to last worktree (gamma)
         key(KeyCode::Enter),
     ]);
 
@@ -1008,10 +1307,12 @@ fn up_from_first_global_action_moves_to_last_worktree() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -1029,9 +1330,9 @@ fn down_navigates_within_global_actions() -> Result<()> {
     let terminal = Terminal::new(backend)?;
     // Go to GlobalActions via down from last worktree, then navigate within GlobalActions
     let events = StubEvents::new(vec![
-        key(KeyCode::Down), // alpha -> beta
-        key(KeyCode::Down), // beta -> gamma
-        key(KeyCode::Down), // gamma -> Create worktree (first GlobalAction)
+        key(KeyCode::Down),  // alpha -> beta
+        key(KeyCode::Down),  // beta -> gamma
+        key(KeyCode::Down),  // gamma -> Create worktree (first GlobalAction)
         key(KeyCode::Enter), // Open create dialog
         char_key('t'),
         char_key('e'),
@@ -1055,10 +1356,12 @@ fn down_navigates_within_global_actions() -> Result<()> {
 
     let mut created = Vec::new();
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |name, base| {
@@ -1099,10 +1402,12 @@ fn vim_keys_navigate_worktrees() -> Result<()> {
 
     let selection = command
         .run(
-            |_, _| {
+            |_, _, _, _| {
                 Ok(RemoveOutcome {
                     local_branch: None,
+                    remote_branch: None,
                     repositioned: false,
+                    removed: true,
                 })
             },
             |_, _| panic!("create should not be called"),
@@ -1138,10 +1443,12 @@ fn left_right_navigate_actions_panel() -> Result<()> {
 
     let mut editor_opened = false;
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -1176,10 +1483,12 @@ fn q_key_exits_interactive_mode() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| panic!("create should not be called"),
@@ -1208,10 +1517,12 @@ fn e_key_opens_editor_directly() -> Result<()> {
 
     let mut editor_calls = Vec::new();
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -1251,10 +1562,12 @@ fn backtab_cycles_focus_backward() -> Result<()> {
     );
 
     let result = command.run(
-        |_, _| {
+        |_, _, _, _| {
             Ok(RemoveOutcome {
                 local_branch: None,
+                remote_branch: None,
                 repositioned: false,
+                removed: true,
             })
         },
         |_, _| Ok(()),
@@ -1265,3 +1578,737 @@ fn backtab_cycles_focus_backward() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn open_in_editor_suspends_and_resumes_before_returning_to_list() -> Result<()> {
+    let backend = TestBackend::new(40, 10);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![char_key('e'), key(KeyCode::Down), key(KeyCode::Enter)]);
+    let worktrees = entries(&["alpha", "beta"]);
+    let screen = RecordingScreen::default();
+    let log = Rc::clone(&screen.log);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    )
+    .with_screen(screen);
+
+    let editor_log = Rc::clone(&log);
+    let result = command.run(
+        |_, _, _, _| panic!("remove should not be called"),
+        |_, _| panic!("create should not be called"),
+        move |_, _| {
+            editor_log.borrow_mut().push("launch");
+            Ok(LaunchOutcome {
+                status: EditorLaunchStatus::Success,
+                message: String::from("Launched `alpha`"),
+            })
+        },
+    )?;
+
+    assert_eq!(*log.borrow(), vec!["suspend", "launch", "resume"]);
+    assert_eq!(
+        result,
+        Some(Selection::Worktree(String::from("beta"))),
+        "the TUI should keep handling input after the editor exits"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn open_in_editor_resumes_screen_when_launch_fails() -> Result<()> {
+    let backend = TestBackend::new(40, 10);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![char_key('e'), key(KeyCode::Enter), key(KeyCode::Esc)]);
+    let worktrees = entries(&["alpha"]);
+    let screen = RecordingScreen::default();
+    let log = Rc::clone(&screen.log);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    )
+    .with_screen(screen);
+
+    let editor_log = Rc::clone(&log);
+    let result = command.run(
+        |_, _, _, _| panic!("remove should not be called"),
+        |_, _| panic!("create should not be called"),
+        move |_, _| {
+            editor_log.borrow_mut().push("launch");
+            Err(eyre::eyre!("editor crashed"))
+        },
+    )?;
+
+    assert_eq!(*log.borrow(), vec!["suspend", "launch", "resume"]);
+    assert!(result.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn reverse_sort_flag_reorders_entries() -> Result<()> {
+    let backend = TestBackend::new(40, 10);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![key(KeyCode::Enter)]);
+    let worktrees = entries(&["alpha", "beta", "gamma"]);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    )
+    .with_sort(SortKey::Name, true);
+
+    let result = command.run(
+        |_, _, _, _| panic!("remove should not be called"),
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    assert_eq!(result, Some(Selection::Worktree(String::from("gamma"))));
+
+    Ok(())
+}
+
+#[test]
+fn toggling_sort_keeps_selected_worktree_highlighted() -> Result<()> {
+    let backend = TestBackend::new(40, 10);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        char_key('r'),
+        key(KeyCode::Enter),
+    ]);
+    let worktrees = entries(&["alpha", "beta", "gamma"]);
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    );
+
+    let result = command.run(
+        |_, _, _, _| panic!("remove should not be called"),
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    assert_eq!(result, Some(Selection::Worktree(String::from("gamma"))));
+
+    Ok(())
+}
+
+#[test]
+fn sort_entries_orders_by_recent_commit_and_dirty_state() -> Result<()> {
+    let dir = tempfile::TempDir::new()?;
+    let commit_at = |name: &str, seconds: i64| -> Result<PathBuf> {
+        let path = dir.path().join(name);
+        let repo = git2::Repository::init(&path)?;
+        let signature =
+            git2::Signature::new("Test", "test@example.com", &git2::Time::new(seconds, 0))?;
+        let tree_id = repo.index()?.write_tree()?;
+        let tree = repo.find_tree(tree_id)?;
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])?;
+        Ok(path)
+    };
+
+    let old = commit_at("old", 1_000)?;
+    let new = commit_at("new", 2_000)?;
+    std::fs::write(old.join("scratch.txt"), "dirty")?;
+
+    let mut worktrees = vec![
+        WorktreeEntry::new(String::from("old"), old),
+        WorktreeEntry::new(String::from("new"), new),
+        WorktreeEntry::new(String::from("aaa-missing"), dir.path().join("missing")),
+    ];
+    let names = |entries: &[WorktreeEntry]| {
+        entries
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    command::sort_entries(&mut worktrees, SortKey::Recent, false);
+    assert_eq!(names(&worktrees), ["new", "old", "aaa-missing"]);
+
+    command::sort_entries(&mut worktrees, SortKey::Dirty, false);
+    assert_eq!(names(&worktrees), ["old", "aaa-missing", "new"]);
+
+    command::sort_entries(&mut worktrees, SortKey::Name, false);
+    assert_eq!(names(&worktrees), ["aaa-missing", "new", "old"]);
+
+    Ok(())
+}
+
+#[test]
+fn theme_highlight_color_is_applied_to_selected_worktree() -> Result<()> {
+    use ratatui::{style::Color, widgets::ListState};
+
+    let theme = Theme {
+        highlight: Color::Yellow,
+        ..Theme::default()
+    };
+    let snapshot = view::Snapshot::new(
+        vec![String::from("alpha")],
+        None,
+        Focus::Worktrees,
+        ActionPanelState::vertical(),
+        0,
+        None,
+        None,
+        true,
+        theme,
+    );
+
+    let mut terminal = Terminal::new(TestBackend::new(60, 20))?;
+    let mut state = ListState::default().with_selected(Some(0));
+    terminal.draw(|frame| snapshot.render(frame, &mut state))?;
+
+    let buffer = terminal.backend().buffer();
+    let area = buffer.area;
+    let position = (area.y..area.bottom())
+        .flat_map(|y| (area.x..area.right()).map(move |x| (x, y)))
+        .find(|&(x, y)| {
+            let row: String = (x..area.right().min(x + 5))
+                .map(|cx| buffer.get(cx, y).symbol())
+                .collect();
+            row == "alpha"
+        })
+        .expect("selected worktree should be rendered");
+
+    assert_eq!(buffer.get(position.0, position.1).fg, Color::Yellow);
+
+    Ok(())
+}
+
+#[test]
+fn title_bar_shows_repository_root_and_branch() -> Result<()> {
+    use ratatui::widgets::ListState;
+
+    let snapshot = view::Snapshot::new(
+        vec![String::from("alpha")],
+        None,
+        Focus::Worktrees,
+        ActionPanelState::vertical(),
+        0,
+        None,
+        None,
+        true,
+        Theme::default(),
+    )
+    .with_title(Some(view::TitleBar {
+        root: String::from("/home/dev/project"),
+        branch: Some(String::from("main")),
+    }));
+
+    let mut terminal = Terminal::new(TestBackend::new(60, 20))?;
+    let mut state = ListState::default().with_selected(Some(0));
+    terminal.draw(|frame| snapshot.render(frame, &mut state))?;
+
+    let buffer = terminal.backend().buffer();
+    let first_row: String = (0..buffer.area.width)
+        .map(|x| buffer.get(x, 0).symbol())
+        .collect();
+    assert_eq!(first_row.trim_end(), " /home/dev/project  on main");
+
+    let second_row: String = (0..buffer.area.width)
+        .map(|x| buffer.get(x, 1).symbol())
+        .collect();
+    assert!(
+        second_row.starts_with('┌'),
+        "panels should start below the title bar: {second_row:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diff_action_runs_git_diff_between_suspend_and_resume() -> Result<()> {
+    let backend = TestBackend::new(40, 12);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![
+        key(KeyCode::Down),
+        key(KeyCode::Tab),
+        key(KeyCode::Up),
+        key(KeyCode::Enter),
+        key(KeyCode::Esc),
+    ]);
+    let worktrees = entries(&["alpha", "beta"]);
+    let screen = RecordingScreen::default();
+    let log = Rc::clone(&screen.log);
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let runner = RecordingDiffRunner {
+        log: Rc::clone(&log),
+        calls: Rc::clone(&calls),
+        success: true,
+    };
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    )
+    .with_screen(screen)
+    .with_diff_runner(runner);
+
+    let result = command.run(
+        |_, _, _, _| panic!("remove should not be called"),
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    assert!(result.is_none());
+    assert_eq!(*log.borrow(), vec!["suspend", "diff", "resume"]);
+    assert_eq!(
+        *calls.borrow(),
+        vec![(
+            String::from("git"),
+            PathBuf::from("/tmp/beta"),
+            vec![
+                String::from("-C"),
+                String::from("/tmp/beta"),
+                String::from("--paginate"),
+                String::from("diff"),
+                String::from("HEAD"),
+            ],
+        )]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diff_action_resumes_screen_when_git_fails() -> Result<()> {
+    let backend = TestBackend::new(40, 12);
+    let terminal = Terminal::new(backend)?;
+    let events = StubEvents::new(vec![
+        key(KeyCode::Tab),
+        key(KeyCode::Up),
+        key(KeyCode::Enter),
+        key(KeyCode::Enter),
+        key(KeyCode::Esc),
+    ]);
+    let worktrees = entries(&["alpha"]);
+    let screen = RecordingScreen::default();
+    let log = Rc::clone(&screen.log);
+    let runner = RecordingDiffRunner {
+        log: Rc::clone(&log),
+        calls: Rc::new(RefCell::new(Vec::new())),
+        success: false,
+    };
+    let command = InteractiveCommand::new(
+        terminal,
+        events,
+        PathBuf::from("/tmp/worktrees"),
+        worktrees,
+        vec![String::from("main")],
+        Some(String::from("main")),
+    )
+    .with_screen(screen)
+    .with_diff_runner(runner);
+
+    let result = command.run(
+        |_, _, _, _| panic!("remove should not be called"),
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    assert_eq!(*log.borrow(), vec!["suspend", "diff", "resume"]);
+    assert!(result.is_none());
+
+    Ok(())
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) -> Result<()> {
+    let status = std::process::Command::new("git")
+        .current_dir(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .status()?;
+    if !status.success() {
+        return Err(eyre::eyre!("`git {}` exited with {status}", args.join(" ")));
+    }
+    Ok(())
+}
+
+fn remove_dirty_worktree_from_dialog(
+    repo: &crate::Repo,
+    worktree: &std::path::Path,
+    name: &str,
+    cycle_uncommitted: usize,
+) -> Result<(UncommittedChanges, Option<String>)> {
+    let mut events = vec![
+        key(KeyCode::Tab),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Enter),
+        char_key(' '),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+    ];
+    events.extend((0..cycle_uncommitted).map(|_| char_key(' ')));
+    events.extend([
+        char_key('y'),
+        key(KeyCode::Enter),
+        key(KeyCode::Esc),
+        key(KeyCode::Esc),
+    ]);
+    let command = InteractiveCommand::new(
+        Terminal::new(TestBackend::new(80, 20))?,
+        StubEvents::new(events),
+        repo.worktrees_dir(),
+        vec![WorktreeEntry::new(name.to_owned(), worktree.to_path_buf())],
+        vec![String::from("main")],
+        Some(String::from("main")),
+    );
+
+    let mut chosen = Vec::new();
+    command.run(
+        |name, remove_local_branch, remove_remote_branch, uncommitted| {
+            let result = runtime::remove_from_dialog(
+                repo,
+                name,
+                remove_local_branch,
+                remove_remote_branch,
+                uncommitted,
+            );
+            chosen.push((uncommitted, result.as_ref().err().map(ToString::to_string)));
+            result
+        },
+        |_, _| panic!("create should not be called"),
+        noop_open_editor(),
+    )?;
+
+    assert_eq!(chosen.len(), 1);
+    Ok(chosen.remove(0))
+}
+
+#[test]
+fn remove_dialog_commits_or_discards_uncommitted_changes() -> Result<()> {
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path();
+    git(path, &["init", "-q", "-b", "main"])?;
+    git(path, &["config", "user.name", "Test"])?;
+    git(path, &["config", "user.email", "test@example.com"])?;
+    std::fs::write(path.join("notes.txt"), "base\n")?;
+    git(path, &["add", "notes.txt"])?;
+    git(path, &["commit", "-q", "-m", "base"])?;
+    let repo = crate::Repo::discover_from(path)?;
+
+    for name in ["alpha", "beta", "gamma"] {
+        let worktree = repo.worktrees_dir().join(name);
+        let worktree_arg = worktree.display().to_string();
+        git(path, &["worktree", "add", "-q", "-b", name, &worktree_arg])?;
+        std::fs::write(worktree.join("notes.txt"), "dirty\n")?;
+    }
+
+    let alpha = repo.worktrees_dir().join("alpha");
+    let (chosen, error) = remove_dirty_worktree_from_dialog(&repo, &alpha, "alpha", 0)?;
+    assert_eq!(chosen, UncommittedChanges::Keep);
+    let error = error.expect("dirty worktree should be kept");
+    assert!(
+        error.contains("set Uncommitted changes to commit or discard in the Remove dialog"),
+        "{error}"
+    );
+    assert!(!error.contains("--force"), "{error}");
+    assert_eq!(std::fs::read_to_string(alpha.join("notes.txt"))?, "dirty\n");
+
+    let beta = repo.worktrees_dir().join("beta");
+    let (chosen, error) = remove_dirty_worktree_from_dialog(&repo, &beta, "beta", 1)?;
+    assert_eq!(chosen, UncommittedChanges::Commit);
+    assert_eq!(error, None);
+    assert!(!beta.exists());
+    let committed = repo.git().revparse_single("beta")?.peel_to_commit()?;
+    let blob = committed
+        .tree()?
+        .get_path(std::path::Path::new("notes.txt"))?;
+    assert_eq!(repo.git().find_blob(blob.id())?.content(), b"dirty\n");
+
+    let gamma = repo.worktrees_dir().join("gamma");
+    let (chosen, error) = remove_dirty_worktree_from_dialog(&repo, &gamma, "gamma", 2)?;
+    assert_eq!(chosen, UncommittedChanges::Discard);
+    assert_eq!(error, None);
+    assert!(!gamma.exists());
+
+    Ok(())
+}
+
+#[test]
+fn first_parent_counts_ignore_merged_side_branch_commits() -> Result<()> {
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path();
+    git(path, &["init", "-q", "-b", "main"])?;
+    git(path, &["commit", "-q", "--allow-empty", "-m", "base"])?;
+    git(path, &["branch", "upstream"])?;
+    git(path, &["checkout", "-q", "-b", "side"])?;
+    for message in ["side 1", "side 2", "side 3"] {
+        git(path, &["commit", "-q", "--allow-empty", "-m", message])?;
+    }
+    git(path, &["checkout", "-q", "main"])?;
+    git(path, &["commit", "-q", "--allow-empty", "-m", "feature"])?;
+    git(
+        path,
+        &["merge", "-q", "--no-ff", "-m", "merge side", "side"],
+    )?;
+
+    let repo = git2::Repository::open(path)?;
+    let local = repo.revparse_single("main")?.id();
+    let remote = repo.revparse_single("upstream")?.id();
+
+    assert_eq!(
+        count_ahead_behind(&repo, local, remote, AheadBehind::default())?,
+        (5, 0)
+    );
+    let first_parent = AheadBehind {
+        first_parent: true,
+        depth: None,
+    };
+    assert_eq!(
+        count_ahead_behind(&repo, local, remote, first_parent)?,
+        (2, 0)
+    );
+
+    let bounded = AheadBehind {
+        first_parent: true,
+        depth: Some(1),
+    };
+    assert_eq!(count_ahead_behind(&repo, local, remote, bounded)?, (2, 0));
+
+    Ok(())
+}
+
+#[test]
+fn info_dialog_uses_error_styling_for_error_kind() -> Result<()> {
+    use super::dialog::InfoDialogKind;
+    use ratatui::{style::Color, widgets::ListState};
+
+    let theme = Theme {
+        highlight: Color::Green,
+        destructive: Color::Red,
+        ..Theme::default()
+    };
+
+    let render = |kind: InfoDialogKind, title: &str| -> Result<Option<Color>> {
+        let snapshot = view::Snapshot::new(
+            vec![String::from("alpha")],
+            None,
+            Focus::Worktrees,
+            ActionPanelState::vertical(),
+            0,
+            None,
+            Some(view::DialogView::Info {
+                message: String::from("Failed to remove `alpha`."),
+                kind,
+            }),
+            true,
+            theme,
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 20))?;
+        let mut state = ListState::default().with_selected(Some(0));
+        terminal.draw(|frame| snapshot.render(frame, &mut state))?;
+
+        let buffer = terminal.backend().buffer();
+        let corner = format!("┌{title}");
+        let border = (0..buffer.area.height).find_map(|y| {
+            let row: Vec<&str> = (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect();
+            let x = (0..row.len()).find(|&x| row[x..].concat().starts_with(&corner))?;
+            Some(buffer.get(x as u16, y).fg)
+        });
+        Ok(border)
+    };
+
+    assert_eq!(render(InfoDialogKind::Error, "Error")?, Some(Color::Red));
+    assert_eq!(render(InfoDialogKind::Info, "Notice")?, Some(Color::Green));
+    assert_eq!(render(InfoDialogKind::Info, "Error")?, None);
+
+    Ok(())
+}
+
+#[test]
+fn detail_lines_use_theme_roles() -> Result<()> {
+    use ratatui::style::Color;
+
+    use super::details::collect_details;
+
+    let dir = tempfile::TempDir::new()?;
+    crate::test_support::init_repo(&dir)?;
+    let theme = Theme {
+        muted: Color::DarkGray,
+        warning: Color::Magenta,
+        success: Color::Blue,
+        ..Theme::default()
+    };
+    let color_of = |text: &str| -> Option<Color> {
+        let details = collect_details("alpha", dir.path(), AheadBehind::default());
+        detail_lines(&details, &theme, AheadBehind::default())
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .find(|span| span.content.trim() == text)
+            .and_then(|span| span.style.fg)
+    };
+
+    assert_eq!(color_of("State:"), Some(Color::DarkGray));
+    assert_eq!(color_of("Clean"), Some(Color::Blue));
+    std::fs::write(dir.path().join("scratch.txt"), "dirty")?;
+    assert_eq!(color_of("1 untracked file"), Some(Color::Magenta));
+
+    Ok(())
+}
+
+#[test]
+fn merge_confirmation_lists_selected_cleanup_steps() -> Result<()> {
+    use ratatui::widgets::ListState;
+
+    use super::dialog::MergeDialog;
+
+    let mut dialog = MergeDialog::new(0);
+    dialog.remove_remote_branch = true;
+    dialog.remove_worktree = true;
+    dialog.confirming = true;
+
+    let snapshot = view::Snapshot::new(
+        vec![String::from("alpha")],
+        None,
+        Focus::Worktrees,
+        ActionPanelState::vertical(),
+        0,
+        None,
+        Some(view::DialogView::Merge {
+            name: String::from("alpha"),
+            dialog: dialog.into(),
+        }),
+        true,
+        Theme::default(),
+    );
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 30))?;
+    let mut state = ListState::default().with_selected(Some(0));
+    terminal.draw(|frame| snapshot.render(frame, &mut state))?;
+
+    let buffer = terminal.backend().buffer();
+    let text: String = (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    for step in [
+        "1. Merge the pull request",
+        "2. Delete the local branch",
+        "3. Delete the remote branch",
+        "4. Remove the worktree",
+    ] {
+        assert!(text.contains(step), "missing `{step}` in:\n{text}");
+    }
+
+    Ok(())
+}
+
+/// Stands in for `git fetch --all` by creating a remote-tracking branch when the fetch runs.
+struct FakeFetchRunner {
+    fetches: usize,
+}
+
+impl CommandRunner for FakeFetchRunner {
+    fn run(
+        &mut self,
+        program: &str,
+        current_dir: &std::path::Path,
+        args: &[String],
+    ) -> Result<CommandOutput> {
+        assert_eq!(program, "git");
+        assert_eq!(args, ["fetch", "--all", "--prune"]);
+        self.fetches += 1;
+        git(
+            current_dir,
+            &["update-ref", "refs/remotes/origin/fresh", "HEAD"],
+        )?;
+        Ok(CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            success: true,
+            status_code: Some(0),
+        })
+    }
+}
+
+#[test]
+fn fetch_runs_before_branch_enumeration() -> Result<()> {
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path();
+    git(path, &["init", "-q", "-b", "main"])?;
+    git(path, &["commit", "-q", "--allow-empty", "-m", "base"])?;
+    let repo = crate::Repo::discover_from(path)?;
+
+    let mut runner = FakeFetchRunner { fetches: 0 };
+    let choices = runtime::load_branch_choices(&repo, &mut runner, false)?;
+    assert_eq!(runner.fetches, 0);
+    assert!(choices.remote.is_empty());
+
+    let choices = runtime::load_branch_choices(&repo, &mut runner, true)?;
+    assert_eq!(runner.fetches, 1);
+    assert_eq!(choices.local, ["main"]);
+    assert_eq!(choices.remote, ["origin/fresh"]);
+
+    let dialog = dialog::CreateDialog::new(&choices.local, &choices.remote, &[], None);
+    let titles: Vec<&str> = dialog
+        .base_groups
+        .iter()
+        .map(|group| group.title.as_str())
+        .collect();
+    assert_eq!(titles, ["Branches", "Remotes"]);
+
+    Ok(())
+}
+
+#[test]
+fn progress_is_printed_before_and_after_post_exit_action() -> Result<()> {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut writer = SharedLog(Rc::clone(&log));
+    let result = runtime::run_with_progress(&mut writer, "`gh pr merge` for `feature`", || {
+        log.borrow_mut().extend_from_slice(b"<gh runs>\n");
+        Err(eyre::eyre!("merge failed"))
+    });
+    assert!(result.is_err());
+    assert_eq!(
+        String::from_utf8(log.borrow().clone())?,
+        "Running `gh pr merge` for `feature`...\n<gh runs>\nFailed `gh pr merge` for `feature`.\n"
+    );
+
+    Ok(())
+}
+
+struct SharedLog(Rc<RefCell<Vec<u8>>>);
+
+impl std::io::Write for SharedLog {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
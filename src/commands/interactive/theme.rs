@@ -0,0 +1,162 @@
+use std::str::FromStr;
+
+use color_eyre::eyre;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::Repo;
+
+/// Colors used by the TUI, keyed by role rather than by widget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Focused items, selections, and primary buttons.
+    pub highlight: Color,
+    /// Destructive actions and errors.
+    pub destructive: Color,
+    /// Section headers in the details pane.
+    pub header: Color,
+    /// Hints, labels, and secondary text.
+    pub muted: Color,
+    /// States that need attention, such as uncommitted changes or a detached HEAD.
+    pub warning: Color,
+    /// Clean states and confirming actions.
+    pub success: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            highlight: Color::Cyan,
+            destructive: Color::Red,
+            header: Color::Cyan,
+            muted: Color::Gray,
+            warning: Color::Yellow,
+            success: Color::Green,
+        }
+    }
+}
+
+/// The `tui` section of the preferences file.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct TuiPreferences {
+    #[serde(default)]
+    theme: Option<ThemePreferences>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ThemePreferences {
+    highlight: Option<String>,
+    destructive: Option<String>,
+    header: Option<String>,
+    muted: Option<String>,
+    warning: Option<String>,
+    success: Option<String>,
+}
+
+impl Theme {
+    /// Loads `tui.theme` from the preferences file, keeping defaults for unset roles.
+    pub fn load(repo: &Repo) -> color_eyre::Result<Self> {
+        match load_theme_preferences(repo)? {
+            Some(theme) => Self::default().with_overrides(&theme),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Each role with its resolved color and whether the preferences file set it.
+    pub(crate) fn load_roles(repo: &Repo) -> color_eyre::Result<Vec<(&'static str, Color, bool)>> {
+        let preferences = load_theme_preferences(repo)?.unwrap_or_default();
+        let theme = Self::default().with_overrides(&preferences)?;
+
+        Ok(vec![
+            (
+                "highlight",
+                theme.highlight,
+                preferences.highlight.is_some(),
+            ),
+            (
+                "destructive",
+                theme.destructive,
+                preferences.destructive.is_some(),
+            ),
+            ("header", theme.header, preferences.header.is_some()),
+            ("muted", theme.muted, preferences.muted.is_some()),
+            ("warning", theme.warning, preferences.warning.is_some()),
+            ("success", theme.success, preferences.success.is_some()),
+        ])
+    }
+
+    fn with_overrides(mut self, theme: &ThemePreferences) -> color_eyre::Result<Self> {
+        for (role, value, slot) in [
+            ("highlight", &theme.highlight, &mut self.highlight),
+            ("destructive", &theme.destructive, &mut self.destructive),
+            ("header", &theme.header, &mut self.header),
+            ("muted", &theme.muted, &mut self.muted),
+            ("warning", &theme.warning, &mut self.warning),
+            ("success", &theme.success, &mut self.success),
+        ] {
+            if let Some(value) = value {
+                *slot = Color::from_str(value.trim())
+                    .map_err(|_| eyre::eyre!("invalid color `{value}` for `tui.theme.{role}`"))?;
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+fn load_theme_preferences(repo: &Repo) -> color_eyre::Result<Option<ThemePreferences>> {
+    Ok(repo.preferences()?.tui.theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::test_support::{init_repo, write_preferences};
+
+    #[test]
+    fn defaults_without_preferences() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = init_repo(&dir).expect("repo");
+
+        assert_eq!(Theme::load(&repo).unwrap(), Theme::default());
+    }
+
+    #[test]
+    fn overrides_only_configured_roles() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = init_repo(&dir).expect("repo");
+        write_preferences(
+            &repo,
+            serde_json::json!({
+                "tui": { "theme": { "highlight": "yellow", "muted": "#808080", "warning": "magenta" } }
+            }),
+        );
+
+        let theme = Theme::load(&repo).unwrap();
+        assert_eq!(theme.highlight, Color::Yellow);
+        assert_eq!(theme.muted, Color::Rgb(0x80, 0x80, 0x80));
+        assert_eq!(theme.destructive, Theme::default().destructive);
+        assert_eq!(theme.header, Theme::default().header);
+        assert_eq!(theme.warning, Color::Magenta);
+        assert_eq!(theme.success, Theme::default().success);
+    }
+
+    #[test]
+    fn rejects_unknown_color_names() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = init_repo(&dir).expect("repo");
+        write_preferences(
+            &repo,
+            serde_json::json!({ "tui": { "theme": { "destructive": "blood" } } }),
+        );
+
+        let err = Theme::load(&repo).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid color `blood` for `tui.theme.destructive`")
+        );
+    }
+}
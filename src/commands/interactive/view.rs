@@ -1,14 +1,14 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
 
 use super::command::ActionPanelState;
 use super::{
-    Action, Focus, StatusMessage,
+    Action, Focus, StatusMessage, Theme,
     dialog::{
         CreateDialogFocus, CreateDialogView, InfoDialogKind, LineType, MergeDialogFocus,
         MergeDialogView, RemoveDialogFocus, RemoveDialogView,
@@ -24,6 +24,16 @@ pub(crate) struct Snapshot {
     status: Option<StatusMessage>,
     dialog: Option<DialogView>,
     has_worktrees: bool,
+    theme: Theme,
+    title: Option<TitleBar>,
+}
+
+/// Repository root and the main checkout's branch, shown above the columns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TitleBar {
+    pub(crate) root: String,
+    /// `None` when the main checkout has a detached or unborn HEAD.
+    pub(crate) branch: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -49,6 +59,7 @@ pub(crate) enum DialogView {
 }
 
 impl Snapshot {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         items: Vec<String>,
         detail: Option<DetailData>,
@@ -58,6 +69,7 @@ impl Snapshot {
         status: Option<StatusMessage>,
         dialog: Option<DialogView>,
         has_worktrees: bool,
+        theme: Theme,
     ) -> Self {
         Self {
             items,
@@ -68,15 +80,27 @@ impl Snapshot {
             status,
             dialog,
             has_worktrees,
+            theme,
+            title: None,
         }
     }
 
+    pub(crate) fn with_title(mut self, title: Option<TitleBar>) -> Self {
+        self.title = title;
+        self
+    }
+
     pub(crate) fn render(&self, frame: &mut Frame, state: &mut ListState) {
         let size = frame.size();
+        let (title_area, body) = split_title(size, self.title.is_some());
+        if let (Some(title), Some(area)) = (&self.title, title_area) {
+            self.render_title(frame, area, title);
+        }
+
         let columns = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
-            .split(size);
+            .split(body);
 
         let global_height = (super::GLOBAL_ACTIONS.len() as u16 + 2).max(3);
 
@@ -155,7 +179,7 @@ impl Snapshot {
 
         let highlight_style = if is_actions_focused {
             Style::default()
-                .fg(Color::Cyan)
+                .fg(self.theme.highlight)
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
         } else {
             Style::default()
@@ -190,7 +214,7 @@ impl Snapshot {
                 frame.render_widget(
                     Paragraph::new("▲")
                         .alignment(Alignment::Right)
-                        .style(Style::default().fg(Color::Gray)),
+                        .style(Style::default().fg(self.theme.muted)),
                     top_area,
                 );
             }
@@ -207,14 +231,14 @@ impl Snapshot {
                 frame.render_widget(
                     Paragraph::new("▼")
                         .alignment(Alignment::Right)
-                        .style(Style::default().fg(Color::Gray)),
+                        .style(Style::default().fg(self.theme.muted)),
                     bottom_area,
                 );
             }
         }
 
         let status_line = if let Some(status) = &self.status {
-            Line::from(Span::styled(status.text.clone(), status.style()))
+            Line::from(Span::styled(status.text.clone(), status.style(&self.theme)))
         } else {
             Line::from("Use Tab to focus actions. Esc exits.")
         };
@@ -231,7 +255,7 @@ impl Snapshot {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(4),
-                Constraint::Length(5),
+                Constraint::Length(7),
                 Constraint::Length(3),
             ])
             .split(popup_area);
@@ -244,21 +268,28 @@ impl Snapshot {
             Block::default()
                 .title("Confirm removal")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
+                .border_style(Style::default().fg(self.theme.destructive)),
         );
         frame.render_widget(header, layout[0]);
 
-        let options = [(dialog.remove_local_branch, "Remove local branch")];
+        let options = [
+            (dialog.remove_local_branch, "Remove local branch"),
+            (dialog.remove_remote_branch, "Remove remote branch"),
+        ];
 
         let mut option_lines = Vec::new();
-        for (idx, (checked, label)) in options.iter().enumerate() {
-            let checkbox = if *checked { "[x]" } else { "[ ]" };
+        let option_style = |idx: usize| {
             let mut style = Style::default();
             if dialog.focus == RemoveDialogFocus::Options && dialog.options_selected == idx {
                 style = style
-                    .fg(Color::Cyan)
+                    .fg(self.theme.highlight)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
             }
+            style
+        };
+        for (idx, (checked, label)) in options.iter().enumerate() {
+            let checkbox = if *checked { "[x]" } else { "[ ]" };
+            let style = option_style(idx);
 
             option_lines.push(Line::from(vec![
                 Span::styled((*checkbox).to_string(), style),
@@ -266,10 +297,16 @@ impl Snapshot {
                 Span::styled((*label).to_string(), style),
             ]));
         }
+        let style = option_style(options.len());
+        option_lines.push(Line::from(vec![
+            Span::styled(format!("<{}>", dialog.uncommitted.label()), style),
+            Span::raw(" "),
+            Span::styled("Uncommitted changes", style),
+        ]));
         option_lines.push(Line::from(""));
         option_lines.push(Line::from(Span::styled(
-            "Space toggles options. Enter confirms.",
-            Style::default().fg(Color::Gray),
+            "Space toggles options and cycles what to do with changes. Enter confirms.",
+            Style::default().fg(self.theme.muted),
         )));
 
         let options_block = Paragraph::new(option_lines).block(
@@ -288,7 +325,11 @@ impl Snapshot {
 
             let mut style = Style::default();
             if dialog.focus == RemoveDialogFocus::Buttons && dialog.buttons_selected == idx {
-                let color = if idx == 1 { Color::Red } else { Color::Cyan };
+                let color = if idx == 1 {
+                    self.theme.destructive
+                } else {
+                    self.theme.highlight
+                };
                 style = style
                     .fg(color)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
@@ -310,12 +351,14 @@ impl Snapshot {
             InfoDialogKind::Info => (
                 "Notice",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(self.theme.highlight)
                     .add_modifier(Modifier::BOLD),
             ),
             InfoDialogKind::Error => (
-                "Attention",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                "Error",
+                Style::default()
+                    .fg(self.theme.destructive)
+                    .add_modifier(Modifier::BOLD),
             ),
         };
 
@@ -346,7 +389,7 @@ impl Snapshot {
         let button = Paragraph::new(Line::from(Span::styled(
             "[ OK ]",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(self.theme.highlight)
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         )))
         .alignment(Alignment::Center);
@@ -354,17 +397,37 @@ impl Snapshot {
 
         let hint = Paragraph::new("Press Enter to continue.")
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::Gray));
+            .style(Style::default().fg(self.theme.muted));
         frame.render_widget(hint, layout[2]);
     }
 
+    fn render_title(&self, frame: &mut Frame, area: Rect, title: &TitleBar) {
+        let branch = title.branch.as_deref().unwrap_or("(detached)");
+        let line = Line::from(vec![
+            Span::styled(
+                format!(" {}", title.root),
+                Style::default()
+                    .fg(self.theme.header)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  on ", Style::default().fg(self.theme.muted)),
+            Span::styled(
+                branch.to_owned(),
+                Style::default()
+                    .fg(self.theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
     fn render_global_actions(&self, frame: &mut Frame, area: Rect) {
         let mut lines = Vec::new();
         for (idx, label) in super::GLOBAL_ACTIONS.iter().enumerate() {
             let mut style = Style::default();
             if self.focus == Focus::GlobalActions && self.global_action_selected == idx {
                 style = style
-                    .fg(Color::Cyan)
+                    .fg(self.theme.highlight)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
             }
 
@@ -398,13 +461,13 @@ impl Snapshot {
         if dialog.focus == CreateDialogFocus::Name {
             name_block = name_block.border_style(
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(self.theme.highlight)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
             );
         }
 
         let name_value = if dialog.name_input.is_empty() {
-            Span::styled("<enter name>", Style::default().fg(Color::DarkGray))
+            Span::styled("<enter name>", Style::default().fg(self.theme.muted))
         } else {
             Span::raw(dialog.name_input.clone())
         };
@@ -431,7 +494,7 @@ impl Snapshot {
         if will_show_top_indicator {
             base_lines.push(Line::from(Span::styled(
                 "  ▲ more above",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(self.theme.muted),
             )));
         }
 
@@ -452,11 +515,11 @@ impl Snapshot {
                         .base_indices()
                         .iter()
                         .position(|&(g, o)| g == *group_idx && o == *option_idx)
-                        .map_or(false, |idx| idx == dialog.base_selected);
+                        == Some(dialog.base_selected);
 
                     let mut style = Style::default();
                     if is_selected {
-                        style = style.fg(Color::Cyan).add_modifier(Modifier::BOLD);
+                        style = style.fg(self.theme.highlight).add_modifier(Modifier::BOLD);
                     }
 
                     base_lines.push(Line::from(vec![Span::styled(option.label.clone(), style)]));
@@ -471,7 +534,7 @@ impl Snapshot {
         if will_show_bottom_indicator {
             base_lines.push(Line::from(Span::styled(
                 "  ▼ more below",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(self.theme.muted),
             )));
         }
 
@@ -481,7 +544,7 @@ impl Snapshot {
         if dialog.focus == CreateDialogFocus::Base {
             base_block = base_block.border_style(
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(self.theme.highlight)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
             );
         }
@@ -491,13 +554,15 @@ impl Snapshot {
         if let Some(error) = &dialog.error {
             footer_lines.push(Line::from(Span::styled(
                 error.clone(),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(self.theme.destructive)
+                    .add_modifier(Modifier::BOLD),
             )));
             footer_lines.push(Line::from(""));
         }
 
         let mut button_spans = Vec::new();
-        for (idx, label) in ["Create", "Cancel"].iter().enumerate() {
+        for (idx, label) in ["Create", "Create & Continue", "Cancel"].iter().enumerate() {
             if idx > 0 {
                 button_spans.push(Span::raw("  "));
             }
@@ -505,7 +570,7 @@ impl Snapshot {
             let mut style = Style::default();
             if dialog.focus == CreateDialogFocus::Buttons && dialog.buttons_selected == idx {
                 style = style
-                    .fg(Color::Cyan)
+                    .fg(self.theme.highlight)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
             }
 
@@ -526,11 +591,18 @@ impl Snapshot {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(4),
-                Constraint::Length(6),
+                // Four options, a blank line and a hint; the confirmation lists up to four steps
+                // plus a hint.
+                Constraint::Length(if dialog.confirming { 7 } else { 8 }),
                 Constraint::Length(3),
             ])
             .split(popup_area);
 
+        if dialog.confirming {
+            self.render_merge_confirmation(frame, &layout, name, dialog);
+            return;
+        }
+
         let header_lines = vec![
             Line::from(format!("Merge PR for `{name}`")),
             Line::from("Choose the cleanup steps to run after merging."),
@@ -549,14 +621,18 @@ impl Snapshot {
         ];
 
         let mut option_lines = Vec::new();
-        for (idx, (checked, label)) in options.iter().enumerate() {
-            let checkbox = if *checked { "[x]" } else { "[ ]" };
+        let option_style = |idx: usize| {
             let mut style = Style::default();
             if dialog.focus == MergeDialogFocus::Options && dialog.options_selected == idx {
                 style = style
-                    .fg(Color::Cyan)
+                    .fg(self.theme.highlight)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
             }
+            style
+        };
+        for (idx, (checked, label)) in options.iter().enumerate() {
+            let checkbox = if *checked { "[x]" } else { "[ ]" };
+            let style = option_style(idx);
 
             option_lines.push(Line::from(vec![
                 Span::styled((*checkbox).to_string(), style),
@@ -564,10 +640,16 @@ impl Snapshot {
                 Span::styled((*label).to_string(), style),
             ]));
         }
+        let style = option_style(options.len());
+        option_lines.push(Line::from(vec![
+            Span::styled(format!("<{}>", dialog.strategy.label()), style),
+            Span::raw(" "),
+            Span::styled("Merge strategy", style),
+        ]));
         option_lines.push(Line::from(""));
         option_lines.push(Line::from(Span::styled(
-            "Space toggles options. Enter confirms.",
-            Style::default().fg(Color::Gray),
+            "Space toggles options and cycles the strategy. Enter confirms.",
+            Style::default().fg(self.theme.muted),
         )));
 
         let options_block = Paragraph::new(option_lines).block(
@@ -586,7 +668,76 @@ impl Snapshot {
 
             let mut style = Style::default();
             if dialog.focus == MergeDialogFocus::Buttons && dialog.buttons_selected == idx {
-                let color = if idx == 1 { Color::Green } else { Color::Cyan };
+                let color = if idx == 1 {
+                    self.theme.success
+                } else {
+                    self.theme.highlight
+                };
+                style = style
+                    .fg(color)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            }
+
+            button_spans.push(Span::styled(format!("[ {label} ]"), style));
+        }
+
+        let buttons_block = Paragraph::new(Line::from(button_spans))
+            .block(Block::default().title("Actions").borders(Borders::ALL));
+        frame.render_widget(buttons_block, layout[2]);
+    }
+
+    fn render_merge_confirmation(
+        &self,
+        frame: &mut Frame,
+        layout: &[Rect],
+        name: &str,
+        dialog: &MergeDialogView,
+    ) {
+        let header_lines = vec![
+            Line::from(format!("Merge PR for `{name}`")),
+            Line::from(Span::styled(
+                "This removes the worktree. Continue?",
+                Style::default()
+                    .fg(self.theme.destructive)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ];
+        let header = Paragraph::new(header_lines).block(
+            Block::default()
+                .title("Confirm merge")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.destructive)),
+        );
+        frame.render_widget(header, layout[0]);
+
+        let mut step_lines: Vec<Line> = dialog
+            .cleanup_steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| Line::from(format!("{}. {step}", idx + 1)))
+            .collect();
+        step_lines.push(Line::from(Span::styled(
+            "y confirms, n merges but keeps the worktree, Esc goes back.",
+            Style::default().fg(self.theme.muted),
+        )));
+        let steps_block =
+            Paragraph::new(step_lines).block(Block::default().title("Steps").borders(Borders::ALL));
+        frame.render_widget(steps_block, layout[1]);
+
+        let buttons = ["Keep worktree", "Remove worktree"];
+        let mut button_spans = Vec::new();
+        for (idx, label) in buttons.iter().enumerate() {
+            if idx > 0 {
+                button_spans.push(Span::raw("   "));
+            }
+
+            let mut style = Style::default();
+            if dialog.buttons_selected == idx {
+                let color = if idx == 1 {
+                    self.theme.destructive
+                } else {
+                    self.theme.highlight
+                };
                 style = style
                     .fg(color)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
@@ -603,7 +754,7 @@ impl Snapshot {
     fn list_highlight_style(&self) -> Style {
         match self.focus {
             Focus::Worktrees => Style::default()
-                .fg(Color::Cyan)
+                .fg(self.theme.highlight)
                 .add_modifier(Modifier::BOLD),
             Focus::Actions | Focus::GlobalActions => Style::default().add_modifier(Modifier::DIM),
         }
@@ -631,3 +782,16 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
 
     vertical[1]
 }
+
+/// Splits off the one-line title bar at the top of `area` when `has_title` is set.
+pub(crate) fn split_title(area: Rect, has_title: bool) -> (Option<Rect>, Rect) {
+    if !has_title || area.height == 0 {
+        return (None, area);
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+    (Some(rows[0]), rows[1])
+}
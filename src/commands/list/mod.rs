@@ -1,21 +1,172 @@
 use std::{
-    collections::VecDeque,
-    fs,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt, fs,
     path::{Path, PathBuf},
 };
 
 use color_eyre::eyre::{self, WrapErr};
+use git2::{BranchType, Oid};
 use owo_colors::{OwoColorize, Stream};
+use serde::Serialize;
 
-use crate::Repo;
+use crate::{
+    Repo,
+    commands::{
+        interactive::{WorkingTree, summarize_worktree},
+        pr_github::{
+            CommandRunner, OPEN_PULL_REQUEST_LIMIT, PullRequestSummary, SystemCommandRunner,
+            list_open_pull_requests,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub struct ListCommand {
+    recurse: bool,
+    candidates: bool,
+    branch: Option<String>,
+    absolute: bool,
+    since_base: bool,
+    with_pr: bool,
+    json: bool,
+    json_object: bool,
+    status: bool,
+}
+
+impl Default for ListCommand {
+    fn default() -> Self {
+        Self {
+            recurse: true,
+            candidates: false,
+            branch: None,
+            absolute: false,
+            since_base: false,
+            with_pr: false,
+            json: false,
+            json_object: false,
+            status: false,
+        }
+    }
+}
 
-#[derive(Debug, Default)]
-pub struct ListCommand;
+/// A branch that has no managed worktree yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CandidateBranch {
+    /// Branch name with any remote prefix stripped.
+    pub(crate) name: String,
+    /// Remote the branch lives on when there is no local branch of the same name.
+    pub(crate) remote: Option<String>,
+}
 
 impl ListCommand {
+    /// When disabled, only worktrees directly under `.rsworktree` are listed.
+    pub fn with_recurse(mut self, recurse: bool) -> Self {
+        self.recurse = recurse;
+        self
+    }
+
+    /// List branches without a managed worktree instead of the worktrees themselves.
+    pub fn with_candidates(mut self, candidates: bool) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Only list worktrees whose HEAD is on `branch`.
+    pub fn with_branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Print each worktree's canonical absolute path, one per line, without a header.
+    pub fn with_absolute(mut self, absolute: bool) -> Self {
+        self.absolute = absolute;
+        self
+    }
+
+    /// Append each worktree's diff stat against its merge-base with the default branch.
+    pub fn with_since_base(mut self, since_base: bool) -> Self {
+        self.since_base = since_base;
+        self
+    }
+
+    /// Annotate each worktree whose branch has an open GitHub pull request, e.g. `#42 open`.
+    pub fn with_pr(mut self, with_pr: bool) -> Self {
+        self.with_pr = with_pr;
+        self
+    }
+
+    /// Print the worktrees as a JSON array instead of text.
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// With `json`, wrap the worktrees in an object that also carries the repository root and
+    /// default branch.
+    pub fn with_json_object(mut self, json_object: bool) -> Self {
+        self.json_object = json_object;
+        self
+    }
+
+    /// Append each worktree's branch and working tree state, e.g. `[main ✓]` or `[feat *3]`.
+    pub fn with_status(mut self, status: bool) -> Self {
+        self.status = status;
+        self
+    }
+
     pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
+        if self.candidates {
+            return list_candidates(repo);
+        }
+
         let worktrees_dir = repo.ensure_worktrees_dir()?;
-        let worktrees = find_worktrees(&worktrees_dir)?;
+        let mut worktrees = if self.recurse {
+            find_worktrees(&worktrees_dir)?
+        } else {
+            find_top_level_worktrees(&worktrees_dir)?
+        };
+
+        if let Some(branch) = &self.branch {
+            worktrees.retain(|path| {
+                worktree_branch(&worktrees_dir.join(path)).as_deref() == Some(branch.as_str())
+            });
+        }
+
+        if self.absolute {
+            for worktree in worktrees {
+                let path = worktrees_dir.join(&worktree);
+                let canonical = path
+                    .canonicalize()
+                    .wrap_err_with(|| eyre::eyre!("failed to resolve `{}`", path.display()))?;
+                println!("{}", canonical.display());
+            }
+            return Ok(());
+        }
+
+        if self.json {
+            let base = if self.since_base {
+                default_branch_tip(repo.git())
+            } else {
+                None
+            };
+            let entries = worktrees
+                .iter()
+                .map(|worktree| WorktreeEntry::new(repo, &worktrees_dir, worktree, base))
+                .collect::<Vec<_>>();
+            let text = if self.json_object {
+                let listing = WorktreeListing {
+                    root: repo.root().display().to_string(),
+                    default_branch: default_branch_name(repo.git()),
+                    worktrees: entries,
+                };
+                serde_json::to_string_pretty(&listing)
+            } else {
+                serde_json::to_string_pretty(&entries)
+            }
+            .wrap_err("failed to serialize worktrees")?;
+            println!("{text}");
+            return Ok(());
+        }
 
         let header_path_raw = format!("{}", worktrees_dir.display());
         let header_path = format!(
@@ -24,7 +175,10 @@ impl ListCommand {
                 .as_str()
                 .if_supports_color(Stream::Stdout, |text| { format!("{}", text.blue().bold()) })
         );
-        let header_raw = format!("Worktrees under `{}`:", header_path);
+        let header_raw = match &self.branch {
+            Some(branch) => format!("Worktrees under `{}` on branch `{}`:", header_path, branch),
+            None => format!("Worktrees under `{}`:", header_path),
+        };
         let header = format!(
             "{}",
             header_raw
@@ -40,6 +194,29 @@ impl ListCommand {
             );
             println!("{}", message);
         } else {
+            let base = if self.since_base {
+                default_branch_tip(repo.git())
+            } else {
+                None
+            };
+            let pull_requests = if self.with_pr {
+                let pull_requests =
+                    fetch_open_pull_requests(&mut SystemCommandRunner::default(), repo.root());
+                match &pull_requests {
+                    None => eprintln!(
+                        "Note: could not list pull requests with `gh`; PR info is omitted."
+                    ),
+                    Some(pull_requests) if pull_requests.len() >= OPEN_PULL_REQUEST_LIMIT => {
+                        eprintln!(
+                            "Warning: only the first {OPEN_PULL_REQUEST_LIMIT} open pull requests were fetched; PR info may be missing for some branches."
+                        )
+                    }
+                    Some(_) => {}
+                }
+                pull_requests
+            } else {
+                None
+            };
             for worktree in worktrees {
                 let entry_raw = format_worktree(&worktree);
                 let entry = format!(
@@ -48,7 +225,45 @@ impl ListCommand {
                         .as_str()
                         .if_supports_color(Stream::Stdout, |text| { format!("{}", text.green()) })
                 );
-                println!("- {}", entry);
+                let path = worktrees_dir.join(&worktree);
+                let entry = if self.status {
+                    let marker = status_marker(&path);
+                    let marker = format!(
+                        "{}",
+                        marker
+                            .as_str()
+                            .if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow()))
+                    );
+                    format!("{entry} {marker}")
+                } else {
+                    entry
+                };
+                let mut annotations = Vec::new();
+                if let Some(stat) =
+                    base.and_then(|base| diff_since_base(repo.git(), base, &path).ok())
+                {
+                    annotations.push(stat.to_string());
+                }
+                if let Some(pull_request) = pull_requests.as_ref().and_then(|pull_requests| {
+                    worktree_branch(&path).and_then(|branch| pull_requests.get(&branch))
+                }) {
+                    annotations.push(pull_request.to_string());
+                }
+
+                if annotations.is_empty() {
+                    println!("- {}", entry);
+                } else {
+                    let annotation = annotations.join(", ");
+                    let annotation = format!(
+                        "{}",
+                        annotation
+                            .as_str()
+                            .if_supports_color(Stream::Stdout, |text| {
+                                format!("{}", text.dimmed())
+                            })
+                    );
+                    println!("- {} {}", entry, annotation);
+                }
             }
         }
 
@@ -56,7 +271,302 @@ impl ListCommand {
     }
 }
 
+/// Size of a branch's changes since it left the default branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) struct BaseDiff {
+    #[serde(rename = "files_changed")]
+    pub(crate) files: usize,
+    pub(crate) insertions: usize,
+    pub(crate) deletions: usize,
+}
+
+impl fmt::Display for BaseDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if self.files == 1 { "file" } else { "files" };
+        write!(
+            f,
+            "+{}/-{} in {} {}",
+            self.insertions, self.deletions, self.files, noun
+        )
+    }
+}
+
+/// `[branch ✓]` for a clean worktree, `[branch *N]` for `N` changed or untracked files and
+/// `[branch !N]` for `N` conflicts. Detached worktrees show `(detached)` instead of a branch.
+fn status_marker(path: &Path) -> String {
+    let branch = worktree_branch(path).unwrap_or_else(|| "(detached)".to_owned());
+    let working_tree = git2::Repository::open(path)
+        .ok()
+        .and_then(|repo| summarize_worktree(&repo));
+    format!("[{branch} {}]", working_tree_marker(working_tree))
+}
+
+fn working_tree_marker(working_tree: Option<WorkingTree>) -> String {
+    match working_tree {
+        None => "?".to_owned(),
+        Some(tree) if tree.conflicts > 0 => format!("!{}", tree.conflicts),
+        Some(tree) if tree.clean => "✓".to_owned(),
+        Some(tree) => format!("*{}", tree.staged + tree.unstaged + tree.untracked),
+    }
+}
+
+/// One worktree in `ls --json` output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct WorktreeEntry {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    /// `None` when HEAD is detached.
+    pub(crate) branch: Option<String>,
+    /// Abbreviated HEAD commit; `None` when it cannot be resolved.
+    pub(crate) head: Option<String>,
+    /// Only with `--since-base`, and only when the diff stat could be computed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) since_base: Option<BaseDiff>,
+}
+
+impl WorktreeEntry {
+    /// With a `base` commit, the entry also carries the diff stat since that base.
+    fn new(repo: &Repo, worktrees_dir: &Path, worktree: &Path, base: Option<Oid>) -> Self {
+        let path = worktrees_dir.join(worktree);
+        let head = git2::Repository::open(&path).ok().and_then(|repo| {
+            let commit = repo.head().ok()?.peel_to_commit().ok()?;
+            let short = commit.as_object().short_id().ok()?;
+            short.as_str().map(str::to_owned)
+        });
+        let since_base = base.and_then(|base| diff_since_base(repo.git(), base, &path).ok());
+        Self {
+            name: format_worktree(worktree),
+            branch: worktree_branch(&path),
+            path: path.canonicalize().unwrap_or(path).display().to_string(),
+            head,
+            since_base,
+        }
+    }
+}
+
+/// `ls --json --json-object` output: the worktrees with the repository context around them.
+#[derive(Debug, Serialize)]
+struct WorktreeListing {
+    root: String,
+    default_branch: Option<String>,
+    worktrees: Vec<WorktreeEntry>,
+}
+
+/// Open pull requests keyed by head branch. `None` when `gh` is missing, fails, or prints
+/// something unexpected.
+pub(crate) fn fetch_open_pull_requests<R: CommandRunner>(
+    runner: &mut R,
+    dir: &Path,
+) -> Option<BTreeMap<String, PullRequestSummary>> {
+    let pull_requests = list_open_pull_requests(runner, dir, None, None).ok()?;
+    Some(
+        pull_requests
+            .into_iter()
+            .map(|pull_request| (pull_request.head_ref_name.clone(), pull_request))
+            .collect(),
+    )
+}
+
+/// Tip of `origin/HEAD` when it is set, otherwise of the main checkout's HEAD.
+pub(crate) fn default_branch_tip(repo: &git2::Repository) -> Option<Oid> {
+    repo.find_reference("refs/remotes/origin/HEAD")
+        .or_else(|_| repo.head())
+        .and_then(|reference| reference.peel_to_commit())
+        .map(|commit| commit.id())
+        .ok()
+}
+
+/// Branch `origin/HEAD` points at when it is set, otherwise the main checkout's branch.
+pub(crate) fn default_branch_name(repo: &git2::Repository) -> Option<String> {
+    let remote_default = repo
+        .find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|reference| {
+            reference
+                .symbolic_target()
+                .and_then(|target| target.strip_prefix("refs/remotes/origin/"))
+                .map(str::to_owned)
+        });
+    remote_default.or_else(|| {
+        let head = repo.head().ok()?;
+        head.is_branch()
+            .then(|| head.shorthand().map(str::to_owned))
+            .flatten()
+    })
+}
+
+/// Diff stat between the merge-base of the worktree HEAD and `base`, and the worktree HEAD.
+pub(crate) fn diff_since_base(
+    repo: &git2::Repository,
+    base: Oid,
+    worktree: &Path,
+) -> color_eyre::Result<BaseDiff> {
+    let head = git2::Repository::open(worktree)
+        .and_then(|worktree_repo| worktree_repo.head()?.peel_to_commit().map(|c| c.id()))
+        .wrap_err_with(|| eyre::eyre!("failed to resolve HEAD of `{}`", worktree.display()))?;
+    let merge_base = repo
+        .merge_base(head, base)
+        .wrap_err("failed to find merge-base with the default branch")?;
+
+    let old_tree = repo.find_commit(merge_base)?.tree()?;
+    let new_tree = repo.find_commit(head)?.tree()?;
+    let stats = repo
+        .diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?
+        .stats()?;
+
+    Ok(BaseDiff {
+        files: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    })
+}
+
+fn list_candidates(repo: &Repo) -> color_eyre::Result<()> {
+    let candidates = find_candidate_branches(repo)?;
+
+    let header = "Branches without a worktree:";
+    println!(
+        "{}",
+        header.if_supports_color(Stream::Stdout, |text| format!("{}", text.bold()))
+    );
+
+    if candidates.is_empty() {
+        let message = format!(
+            "{}",
+            "(none)".if_supports_color(Stream::Stdout, |text| { format!("{}", text.dimmed()) })
+        );
+        println!("{}", message);
+        return Ok(());
+    }
+
+    for candidate in candidates {
+        match &candidate.remote {
+            Some(remote) => {
+                let entry_raw = format!("{remote}/{}", candidate.name);
+                let entry = format!(
+                    "{}",
+                    entry_raw
+                        .as_str()
+                        .if_supports_color(Stream::Stdout, |text| { format!("{}", text.cyan()) })
+                );
+                println!("- {} (remote)", entry);
+            }
+            None => {
+                let entry = format!(
+                    "{}",
+                    candidate
+                        .name
+                        .as_str()
+                        .if_supports_color(Stream::Stdout, |text| { format!("{}", text.green()) })
+                );
+                println!("- {}", entry);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Local and remote-tracking branches that are not checked out in the main repository or any
+/// managed worktree, sorted by name. Remote branches that shadow a local branch are skipped.
+pub(crate) fn find_candidate_branches(repo: &Repo) -> color_eyre::Result<Vec<CandidateBranch>> {
+    let git_repo = repo.git();
+    let mut taken = BTreeSet::new();
+
+    if let Ok(head) = git_repo.head()
+        && head.is_branch()
+        && let Some(name) = head.shorthand()
+    {
+        taken.insert(name.to_owned());
+    }
+
+    let worktrees_dir = repo.worktrees_dir();
+    if worktrees_dir.exists() {
+        for path in find_worktrees(&worktrees_dir)? {
+            let name = format_worktree(&path);
+            taken.insert(worktree_branch(&worktrees_dir.join(&path)).unwrap_or(name));
+        }
+    }
+
+    let mut local = BTreeSet::new();
+    for entry in git_repo
+        .branches(Some(BranchType::Local))
+        .wrap_err("failed to list local branches")?
+    {
+        let (branch, _) = entry.wrap_err("failed to read local branch")?;
+        if let Some(name) = branch.name().ok().flatten() {
+            local.insert(name.to_owned());
+        }
+    }
+
+    let mut candidates: Vec<CandidateBranch> = local
+        .iter()
+        .filter(|name| !taken.contains(*name))
+        .map(|name| CandidateBranch {
+            name: name.clone(),
+            remote: None,
+        })
+        .collect();
+
+    for entry in git_repo
+        .branches(Some(BranchType::Remote))
+        .wrap_err("failed to list remote branches")?
+    {
+        let (branch, _) = entry.wrap_err("failed to read remote branch")?;
+        let reference = branch.get();
+        if reference.symbolic_target().is_some() {
+            continue;
+        }
+        let Some(full_name) = reference.name() else {
+            continue;
+        };
+        let Ok(remote) = git_repo.branch_remote_name(full_name) else {
+            continue;
+        };
+        let Some(remote) = remote.as_str() else {
+            continue;
+        };
+        let Some(name) = branch
+            .name()
+            .ok()
+            .flatten()
+            .and_then(|name| name.strip_prefix(&format!("{remote}/")).map(str::to_owned))
+        else {
+            continue;
+        };
+        if name == "HEAD" || local.contains(&name) || taken.contains(&name) {
+            continue;
+        }
+        candidates.push(CandidateBranch {
+            name,
+            remote: Some(remote.to_owned()),
+        });
+    }
+
+    candidates.sort_by(|a, b| a.name.cmp(&b.name).then(a.remote.cmp(&b.remote)));
+    Ok(candidates)
+}
+
+/// Short name of the branch checked out in the worktree at `path`, if HEAD is on a branch.
+pub(crate) fn worktree_branch(path: &Path) -> Option<String> {
+    let repo = git2::Repository::open(path).ok()?;
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(str::to_owned)
+}
+
 pub(crate) fn find_worktrees(base: &Path) -> color_eyre::Result<Vec<PathBuf>> {
+    walk_worktrees(base, true)
+}
+
+pub(crate) fn find_top_level_worktrees(base: &Path) -> color_eyre::Result<Vec<PathBuf>> {
+    walk_worktrees(base, false)
+}
+
+fn walk_worktrees(base: &Path, recurse: bool) -> color_eyre::Result<Vec<PathBuf>> {
+    let registered = registered_worktree_paths(base);
     let mut results = Vec::new();
     let mut queue = VecDeque::new();
     queue.push_back(base.to_path_buf());
@@ -73,6 +583,9 @@ pub(crate) fn find_worktrees(base: &Path) -> color_eyre::Result<Vec<PathBuf>> {
                 .is_dir()
             {
                 if path.join(".git").exists() {
+                    if !is_managed_worktree(&path, &registered) {
+                        continue;
+                    }
                     let rel = path.strip_prefix(base).wrap_err_with(|| {
                         eyre::eyre!(
                             "failed to compute worktree path relative to `{}`",
@@ -80,7 +593,7 @@ pub(crate) fn find_worktrees(base: &Path) -> color_eyre::Result<Vec<PathBuf>> {
                         )
                     })?;
                     results.push(rel.to_path_buf());
-                } else {
+                } else if recurse {
                     queue.push_back(path);
                 }
             }
@@ -91,6 +604,105 @@ pub(crate) fn find_worktrees(base: &Path) -> color_eyre::Result<Vec<PathBuf>> {
     Ok(results)
 }
 
+/// Canonical paths of the worktrees registered with the repository that owns `base`.
+fn registered_worktree_paths(base: &Path) -> BTreeSet<PathBuf> {
+    let Ok(repo) = git2::Repository::discover(base) else {
+        return BTreeSet::new();
+    };
+    let Ok(names) = repo.worktrees() else {
+        return BTreeSet::new();
+    };
+
+    names
+        .iter()
+        .flatten()
+        .filter_map(|name| repo.find_worktree(name).ok())
+        .filter_map(|worktree| worktree.path().canonicalize().ok())
+        .collect()
+}
+
+/// Whether the directory at `path`, which has a `.git` entry, is a worktree rather than an embedded
+/// repository or submodule. Registered worktrees always count; otherwise a `.git` file pointing at
+/// `<gitdir>/worktrees/<name>` marks a worktree whose registration is broken.
+fn is_managed_worktree(path: &Path, registered: &BTreeSet<PathBuf>) -> bool {
+    if path
+        .canonicalize()
+        .is_ok_and(|canonical| registered.contains(&canonical))
+    {
+        return true;
+    }
+
+    let Ok(contents) = fs::read_to_string(path.join(".git")) else {
+        return false;
+    };
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .map(|gitdir| Path::new(gitdir.trim()))
+        .and_then(Path::parent)
+        .is_some_and(|parent| parent.file_name().is_some_and(|name| name == "worktrees"))
+}
+
+/// Resolves a partial worktree name for when `name` is not itself a worktree path.
+///
+/// A worktree whose last path segments equal `name` wins, e.g. `long-name` for
+/// `feature/long-name`; failing that, any worktree whose name contains `name`. Several matches
+/// in the same tier are an error listing them.
+pub(crate) fn match_worktree_name(
+    worktrees_dir: &Path,
+    name: &str,
+) -> color_eyre::Result<Option<String>> {
+    let matches = worktree_name_matches(worktrees_dir, name)?;
+    match matches.as_slice() {
+        [] => Ok(None),
+        [only] => Ok(Some(only.clone())),
+        _ => Err(ambiguous_worktree_name(name, &matches)),
+    }
+}
+
+/// Every worktree in the best tier of [`match_worktree_name`] for `name`.
+pub(crate) fn worktree_name_matches(
+    worktrees_dir: &Path,
+    name: &str,
+) -> color_eyre::Result<Vec<String>> {
+    if !worktrees_dir.exists() || name.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let names = find_worktrees(worktrees_dir)?
+        .iter()
+        .map(|worktree| format_worktree(worktree))
+        .collect::<Vec<_>>();
+    let matches = names
+        .iter()
+        .filter(|candidate| matches_last_segments(candidate, name))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !matches.is_empty() {
+        return Ok(matches);
+    }
+    Ok(names
+        .into_iter()
+        .filter(|candidate| candidate.contains(name))
+        .collect())
+}
+
+/// Whether the last path segments of worktree `candidate` are exactly `name`, as opposed to the
+/// looser substring match that [`match_worktree_name`] falls back to.
+pub(crate) fn matches_last_segments(candidate: &str, name: &str) -> bool {
+    candidate
+        .strip_suffix(name)
+        .is_some_and(|rest| rest.is_empty() || rest.ends_with('/'))
+}
+
+pub(crate) fn ambiguous_worktree_name(name: &str, matches: &[String]) -> eyre::Report {
+    eyre::eyre!(
+        "worktree identifier `{}` is ambiguous. Matches: {}",
+        name,
+        matches.join(", ")
+    )
+}
+
 pub(crate) fn format_worktree(path: &Path) -> String {
     path.components()
         .map(|component| component.as_os_str().to_string_lossy().into_owned())
@@ -105,7 +717,10 @@ mod tests {
 
     use tempfile::TempDir;
 
-    use crate::Repo;
+    use crate::{
+        Repo,
+        test_support::{MockCommandRunner, init_repo, output},
+    };
 
     fn init_git_repo(dir: &TempDir) -> color_eyre::Result<()> {
         run(dir, ["git", "init"])
@@ -130,17 +745,22 @@ mod tests {
     #[test]
     fn lists_worktrees_recursively_in_alpha_order() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktrees_dir = repo.ensure_worktrees_dir()?;
 
         let worktree_a = worktrees_dir.join("feature/test");
         fs::create_dir_all(&worktree_a)?;
-        fs::write(worktree_a.join(".git"), "gitdir: ../..")?;
+        fs::write(
+            worktree_a.join(".git"),
+            "gitdir: ../../.git/worktrees/fixture",
+        )?;
 
         let worktree_b = worktrees_dir.join("bugfix/squash");
         fs::create_dir_all(&worktree_b)?;
-        fs::write(worktree_b.join(".git"), "gitdir: ../..")?;
+        fs::write(
+            worktree_b.join(".git"),
+            "gitdir: ../../.git/worktrees/fixture",
+        )?;
 
         let found = find_worktrees(&worktrees_dir)?;
         let labels: Vec<String> = found.iter().map(|path| format_worktree(path)).collect();
@@ -151,11 +771,123 @@ mod tests {
     }
 
     #[test]
-    fn find_worktrees_returns_empty_for_empty_dir() -> color_eyre::Result<()> {
+    fn matches_partial_worktree_names() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktrees_dir = repo.ensure_worktrees_dir()?;
+
+        for name in [
+            "feature/some-long-name",
+            "feature/login",
+            "bugfix/login-redirect",
+        ] {
+            let worktree = worktrees_dir.join(name);
+            fs::create_dir_all(&worktree)?;
+            fs::write(
+                worktree.join(".git"),
+                "gitdir: ../../.git/worktrees/fixture",
+            )?;
+        }
+
+        let matched = |name| match_worktree_name(&worktrees_dir, name);
+        assert_eq!(
+            matched("some-long-name")?.as_deref(),
+            Some("feature/some-long-name")
+        );
+        assert_eq!(matched("long")?.as_deref(), Some("feature/some-long-name"));
+        // A whole last segment beats the substring match in `login-redirect`.
+        assert_eq!(matched("login")?.as_deref(), Some("feature/login"));
+        assert_eq!(matched("missing")?, None);
+
+        let err = matched("feature").expect_err("two worktrees contain `feature`");
+        assert_eq!(
+            err.to_string(),
+            "worktree identifier `feature` is ambiguous. Matches: feature/login, feature/some-long-name"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn top_level_walk_skips_nested_worktrees() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktrees_dir = repo.ensure_worktrees_dir()?;
+
+        for name in ["alpha", "feature/test", "feature/deep/nested"] {
+            let worktree = worktrees_dir.join(name);
+            fs::create_dir_all(&worktree)?;
+            fs::write(
+                worktree.join(".git"),
+                "gitdir: ../../.git/worktrees/fixture",
+            )?;
+        }
+
+        let top_level = find_top_level_worktrees(&worktrees_dir)?;
+        let labels: Vec<String> = top_level.iter().map(|path| format_worktree(path)).collect();
+        assert_eq!(labels, vec!["alpha"]);
+
+        let all = find_worktrees(&worktrees_dir)?;
+        assert_eq!(all.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_worktrees_skips_embedded_repositories_and_submodules() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
         init_git_repo(&repo_dir)?;
+        run(
+            &repo_dir,
+            [
+                "git",
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "Initial commit",
+            ],
+        )?;
         let repo = Repo::discover_from(repo_dir.path())?;
         let worktrees_dir = repo.ensure_worktrees_dir()?;
+        run(
+            &repo_dir,
+            [
+                "git",
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "feature",
+                ".rsworktree/feature",
+            ],
+        )?;
+
+        git2::Repository::init(worktrees_dir.join("vendor/embedded"))?;
+        let submodule = worktrees_dir.join("vendor/submodule");
+        fs::create_dir_all(&submodule)?;
+        fs::write(
+            submodule.join(".git"),
+            "gitdir: ../../../.git/modules/submodule\n",
+        )?;
+
+        let found = find_worktrees(&worktrees_dir)?;
+        let labels: Vec<String> = found.iter().map(|path| format_worktree(path)).collect();
+
+        assert_eq!(labels, vec!["feature"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_worktrees_returns_empty_for_empty_dir() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktrees_dir = repo.ensure_worktrees_dir()?;
 
         let found = find_worktrees(&worktrees_dir)?;
         assert!(found.is_empty());
@@ -163,6 +895,184 @@ mod tests {
         Ok(())
     }
 
+    fn commit_all(
+        dir: &TempDir,
+        cwd: &'static str,
+        message: &'static str,
+    ) -> color_eyre::Result<()> {
+        run(dir, ["git", "-C", cwd, "add", "."])?;
+        run(
+            dir,
+            [
+                "git",
+                "-C",
+                cwd,
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                message,
+            ],
+        )
+    }
+
+    #[test]
+    fn diff_since_base_counts_only_changes_after_the_merge_base() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        init_git_repo(&repo_dir)?;
+        fs::write(repo_dir.path().join("README.md"), "one\ntwo\n")?;
+        commit_all(&repo_dir, ".", "Initial commit")?;
+        let repo = Repo::discover_from(repo_dir.path())?;
+        repo.ensure_worktrees_dir()?;
+        run(
+            &repo_dir,
+            [
+                "git",
+                "worktree",
+                "add",
+                "-b",
+                "feature",
+                ".rsworktree/feature",
+            ],
+        )?;
+
+        let worktree = repo_dir.path().join(".rsworktree/feature");
+        fs::write(worktree.join("README.md"), "one\nTWO\n")?;
+        fs::write(worktree.join("notes.txt"), "a\nb\n")?;
+        commit_all(&repo_dir, ".rsworktree/feature", "Feature work")?;
+
+        // Work on the default branch after the fork must not count against the feature branch.
+        fs::write(repo_dir.path().join("CHANGELOG.md"), "x\ny\nz\n")?;
+        commit_all(&repo_dir, ".", "Default branch work")?;
+
+        let base = default_branch_tip(repo.git()).expect("default branch tip");
+        let stat = diff_since_base(repo.git(), base, &worktree)?;
+
+        assert_eq!(
+            stat,
+            BaseDiff {
+                files: 2,
+                insertions: 3,
+                deletions: 1,
+            }
+        );
+        assert_eq!(stat.to_string(), "+3/-1 in 2 files");
+
+        Ok(())
+    }
+
+    #[test]
+    fn candidate_branches_skip_checked_out_and_shadowed_branches() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        init_git_repo(&repo_dir)?;
+        fs::write(repo_dir.path().join("README.md"), "test")?;
+        run(&repo_dir, ["git", "add", "README.md"])?;
+        run(
+            &repo_dir,
+            [
+                "git",
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "Initial commit",
+            ],
+        )?;
+        for name in ["feature/free", "feature/busy"] {
+            let status = Command::new("git")
+                .current_dir(repo_dir.path())
+                .args(["branch", name])
+                .status()?;
+            assert!(status.success());
+        }
+        run(
+            &repo_dir,
+            ["git", "remote", "add", "origin", "../origin.git"],
+        )?;
+        for name in ["feature/free", "feature/remote-only", "feature/busy"] {
+            let status = Command::new("git")
+                .current_dir(repo_dir.path())
+                .args(["update-ref", &format!("refs/remotes/origin/{name}"), "HEAD"])
+                .status()?;
+            assert!(status.success());
+        }
+        run(
+            &repo_dir,
+            [
+                "git",
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/feature/free",
+            ],
+        )?;
+
+        let repo = Repo::discover_from(repo_dir.path())?;
+        crate::commands::create::CreateCommand::new("feature/busy".into(), None)
+            .create_without_enter(&repo, true)?;
+
+        let candidates = find_candidate_branches(&repo)?;
+        assert_eq!(
+            candidates,
+            vec![
+                CandidateBranch {
+                    name: "feature/free".into(),
+                    remote: None,
+                },
+                CandidateBranch {
+                    name: "feature/remote-only".into(),
+                    remote: Some("origin".into()),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn worktree_branch_reads_head_of_renamed_branch() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        init_git_repo(&repo_dir)?;
+        fs::write(repo_dir.path().join("README.md"), "test")?;
+        run(&repo_dir, ["git", "add", "README.md"])?;
+        run(
+            &repo_dir,
+            [
+                "git",
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "Initial commit",
+            ],
+        )?;
+        let repo = Repo::discover_from(repo_dir.path())?;
+        crate::commands::create::CreateCommand::new("feature/dir".into(), None)
+            .create_without_enter(&repo, true)?;
+        let worktree = repo.worktrees_dir().join("feature/dir");
+        let status = Command::new("git")
+            .current_dir(&worktree)
+            .args(["branch", "-m", "feature/renamed"])
+            .status()?;
+        assert!(status.success());
+
+        assert_eq!(
+            worktree_branch(&worktree).as_deref(),
+            Some("feature/renamed")
+        );
+        assert_eq!(
+            worktree_branch(repo_dir.path().join("missing").as_path()),
+            None
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn format_worktree_handles_single_component() {
         let path = PathBuf::from("feature");
@@ -178,29 +1088,87 @@ mod tests {
     #[test]
     fn list_command_execute_shows_worktrees() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktrees_dir = repo.ensure_worktrees_dir()?;
 
         let worktree = worktrees_dir.join("my-feature");
         fs::create_dir_all(&worktree)?;
         fs::write(worktree.join(".git"), "gitdir: ..")?;
 
-        let cmd = ListCommand;
+        let cmd = ListCommand::default();
         // Just verify it doesn't error - output goes to stdout
         cmd.execute(&repo)?;
 
         Ok(())
     }
 
+    #[test]
+    fn open_pull_requests_are_keyed_by_head_branch() {
+        let mut runner = MockCommandRunner::answering(output(
+            r#"[{"number":42,"headRefName":"feature/login","state":"OPEN"},
+                {"number":7,"headRefName":"fix/typo","state":"OPEN"}]"#,
+            true,
+        ));
+
+        let pull_requests =
+            fetch_open_pull_requests(&mut runner, Path::new("/tmp")).expect("pull requests");
+
+        assert_eq!(runner.calls.len(), 1, "PRs should be fetched in one batch");
+        assert_eq!(runner.calls[0].program, "gh");
+        assert!(
+            runner.calls[0]
+                .args
+                .starts_with(&["pr".into(), "list".into()])
+        );
+        assert_eq!(pull_requests["feature/login"].to_string(), "#42 open");
+        assert_eq!(pull_requests["fix/typo"].to_string(), "#7 open");
+        assert!(!pull_requests.contains_key("main"));
+    }
+
+    #[test]
+    fn open_pull_requests_are_omitted_when_gh_fails() {
+        let mut failing = MockCommandRunner::answering(output("", false));
+        assert_eq!(
+            fetch_open_pull_requests(&mut failing, Path::new("/tmp")),
+            None
+        );
+
+        let mut garbled = MockCommandRunner::answering(output("not json", true));
+        assert_eq!(
+            fetch_open_pull_requests(&mut garbled, Path::new("/tmp")),
+            None
+        );
+    }
+
+    #[test]
+    fn working_tree_marker_prefers_conflicts_over_changes() {
+        let tree = |clean, unstaged, untracked, conflicts| WorkingTree {
+            clean,
+            staged: 1,
+            unstaged,
+            untracked,
+            conflicts,
+        };
+
+        assert_eq!(working_tree_marker(None), "?");
+        assert_eq!(
+            working_tree_marker(Some(WorkingTree {
+                clean: true,
+                ..WorkingTree::default()
+            })),
+            "✓"
+        );
+        assert_eq!(working_tree_marker(Some(tree(false, 1, 1, 0))), "*3");
+        assert_eq!(working_tree_marker(Some(tree(false, 1, 0, 2))), "!2");
+    }
+
     #[test]
     fn list_command_execute_handles_empty() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let _worktrees_dir = repo.ensure_worktrees_dir()?;
 
-        let cmd = ListCommand;
+        let cmd = ListCommand::default();
         cmd.execute(&repo)?;
 
         Ok(())
@@ -2,24 +2,66 @@ use std::path::{Path, PathBuf};
 
 use color_eyre::eyre::{self, WrapErr};
 use owo_colors::{OwoColorize, Stream};
-use serde::Deserialize;
 
 use crate::{
     Repo,
-    commands::pr_github::{CommandOutput, CommandRunner, SystemCommandRunner},
+    commands::pr_github::{
+        CommandOutput, CommandRunner, SystemCommandRunner, find_open_pull_request,
+    },
+    error::WorktreeNotFound,
 };
 
+/// How `gh pr merge` lands the PR's commits on the base branch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeStrategy {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            MergeStrategy::Merge => "merge",
+            MergeStrategy::Squash => "squash",
+            MergeStrategy::Rebase => "rebase",
+        }
+    }
+
+    /// The following strategy, wrapping around; used by the TUI selector.
+    pub(crate) fn next(self) -> Self {
+        match self {
+            MergeStrategy::Merge => MergeStrategy::Squash,
+            MergeStrategy::Squash => MergeStrategy::Rebase,
+            MergeStrategy::Rebase => MergeStrategy::Merge,
+        }
+    }
+
+    fn gh_flag(self) -> &'static str {
+        match self {
+            MergeStrategy::Merge => "--merge",
+            MergeStrategy::Squash => "--squash",
+            MergeStrategy::Rebase => "--rebase",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct MergePrGithubCommand<R = SystemCommandRunner> {
     name: String,
     remove_local_branch: bool,
     remove_remote_branch: bool,
+    strategy: MergeStrategy,
+    auto: bool,
+    cleanup_now: bool,
+    quiet: bool,
     runner: R,
 }
 
 impl MergePrGithubCommand {
     pub fn new(name: String) -> Self {
-        Self::with_runner(name, SystemCommandRunner)
+        Self::with_runner(name, SystemCommandRunner::default())
     }
 }
 
@@ -32,6 +74,10 @@ where
             name,
             remove_local_branch: true,
             remove_remote_branch: false,
+            strategy: MergeStrategy::default(),
+            auto: false,
+            cleanup_now: false,
+            quiet: false,
             runner,
         }
     }
@@ -44,6 +90,31 @@ where
         self.remove_remote_branch = true;
     }
 
+    /// Merge with `--squash` or `--rebase` instead of the default `--merge`.
+    pub fn with_strategy(mut self, strategy: MergeStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Enable GitHub auto-merge (`gh pr merge --auto`) instead of merging now. Branch cleanup
+    /// is skipped since the merge has not happened yet, unless `with_cleanup_now` asks for it.
+    pub fn with_auto(mut self, auto: bool) -> Self {
+        self.auto = auto;
+        self
+    }
+
+    /// With `with_auto`, still detach the worktree and delete branches right away.
+    pub fn with_cleanup_now(mut self, cleanup_now: bool) -> Self {
+        self.cleanup_now = cleanup_now;
+        self
+    }
+
+    /// Only print the merge result and warnings, not progress or branch cleanup messages.
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
     pub fn execute(&mut self, repo: &Repo) -> color_eyre::Result<()> {
         let worktree_path = self.ensure_worktree_path(repo)?;
         let branch = self.determine_branch(&worktree_path)?;
@@ -53,10 +124,12 @@ where
         let path_label = format_with_color(&worktree_path.display().to_string(), |text| {
             format!("{}", text.blue())
         });
-        println!(
-            "Looking for open PR for `{}` from `{}`...",
-            branch_label, path_label
-        );
+        if !self.quiet {
+            println!(
+                "Looking for open PR for `{}` from `{}`...",
+                branch_label, path_label
+            );
+        }
 
         match self.find_pull_request(&repo_root, &branch)? {
             Some(pr_number) => {
@@ -73,11 +146,7 @@ where
         let worktrees_dir = repo.ensure_worktrees_dir()?;
         let worktree_path = worktrees_dir.join(&self.name);
         if !worktree_path.exists() {
-            return Err(eyre::eyre!(
-                "worktree `{}` does not exist under `{}`",
-                self.name,
-                worktrees_dir.display()
-            ));
+            return Err(WorktreeNotFound::new(&self.name, &worktrees_dir).into());
         }
         Ok(worktree_path)
     }
@@ -102,6 +171,13 @@ where
             return Err(eyre::eyre!("`git rev-parse` produced empty branch name"));
         }
 
+        if branch == "HEAD" {
+            return Err(eyre::eyre!(
+                "worktree `{}` is in detached HEAD; check out a branch before merging a PR",
+                self.name
+            ));
+        }
+
         Ok(branch.to_owned())
     }
 
@@ -110,37 +186,7 @@ where
         repo_path: &Path,
         branch: &str,
     ) -> color_eyre::Result<Option<u64>> {
-        let args = vec![
-            "pr".to_owned(),
-            "list".to_owned(),
-            "--head".to_owned(),
-            branch.to_owned(),
-            "--state".to_owned(),
-            "open".to_owned(),
-            "--json".to_owned(),
-            "number".to_owned(),
-            "--limit".to_owned(),
-            "1".to_owned(),
-        ];
-
-        let output = self
-            .runner
-            .run("gh", repo_path, &args)
-            .wrap_err("failed to run `gh pr list`")?;
-
-        if !output.success {
-            return Err(command_failure("gh", &args, &output));
-        }
-
-        let stdout = output.stdout.trim();
-        if stdout.is_empty() {
-            return Ok(None);
-        }
-
-        let prs: Vec<PullRequestInfo> =
-            serde_json::from_str(stdout).wrap_err("failed to parse `gh pr list` output as JSON")?;
-
-        Ok(prs.into_iter().next().map(|pr| pr.number))
+        find_open_pull_request(&mut self.runner, repo_path, None, branch)
     }
 
     fn merge_pull_request(
@@ -150,19 +196,20 @@ where
         worktree_path: &Path,
         pr_number: u64,
     ) -> color_eyre::Result<()> {
+        let cleanup = !self.auto || self.cleanup_now;
+        let remove_local_branch = self.remove_local_branch && cleanup;
         let mut detached_for_deletion = false;
-        if self.remove_local_branch {
+        if remove_local_branch {
             self.detach_worktree_head(worktree_path)?;
             detached_for_deletion = true;
         }
 
-        let mut args = vec![
-            "pr".to_owned(),
-            "merge".to_owned(),
-            pr_number.to_string(),
-            "--merge".to_owned(),
-        ];
-        if self.remove_local_branch {
+        let mut args = vec!["pr".to_owned(), "merge".to_owned(), pr_number.to_string()];
+        if self.auto {
+            args.push("--auto".to_owned());
+        }
+        args.push(self.strategy.gh_flag().to_owned());
+        if remove_local_branch {
             args.push("--delete-branch".to_owned());
         }
 
@@ -171,7 +218,7 @@ where
             .run("gh", repo_path, &args)
             .wrap_err("failed to run `gh pr merge`")?;
 
-        let branch_delete_failed = self.remove_local_branch && gh_branch_delete_failure(&output);
+        let branch_delete_failed = remove_local_branch && gh_branch_delete_failure(&output);
 
         if !output.success && !branch_delete_failed {
             if detached_for_deletion {
@@ -185,6 +232,17 @@ where
         });
         let branch_label = format_with_color(branch, |text| format!("{}", text.magenta().bold()));
 
+        if !cleanup {
+            println!(
+                "Enabled auto-merge for PR {} for branch `{}`; it will merge once required checks pass.",
+                pr_label, branch_label
+            );
+            if !self.quiet {
+                println!("Left the worktree and branches in place until the PR merges.");
+            }
+            return Ok(());
+        }
+
         if branch_delete_failed {
             let warning = format!(
                 "PR {} merged but `gh` could not delete branch `{}`. Leaving the branch intact.",
@@ -194,18 +252,26 @@ where
                 "{}",
                 warning.if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow()))
             );
-        } else if self.remove_local_branch {
+        } else if remove_local_branch && !self.quiet {
             println!("Deleted local branch `{}`.", branch_label);
         }
 
-        if !self.remove_local_branch || branch_delete_failed {
+        if !remove_local_branch || branch_delete_failed {
             self.restore_worktree_branch(worktree_path, branch)?;
         }
 
-        if self.remove_remote_branch {
+        // Deleting the head branch before the auto-merge lands would close the PR.
+        if self.remove_remote_branch && !self.auto {
             self.delete_remote_branch(repo_path, branch)?;
         }
-        println!("Merged PR {} for branch `{}`.", pr_label, branch_label);
+        if self.auto {
+            println!(
+                "Enabled auto-merge for PR {} for branch `{}`.",
+                pr_label, branch_label
+            );
+        } else {
+            println!("Merged PR {} for branch `{}`.", pr_label, branch_label);
+        }
         Ok(())
     }
 
@@ -262,13 +328,17 @@ where
 
         if !output.success {
             if remote_branch_already_gone(&output) {
-                println!("Remote branch `{}` was already removed.", branch_label);
+                if !self.quiet {
+                    println!("Remote branch `{}` was already removed.", branch_label);
+                }
                 return Ok(());
             }
             return Err(command_failure("git", &args, &output));
         }
 
-        println!("Removed remote branch `{}`.", branch_label);
+        if !self.quiet {
+            println!("Removed remote branch `{}`.", branch_label);
+        }
         Ok(())
     }
 }
@@ -335,92 +405,19 @@ fn format_with_color(value: &str, paint: impl Fn(&str) -> String) -> String {
         .to_string()
 }
 
-#[derive(Debug, Deserialize)]
-struct PullRequestInfo {
-    number: u64,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{collections::VecDeque, fs};
+    use std::fs;
 
     use tempfile::TempDir;
 
-    use crate::Repo;
-
-    #[derive(Debug, Default)]
-    struct MockCommandRunner {
-        responses: VecDeque<color_eyre::Result<CommandOutput>>,
-        calls: Vec<RecordedCall>,
-    }
-
-    #[derive(Debug, PartialEq, Eq)]
-    struct RecordedCall {
-        program: String,
-        dir: PathBuf,
-        args: Vec<String>,
-    }
-
-    impl CommandRunner for MockCommandRunner {
-        fn run(
-            &mut self,
-            program: &str,
-            current_dir: &Path,
-            args: &[String],
-        ) -> color_eyre::Result<CommandOutput> {
-            self.calls.push(RecordedCall {
-                program: program.to_owned(),
-                dir: current_dir.to_path_buf(),
-                args: args.to_vec(),
-            });
-            self.responses
-                .pop_front()
-                .unwrap_or_else(|| Err(eyre::eyre!("unexpected command invocation")))
-        }
-    }
-
-    fn init_git_repo(dir: &TempDir) -> color_eyre::Result<()> {
-        run(dir, ["git", "init"])?;
-        fs::write(dir.path().join("README.md"), "test")?;
-        run(dir, ["git", "add", "README.md"])?;
-        run(
-            dir,
-            [
-                "git",
-                "-c",
-                "user.name=Test",
-                "-c",
-                "user.email=test@example.com",
-                "commit",
-                "-m",
-                "Initial commit",
-            ],
-        )?;
-        Ok(())
-    }
-
-    fn run(dir: &TempDir, cmd: impl IntoIterator<Item = &'static str>) -> color_eyre::Result<()> {
-        let mut iter = cmd.into_iter();
-        let program = iter.next().expect("command must not be empty");
-        let status = std::process::Command::new(program)
-            .current_dir(dir.path())
-            .args(iter)
-            .status()
-            .wrap_err_with(|| eyre::eyre!("failed to run `{program}`"))?;
-
-        if !status.success() {
-            return Err(eyre::eyre!("`{program}` exited with status {status}"));
-        }
-
-        Ok(())
-    }
+    use crate::test_support::{MockCommandRunner, RecordedCall, init_repo};
 
     #[test]
     fn merges_when_pull_request_found() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let repo_root = repo.root().to_path_buf();
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
@@ -434,7 +431,8 @@ mod tests {
                 status_code: Some(0),
             }),
             Ok(CommandOutput {
-                stdout: "[{\"number\":42}]".into(),
+                stdout: "[{\"number\":42,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]"
+                    .into(),
                 stderr: String::new(),
                 success: true,
                 status_code: Some(0),
@@ -475,7 +473,7 @@ mod tests {
                         "--state".into(),
                         "open".into(),
                         "--json".into(),
-                        "number".into(),
+                        "number,headRefName,state".into(),
                         "--limit".into(),
                         "1".into(),
                     ],
@@ -553,6 +551,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn determine_branch_errors_on_detached_head() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+
+        let mut runner = MockCommandRunner::default();
+        runner.responses.push_back(Ok(CommandOutput {
+            stdout: "HEAD\n".into(),
+            stderr: String::new(),
+            success: true,
+            status_code: Some(0),
+        }));
+
+        let mut command = MergePrGithubCommand::with_runner("feature/test".into(), runner);
+        let error = command.determine_branch(dir.path()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "worktree `feature/test` is in detached HEAD; check out a branch before merging a PR"
+        );
+        assert_eq!(command.runner.calls.len(), 1, "no gh command should run");
+
+        Ok(())
+    }
+
     #[test]
     fn find_pull_request_errors_on_invalid_json() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
@@ -578,8 +599,7 @@ mod tests {
     #[test]
     fn removes_remote_branch_when_requested() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let repo_root = repo.root().to_path_buf();
         let worktree_path = repo.worktrees_dir().join("feature/remove");
         fs::create_dir_all(&worktree_path)?;
@@ -593,7 +613,8 @@ mod tests {
                 status_code: Some(0),
             }),
             Ok(CommandOutput {
-                stdout: "[{\"number\":99}]".into(),
+                stdout: "[{\"number\":99,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]"
+                    .into(),
                 stderr: String::new(),
                 success: true,
                 status_code: Some(0),
@@ -641,7 +662,7 @@ mod tests {
                         "--state".into(),
                         "open".into(),
                         "--json".into(),
-                        "number".into(),
+                        "number,headRefName,state".into(),
                         "--limit".into(),
                         "1".into(),
                     ],
@@ -678,11 +699,125 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn passes_selected_strategy_to_gh_pr_merge() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        for (strategy, flag) in [
+            (MergeStrategy::Squash, "--squash"),
+            (MergeStrategy::Rebase, "--rebase"),
+        ] {
+            let mut runner = MockCommandRunner::default();
+            // Branch lookup, PR lookup, merge, then restoring the kept local branch.
+            for stdout in [
+                "feature/test\n",
+                "[{\"number\":7,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]",
+                "",
+                "",
+            ] {
+                runner.responses.push_back(Ok(CommandOutput {
+                    stdout: stdout.into(),
+                    stderr: String::new(),
+                    success: true,
+                    status_code: Some(0),
+                }));
+            }
+
+            let mut command = MergePrGithubCommand::with_runner("feature/test".into(), runner)
+                .with_strategy(strategy);
+            command.disable_remove_local();
+            command.execute(&repo)?;
+
+            assert_eq!(
+                command.runner.calls[2].args,
+                vec!["pr", "merge", "7", flag],
+                "strategy {strategy:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn auto_merge_passes_auto_and_defers_cleanup() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut runner = MockCommandRunner::default();
+        for stdout in [
+            "feature/test\n",
+            "[{\"number\":7,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]",
+            "",
+        ] {
+            runner.responses.push_back(Ok(CommandOutput {
+                stdout: stdout.into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }));
+        }
+
+        let mut command = MergePrGithubCommand::with_runner("feature/test".into(), runner)
+            .with_strategy(MergeStrategy::Squash)
+            .with_auto(true);
+        command.enable_remove_remote();
+        command.execute(&repo)?;
+
+        let calls = &command.runner.calls;
+        assert_eq!(calls.len(), 3, "no switch or push expected: {calls:?}");
+        assert_eq!(
+            calls[2].args,
+            vec!["pr", "merge", "7", "--auto", "--squash"]
+        );
+        assert!(calls.iter().all(|call| call.args[0] != "switch"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn auto_merge_with_cleanup_now_detaches_and_deletes_branch() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut runner = MockCommandRunner::default();
+        for stdout in [
+            "feature/test\n",
+            "[{\"number\":7,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]",
+            "",
+            "",
+        ] {
+            runner.responses.push_back(Ok(CommandOutput {
+                stdout: stdout.into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }));
+        }
+
+        let mut command = MergePrGithubCommand::with_runner("feature/test".into(), runner)
+            .with_auto(true)
+            .with_cleanup_now(true);
+        command.execute(&repo)?;
+
+        let calls = &command.runner.calls;
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[2].args, vec!["switch", "--detach", "HEAD"]);
+        assert_eq!(
+            calls[3].args,
+            vec!["pr", "merge", "7", "--auto", "--merge", "--delete-branch"]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn keeps_local_branch_when_disabled() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let repo_root = repo.root().to_path_buf();
         let worktree_path = repo.worktrees_dir().join("feature/keep-local");
         fs::create_dir_all(&worktree_path)?;
@@ -696,7 +831,8 @@ mod tests {
                 status_code: Some(0),
             }),
             Ok(CommandOutput {
-                stdout: "[{\"number\":123}]".into(),
+                stdout: "[{\"number\":123,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]"
+                    .into(),
                 stderr: String::new(),
                 success: true,
                 status_code: Some(0),
@@ -738,7 +874,7 @@ mod tests {
                         "--state".into(),
                         "open".into(),
                         "--json".into(),
-                        "number".into(),
+                        "number,headRefName,state".into(),
                         "--limit".into(),
                         "1".into(),
                     ],
@@ -762,8 +898,7 @@ mod tests {
     #[test]
     fn treat_missing_remote_branch_as_success() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_path = repo.worktrees_dir().join("feature/missing");
         fs::create_dir_all(&worktree_path)?;
 
@@ -776,7 +911,8 @@ mod tests {
                 status_code: Some(0),
             }),
             Ok(CommandOutput {
-                stdout: "[{\"number\":7}]".into(),
+                stdout: "[{\"number\":7,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]"
+                    .into(),
                 stderr: String::new(),
                 success: true,
                 status_code: Some(0),
@@ -816,8 +952,7 @@ mod tests {
     #[test]
     fn surface_remote_branch_deletion_failures() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_path = repo.worktrees_dir().join("feature/error");
         fs::create_dir_all(&worktree_path)?;
 
@@ -830,7 +965,8 @@ mod tests {
                 status_code: Some(0),
             }),
             Ok(CommandOutput {
-                stdout: "[{\"number\":13}]".into(),
+                stdout: "[{\"number\":13,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]"
+                    .into(),
                 stderr: String::new(),
                 success: true,
                 status_code: Some(0),
@@ -869,8 +1005,7 @@ mod tests {
     #[test]
     fn treats_branch_delete_failure_as_success() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let repo_root = repo.root().to_path_buf();
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
@@ -884,7 +1019,8 @@ mod tests {
                 status_code: Some(0),
             }),
             Ok(CommandOutput {
-                stdout: "[{\"number\":42}]".into(),
+                stdout: "[{\"number\":42,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]"
+                    .into(),
                 stderr: String::new(),
                 success: true,
                 status_code: Some(0),
@@ -931,7 +1067,7 @@ mod tests {
                         "--state".into(),
                         "open".into(),
                         "--json".into(),
-                        "number".into(),
+                        "number,headRefName,state".into(),
                         "--limit".into(),
                         "1".into(),
                     ],
@@ -966,8 +1102,7 @@ mod tests {
     #[test]
     fn skips_merge_when_no_pull_request_found() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let repo_root = repo.root().to_path_buf();
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
@@ -1010,7 +1145,7 @@ mod tests {
                         "--state".into(),
                         "open".into(),
                         "--json".into(),
-                        "number".into(),
+                        "number,headRefName,state".into(),
                         "--limit".into(),
                         "1".into(),
                     ],
@@ -1024,8 +1159,7 @@ mod tests {
     #[test]
     fn surfaces_command_failures() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
 
@@ -1046,8 +1180,7 @@ mod tests {
     #[test]
     fn surfaces_switch_failures() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let repo_root = repo.root().to_path_buf();
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
@@ -1061,7 +1194,8 @@ mod tests {
                 status_code: Some(0),
             }),
             Ok(CommandOutput {
-                stdout: "[{\"number\":42}]".into(),
+                stdout: "[{\"number\":42,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]"
+                    .into(),
                 stderr: String::new(),
                 success: true,
                 status_code: Some(0),
@@ -1097,7 +1231,7 @@ mod tests {
                         "--state".into(),
                         "open".into(),
                         "--json".into(),
-                        "number".into(),
+                        "number,headRefName,state".into(),
                         "--limit".into(),
                         "1".into(),
                     ],
@@ -1,9 +1,21 @@
+pub(crate) mod batch;
 pub mod cd;
+pub mod config;
 pub mod create;
+pub mod describe;
+pub mod exec;
+pub(crate) mod hooks;
 pub mod interactive;
 pub mod list;
 pub mod merge_pr_github;
 pub mod open_editor;
 
 pub mod pr_github;
+pub mod pr_gitlab;
+pub(crate) mod prompt;
+pub mod prune;
+pub mod rename;
+pub mod rename_branch;
+pub mod repair;
 pub mod rm;
+pub mod version;
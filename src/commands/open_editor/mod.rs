@@ -4,7 +4,10 @@ use color_eyre::eyre::{self, Context};
 
 use crate::{
     Repo,
-    commands::list::{find_worktrees, format_worktree},
+    commands::{
+        create::CreateCommand,
+        list::{find_worktrees, format_worktree},
+    },
     editor::launch_worktree,
     telemetry::{EditorLaunchStatus, log_editor_launch_attempt},
 };
@@ -12,16 +15,43 @@ use crate::{
 pub struct OpenEditorCommand {
     name: Option<String>,
     path: Option<PathBuf>,
+    create_missing: bool,
+    base: Option<String>,
+    editor_args: Vec<String>,
 }
 
 impl OpenEditorCommand {
     pub fn new(name: Option<String>, path: Option<PathBuf>) -> Self {
-        Self { name, path }
+        Self {
+            name,
+            path,
+            create_missing: false,
+            base: None,
+            editor_args: Vec::new(),
+        }
+    }
+
+    pub fn with_create_missing(mut self, create: bool, base: Option<String>) -> Self {
+        self.create_missing = create;
+        self.base = base;
+        self
+    }
+
+    /// Extra arguments passed to the editor after the ones from the preference.
+    pub fn with_editor_args(mut self, editor_args: Vec<String>) -> Self {
+        self.editor_args = editor_args;
+        self
     }
 
     pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
         let resolved = self.resolve_target(repo)?;
-        let outcome = match launch_worktree(repo, &resolved.name, &resolved.path, false) {
+        let outcome = match launch_worktree(
+            repo,
+            &resolved.name,
+            &resolved.path,
+            false,
+            &self.editor_args,
+        ) {
             Ok(outcome) => {
                 log_editor_launch_attempt(
                     &resolved.name,
@@ -72,7 +102,25 @@ impl OpenEditorCommand {
             .name
             .as_ref()
             .ok_or_else(|| eyre::eyre!("worktree name or --path must be provided"))?;
-        resolve_by_name(name, repo)
+
+        if let Some(resolved) = resolve_by_name(name, repo)? {
+            return Ok(resolved);
+        }
+
+        if !self.create_missing {
+            return Err(eyre::eyre!(
+                "worktree `{}` not found. Run `rsworktree ls` to view available worktrees.",
+                name
+            ));
+        }
+
+        CreateCommand::new(name.clone(), self.base.clone()).create_without_enter(repo, false)?;
+        resolve_by_name(name, repo)?.ok_or_else(|| {
+            eyre::eyre!(
+                "worktree `{}` was created but could not be resolved afterwards",
+                name
+            )
+        })
     }
 }
 
@@ -81,7 +129,7 @@ struct ResolvedWorktree {
     path: PathBuf,
 }
 
-fn resolve_by_name(name: &str, repo: &Repo) -> color_eyre::Result<ResolvedWorktree> {
+fn resolve_by_name(name: &str, repo: &Repo) -> color_eyre::Result<Option<ResolvedWorktree>> {
     let worktrees_dir = repo.ensure_worktrees_dir()?;
     let entries = find_worktrees(&worktrees_dir)?;
 
@@ -103,10 +151,7 @@ fn resolve_by_name(name: &str, repo: &Repo) -> color_eyre::Result<ResolvedWorktr
     }
 
     if matches.is_empty() {
-        return Err(eyre::eyre!(
-            "worktree `{}` not found. Run `rsworktree ls` to view available worktrees.",
-            name
-        ));
+        return Ok(None);
     }
 
     if matches.len() > 1 {
@@ -137,10 +182,10 @@ fn resolve_by_name(name: &str, repo: &Repo) -> color_eyre::Result<ResolvedWorktr
         .canonicalize()
         .wrap_err_with(|| eyre::eyre!("failed to resolve `{}`", absolute.display()))?;
 
-    Ok(ResolvedWorktree {
+    Ok(Some(ResolvedWorktree {
         name: display,
         path: canonical,
-    })
+    }))
 }
 
 fn resolve_by_path(path: &Path, repo: &Repo) -> color_eyre::Result<ResolvedWorktree> {
@@ -155,6 +200,13 @@ fn resolve_by_path(path: &Path, repo: &Repo) -> color_eyre::Result<ResolvedWorkt
         .canonicalize()
         .wrap_err_with(|| eyre::eyre!("failed to resolve `{}`", path.display()))?;
 
+    if git2::Repository::open(&canonical).is_err() {
+        return Err(eyre::eyre!(
+            "`{}` is not a git worktree; pass the root directory of a worktree",
+            canonical.display()
+        ));
+    }
+
     let worktrees_dir = repo.ensure_worktrees_dir()?;
     let display = if let Ok(relative) = canonical.strip_prefix(&worktrees_dir) {
         format_worktree(relative)
@@ -1,13 +1,17 @@
 use std::{
-    fmt,
+    env, fmt,
+    io::{self, Read, Write},
     path::{Path, PathBuf},
-    process::Command,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
 };
 
 use color_eyre::eyre::{self, WrapErr};
 use owo_colors::{OwoColorize, Stream};
+use serde::Deserialize;
 
-use crate::Repo;
+use crate::{Repo, error::WorktreeNotFound};
 
 #[derive(Debug)]
 pub struct PrGithubOptions {
@@ -16,9 +20,12 @@ pub struct PrGithubOptions {
     pub draft: bool,
     pub fill: bool,
     pub web: bool,
-    pub remote: String,
+    /// Remotes to push to, in order; the first one becomes the branch's upstream.
+    pub remotes: Vec<String>,
     pub reviewers: Vec<String>,
     pub extra_args: Vec<String>,
+    /// Branch the PR should merge into; defaults to the repository's default branch.
+    pub base: Option<String>,
 }
 
 #[derive(Debug)]
@@ -28,15 +35,23 @@ pub struct PrGithubCommand<R = SystemCommandRunner> {
     draft: bool,
     fill: bool,
     web: bool,
-    remote: String,
+    remotes: Vec<String>,
     reviewers: Vec<String>,
     extra_args: Vec<String>,
+    base: Option<String>,
+    dry_run: bool,
+    no_verify: bool,
+    repo_slug: Option<String>,
+    milestone: Option<String>,
+    projects: Vec<String>,
+    quiet: bool,
+    update: bool,
     runner: R,
 }
 
 impl PrGithubCommand {
     pub fn new(options: PrGithubOptions) -> Self {
-        Self::with_runner(options, SystemCommandRunner)
+        Self::with_runner(options, SystemCommandRunner::default())
     }
 }
 
@@ -51,9 +66,10 @@ where
             draft,
             fill,
             web,
-            remote,
+            remotes,
             reviewers,
             extra_args,
+            base,
         } = options;
         Self {
             name,
@@ -61,13 +77,64 @@ where
             draft,
             fill,
             web,
-            remote,
+            remotes,
             reviewers,
             extra_args,
+            base,
+            dry_run: false,
+            no_verify: false,
+            repo_slug: None,
+            milestone: None,
+            projects: Vec::new(),
+            quiet: false,
+            update: false,
             runner,
         }
     }
 
+    /// Print the `git push` and `gh pr create` command lines instead of running them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Pass `--no-verify` to `git push` to skip pre-push hooks.
+    pub fn with_no_verify(mut self, no_verify: bool) -> Self {
+        self.no_verify = no_verify;
+        self
+    }
+
+    /// Open the PR against `owner/name` (`gh pr create --repo`), e.g. upstream of a fork.
+    pub fn with_repo_slug(mut self, repo_slug: Option<String>) -> Self {
+        self.repo_slug = repo_slug;
+        self
+    }
+
+    /// Add the PR to this milestone (`gh pr create --milestone`).
+    pub fn with_milestone(mut self, milestone: Option<String>) -> Self {
+        self.milestone = milestone;
+        self
+    }
+
+    /// Add the PR to each of these projects (`gh pr create --project`).
+    pub fn with_projects(mut self, projects: Vec<String>) -> Self {
+        self.projects = projects;
+        self
+    }
+
+    /// Only print the created PR, capturing `git push` output instead of streaming it.
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// When the branch already has an open PR, push to it and apply metadata with `gh pr edit`
+    /// instead of failing in `gh pr create`.
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
     pub fn execute(&mut self, repo: &Repo) -> color_eyre::Result<()> {
         let worktree_path = self.ensure_worktree_path(repo)?;
         let branch = self.determine_branch(&worktree_path)?;
@@ -76,93 +143,106 @@ where
         let path_label = format_with_color(&worktree_path.display().to_string(), |text| {
             format!("{}", text.blue())
         });
-        println!(
-            "Preparing GitHub PR for `{}` from `{}`...",
-            branch_label, path_label
-        );
+        if !self.quiet {
+            println!(
+                "Preparing GitHub PR for `{}` from `{}`...",
+                branch_label, path_label
+            );
+        }
+
+        let existing_pr = if self.update && !self.dry_run {
+            find_open_pull_request(
+                &mut self.runner,
+                &worktree_path,
+                self.repo_slug.as_deref(),
+                &branch,
+            )?
+        } else {
+            None
+        };
+        // Flags `gh pr edit` would reject are caught before anything is pushed.
+        let edit_args = match existing_pr {
+            Some(number) => Some((number, self.pr_edit_args(number)?)),
+            None => {
+                self.ensure_pr_metadata_options()?;
+                None
+            }
+        };
 
-        self.ensure_pr_metadata_options()?;
+        if self.dry_run {
+            return self.print_dry_run(&branch);
+        }
 
         if self.push {
             self.push_branch(&worktree_path, &branch)?;
         } else {
-            let message = format!("Skipping push for `{}` (push disabled).", branch_label);
-            println!(
-                "{}",
-                message.if_supports_color(Stream::Stdout, |text| { format!("{}", text.dimmed()) })
-            );
+            self.ensure_branch_on_remote(&worktree_path, &branch)?;
+            if !self.quiet {
+                let message = format!("Skipping push for `{}` (push disabled).", branch_label);
+                println!(
+                    "{}",
+                    message
+                        .if_supports_color(Stream::Stdout, |text| { format!("{}", text.dimmed()) })
+                );
+            }
         }
 
-        self.create_pull_request(&worktree_path, &branch)
+        match edit_args {
+            Some((number, args)) => self.update_pull_request(&worktree_path, &branch, number, args),
+            None => self.create_pull_request(&worktree_path, &branch),
+        }
     }
 
     fn ensure_worktree_path(&self, repo: &Repo) -> color_eyre::Result<PathBuf> {
         let worktrees_dir = repo.ensure_worktrees_dir()?;
         let worktree_path = worktrees_dir.join(&self.name);
         if !worktree_path.exists() {
-            return Err(eyre::eyre!(
-                "worktree `{}` does not exist under `{}`",
-                self.name,
-                worktrees_dir.display()
-            ));
+            return Err(WorktreeNotFound::new(&self.name, &worktrees_dir).into());
         }
         Ok(worktree_path)
     }
 
     fn determine_branch(&mut self, worktree_path: &Path) -> color_eyre::Result<String> {
-        let args = vec![
-            "rev-parse".to_owned(),
-            "--abbrev-ref".to_owned(),
-            "HEAD".to_owned(),
-        ];
-        let output = self
-            .runner
-            .run("git", worktree_path, &args)
-            .wrap_err("failed to determine current branch with `git rev-parse`")?;
-
-        if !output.success {
-            return Err(command_failure("git", &args, &output));
-        }
-
-        let branch = output.stdout.trim();
-        if branch.is_empty() {
-            return Err(eyre::eyre!("`git rev-parse` produced empty branch name"));
-        }
-
-        Ok(branch.to_owned())
+        current_branch(&mut self.runner, worktree_path, &self.name, "a PR")
     }
 
-    fn push_branch(&mut self, worktree_path: &Path, branch: &str) -> color_eyre::Result<()> {
-        let args = vec![
-            "push".to_owned(),
-            "-u".to_owned(),
-            self.remote.clone(),
-            branch.to_owned(),
-        ];
-        let output = self
-            .runner
-            .run("git", worktree_path, &args)
-            .wrap_err("failed to run `git push`")?;
-
-        if !output.success {
-            return Err(command_failure("git", &args, &output));
+    fn print_dry_run(&self, branch: &str) -> color_eyre::Result<()> {
+        let note = "Dry run: no commands will be executed.";
+        println!(
+            "{}",
+            note.if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow()))
+        );
+        if self.push {
+            for (index, remote) in self.remotes.iter().enumerate() {
+                println!(
+                    "{}",
+                    format_command(
+                        "git",
+                        &push_args(remote, branch, index == 0, self.no_verify)
+                    )
+                );
+            }
         }
-
-        let remote_label = format_with_color(&self.remote, |text| format!("{}", text.green()));
-        let branch_label = format_with_color(branch, |text| format!("{}", text.magenta().bold()));
-        println!("Pushed `{}` to remote `{}`.", branch_label, remote_label);
-
+        if self.update {
+            let lookup = pr_list_args(self.repo_slug.as_deref(), Some(branch));
+            println!("{}", format_command("gh", &lookup));
+        }
+        println!("{}", format_command("gh", &self.pr_create_args(branch)));
         Ok(())
     }
 
-    fn create_pull_request(
-        &mut self,
-        worktree_path: &Path,
-        branch: &str,
-    ) -> color_eyre::Result<()> {
+    fn pr_create_args(&self, branch: &str) -> Vec<String> {
         let mut args = vec!["pr".to_owned(), "create".to_owned()];
+        if let Some(slug) = &self.repo_slug {
+            args.push("--repo".to_owned());
+            args.push(slug.clone());
+        }
         args.push("--head".to_owned());
         args.push(branch.to_owned());
+        if let Some(base) = &self.base {
+            args.push("--base".to_owned());
+            args.push(base.clone());
+        }
 
         if self.draft {
             args.push("--draft".to_owned());
@@ -178,8 +258,81 @@ where
             args.push("--reviewer".to_owned());
             args.push(reviewer.clone());
         }
+        if let Some(milestone) = &self.milestone {
+            args.push("--milestone".to_owned());
+            args.push(milestone.clone());
+        }
+        for project in &self.projects {
+            args.push("--project".to_owned());
+            args.push(project.clone());
+        }
 
         args.extend(self.extra_args.clone());
+        args
+    }
+
+    /// `gh pr edit` arguments for the metadata options that also apply to an existing PR, or
+    /// `None` when there is nothing to change. Extra `gh pr create` arguments are translated,
+    /// and ones that only make sense for a new PR are refused.
+    fn pr_edit_args(&self, number: u64) -> color_eyre::Result<Option<Vec<String>>> {
+        let mut args = Vec::new();
+        if let Some(base) = &self.base {
+            args.push("--base".to_owned());
+            args.push(base.clone());
+        }
+        for reviewer in &self.reviewers {
+            args.push("--add-reviewer".to_owned());
+            args.push(reviewer.clone());
+        }
+        if let Some(milestone) = &self.milestone {
+            args.push("--milestone".to_owned());
+            args.push(milestone.clone());
+        }
+        for project in &self.projects {
+            args.push("--add-project".to_owned());
+            args.push(project.clone());
+        }
+        for arg in &self.extra_args {
+            args.push(edit_arg(arg, number)?);
+        }
+        if args.is_empty() {
+            return Ok(None);
+        }
+
+        let mut edit = vec!["pr".to_owned(), "edit".to_owned(), number.to_string()];
+        if let Some(slug) = &self.repo_slug {
+            edit.push("--repo".to_owned());
+            edit.push(slug.clone());
+        }
+        edit.extend(args);
+        Ok(Some(edit))
+    }
+
+    fn push_branch(&mut self, worktree_path: &Path, branch: &str) -> color_eyre::Result<()> {
+        push_to_remotes(
+            &mut self.runner,
+            worktree_path,
+            &self.remotes,
+            branch,
+            self.no_verify,
+            self.quiet,
+        )
+    }
+
+    fn ensure_branch_on_remote(
+        &mut self,
+        worktree_path: &Path,
+        branch: &str,
+    ) -> color_eyre::Result<()> {
+        ensure_branch_on_remotes(&mut self.runner, worktree_path, &self.remotes, branch)
+    }
+
+    fn create_pull_request(
+        &mut self,
+        worktree_path: &Path,
+        branch: &str,
+    ) -> color_eyre::Result<()> {
+        let args = self.pr_create_args(branch);
 
         let output = self
             .runner
@@ -203,6 +356,32 @@ where
         Ok(())
     }
 
+    fn update_pull_request(
+        &mut self,
+        worktree_path: &Path,
+        branch: &str,
+        number: u64,
+        args: Option<Vec<String>>,
+    ) -> color_eyre::Result<()> {
+        if let Some(args) = args {
+            let output = self
+                .runner
+                .run("gh", worktree_path, &args)
+                .wrap_err("failed to run `gh pr edit`")?;
+
+            if !output.success {
+                return Err(command_failure("gh", &args, &output));
+            }
+        }
+
+        let branch_label = format_with_color(branch, |text| format!("{}", text.magenta().bold()));
+        println!(
+            "GitHub pull request #{} for `{}` updated.",
+            number, branch_label
+        );
+        Ok(())
+    }
+
     fn ensure_pr_metadata_options(&mut self) -> color_eyre::Result<()> {
         if self.fill || self.web {
             return Ok(());
@@ -216,15 +395,253 @@ where
             return Ok(());
         }
 
-        let note = "No PR metadata flags provided; defaulting to `--fill`.";
-        let message = note.if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow()));
-        println!("{}", message);
+        if !self.quiet {
+            let note = "No PR metadata flags provided; defaulting to `--fill`.";
+            let message =
+                note.if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow()));
+            println!("{}", message);
+        }
         self.fill = true;
         Ok(())
     }
 }
 
-fn command_failure(program: &str, args: &[String], output: &CommandOutput) -> color_eyre::Report {
+/// An open pull request as reported by `gh pr list`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct PullRequestSummary {
+    pub(crate) number: u64,
+    #[serde(default, rename = "headRefName")]
+    pub(crate) head_ref_name: String,
+    pub(crate) state: String,
+}
+
+impl fmt::Display for PullRequestSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {}", self.number, self.state.to_lowercase())
+    }
+}
+
+/// Most open pull requests fetched when listing them all rather than one branch's.
+pub(crate) const OPEN_PULL_REQUEST_LIMIT: usize = 200;
+
+fn pr_list_args(repo_slug: Option<&str>, head: Option<&str>) -> Vec<String> {
+    let mut args = vec!["pr".to_owned(), "list".to_owned()];
+    if let Some(slug) = repo_slug {
+        args.push("--repo".to_owned());
+        args.push(slug.to_owned());
+    }
+    if let Some(branch) = head {
+        args.push("--head".to_owned());
+        args.push(branch.to_owned());
+    }
+    let limit = if head.is_some() {
+        1
+    } else {
+        OPEN_PULL_REQUEST_LIMIT
+    };
+    args.extend(
+        [
+            "--state",
+            "open",
+            "--json",
+            "number,headRefName,state",
+            "--limit",
+        ]
+        .map(str::to_owned),
+    );
+    args.push(limit.to_string());
+    args
+}
+
+/// Open pull requests, only the one whose head is `head` when given, from a single
+/// `gh pr list`.
+pub(crate) fn list_open_pull_requests<R: CommandRunner>(
+    runner: &mut R,
+    current_dir: &Path,
+    repo_slug: Option<&str>,
+    head: Option<&str>,
+) -> color_eyre::Result<Vec<PullRequestSummary>> {
+    let args = pr_list_args(repo_slug, head);
+    let output = runner
+        .run("gh", current_dir, &args)
+        .wrap_err("failed to run `gh pr list`")?;
+
+    if !output.success {
+        return Err(command_failure("gh", &args, &output));
+    }
+
+    let stdout = output.stdout.trim();
+    if stdout.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_str(stdout).wrap_err("failed to parse `gh pr list` output as JSON")
+}
+
+/// The number of the open PR whose head is `branch`, if there is one.
+pub(crate) fn find_open_pull_request<R: CommandRunner>(
+    runner: &mut R,
+    current_dir: &Path,
+    repo_slug: Option<&str>,
+    branch: &str,
+) -> color_eyre::Result<Option<u64>> {
+    let pull_requests = list_open_pull_requests(runner, current_dir, repo_slug, Some(branch))?;
+    Ok(pull_requests
+        .first()
+        .map(|pull_request| pull_request.number))
+}
+
+/// The branch checked out in `worktree_path`; errors on a detached HEAD, naming `purpose`.
+pub(crate) fn current_branch<R: CommandRunner>(
+    runner: &mut R,
+    worktree_path: &Path,
+    name: &str,
+    purpose: &str,
+) -> color_eyre::Result<String> {
+    let args = vec![
+        "rev-parse".to_owned(),
+        "--abbrev-ref".to_owned(),
+        "HEAD".to_owned(),
+    ];
+    let output = runner
+        .run("git", worktree_path, &args)
+        .wrap_err("failed to determine current branch with `git rev-parse`")?;
+
+    if !output.success {
+        return Err(command_failure("git", &args, &output));
+    }
+
+    let branch = output.stdout.trim();
+    if branch.is_empty() {
+        return Err(eyre::eyre!("`git rev-parse` produced empty branch name"));
+    }
+
+    if branch == "HEAD" {
+        return Err(eyre::eyre!(
+            "worktree `{}` is in detached HEAD; check out a branch before opening {}",
+            name,
+            purpose
+        ));
+    }
+
+    Ok(branch.to_owned())
+}
+
+pub(crate) fn push_args(
+    remote: &str,
+    branch: &str,
+    set_upstream: bool,
+    no_verify: bool,
+) -> Vec<String> {
+    let mut args = vec!["push".to_owned()];
+    if set_upstream {
+        args.push("-u".to_owned());
+    }
+    if no_verify {
+        args.push("--no-verify".to_owned());
+    }
+    args.push(remote.to_owned());
+    args.push(branch.to_owned());
+    args
+}
+
+/// Pushes `branch` to each remote in order, setting the upstream on the first one.
+pub(crate) fn push_to_remotes<R: CommandRunner>(
+    runner: &mut R,
+    worktree_path: &Path,
+    remotes: &[String],
+    branch: &str,
+    no_verify: bool,
+    quiet: bool,
+) -> color_eyre::Result<()> {
+    let branch_label = format_with_color(branch, |text| format!("{}", text.magenta().bold()));
+    let mut failed = Vec::new();
+
+    for (index, remote) in remotes.iter().enumerate() {
+        let args = push_args(remote, branch, index == 0, no_verify);
+        let output = if quiet {
+            runner.run("git", worktree_path, &args)
+        } else {
+            runner.run_streamed("git", worktree_path, &args)
+        }
+        .wrap_err("failed to run `git push`")?;
+
+        let remote_label = format_with_color(remote, |text| format!("{}", text.green()));
+        if !output.success {
+            if remotes.len() == 1 {
+                return Err(command_failure("git", &args, &output));
+            }
+            eprintln!(
+                "Failed to push `{}` to remote `{}`: {}",
+                branch_label,
+                remote_label,
+                output.stderr.trim()
+            );
+            failed.push(remote.as_str());
+            continue;
+        }
+
+        if !quiet {
+            println!("Pushed `{}` to remote `{}`.", branch_label, remote_label);
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(eyre::eyre!(
+            "failed to push `{}` to {} of {} remotes: {}",
+            branch,
+            failed.len(),
+            remotes.len(),
+            failed.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn ensure_branch_on_remotes<R: CommandRunner>(
+    runner: &mut R,
+    worktree_path: &Path,
+    remotes: &[String],
+    branch: &str,
+) -> color_eyre::Result<()> {
+    for remote in remotes {
+        let args = vec![
+            "ls-remote".to_owned(),
+            "--heads".to_owned(),
+            remote.to_owned(),
+            branch.to_owned(),
+        ];
+        let output = runner
+            .run("git", worktree_path, &args)
+            .wrap_err("failed to run `git ls-remote`")?;
+
+        if !output.success {
+            return Err(command_failure("git", &args, &output));
+        }
+
+        let full_ref = format!("refs/heads/{branch}");
+        let on_remote = output
+            .stdout
+            .lines()
+            .any(|line| line.split_whitespace().nth(1) == Some(full_ref.as_str()));
+        if !on_remote {
+            return Err(eyre::eyre!(
+                "branch `{}` is not on remote `{}`; push it first or rerun without `--no-push`",
+                branch,
+                remote
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn command_failure(
+    program: &str,
+    args: &[String],
+    output: &CommandOutput,
+) -> color_eyre::Report {
     let command_line = format_command(program, args);
     let status = match output.status_code {
         Some(code) => format!("exit status {code}"),
@@ -241,13 +658,13 @@ fn command_failure(program: &str, args: &[String], output: &CommandOutput) -> co
     eyre::eyre!(message)
 }
 
-fn format_with_color(value: &str, paint: impl Fn(&str) -> String) -> String {
+pub(crate) fn format_with_color(value: &str, paint: impl Fn(&str) -> String) -> String {
     value
         .if_supports_color(Stream::Stdout, |text| paint(text))
         .to_string()
 }
 
-fn format_command(program: &str, args: &[String]) -> String {
+pub(crate) fn format_command(program: &str, args: &[String]) -> String {
     let mut parts = Vec::with_capacity(1 + args.len());
     parts.push(quote_arg(program));
     for arg in args {
@@ -256,6 +673,58 @@ fn format_command(program: &str, args: &[String]) -> String {
     parts.join(" ")
 }
 
+/// `gh pr create` flags that add to a list, with the `gh pr edit` flag doing the same.
+const EDIT_FLAG_RENAMES: &[(&[&str], &str)] = &[
+    (&["--label", "-l"], "--add-label"),
+    (&["--reviewer", "-r"], "--add-reviewer"),
+    (&["--assignee", "-a"], "--add-assignee"),
+    (&["--project", "-p"], "--add-project"),
+];
+
+/// `gh pr create` flags with no `gh pr edit` counterpart.
+const CREATE_ONLY_FLAGS: &[&str] = &[
+    "--draft",
+    "-d",
+    "--fill",
+    "-f",
+    "--fill-first",
+    "--fill-verbose",
+    "--web",
+    "-w",
+    "--editor",
+    "-e",
+    "--head",
+    "-H",
+    "--template",
+    "-T",
+    "--no-maintainer-edit",
+    "--recover",
+    "--dry-run",
+];
+
+/// Translates one extra `gh pr create` argument for `gh pr edit` of PR `number`.
+fn edit_arg(arg: &str, number: u64) -> color_eyre::Result<String> {
+    let (flag, value) = match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with('-') => (flag, Some(value)),
+        _ => (arg, None),
+    };
+    if CREATE_ONLY_FLAGS.contains(&flag) {
+        return Err(eyre::eyre!(
+            "`{flag}` only applies when creating a PR; drop it to update PR #{number}"
+        ));
+    }
+
+    let renamed = EDIT_FLAG_RENAMES
+        .iter()
+        .find(|(names, _)| names.contains(&flag))
+        .map(|(_, renamed)| *renamed);
+    Ok(match (renamed, value) {
+        (Some(renamed), Some(value)) => format!("{renamed}={value}"),
+        (Some(renamed), None) => renamed.to_owned(),
+        (None, _) => arg.to_owned(),
+    })
+}
+
 fn metadata_flag_allows_noninteractive(arg: &str) -> bool {
     let cleaned = arg.trim();
     if cleaned == "--" {
@@ -281,7 +750,8 @@ fn metadata_flag_allows_noninteractive(arg: &str) -> bool {
         || cleaned.starts_with("--body-file=")
 }
 
-fn quote_arg(value: &str) -> String {
+/// Quotes `value` for a POSIX shell, leaving plain words untouched.
+pub(crate) fn quote_arg(value: &str) -> String {
     if value
         .chars()
         .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | '/' | '='))
@@ -308,10 +778,54 @@ pub trait CommandRunner {
         current_dir: &Path,
         args: &[String],
     ) -> color_eyre::Result<CommandOutput>;
+
+    /// Runs the command with inherited stdio; only the exit status is captured.
+    fn run_streamed(
+        &mut self,
+        program: &str,
+        current_dir: &Path,
+        args: &[String],
+    ) -> color_eyre::Result<CommandOutput> {
+        self.run(program, current_dir, args)
+    }
 }
 
+/// Environment variable holding the default timeout, in seconds, for external commands.
+pub(crate) const COMMAND_TIMEOUT_ENV: &str = "RSWORKTREE_CMD_TIMEOUT";
+
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
 #[derive(Debug, Clone, Default)]
-pub struct SystemCommandRunner;
+pub struct SystemCommandRunner {
+    timeout: Option<Duration>,
+}
+
+impl SystemCommandRunner {
+    /// Kill commands that run longer than `timeout`. Without it, `RSWORKTREE_CMD_TIMEOUT`
+    /// (seconds, `0` disables) is consulted; by default commands may run indefinitely.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub(crate) fn effective_timeout(&self) -> color_eyre::Result<Option<Duration>> {
+        if self.timeout.is_some() {
+            return Ok(self.timeout);
+        }
+
+        match env::var(COMMAND_TIMEOUT_ENV) {
+            Ok(value) if !value.trim().is_empty() => {
+                let seconds: u64 = value.trim().parse().map_err(|_| {
+                    eyre::eyre!(
+                        "invalid `{COMMAND_TIMEOUT_ENV}` value `{value}`; expected a whole number of seconds"
+                    )
+                })?;
+                Ok((seconds > 0).then(|| Duration::from_secs(seconds)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
 
 impl CommandRunner for SystemCommandRunner {
     fn run(
@@ -320,62 +834,265 @@ impl CommandRunner for SystemCommandRunner {
         current_dir: &Path,
         args: &[String],
     ) -> color_eyre::Result<CommandOutput> {
-        let output = Command::new(program)
+        let Some(timeout) = self.effective_timeout()? else {
+            let output = Command::new(program)
+                .current_dir(current_dir)
+                .args(args)
+                .output()
+                .wrap_err_with(|| {
+                    eyre::eyre!("failed to execute `{}`", format_command(program, args))
+                })?;
+
+            return Ok(CommandOutput {
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                success: output.status.success(),
+                status_code: output.status.code(),
+            });
+        };
+
+        let mut child = Command::new(program)
             .current_dir(current_dir)
             .args(args)
-            .output()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
             .wrap_err_with(|| {
                 eyre::eyre!("failed to execute `{}`", format_command(program, args))
             })?;
 
+        // Drain the pipes on threads so a chatty child cannot block on a full pipe while we wait.
+        let stdout = child.stdout.take().map(read_to_end_in_background);
+        let stderr = child.stderr.take().map(read_to_end_in_background);
+
+        let status = wait_with_timeout(&mut child, timeout, program, args)?;
+        let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+            reader
+                .and_then(|handle| handle.join().ok())
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default()
+        };
+
         Ok(CommandOutput {
-            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
-            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
-            success: output.status.success(),
-            status_code: output.status.code(),
+            stdout: collect(stdout),
+            stderr: collect(stderr),
+            success: status.success(),
+            status_code: status.code(),
         })
     }
-}
 
-impl fmt::Display for CommandOutput {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "success: {}, status_code: {:?}, stdout: {:?}, stderr: {:?}",
-            self.success, self.status_code, self.stdout, self.stderr
-        )
+    fn run_streamed(
+        &mut self,
+        program: &str,
+        current_dir: &Path,
+        args: &[String],
+    ) -> color_eyre::Result<CommandOutput> {
+        let mut command = Command::new(program);
+        command.current_dir(current_dir).args(args);
+
+        let status = match self.effective_timeout()? {
+            None => command.status().wrap_err_with(|| {
+                eyre::eyre!("failed to execute `{}`", format_command(program, args))
+            })?,
+            Some(timeout) => {
+                let mut child = command.spawn().wrap_err_with(|| {
+                    eyre::eyre!("failed to execute `{}`", format_command(program, args))
+                })?;
+                wait_with_timeout(&mut child, timeout, program, args)?
+            }
+        };
+
+        Ok(CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            success: status.success(),
+            status_code: status.code(),
+        })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::{collections::VecDeque, fs};
+fn read_to_end_in_background(
+    mut reader: impl Read + Send + 'static,
+) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = reader.read_to_end(&mut buffer);
+        buffer
+    })
+}
 
-    use tempfile::TempDir;
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Duration,
+    program: &str,
+    args: &[String],
+) -> color_eyre::Result<ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait().wrap_err_with(|| {
+            eyre::eyre!("failed to wait for `{}`", format_command(program, args))
+        })? {
+            return Ok(status);
+        }
 
-    #[test]
-    fn metadata_flag_allows_known_noninteractive_values() {
-        for flag in [
-            "--fill",
-            "-f",
-            "--fill-first",
-            "--fill-verbose",
-            "--web",
-            "-w",
-            "--title",
-            "--body",
-            "--body-file",
-            "--title=Ready",
-            "--body=Looks good",
-            "--body-file=notes.md",
-        ] {
-            assert!(
-                metadata_flag_allows_noninteractive(flag),
-                "flag `{flag}` should be accepted"
-            );
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(eyre::eyre!(
+                "`{}` timed out after {}s and was killed",
+                format_command(program, args),
+                timeout.as_secs_f64()
+            ));
         }
-    }
+
+        thread::sleep(TIMEOUT_POLL_INTERVAL);
+    }
+}
+
+/// Wraps another runner and echoes every invocation and its raw output to a writer.
+#[derive(Debug)]
+pub struct VerboseCommandRunner<R, W = io::Stderr> {
+    inner: R,
+    writer: W,
+}
+
+impl<R> VerboseCommandRunner<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_writer(inner, io::stderr())
+    }
+}
+
+impl<R, W> VerboseCommandRunner<R, W> {
+    pub fn with_writer(inner: R, writer: W) -> Self {
+        Self { inner, writer }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn into_parts(self) -> (R, W) {
+        (self.inner, self.writer)
+    }
+}
+
+impl<R, W> VerboseCommandRunner<R, W>
+where
+    W: Write,
+{
+    fn echo(
+        &mut self,
+        program: &str,
+        current_dir: &Path,
+        args: &[String],
+        result: &color_eyre::Result<CommandOutput>,
+        streamed: bool,
+    ) {
+        // Echo failures are ignored; diagnostics must never change command outcomes.
+        let writer = &mut self.writer;
+        let _ = writeln!(
+            writer,
+            "[git] $ {} (in `{}`)",
+            format_command(program, args),
+            current_dir.display()
+        );
+        match result {
+            Ok(output) => {
+                if !streamed {
+                    let _ = write_stream(writer, "stdout", &output.stdout);
+                    let _ = write_stream(writer, "stderr", &output.stderr);
+                }
+                let status = output
+                    .status_code
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| String::from("terminated by signal"));
+                let _ = writeln!(writer, "[git] status: {status}");
+            }
+            Err(err) => {
+                let _ = writeln!(writer, "[git] error: {err}");
+            }
+        }
+    }
+}
+
+fn write_stream(writer: &mut impl Write, label: &str, text: &str) -> io::Result<()> {
+    if text.is_empty() {
+        return writeln!(writer, "[git] {label}: (empty)");
+    }
+    writeln!(writer, "[git] {label}:")?;
+    for line in text.lines() {
+        writeln!(writer, "  {line}")?;
+    }
+    Ok(())
+}
+
+impl<R, W> CommandRunner for VerboseCommandRunner<R, W>
+where
+    R: CommandRunner,
+    W: Write,
+{
+    fn run(
+        &mut self,
+        program: &str,
+        current_dir: &Path,
+        args: &[String],
+    ) -> color_eyre::Result<CommandOutput> {
+        let result = self.inner.run(program, current_dir, args);
+        self.echo(program, current_dir, args, &result, false);
+        result
+    }
+
+    fn run_streamed(
+        &mut self,
+        program: &str,
+        current_dir: &Path,
+        args: &[String],
+    ) -> color_eyre::Result<CommandOutput> {
+        let result = self.inner.run_streamed(program, current_dir, args);
+        self.echo(program, current_dir, args, &result, true);
+        result
+    }
+}
+
+impl fmt::Display for CommandOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "success: {}, status_code: {:?}, stdout: {:?}, stderr: {:?}",
+            self.success, self.status_code, self.stdout, self.stderr
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::test_support::{MockCommandRunner, RecordedCall, init_repo, output};
+
+    #[test]
+    fn metadata_flag_allows_known_noninteractive_values() {
+        for flag in [
+            "--fill",
+            "-f",
+            "--fill-first",
+            "--fill-verbose",
+            "--web",
+            "-w",
+            "--title",
+            "--body",
+            "--body-file",
+            "--title=Ready",
+            "--body=Looks good",
+            "--body-file=notes.md",
+        ] {
+            assert!(
+                metadata_flag_allows_noninteractive(flag),
+                "flag `{flag}` should be accepted"
+            );
+        }
+    }
 
     #[test]
     fn metadata_flag_allows_noninteractive_rejects_positional_separator_and_plain_args() {
@@ -401,78 +1118,130 @@ mod tests {
         assert_eq!(command, "gh pr create --title 'Ready for review'");
     }
 
-    #[derive(Debug, Default)]
-    struct MockCommandRunner {
-        responses: VecDeque<color_eyre::Result<CommandOutput>>,
-        calls: Vec<RecordedCall>,
+    #[test]
+    fn system_runner_streamed_propagates_exit_status() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let mut runner = SystemCommandRunner::default();
+
+        let failed = runner.run_streamed("sh", dir.path(), &["-c".into(), "exit 3".into()])?;
+        assert!(!failed.success);
+        assert_eq!(failed.status_code, Some(3));
+        assert!(failed.stdout.is_empty());
+
+        let succeeded = runner.run_streamed("sh", dir.path(), &["-c".into(), "exit 0".into()])?;
+        assert!(succeeded.success);
+        assert_eq!(succeeded.status_code, Some(0));
+
+        Ok(())
     }
 
-    #[derive(Debug, PartialEq, Eq)]
-    struct RecordedCall {
-        program: String,
-        dir: PathBuf,
-        args: Vec<String>,
+    #[test]
+    fn system_runner_kills_command_after_timeout() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let mut runner =
+            SystemCommandRunner::default().with_timeout(Some(Duration::from_millis(200)));
+        let args = vec!["-c".to_owned(), "sleep 5".to_owned()];
+
+        let started = Instant::now();
+        let err = runner.run("sh", dir.path(), &args).unwrap_err();
+        assert!(
+            err.to_string().contains("timed out after 0.2s"),
+            "unexpected error: {err}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(4));
+
+        let err = runner.run_streamed("sh", dir.path(), &args).unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+
+        Ok(())
     }
 
-    impl CommandRunner for MockCommandRunner {
-        fn run(
-            &mut self,
-            program: &str,
-            current_dir: &Path,
-            args: &[String],
-        ) -> color_eyre::Result<CommandOutput> {
-            self.calls.push(RecordedCall {
-                program: program.to_owned(),
-                dir: current_dir.to_path_buf(),
-                args: args.to_vec(),
-            });
-            self.responses
-                .pop_front()
-                .unwrap_or_else(|| Err(eyre::eyre!("unexpected command invocation")))
-        }
-    }
-
-    fn init_git_repo(dir: &TempDir) -> color_eyre::Result<()> {
-        run(dir, ["git", "init"])?;
-        fs::write(dir.path().join("README.md"), "test")?;
-        run(dir, ["git", "add", "README.md"])?;
-        run(
-            dir,
-            [
-                "git",
-                "-c",
-                "user.name=Test",
-                "-c",
-                "user.email=test@example.com",
-                "commit",
-                "-m",
-                "Initial commit",
-            ],
+    #[test]
+    fn system_runner_with_timeout_captures_output_of_fast_commands() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let mut runner = SystemCommandRunner::default().with_timeout(Some(Duration::from_secs(5)));
+
+        let output = runner.run(
+            "sh",
+            dir.path(),
+            &["-c".into(), "echo out; echo err >&2; exit 2".into()],
         )?;
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.stderr, "err\n");
+        assert!(!output.success);
+        assert_eq!(output.status_code, Some(2));
+
         Ok(())
     }
 
-    fn run(dir: &TempDir, cmd: impl IntoIterator<Item = &'static str>) -> color_eyre::Result<()> {
-        let mut iter = cmd.into_iter();
-        let program = iter.next().expect("command must not be empty");
-        let status = Command::new(program)
-            .current_dir(dir.path())
-            .args(iter)
-            .status()
-            .wrap_err_with(|| eyre::eyre!("failed to run `{program}`"))?;
+    #[test]
+    fn system_runner_streamed_errors_when_program_missing() {
+        let dir = TempDir::new().expect("tempdir");
+        let mut runner = SystemCommandRunner::default();
+        let err = runner
+            .run_streamed("rsworktree-missing-program", dir.path(), &[])
+            .unwrap_err();
+        assert!(err.to_string().contains("failed to execute"));
+    }
 
-        if !status.success() {
-            return Err(eyre::eyre!("`{program}` exited with status {status}"));
-        }
+    #[test]
+    fn verbose_runner_echoes_command_output_and_status() -> color_eyre::Result<()> {
+        let mut inner = MockCommandRunner::default();
+        inner.responses.push_back(Ok(CommandOutput {
+            stdout: "feature/test\n".into(),
+            stderr: String::new(),
+            success: true,
+            status_code: Some(0),
+        }));
+        inner.responses.push_back(Ok(CommandOutput {
+            stdout: String::new(),
+            stderr: "rejected\n".into(),
+            success: false,
+            status_code: Some(1),
+        }));
+
+        let mut runner = VerboseCommandRunner::with_writer(inner, Vec::new());
+        let dir = PathBuf::from("/tmp/worktree");
+        let output = runner.run("git", &dir, &["rev-parse".into(), "HEAD".into()])?;
+        assert_eq!(output.stdout, "feature/test\n");
+        let failed = runner.run("git", &dir, &["push".into(), "my branch".into()])?;
+        assert!(!failed.success);
+
+        let (inner, written) = runner.into_parts();
+        assert_eq!(inner.calls.len(), 2, "inner runner should see every call");
+        let echoed = String::from_utf8(written)?;
+        assert_eq!(
+            echoed,
+            "[git] $ git rev-parse HEAD (in `/tmp/worktree`)\n\
+             [git] stdout:\n  feature/test\n\
+             [git] stderr: (empty)\n\
+             [git] status: 0\n\
+             [git] $ git push 'my branch' (in `/tmp/worktree`)\n\
+             [git] stdout: (empty)\n\
+             [git] stderr:\n  rejected\n\
+             [git] status: 1\n"
+        );
 
         Ok(())
     }
 
+    #[test]
+    fn verbose_runner_reports_errors_from_inner_runner() {
+        let mut runner =
+            VerboseCommandRunner::with_writer(MockCommandRunner::default(), Vec::new());
+        let result = runner.run_streamed("gh", Path::new("/tmp"), &["pr".into()]);
+        assert!(result.is_err());
+
+        let (_, written) = runner.into_parts();
+        let echoed = String::from_utf8(written).expect("utf8");
+        assert!(echoed.starts_with("[git] $ gh pr (in `/tmp`)\n"));
+        assert!(echoed.contains("[git] error: unexpected command invocation"));
+    }
+
     #[test]
     fn executes_push_and_gh() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
 
@@ -552,9 +1321,10 @@ mod tests {
             draft: false,
             fill: true,
             web: false,
-            remote: "origin".into(),
+            remotes: vec!["origin".into()],
             reviewers: vec!["octocat".into()],
             extra_args: vec!["--label".into(), "ready".into()],
+            base: None,
         };
         let mut command = PrGithubCommand::with_runner(options, runner);
 
@@ -594,6 +1364,184 @@ mod tests {
         ];
 
         assert_eq!(command.runner.calls, expected_calls);
+        assert_eq!(command.runner.streamed_calls, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn quiet_captures_push_output_instead_of_streaming() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut runner = MockCommandRunner::default();
+        for stdout in [
+            "feature/test\n",
+            "",
+            "https://github.com/owner/repo/pull/1\n",
+        ] {
+            runner.responses.push_back(Ok(CommandOutput {
+                stdout: stdout.into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }));
+        }
+
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: true,
+            draft: false,
+            fill: false,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+            base: None,
+        };
+        let mut command = PrGithubCommand::with_runner(options, runner).with_quiet(true);
+
+        command.execute(&repo)?;
+
+        assert_eq!(command.runner.calls.len(), 3);
+        assert_eq!(command.runner.calls[1].args[0], "push");
+        assert_eq!(command.runner.streamed_calls, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pushes_to_each_remote_in_order() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut runner = MockCommandRunner::default();
+        for stdout in [
+            "feature/test\n",
+            "",
+            "",
+            "https://github.com/owner/repo/pull/1\n",
+        ] {
+            runner.responses.push_back(Ok(CommandOutput {
+                stdout: stdout.into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }));
+        }
+
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: true,
+            draft: false,
+            fill: true,
+            web: false,
+            remotes: vec!["origin".into(), "mirror".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+            base: None,
+        };
+        let mut command = PrGithubCommand::with_runner(options, runner);
+
+        command.execute(&repo)?;
+
+        let pushes: Vec<Vec<&str>> = command
+            .runner
+            .calls
+            .iter()
+            .filter(|call| call.args.first().map(String::as_str) == Some("push"))
+            .map(|call| call.args.iter().map(String::as_str).collect())
+            .collect();
+        assert_eq!(
+            pushes,
+            vec![
+                vec!["push", "-u", "origin", "feature/test"],
+                vec!["push", "mirror", "feature/test"],
+            ]
+        );
+        assert_eq!(command.runner.calls[3].program, "gh");
+
+        Ok(())
+    }
+
+    #[test]
+    fn reports_remotes_that_failed_to_push() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut runner = MockCommandRunner::default();
+        for (stdout, success) in [("feature/test\n", true), ("", false), ("", true)] {
+            runner.responses.push_back(Ok(CommandOutput {
+                stdout: stdout.into(),
+                stderr: if success { "" } else { "rejected" }.into(),
+                success,
+                status_code: Some(if success { 0 } else { 1 }),
+            }));
+        }
+
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: true,
+            draft: false,
+            fill: true,
+            web: false,
+            remotes: vec!["origin".into(), "mirror".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+            base: None,
+        };
+        let mut command = PrGithubCommand::with_runner(options, runner);
+
+        let error = command.execute(&repo).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to push `feature/test` to 1 of 2 remotes: origin"
+        );
+        assert_eq!(
+            command.runner.calls.len(),
+            3,
+            "the second remote is still pushed, but no PR is created"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn detached_head_errors_before_pushing() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut runner = MockCommandRunner::default();
+        runner.responses.push_back(Ok(CommandOutput {
+            stdout: "HEAD\n".into(),
+            stderr: String::new(),
+            success: true,
+            status_code: Some(0),
+        }));
+
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: true,
+            draft: false,
+            fill: true,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+            base: None,
+        };
+        let mut command = PrGithubCommand::with_runner(options, runner);
+
+        let error = command.execute(&repo).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "worktree `feature/test` is in detached HEAD; check out a branch before opening a PR"
+        );
+        assert_eq!(command.runner.calls.len(), 1, "nothing should be pushed");
 
         Ok(())
     }
@@ -601,8 +1549,7 @@ mod tests {
     #[test]
     fn skips_push_when_disabled() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
 
@@ -614,6 +1561,12 @@ mod tests {
                 success: true,
                 status_code: Some(0),
             }),
+            Ok(CommandOutput {
+                stdout: "0123456789abcdef\trefs/heads/feature/test\n".into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }),
             Ok(CommandOutput {
                 stdout: String::new(),
                 stderr: String::new(),
@@ -640,9 +1593,10 @@ mod tests {
             draft: true,
             fill: true,
             web: true,
-            remote: "origin".into(),
+            remotes: vec!["origin".into()],
             reviewers: Vec::new(),
             extra_args: Vec::new(),
+            base: None,
         };
         let mut command = PrGithubCommand::with_runner(options, runner);
 
@@ -654,6 +1608,16 @@ mod tests {
                 dir: worktree_path.clone(),
                 args: vec!["rev-parse".into(), "--abbrev-ref".into(), "HEAD".into()],
             },
+            RecordedCall {
+                program: "git".into(),
+                dir: worktree_path.clone(),
+                args: vec![
+                    "ls-remote".into(),
+                    "--heads".into(),
+                    "origin".into(),
+                    "feature/test".into(),
+                ],
+            },
             RecordedCall {
                 program: "gh".into(),
                 dir: worktree_path.clone(),
@@ -674,11 +1638,425 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn dry_run_prints_commands_without_running_them() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktree_path = repo.worktrees_dir().join("feature/test");
+        fs::create_dir_all(&worktree_path)?;
+
+        let mut runner = MockCommandRunner::default();
+        runner.responses.push_back(Ok(CommandOutput {
+            stdout: "feature/test\n".into(),
+            stderr: String::new(),
+            success: true,
+            status_code: Some(0),
+        }));
+
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: true,
+            draft: true,
+            fill: false,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: vec!["octocat".into()],
+            extra_args: Vec::new(),
+            base: None,
+        };
+        let mut command = PrGithubCommand::with_runner(options, runner).with_dry_run(true);
+
+        command.execute(&repo)?;
+
+        // Only the read-only branch lookup runs; push and `gh pr create` are printed instead.
+        assert_eq!(
+            command.runner.calls,
+            vec![RecordedCall {
+                program: "git".into(),
+                dir: worktree_path.clone(),
+                args: vec!["rev-parse".into(), "--abbrev-ref".into(), "HEAD".into()],
+            }]
+        );
+        assert_eq!(
+            format_command("git", &push_args("origin", "feature/test", true, false)),
+            "git push -u origin feature/test"
+        );
+        assert_eq!(
+            format_command("gh", &command.pr_create_args("feature/test")),
+            "gh pr create --head feature/test --draft --fill --reviewer octocat"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn pr_create_args_place_milestone_and_projects_after_reviewers() {
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: true,
+            draft: false,
+            fill: true,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: vec!["octocat".into()],
+            extra_args: vec!["--label".into(), "ready".into()],
+            base: None,
+        };
+        let command = PrGithubCommand::with_runner(options, MockCommandRunner::default())
+            .with_milestone(Some("v1.2".into()))
+            .with_projects(vec!["Roadmap".into(), "Q3 Triage".into()]);
+
+        assert_eq!(
+            format_command("gh", &command.pr_create_args("feature/test")),
+            "gh pr create --head feature/test --fill --reviewer octocat --milestone v1.2 \
+             --project Roadmap --project 'Q3 Triage' --label ready"
+        );
+    }
+
+    #[test]
+    fn pr_create_args_target_base_without_counting_as_metadata() -> color_eyre::Result<()> {
+        let options = PrGithubOptions {
+            name: "feature/child".into(),
+            push: true,
+            draft: false,
+            fill: false,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+            base: Some("feature/parent".into()),
+        };
+        let mut command =
+            PrGithubCommand::with_runner(options, MockCommandRunner::default()).with_quiet(true);
+        command.ensure_pr_metadata_options()?;
+
+        assert_eq!(
+            format_command("gh", &command.pr_create_args("feature/child")),
+            "gh pr create --head feature/child --base feature/parent --fill"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn push_passes_no_verify_only_when_requested() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktree_path = repo.worktrees_dir().join("feature/test");
+        fs::create_dir_all(&worktree_path)?;
+
+        let ok = |stdout: &str| {
+            Ok(CommandOutput {
+                stdout: stdout.into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            })
+        };
+
+        for no_verify in [true, false] {
+            let mut runner = MockCommandRunner::default();
+            runner
+                .responses
+                .extend([ok("feature/test\n"), ok(""), ok("")]);
+            let options = PrGithubOptions {
+                name: "feature/test".into(),
+                push: true,
+                draft: false,
+                fill: true,
+                web: false,
+                remotes: vec!["origin".into()],
+                reviewers: Vec::new(),
+                extra_args: Vec::new(),
+                base: None,
+            };
+            let mut command =
+                PrGithubCommand::with_runner(options, runner).with_no_verify(no_verify);
+
+            command.execute(&repo)?;
+
+            let push = &command.runner.calls[1];
+            assert_eq!(push.program, "git");
+            let mut expected = vec!["push".to_owned(), "-u".to_owned()];
+            if no_verify {
+                expected.push("--no-verify".to_owned());
+            }
+            expected.extend(["origin".to_owned(), "feature/test".to_owned()]);
+            assert_eq!(push.args, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn pr_create_forwards_repo_slug() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktree_path = repo.worktrees_dir().join("feature/test");
+        fs::create_dir_all(&worktree_path)?;
+
+        let mut runner = MockCommandRunner::default();
+        runner.responses.extend([
+            Ok(CommandOutput {
+                stdout: "feature/test\n".into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }),
+            Ok(CommandOutput {
+                stdout: "0123456789abcdef\trefs/heads/feature/test\n".into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }),
+            Ok(CommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }),
+        ]);
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: false,
+            draft: false,
+            fill: true,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+            base: None,
+        };
+        let mut command = PrGithubCommand::with_runner(options, runner)
+            .with_repo_slug(Some("upstream-org/project".into()));
+
+        command.execute(&repo)?;
+
+        let create = &command.runner.calls[2];
+        assert_eq!(create.program, "gh");
+        assert_eq!(
+            create.args,
+            vec![
+                "pr",
+                "create",
+                "--repo",
+                "upstream-org/project",
+                "--head",
+                "feature/test",
+                "--fill",
+            ]
+        );
+
+        Ok(())
+    }
+
+    fn update_command(responses: &[&str]) -> PrGithubCommand<MockCommandRunner> {
+        let mut runner = MockCommandRunner::default();
+        for stdout in responses {
+            runner.responses.push_back(Ok(CommandOutput {
+                stdout: (*stdout).into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }));
+        }
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: true,
+            draft: false,
+            fill: false,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: vec!["octocat".into()],
+            extra_args: Vec::new(),
+            base: None,
+        };
+        PrGithubCommand::with_runner(options, runner).with_update(true)
+    }
+
+    #[test]
+    fn update_pushes_and_edits_existing_pr() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut command = update_command(&[
+            "feature/test\n",
+            "[{\"number\":42,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]",
+            "",
+            "",
+        ]);
+        command.execute(&repo)?;
+
+        let calls: Vec<_> = command
+            .runner
+            .calls
+            .iter()
+            .map(|call| format_command(&call.program, &call.args))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                "git rev-parse --abbrev-ref HEAD",
+                "gh pr list --head feature/test --state open --json 'number,headRefName,state' --limit 1",
+                "git push -u origin feature/test",
+                "gh pr edit 42 --add-reviewer octocat",
+            ]
+        );
+        assert!(!command.fill, "`--fill` only applies to new PRs");
+
+        Ok(())
+    }
+
+    #[test]
+    fn listing_all_open_prs_is_capped_and_tolerates_missing_head() -> color_eyre::Result<()> {
+        let mut runner =
+            MockCommandRunner::answering(output(r#"[{"number": 42, "state": "OPEN"}]"#, true));
+
+        let pull_requests = list_open_pull_requests(&mut runner, Path::new("/tmp"), None, None)?;
+
+        assert_eq!(
+            format_command(&runner.calls[0].program, &runner.calls[0].args),
+            "gh pr list --state open --json 'number,headRefName,state' --limit 200"
+        );
+        assert_eq!(pull_requests[0].number, 42);
+        assert_eq!(pull_requests[0].head_ref_name, "");
+        Ok(())
+    }
+
+    #[test]
+    fn update_translates_create_flags_for_edit() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+        let open_pr = "[{\"number\":42,\"headRefName\":\"feature/test\",\"state\":\"OPEN\"}]";
+
+        let mut command = update_command(&["feature/test\n", open_pr, "", ""]);
+        command.extra_args = ["--label", "bug", "-a=octocat", "--title", "New title"]
+            .map(String::from)
+            .to_vec();
+        command.execute(&repo)?;
+        assert_eq!(
+            command.runner.calls[3].args,
+            vec![
+                "pr",
+                "edit",
+                "42",
+                "--add-reviewer",
+                "octocat",
+                "--add-label",
+                "bug",
+                "--add-assignee=octocat",
+                "--title",
+                "New title",
+            ]
+        );
+
+        let mut command = update_command(&["feature/test\n", open_pr]);
+        command.extra_args = vec!["--draft".into()];
+        let err = command
+            .execute(&repo)
+            .expect_err("`--draft` has no edit counterpart");
+        assert_eq!(
+            err.to_string(),
+            "`--draft` only applies when creating a PR; drop it to update PR #42"
+        );
+        assert_eq!(command.runner.calls.len(), 2, "nothing is pushed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_creates_pr_when_none_is_open() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        fs::create_dir_all(repo.worktrees_dir().join("feature/test"))?;
+
+        let mut command = update_command(&[
+            "feature/test\n",
+            "[]",
+            "",
+            "https://github.com/owner/repo/pull/1\n",
+        ]);
+        command.execute(&repo)?;
+
+        let calls = &command.runner.calls;
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[1].args[..2], ["pr", "list"]);
+        assert_eq!(calls[2].args[0], "push");
+        assert_eq!(
+            calls[3].args,
+            vec![
+                "pr",
+                "create",
+                "--head",
+                "feature/test",
+                "--fill",
+                "--reviewer",
+                "octocat"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_push_errors_when_branch_is_not_on_remote() -> color_eyre::Result<()> {
+        let repo_dir = TempDir::new()?;
+        let repo = init_repo(&repo_dir)?;
+        let worktree_path = repo.worktrees_dir().join("feature/test");
+        fs::create_dir_all(&worktree_path)?;
+
+        let mut runner = MockCommandRunner::default();
+        runner.responses.extend([
+            Ok(CommandOutput {
+                stdout: "feature/test\n".into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }),
+            Ok(CommandOutput {
+                stdout: "0123456789abcdef\trefs/heads/feature/test-other\n".into(),
+                stderr: String::new(),
+                success: true,
+                status_code: Some(0),
+            }),
+        ]);
+        let options = PrGithubOptions {
+            name: "feature/test".into(),
+            push: false,
+            draft: false,
+            fill: true,
+            web: false,
+            remotes: vec!["upstream".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+            base: None,
+        };
+        let mut command = PrGithubCommand::with_runner(options, runner);
+
+        let err = command.execute(&repo).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "branch `feature/test` is not on remote `upstream`; push it first or rerun without `--no-push`"
+        );
+        assert_eq!(command.runner.calls.len(), 2);
+        assert_eq!(
+            command.runner.calls[1].args,
+            vec!["ls-remote", "--heads", "upstream", "feature/test"]
+        );
+        assert!(
+            command.runner.calls.iter().all(|call| call.program != "gh"),
+            "`gh pr create` must not run when the branch is missing"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn errors_when_worktree_missing() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
 
         let options = PrGithubOptions {
             name: "missing".into(),
@@ -686,9 +2064,10 @@ mod tests {
             draft: false,
             fill: false,
             web: false,
-            remote: "origin".into(),
+            remotes: vec!["origin".into()],
             reviewers: Vec::new(),
             extra_args: Vec::new(),
+            base: None,
         };
         let mut command = PrGithubCommand::with_runner(options, MockCommandRunner::default());
 
@@ -700,8 +2079,7 @@ mod tests {
     #[test]
     fn surfaces_command_failure() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
 
@@ -719,9 +2097,10 @@ mod tests {
             draft: false,
             fill: false,
             web: false,
-            remote: "origin".into(),
+            remotes: vec!["origin".into()],
             reviewers: Vec::new(),
             extra_args: Vec::new(),
+            base: None,
         };
         let mut command = PrGithubCommand::with_runner(options, runner);
 
@@ -733,8 +2112,7 @@ mod tests {
     #[test]
     fn defaults_to_fill_when_metadata_missing() -> color_eyre::Result<()> {
         let repo_dir = TempDir::new()?;
-        init_git_repo(&repo_dir)?;
-        let repo = Repo::discover_from(repo_dir.path())?;
+        let repo = init_repo(&repo_dir)?;
         let worktree_path = repo.worktrees_dir().join("feature/test");
         fs::create_dir_all(&worktree_path)?;
 
@@ -766,9 +2144,10 @@ mod tests {
             draft: false,
             fill: false,
             web: false,
-            remote: "origin".into(),
+            remotes: vec!["origin".into()],
             reviewers: Vec::new(),
             extra_args: Vec::new(),
+            base: None,
         };
         let mut command = PrGithubCommand::with_runner(options, runner);
 
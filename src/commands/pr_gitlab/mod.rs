@@ -0,0 +1,458 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::WrapErr;
+use owo_colors::{OwoColorize, Stream};
+
+use crate::{
+    Repo,
+    commands::pr_github::{
+        CommandRunner, SystemCommandRunner, command_failure, current_branch,
+        ensure_branch_on_remotes, format_command, format_with_color, push_args, push_to_remotes,
+    },
+    error::WorktreeNotFound,
+};
+
+#[derive(Debug)]
+pub struct PrGitlabOptions {
+    pub name: String,
+    pub push: bool,
+    pub draft: bool,
+    pub fill: bool,
+    pub web: bool,
+    /// Remotes to push to, in order; the first one becomes the branch's upstream.
+    pub remotes: Vec<String>,
+    pub reviewers: Vec<String>,
+    pub extra_args: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct PrGitlabCommand<R = SystemCommandRunner> {
+    name: String,
+    push: bool,
+    draft: bool,
+    fill: bool,
+    web: bool,
+    remotes: Vec<String>,
+    reviewers: Vec<String>,
+    extra_args: Vec<String>,
+    dry_run: bool,
+    no_verify: bool,
+    runner: R,
+}
+
+impl<R> PrGitlabCommand<R>
+where
+    R: CommandRunner,
+{
+    pub fn with_runner(options: PrGitlabOptions, runner: R) -> Self {
+        let PrGitlabOptions {
+            name,
+            push,
+            draft,
+            fill,
+            web,
+            remotes,
+            reviewers,
+            extra_args,
+        } = options;
+        Self {
+            name,
+            push,
+            draft,
+            fill,
+            web,
+            remotes,
+            reviewers,
+            extra_args,
+            dry_run: false,
+            no_verify: false,
+            runner,
+        }
+    }
+
+    /// Print the `git push` and `glab mr create` command lines instead of running them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Pass `--no-verify` to `git push` to skip pre-push hooks.
+    pub fn with_no_verify(mut self, no_verify: bool) -> Self {
+        self.no_verify = no_verify;
+        self
+    }
+
+    pub fn execute(&mut self, repo: &Repo) -> color_eyre::Result<()> {
+        let worktree_path = self.ensure_worktree_path(repo)?;
+        let branch = current_branch(&mut self.runner, &worktree_path, &self.name, "an MR")?;
+
+        let branch_label = format_with_color(&branch, |text| format!("{}", text.magenta().bold()));
+        let path_label = format_with_color(&worktree_path.display().to_string(), |text| {
+            format!("{}", text.blue())
+        });
+        println!(
+            "Preparing GitLab MR for `{}` from `{}`...",
+            branch_label, path_label
+        );
+
+        self.ensure_mr_metadata_options();
+
+        if self.dry_run {
+            return self.print_dry_run(&branch);
+        }
+
+        if self.push {
+            push_to_remotes(
+                &mut self.runner,
+                &worktree_path,
+                &self.remotes,
+                &branch,
+                self.no_verify,
+                false,
+            )?;
+        } else {
+            ensure_branch_on_remotes(&mut self.runner, &worktree_path, &self.remotes, &branch)?;
+            let message = format!("Skipping push for `{}` (push disabled).", branch_label);
+            println!(
+                "{}",
+                message.if_supports_color(Stream::Stdout, |text| { format!("{}", text.dimmed()) })
+            );
+        }
+
+        self.create_merge_request(&worktree_path, &branch)
+    }
+
+    fn ensure_worktree_path(&self, repo: &Repo) -> color_eyre::Result<PathBuf> {
+        let worktrees_dir = repo.ensure_worktrees_dir()?;
+        let worktree_path = worktrees_dir.join(&self.name);
+        if !worktree_path.exists() {
+            return Err(WorktreeNotFound::new(&self.name, &worktrees_dir).into());
+        }
+        Ok(worktree_path)
+    }
+
+    fn print_dry_run(&self, branch: &str) -> color_eyre::Result<()> {
+        let note = "Dry run: no commands will be executed.";
+        println!(
+            "{}",
+            note.if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow()))
+        );
+        if self.push {
+            for (index, remote) in self.remotes.iter().enumerate() {
+                println!(
+                    "{}",
+                    format_command(
+                        "git",
+                        &push_args(remote, branch, index == 0, self.no_verify)
+                    )
+                );
+            }
+        }
+        println!("{}", format_command("glab", &self.mr_create_args(branch)));
+        Ok(())
+    }
+
+    fn mr_create_args(&self, branch: &str) -> Vec<String> {
+        let mut args = vec![
+            "mr".to_owned(),
+            "create".to_owned(),
+            "--source-branch".to_owned(),
+            branch.to_owned(),
+        ];
+
+        if self.draft {
+            args.push("--draft".to_owned());
+        }
+        if self.fill {
+            args.push("--fill".to_owned());
+        }
+        if self.web {
+            args.push("--web".to_owned());
+        } else {
+            // Output is captured, so `glab`'s confirmation prompt would never be seen.
+            args.push("--yes".to_owned());
+        }
+
+        for reviewer in &self.reviewers {
+            args.push("--reviewer".to_owned());
+            args.push(reviewer.clone());
+        }
+
+        args.extend(self.extra_args.clone());
+        args
+    }
+
+    fn create_merge_request(
+        &mut self,
+        worktree_path: &Path,
+        branch: &str,
+    ) -> color_eyre::Result<()> {
+        let args = self.mr_create_args(branch);
+
+        let output = self
+            .runner
+            .run("glab", worktree_path, &args)
+            .wrap_err("failed to run `glab mr create`")?;
+
+        if !output.success {
+            return Err(command_failure("glab", &args, &output));
+        }
+
+        let branch_label = format_with_color(branch, |text| format!("{}", text.magenta().bold()));
+        println!("GitLab merge request created for `{}`.", branch_label);
+        let mut lines = output.stdout.lines().map(str::trim);
+        if let Some(mr_link) = lines
+            .clone()
+            .find(|line| line.starts_with("https://") || line.starts_with("http://"))
+            .or_else(|| lines.rfind(|line| !line.is_empty()))
+        {
+            println!("{}", mr_link);
+        }
+        Ok(())
+    }
+
+    fn ensure_mr_metadata_options(&mut self) {
+        if self.fill || self.web {
+            return;
+        }
+
+        if self
+            .extra_args
+            .iter()
+            .any(|arg| metadata_flag_allows_noninteractive(arg))
+        {
+            return;
+        }
+
+        let note = "No MR metadata flags provided; defaulting to `--fill`.";
+        let message = note.if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow()));
+        println!("{}", message);
+        self.fill = true;
+    }
+}
+
+fn metadata_flag_allows_noninteractive(arg: &str) -> bool {
+    let cleaned = arg.trim();
+    if cleaned == "--" {
+        return false;
+    }
+
+    matches!(
+        cleaned,
+        "--fill" | "-f" | "--web" | "--title" | "-t" | "--description" | "-d"
+    ) || cleaned.starts_with("--title=")
+        || cleaned.starts_with("--description=")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::test_support::{MockCommandRunner, output};
+
+    fn calls(runner: &MockCommandRunner) -> Vec<(String, Vec<String>)> {
+        runner
+            .calls
+            .iter()
+            .map(|call| (call.program.clone(), call.args.clone()))
+            .collect()
+    }
+
+    fn repo_with_worktree(dir: &TempDir, name: &str) -> color_eyre::Result<Repo> {
+        git2::Repository::init(dir.path())?;
+        let repo = Repo::discover_from(dir.path())?;
+        fs::create_dir_all(repo.worktrees_dir().join(name))?;
+        Ok(repo)
+    }
+
+    fn options(name: &str) -> PrGitlabOptions {
+        PrGitlabOptions {
+            name: name.into(),
+            push: true,
+            draft: false,
+            fill: false,
+            web: false,
+            remotes: vec!["origin".into()],
+            reviewers: Vec::new(),
+            extra_args: Vec::new(),
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_owned()).collect()
+    }
+
+    #[test]
+    fn pushes_and_runs_glab_mr_create() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktree(&dir, "feature")?;
+
+        let runner = MockCommandRunner::with_outputs([
+            output("feature\n", true),
+            output("", true),
+            output("https://gitlab.com/acme/app/-/merge_requests/7\n", true),
+        ]);
+
+        let mut command = PrGitlabCommand::with_runner(
+            PrGitlabOptions {
+                draft: true,
+                fill: true,
+                reviewers: vec!["alice".into(), "bob".into()],
+                extra_args: vec!["--label".into(), "ready".into()],
+                ..options("feature")
+            },
+            runner,
+        );
+        command.execute(&repo)?;
+
+        assert_eq!(
+            calls(&command.runner),
+            vec![
+                (
+                    "git".to_owned(),
+                    strings(&["rev-parse", "--abbrev-ref", "HEAD"])
+                ),
+                (
+                    "git".to_owned(),
+                    strings(&["push", "-u", "origin", "feature"])
+                ),
+                (
+                    "glab".to_owned(),
+                    strings(&[
+                        "mr",
+                        "create",
+                        "--source-branch",
+                        "feature",
+                        "--draft",
+                        "--fill",
+                        "--yes",
+                        "--reviewer",
+                        "alice",
+                        "--reviewer",
+                        "bob",
+                        "--label",
+                        "ready",
+                    ])
+                ),
+            ]
+        );
+        assert_eq!(command.runner.streamed_calls, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn defaults_to_fill_and_skips_prompt_unless_web() -> color_eyre::Result<()> {
+        let mut command =
+            PrGitlabCommand::with_runner(options("feature"), MockCommandRunner::default());
+        command.ensure_mr_metadata_options();
+        assert_eq!(
+            command.mr_create_args("feature"),
+            strings(&[
+                "mr",
+                "create",
+                "--source-branch",
+                "feature",
+                "--fill",
+                "--yes"
+            ])
+        );
+
+        let mut command = PrGitlabCommand::with_runner(
+            PrGitlabOptions {
+                web: true,
+                ..options("feature")
+            },
+            MockCommandRunner::default(),
+        );
+        command.ensure_mr_metadata_options();
+        assert_eq!(
+            command.mr_create_args("feature"),
+            strings(&["mr", "create", "--source-branch", "feature", "--web"])
+        );
+
+        let mut command = PrGitlabCommand::with_runner(
+            PrGitlabOptions {
+                extra_args: vec!["--title=Ship it".into()],
+                ..options("feature")
+            },
+            MockCommandRunner::default(),
+        );
+        command.ensure_mr_metadata_options();
+        assert!(!command.fill);
+        Ok(())
+    }
+
+    #[test]
+    fn no_push_checks_remote_before_creating() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktree(&dir, "feature")?;
+
+        let runner = MockCommandRunner::with_outputs([output("feature\n", true), output("", true)]);
+
+        let mut command = PrGitlabCommand::with_runner(
+            PrGitlabOptions {
+                push: false,
+                ..options("feature")
+            },
+            runner,
+        );
+        let err = command.execute(&repo).expect_err("branch is not on remote");
+
+        assert!(
+            err.to_string()
+                .contains("branch `feature` is not on remote `origin`")
+        );
+        assert_eq!(command.runner.calls.len(), 2);
+        assert_eq!(
+            calls(&command.runner)[1],
+            (
+                "git".to_owned(),
+                strings(&["ls-remote", "--heads", "origin", "feature"])
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn surfaces_glab_failure() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktree(&dir, "feature")?;
+
+        let runner = MockCommandRunner::with_outputs([
+            output("feature\n", true),
+            output("", true),
+            output("", false),
+        ]);
+
+        let mut command = PrGitlabCommand::with_runner(options("feature"), runner);
+        let err = command.execute(&repo).expect_err("glab should fail");
+
+        assert!(
+            err.to_string()
+                .starts_with("`glab mr create --source-branch feature --fill --yes` failed")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detached_head_errors_before_pushing() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = repo_with_worktree(&dir, "feature")?;
+
+        let runner = MockCommandRunner::with_outputs([output("HEAD\n", true)]);
+
+        let mut command = PrGitlabCommand::with_runner(options("feature"), runner);
+        let err = command.execute(&repo).expect_err("detached HEAD");
+
+        assert_eq!(
+            err.to_string(),
+            "worktree `feature` is in detached HEAD; check out a branch before opening an MR"
+        );
+        assert_eq!(command.runner.calls.len(), 1);
+        Ok(())
+    }
+}
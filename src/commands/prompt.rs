@@ -0,0 +1,35 @@
+use std::io::{self, BufRead, Write};
+
+use color_eyre::eyre::Context;
+
+/// Asks `question` on stdout with a `[y/N]` suffix. Only `y` or `yes` counts as agreeing.
+pub(crate) fn confirm(question: &str) -> color_eyre::Result<bool> {
+    print!("{question} [y/N] ");
+    io::stdout().flush().wrap_err("failed to flush stdout")?;
+    read_answer(&mut io::stdin().lock())
+}
+
+fn read_answer(input: &mut impl BufRead) -> color_eyre::Result<bool> {
+    let mut answer = String::new();
+    input
+        .read_line(&mut answer)
+        .wrap_err("failed to read confirmation")?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_yes_answers_agree() -> color_eyre::Result<()> {
+        for answer in ["y\n", "Y\n", "yes\n", " Yes \n"] {
+            assert!(read_answer(&mut answer.as_bytes())?, "{answer:?}");
+        }
+        for answer in ["\n", "n\n", "no\n", "YES\n", "sure\n", ""] {
+            assert!(!read_answer(&mut answer.as_bytes())?, "{answer:?}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,486 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use color_eyre::eyre::{self, Context};
+use owo_colors::{OwoColorize, Stream};
+
+use git2::{BranchType, Oid, StatusOptions};
+
+use crate::{
+    Repo,
+    commands::{
+        batch::{BatchOutcome, BatchReporter},
+        create::{find_branch_checkout, find_worktree_metadata_name},
+        list::{
+            default_branch_name, default_branch_tip, find_worktrees, format_worktree,
+            worktree_branch,
+        },
+        prompt::confirm,
+        rm::RemoveCommand,
+    },
+};
+
+#[derive(Debug, Default)]
+pub struct PruneCommand {
+    expire: Option<Duration>,
+    include_unmerged: bool,
+    yes: bool,
+    json_lines: bool,
+    merged: bool,
+    into: Option<String>,
+    dry_run: bool,
+}
+
+impl PruneCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also delete local branches without a worktree whose tip is older than `expire`.
+    pub fn with_expire(mut self, expire: Option<Duration>) -> Self {
+        self.expire = expire;
+        self
+    }
+
+    /// Also expire branches that are merged into neither the default branch nor their upstream.
+    pub fn with_include_unmerged(mut self, include_unmerged: bool) -> Self {
+        self.include_unmerged = include_unmerged;
+        self
+    }
+
+    /// Skip the confirmation before deleting expired branches.
+    pub fn with_yes(mut self, yes: bool) -> Self {
+        self.yes = yes;
+        self
+    }
+
+    /// Print one JSON object per pruned or deleted item instead of human-readable output.
+    pub fn with_json_lines(mut self, json_lines: bool) -> Self {
+        self.json_lines = json_lines;
+        self
+    }
+
+    /// Also remove clean worktrees whose branch is fully merged into the default branch.
+    pub fn with_merged(mut self, merged: bool) -> Self {
+        self.merged = merged;
+        self
+    }
+
+    /// Branch or commit that `with_merged` checks against instead of the default branch.
+    pub fn with_into(mut self, into: Option<String>) -> Self {
+        self.into = into;
+        self
+    }
+
+    /// Report the merged worktrees that would be removed without removing them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
+        if self.json_lines && self.expire.is_some() && !self.yes {
+            return Err(eyre::eyre!(
+                "`--json-lines` cannot prompt before deleting expired branches; pass `--yes`"
+            ));
+        }
+
+        let worktrees = prune_missing_worktrees(
+            repo,
+            BatchReporter::new("worktree", "pruned").with_json_lines(self.json_lines),
+        )?;
+        if worktrees.is_empty() {
+            if !self.json_lines {
+                println!("No worktrees with a missing directory to prune.");
+            }
+        } else {
+            worktrees.print_summary("Worktrees");
+        }
+
+        let merged = if self.merged {
+            Some(self.remove_merged_worktrees(repo)?)
+        } else {
+            None
+        };
+
+        let branches = match self.expire {
+            Some(expire) => self.delete_expired_branches(repo, expire)?,
+            None => None,
+        };
+
+        if worktrees.failed() > 0 {
+            return Err(eyre::eyre!(
+                "some worktrees could not be pruned ({})",
+                worktrees.summary()
+            ));
+        }
+        if let Some(merged) = merged
+            && merged.failed() > 0
+        {
+            return Err(eyre::eyre!(
+                "some merged worktrees could not be removed ({})",
+                merged.summary()
+            ));
+        }
+        if let Some(branches) = branches
+            && branches.failed() > 0
+        {
+            return Err(eyre::eyre!(
+                "some expired branches could not be deleted ({})",
+                branches.summary()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn remove_merged_worktrees(&self, repo: &Repo) -> color_eyre::Result<BatchReporter> {
+        let target = match &self.into {
+            Some(into) => repo
+                .git()
+                .revparse_single(into)
+                .and_then(|object| object.peel_to_commit())
+                .map(|commit| commit.id())
+                .wrap_err_with(|| eyre::eyre!("failed to resolve `{into}`"))?,
+            None => default_branch_tip(repo.git()).ok_or_else(|| {
+                eyre::eyre!("failed to resolve the default branch; pass `--into`")
+            })?,
+        };
+
+        let verb = if self.dry_run {
+            "would be removed"
+        } else {
+            "removed"
+        };
+        let mut reporter = BatchReporter::new("worktree", verb).with_json_lines(self.json_lines);
+        let worktrees_dir = repo.worktrees_dir();
+        let worktrees = if worktrees_dir.exists() {
+            find_worktrees(&worktrees_dir)?
+        } else {
+            Vec::new()
+        };
+
+        for worktree in worktrees {
+            let name = format_worktree(&worktree);
+            let path = worktrees_dir.join(&worktree);
+            let Some(branch) = worktree_branch(&path) else {
+                reporter.record(
+                    &name,
+                    BatchOutcome::Skipped,
+                    format!("Skipped worktree `{name}`; its HEAD is detached."),
+                );
+                continue;
+            };
+
+            match merge_state(repo, &path, target) {
+                Ok(MergeState::Unmerged) => continue,
+                Ok(MergeState::Dirty) => {
+                    reporter.record(
+                        &name,
+                        BatchOutcome::Skipped,
+                        format!("Skipped worktree `{name}`; it has uncommitted changes."),
+                    );
+                    continue;
+                }
+                Ok(MergeState::Merged) => {}
+                Err(err) => {
+                    reporter.record(
+                        &name,
+                        BatchOutcome::Failed,
+                        format!("Failed to check worktree `{name}`: {err:#}"),
+                    );
+                    continue;
+                }
+            }
+
+            let locked = find_worktree_metadata_name(repo.git(), &path)?
+                .and_then(|metadata| repo.git().find_worktree(&metadata).ok())
+                .is_some_and(|worktree| {
+                    !matches!(worktree.is_locked(), Ok(git2::WorktreeLockStatus::Unlocked))
+                });
+            if locked {
+                reporter.record(
+                    &name,
+                    BatchOutcome::Skipped,
+                    format!("Skipped worktree `{name}`; it is locked."),
+                );
+                continue;
+            }
+
+            let label = format!(
+                "{}",
+                name.as_str()
+                    .if_supports_color(Stream::Stdout, |text| format!("{}", text.red().bold()))
+            );
+            if self.dry_run {
+                reporter.record(
+                    &name,
+                    BatchOutcome::Succeeded,
+                    format!("Would remove worktree `{label}`; branch `{branch}` is merged."),
+                );
+                continue;
+            }
+
+            let removed = RemoveCommand::new(name.clone(), false)
+                .with_quiet(true)
+                .with_spawn_shell(false)
+                .execute(repo);
+            match removed {
+                Ok(_) => reporter.record(
+                    &name,
+                    BatchOutcome::Succeeded,
+                    format!("Removed worktree `{label}`; branch `{branch}` is merged."),
+                ),
+                Err(err) => reporter.record(
+                    &name,
+                    BatchOutcome::Failed,
+                    format!("Failed to remove worktree `{name}`: {err:#}"),
+                ),
+            }
+        }
+
+        if reporter.is_empty() {
+            if !self.json_lines {
+                println!("No merged worktrees to remove.");
+            }
+        } else {
+            reporter.print_summary("Merged worktrees");
+        }
+        Ok(reporter)
+    }
+
+    fn delete_expired_branches(
+        &self,
+        repo: &Repo,
+        expire: Duration,
+    ) -> color_eyre::Result<Option<BatchReporter>> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .wrap_err("system clock is before the Unix epoch")?;
+        let cutoff = now.saturating_sub(expire).as_secs() as i64;
+        let expired = find_expired_branches(repo, cutoff, self.include_unmerged)?;
+        if expired.is_empty() {
+            if !self.json_lines {
+                println!("No local branches without a worktree are older than the cutoff.");
+            }
+            return Ok(None);
+        }
+
+        if !self.yes && !confirm_branch_deletion(&expired)? {
+            println!("Kept {} local branch(es).", expired.len());
+            return Ok(None);
+        }
+
+        let git_repo = repo.git();
+        let mut reporter = BatchReporter::new("branch", "deleted").with_json_lines(self.json_lines);
+        for name in &expired {
+            let deleted = git_repo
+                .find_branch(name, BranchType::Local)
+                .and_then(|mut branch| branch.delete());
+            if let Err(err) = deleted {
+                reporter.record(
+                    name,
+                    BatchOutcome::Failed,
+                    format!("Failed to delete local branch `{name}`: {}", err.message()),
+                );
+                continue;
+            }
+
+            let label = format!(
+                "{}",
+                name.as_str().if_supports_color(Stream::Stdout, |text| {
+                    format!("{}", text.magenta().bold())
+                })
+            );
+            reporter.record(
+                name,
+                BatchOutcome::Succeeded,
+                format!("Deleted local branch `{}`.", label),
+            );
+        }
+
+        reporter.print_summary("Branches");
+        Ok(Some(reporter))
+    }
+}
+
+/// Prunes the metadata of worktrees whose directory has been deleted, reporting each one.
+/// Locked worktrees are skipped.
+fn prune_missing_worktrees(
+    repo: &Repo,
+    mut reporter: BatchReporter,
+) -> color_eyre::Result<BatchReporter> {
+    let git_repo = repo.git();
+    let names = git_repo
+        .worktrees()
+        .wrap_err("failed to list repository worktrees")?;
+    let worktrees_dir = repo.worktrees_dir();
+
+    for name in names.iter().flatten() {
+        let Ok(worktree) = git_repo.find_worktree(name) else {
+            continue;
+        };
+        if worktree.path().exists() {
+            continue;
+        }
+
+        let path = worktree.path();
+        let display = path
+            .strip_prefix(&worktrees_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string();
+        if !worktree.is_prunable(None).unwrap_or(false) {
+            reporter.record(
+                &display,
+                BatchOutcome::Skipped,
+                format!("Skipped worktree `{display}`; it is locked."),
+            );
+            continue;
+        }
+
+        if let Err(err) = worktree.prune(None) {
+            reporter.record(
+                &display,
+                BatchOutcome::Failed,
+                format!("Failed to prune worktree `{display}`: {}", err.message()),
+            );
+            continue;
+        }
+
+        let label = format!(
+            "{}",
+            display
+                .as_str()
+                .if_supports_color(Stream::Stdout, |text| format!("{}", text.red().bold()))
+        );
+        reporter.record(
+            &display,
+            BatchOutcome::Succeeded,
+            format!(
+                "Pruned worktree `{}`; its directory no longer exists.",
+                label
+            ),
+        );
+    }
+
+    Ok(reporter)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MergeState {
+    Merged,
+    Unmerged,
+    /// Merged, but with uncommitted changes that removal would lose.
+    Dirty,
+}
+
+/// Whether the worktree at `path` has no commits that `target` lacks, and is clean.
+fn merge_state(repo: &Repo, path: &std::path::Path, target: Oid) -> color_eyre::Result<MergeState> {
+    let worktree_repo = git2::Repository::open(path)
+        .wrap_err_with(|| eyre::eyre!("failed to open `{}`", path.display()))?;
+    let head = worktree_repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .wrap_err("failed to resolve HEAD")?
+        .id();
+    let (ahead, _) = repo
+        .git()
+        .graph_ahead_behind(head, target)
+        .wrap_err("failed to compare with the target branch")?;
+    if ahead > 0 {
+        return Ok(MergeState::Unmerged);
+    }
+
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let statuses = worktree_repo
+        .statuses(Some(&mut options))
+        .wrap_err("failed to read status")?;
+    if statuses.is_empty() {
+        Ok(MergeState::Merged)
+    } else {
+        Ok(MergeState::Dirty)
+    }
+}
+
+/// Local branches whose tip was committed before `cutoff` (Unix seconds) and that are not checked
+/// out anywhere. The default branch (`origin/HEAD`, `init.defaultBranch`, `main`, `master`) and the
+/// current branch are never included, and neither are branches whose tip is in neither the
+/// default branch nor their upstream unless `include_unmerged` is set.
+pub(crate) fn find_expired_branches(
+    repo: &Repo,
+    cutoff: i64,
+    include_unmerged: bool,
+) -> color_eyre::Result<Vec<String>> {
+    let git_repo = repo.git();
+    let mut protected = vec!["main".to_owned(), "master".to_owned()];
+    protected.extend(default_branch_name(git_repo));
+    protected.extend(
+        git_repo
+            .config()
+            .and_then(|config| config.get_string("init.defaultBranch"))
+            .ok(),
+    );
+    if let Ok(head) = git_repo.head()
+        && head.is_branch()
+    {
+        protected.extend(head.shorthand().map(str::to_owned));
+    }
+    let default_tip = default_branch_tip(git_repo);
+
+    let branches = git_repo
+        .branches(Some(BranchType::Local))
+        .wrap_err("failed to list local branches")?;
+
+    let mut expired = Vec::new();
+    for entry in branches {
+        let (branch, _) = entry.wrap_err("failed to read local branch")?;
+        let Some(name) = branch.name().ok().flatten() else {
+            continue;
+        };
+        if protected.iter().any(|protected| protected == name) {
+            continue;
+        }
+
+        let commit = branch
+            .get()
+            .peel_to_commit()
+            .wrap_err_with(|| eyre::eyre!("failed to resolve tip of branch `{name}`"))?;
+        if commit.committer().when().seconds() >= cutoff {
+            continue;
+        }
+
+        if find_branch_checkout(git_repo, repo.root(), name)?.is_some() {
+            continue;
+        }
+
+        if !include_unmerged {
+            let upstream_tip = branch
+                .upstream()
+                .ok()
+                .and_then(|upstream| upstream.get().target());
+            let contained = |target: Oid| {
+                target == commit.id()
+                    || git_repo
+                        .graph_descendant_of(target, commit.id())
+                        .unwrap_or(false)
+            };
+            if !default_tip.is_some_and(contained) && !upstream_tip.is_some_and(contained) {
+                continue;
+            }
+        }
+
+        expired.push(name.to_owned());
+    }
+
+    expired.sort();
+    Ok(expired)
+}
+
+fn confirm_branch_deletion(branches: &[String]) -> color_eyre::Result<bool> {
+    println!("Local branches without a worktree and no recent commits:");
+    for name in branches {
+        println!("  {name}");
+    }
+    confirm(&format!("Delete {} branch(es)?", branches.len()))
+}
@@ -0,0 +1,308 @@
+use std::{fs, path::Path};
+
+use color_eyre::eyre::{self, WrapErr};
+use git2::BranchType;
+use owo_colors::{OwoColorize, Stream};
+
+use crate::{
+    Repo,
+    commands::{
+        create::{ensure_branch_creatable, rename_stack_worktree},
+        list::{match_worktree_name, worktree_branch},
+        pr_github::{CommandRunner, SystemCommandRunner, command_failure},
+    },
+    error::WorktreeNotFound,
+};
+
+#[derive(Debug)]
+pub struct RenameCommand<R = SystemCommandRunner> {
+    old: String,
+    new: String,
+    runner: R,
+}
+
+impl RenameCommand {
+    pub fn new(old: String, new: String) -> Self {
+        Self::with_runner(old, new, SystemCommandRunner::default())
+    }
+}
+
+impl<R> RenameCommand<R>
+where
+    R: CommandRunner,
+{
+    pub fn with_runner(old: String, new: String, runner: R) -> Self {
+        Self { old, new, runner }
+    }
+
+    pub fn execute(&mut self, repo: &Repo) -> color_eyre::Result<()> {
+        let worktrees_dir = repo.worktrees_dir();
+        let mut old = self.old.clone();
+        if !worktrees_dir.join(&old).exists()
+            && let Some(matched) = match_worktree_name(&worktrees_dir, &old)?
+        {
+            old = matched;
+        }
+        let old_path = worktrees_dir.join(&old);
+        if !old_path.exists() {
+            return Err(WorktreeNotFound::new(&self.old, &worktrees_dir).into());
+        }
+
+        if !git2::Reference::is_valid_name(&format!("refs/heads/{}", self.new)) {
+            return Err(eyre::eyre!(
+                "cannot rename worktree `{}` to `{}`: `{}` is not a valid worktree name",
+                old,
+                self.new,
+                self.new
+            ));
+        }
+        let new_path = worktrees_dir.join(&self.new);
+        if new_path.exists() {
+            return Err(eyre::eyre!(
+                "cannot rename worktree `{}` to `{}`: `{}` already exists",
+                old,
+                self.new,
+                new_path.display()
+            ));
+        }
+
+        // Only a branch named after the worktree follows it; one chosen with `create --branch`
+        // is left alone.
+        let git_repo = repo.git();
+        let rename_branch = worktree_branch(&old_path).filter(|branch| *branch == old);
+        if rename_branch.is_some() {
+            if git_repo.find_branch(&self.new, BranchType::Local).is_ok() {
+                return Err(eyre::eyre!(
+                    "cannot rename worktree `{}` to `{}`: a local branch named `{}` already exists",
+                    old,
+                    self.new,
+                    self.new
+                ));
+            }
+            ensure_branch_creatable(git_repo, &self.new).wrap_err_with(|| {
+                eyre::eyre!("cannot rename worktree `{}` to `{}`", old, self.new)
+            })?;
+        }
+
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent).wrap_err_with(|| {
+                eyre::eyre!("failed to prepare directory `{}`", parent.display())
+            })?;
+        }
+        // `git worktree move` refuses locked worktrees and updates git's link itself.
+        let args = vec![
+            "worktree".to_owned(),
+            "move".to_owned(),
+            old_path.display().to_string(),
+            new_path.display().to_string(),
+        ];
+        let output = self
+            .runner
+            .run("git", repo.root(), &args)
+            .wrap_err("failed to run `git worktree move`")?;
+        if !output.success {
+            remove_empty_parents(&new_path, &worktrees_dir);
+            return Err(command_failure("git", &args, &output)).wrap_err_with(|| {
+                eyre::eyre!("failed to rename worktree `{}` to `{}`", old, self.new)
+            });
+        }
+        remove_empty_parents(&old_path, &worktrees_dir);
+
+        if let Some(branch) = rename_branch.as_deref() {
+            git_repo
+                .find_branch(branch, BranchType::Local)
+                .wrap_err_with(|| eyre::eyre!("failed to look up local branch `{branch}`"))?
+                .rename(&self.new, false)
+                .wrap_err_with(|| {
+                    eyre::eyre!("failed to rename branch `{branch}` to `{}`", self.new)
+                })?;
+        }
+        if let Err(err) = rename_stack_worktree(repo, &old, &self.new) {
+            eprintln!("Warning: {err:#}");
+        }
+
+        let new_label = format!(
+            "{}",
+            self.new
+                .as_str()
+                .if_supports_color(Stream::Stdout, |text| format!("{}", text.green().bold()))
+        );
+        println!(
+            "Renamed worktree `{}` to `{}` at `{}`.",
+            old,
+            new_label,
+            new_path.display()
+        );
+        if rename_branch.is_some() {
+            println!("Renamed branch `{}` to `{}`.", old, self.new);
+        }
+        Ok(())
+    }
+}
+
+/// Removes the directories left empty between `path` and `stop` after moving `path` away.
+fn remove_empty_parents(path: &Path, stop: &Path) {
+    for dir in path.ancestors().skip(1) {
+        if dir == stop || !dir.starts_with(stop) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    use tempfile::TempDir;
+
+    use crate::{commands::create::CreateCommand, test_support::init_repo};
+
+    fn git_status_ok(path: &Path) -> color_eyre::Result<bool> {
+        Ok(Command::new("git")
+            .current_dir(path)
+            .args(["status", "--short"])
+            .output()?
+            .status
+            .success())
+    }
+
+    #[test]
+    fn moves_worktree_and_renames_its_branch() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature/bad-name".into(), None).create_without_enter(&repo, true)?;
+
+        RenameCommand::new("feature/bad-name".into(), "good-name".into()).execute(&repo)?;
+
+        let new_path = repo.worktrees_dir().join("good-name");
+        assert!(
+            git_status_ok(&new_path)?,
+            "moved worktree should still work"
+        );
+        assert_eq!(worktree_branch(&new_path).as_deref(), Some("good-name"));
+        assert!(
+            repo.git()
+                .find_branch("feature/bad-name", BranchType::Local)
+                .is_err()
+        );
+        assert!(
+            !repo.worktrees_dir().join("feature").exists(),
+            "emptied parent directory should be removed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_a_branch_not_named_after_the_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("review".into(), None)
+            .with_branch(Some("teammate/topic".into()))
+            .create_without_enter(&repo, true)?;
+
+        RenameCommand::new("review".into(), "review-topic".into()).execute(&repo)?;
+
+        let new_path = repo.worktrees_dir().join("review-topic");
+        assert_eq!(
+            worktree_branch(&new_path).as_deref(),
+            Some("teammate/topic")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_existing_worktree_or_branch_name() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        for name in ["one", "two"] {
+            CreateCommand::new(name.into(), None).create_without_enter(&repo, true)?;
+        }
+        let head = repo.git().head()?.peel_to_commit()?;
+        repo.git().branch("taken", &head, false)?;
+
+        let err = RenameCommand::new("one".into(), "two".into())
+            .execute(&repo)
+            .expect_err("`two` already exists");
+        assert!(
+            err.to_string()
+                .starts_with("cannot rename worktree `one` to `two`: `")
+        );
+
+        let err = RenameCommand::new("one".into(), "taken".into())
+            .execute(&repo)
+            .expect_err("branch `taken` already exists");
+        assert_eq!(
+            err.to_string(),
+            "cannot rename worktree `one` to `taken`: a local branch named `taken` already exists"
+        );
+        assert!(repo.worktrees_dir().join("one").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_names_outside_the_worktrees_dir() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("one".into(), None).create_without_enter(&repo, true)?;
+        let outside = dir.path().join("outside").display().to_string();
+
+        for new in ["../escape", outside.as_str(), "a/./b", ""] {
+            let err = RenameCommand::new("one".into(), new.into())
+                .execute(&repo)
+                .expect_err("name should be rejected");
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "cannot rename worktree `one` to `{new}`: `{new}` is not a valid worktree name"
+                )
+            );
+        }
+        assert!(repo.worktrees_dir().join("one").exists());
+        assert!(!dir.path().join("outside").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn leaves_worktree_in_place_when_branch_cannot_be_renamed() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature".into(), None).create_without_enter(&repo, true)?;
+
+        let err = RenameCommand::new("feature".into(), "feature/x".into())
+            .execute(&repo)
+            .expect_err("`feature/x` conflicts with branch `feature`");
+        assert_eq!(
+            format!("{err:#}"),
+            "cannot rename worktree `feature` to `feature/x`: cannot create branch `feature/x` because branch `feature` exists"
+        );
+        let old_path = repo.worktrees_dir().join("feature");
+        assert_eq!(worktree_branch(&old_path).as_deref(), Some("feature"));
+        assert!(git_status_ok(&old_path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_to_move_a_locked_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("pinned".into(), None).create_without_enter(&repo, true)?;
+        let old_path = repo.worktrees_dir().join("pinned");
+        let status = Command::new("git")
+            .current_dir(dir.path())
+            .args(["worktree", "lock"])
+            .arg(&old_path)
+            .status()?;
+        assert!(status.success());
+
+        let err = RenameCommand::new("pinned".into(), "nested/moved".into())
+            .execute(&repo)
+            .expect_err("locked worktrees must not move");
+        assert!(format!("{err:#}").contains("locked"), "{err:#}");
+
+        assert!(git_status_ok(&old_path)?);
+        assert!(!repo.worktrees_dir().join("nested").exists());
+        assert!(repo.git().find_branch("pinned", BranchType::Local).is_ok());
+        Ok(())
+    }
+}
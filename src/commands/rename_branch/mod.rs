@@ -0,0 +1,86 @@
+use color_eyre::eyre::{self, WrapErr};
+use git2::BranchType;
+use owo_colors::{OwoColorize, Stream};
+
+use crate::{Repo, error::WorktreeNotFound, repo::open_worktree_at};
+
+#[derive(Debug)]
+pub struct RenameBranchCommand {
+    name: String,
+    new_branch: String,
+}
+
+impl RenameBranchCommand {
+    pub fn new(name: String, new_branch: String) -> Self {
+        Self { name, new_branch }
+    }
+
+    pub fn execute(&self, repo: &Repo) -> color_eyre::Result<()> {
+        let worktrees_dir = repo.worktrees_dir();
+        let worktree_path = worktrees_dir.join(&self.name);
+        if !worktree_path.exists() {
+            return Err(WorktreeNotFound::new(&self.name, &worktrees_dir).into());
+        }
+
+        let worktree_repo = open_worktree_at(&self.name, &worktree_path)?;
+        let head = worktree_repo
+            .head()
+            .wrap_err_with(|| eyre::eyre!("failed to read HEAD of worktree `{}`", self.name))?;
+        let old_branch = match head.shorthand() {
+            Some(branch) if head.is_branch() => branch.to_owned(),
+            _ => {
+                return Err(eyre::eyre!(
+                    "worktree `{}` is in detached HEAD; there is no branch to rename",
+                    self.name
+                ));
+            }
+        };
+
+        if old_branch == self.new_branch {
+            println!("Worktree `{}` is already on `{}`.", self.name, old_branch);
+            return Ok(());
+        }
+
+        let git_repo = repo.git();
+        if git_repo
+            .find_branch(&self.new_branch, BranchType::Local)
+            .is_ok()
+        {
+            return Err(eyre::eyre!(
+                "a local branch named `{}` already exists",
+                self.new_branch
+            ));
+        }
+
+        // Renaming moves the `branch.<name>` config section, so the upstream follows the branch.
+        git_repo
+            .find_branch(&old_branch, BranchType::Local)
+            .wrap_err_with(|| eyre::eyre!("failed to look up local branch `{old_branch}`"))?
+            .rename(&self.new_branch, false)
+            .wrap_err_with(|| {
+                eyre::eyre!(
+                    "failed to rename branch `{old_branch}` to `{}`",
+                    self.new_branch
+                )
+            })?;
+
+        let old_label = format!(
+            "{}",
+            old_branch
+                .as_str()
+                .if_supports_color(Stream::Stdout, |text| format!("{}", text.magenta()))
+        );
+        let new_label = format!(
+            "{}",
+            self.new_branch
+                .as_str()
+                .if_supports_color(Stream::Stdout, |text| format!("{}", text.magenta().bold()))
+        );
+        println!(
+            "Renamed branch `{}` to `{}` in worktree `{}`.",
+            old_label, new_label, self.name
+        );
+
+        Ok(())
+    }
+}
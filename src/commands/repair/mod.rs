@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::{self, WrapErr};
+use owo_colors::{OwoColorize, Stream};
+
+use crate::{
+    Repo,
+    commands::{
+        list::find_worktrees,
+        pr_github::{CommandRunner, SystemCommandRunner},
+    },
+};
+
+#[derive(Debug)]
+pub struct RepairCommand<R = SystemCommandRunner> {
+    paths: Vec<PathBuf>,
+    runner: R,
+}
+
+impl RepairCommand {
+    /// Repairs the links of `paths`, or of every worktree under the worktrees directory when empty.
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self::with_runner(paths, SystemCommandRunner::default())
+    }
+}
+
+impl<R> RepairCommand<R>
+where
+    R: CommandRunner,
+{
+    pub fn with_runner(paths: Vec<PathBuf>, runner: R) -> Self {
+        Self { paths, runner }
+    }
+
+    pub fn execute(&mut self, repo: &Repo) -> color_eyre::Result<()> {
+        let paths = if self.paths.is_empty() {
+            // A moved worktree is no longer where git expects it, so name every one explicitly.
+            let worktrees_dir = repo.worktrees_dir();
+            if worktrees_dir.exists() {
+                find_worktrees(&worktrees_dir)?
+                    .into_iter()
+                    .map(|worktree| worktrees_dir.join(worktree))
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        } else {
+            self.paths.clone()
+        };
+
+        let mut args = vec!["worktree".to_owned(), "repair".to_owned()];
+        args.extend(paths.iter().map(|path| path.display().to_string()));
+        let output = self
+            .runner
+            .run("git", repo.root(), &args)
+            .wrap_err("failed to run `git worktree repair`")?;
+
+        // git reports each fixed link on stderr as `repair: <problem>: <path>`.
+        let mut repaired = 0;
+        let mut problems = Vec::new();
+        for line in output.stderr.lines() {
+            let Some(detail) = line.strip_prefix("repair: ") else {
+                if !line.trim().is_empty() {
+                    problems.push(line.trim());
+                }
+                continue;
+            };
+            repaired += 1;
+            let (problem, path) = detail.split_once(": ").unwrap_or(("", detail));
+            let path = format!(
+                "{}",
+                path.if_supports_color(Stream::Stdout, |text| format!("{}", text.blue()))
+            );
+            if problem.is_empty() {
+                println!("Repaired `{path}`.");
+            } else {
+                println!("Repaired `{path}` ({problem}).");
+            }
+        }
+
+        if !output.success {
+            return Err(eyre::eyre!(
+                "`git worktree repair` failed: {}",
+                problems.join("\n")
+            ));
+        }
+
+        if repaired == 0 {
+            println!("No broken worktree links found.");
+        } else {
+            let noun = if repaired == 1 { "link" } else { "links" };
+            println!("Repaired {repaired} worktree {noun}.");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::{
+        commands::pr_github::CommandOutput,
+        test_support::{MockCommandRunner, RecordedCall, init_repo},
+    };
+
+    fn runner(stderr: &str, success: bool) -> MockCommandRunner {
+        MockCommandRunner::answering(CommandOutput {
+            stdout: String::new(),
+            stderr: stderr.into(),
+            success,
+            status_code: Some(if success { 0 } else { 128 }),
+        })
+    }
+
+    fn fixture_worktree(repo: &Repo, name: &str) -> color_eyre::Result<PathBuf> {
+        let path = repo.worktrees_dir().join(name);
+        std::fs::create_dir_all(&path)?;
+        std::fs::write(
+            path.join(".git"),
+            format!("gitdir: {}/.git/worktrees/{name}\n", repo.root().display()),
+        )?;
+        Ok(path)
+    }
+
+    #[test]
+    fn repairs_every_managed_worktree_from_repo_root() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        repo.ensure_worktrees_dir()?;
+        let alpha = fixture_worktree(&repo, "alpha")?;
+        let beta = fixture_worktree(&repo, "beta")?;
+
+        let runner = runner(
+            "repair: gitdir incorrect: /repo/.git/worktrees/alpha/gitdir\n",
+            true,
+        );
+        let mut command = RepairCommand::with_runner(Vec::new(), runner);
+        command.execute(&repo)?;
+
+        assert_eq!(
+            command.runner.calls,
+            vec![RecordedCall {
+                program: "git".to_owned(),
+                dir: repo.root().to_path_buf(),
+                args: vec![
+                    "worktree".to_owned(),
+                    "repair".to_owned(),
+                    alpha.display().to_string(),
+                    beta.display().to_string(),
+                ],
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn passes_explicit_paths_and_reports_failures() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let runner = runner("error: not a valid path: /elsewhere/wt\n", false);
+        let mut command = RepairCommand::with_runner(vec!["/elsewhere/wt".into()], runner);
+
+        let err = command.execute(&repo).unwrap_err();
+
+        assert_eq!(
+            command.runner.calls[0].args,
+            ["worktree", "repair", "/elsewhere/wt"]
+        );
+        assert_eq!(
+            err.to_string(),
+            "`git worktree repair` failed: error: not a valid path: /elsewhere/wt"
+        );
+        Ok(())
+    }
+}
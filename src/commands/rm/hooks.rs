@@ -0,0 +1,53 @@
+use serde::Deserialize;
+
+use crate::Repo;
+
+/// Shell commands from the `remove` preferences, run around `rm`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct RemoveHooks {
+    /// Runs in the worktree before it is removed; a failure aborts the removal.
+    #[serde(default)]
+    pub(crate) pre_remove: Option<String>,
+    /// Runs in the repository root after the worktree is gone; a failure only warns.
+    #[serde(default)]
+    pub(crate) post_remove: Option<String>,
+}
+
+pub(crate) fn load_remove_hooks(repo: &Repo) -> color_eyre::Result<RemoveHooks> {
+    let mut hooks = repo.preferences()?.remove;
+    hooks.pre_remove = hooks.pre_remove.filter(|hook| !hook.trim().is_empty());
+    hooks.post_remove = hooks.post_remove.filter(|hook| !hook.trim().is_empty());
+    Ok(hooks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::test_support::init_repo;
+
+    #[test]
+    fn loads_hooks_and_ignores_blank_commands() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        repo.ensure_worktrees_dir()?;
+        assert_eq!(load_remove_hooks(&repo)?, RemoveHooks::default());
+
+        fs::write(
+            repo.preferences_path(),
+            r#"{ "remove": { "pre_remove": "docker compose down", "post_remove": "  " } }"#,
+        )?;
+
+        assert_eq!(
+            load_remove_hooks(&repo)?,
+            RemoveHooks {
+                pre_remove: Some("docker compose down".into()),
+                post_remove: None,
+            }
+        );
+        Ok(())
+    }
+}
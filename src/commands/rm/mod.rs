@@ -5,18 +5,49 @@ use owo_colors::{OwoColorize, Stream};
 
 use git2::{BranchType, ErrorCode, WorktreePruneOptions};
 
-use crate::{Repo, commands::cd::shell_command};
+use crate::{
+    Repo,
+    commands::{
+        batch::{BatchOutcome, BatchReporter},
+        cd::run_interactive_shell,
+        create::{find_branch_checkout, find_worktree_metadata_name, forget_stack_worktree},
+        hooks::run_hook,
+        interactive::{last_commit_time, summarize_worktree},
+        list::{
+            find_worktrees, format_worktree, match_worktree_name, matches_last_segments,
+            worktree_branch,
+        },
+        pr_github::{CommandRunner, SystemCommandRunner},
+        prompt::confirm,
+    },
+};
+
+mod hooks;
+
+pub(crate) use hooks::{RemoveHooks, load_remove_hooks};
 
 #[cfg(test)]
 use crate::commands::cd::SHELL_OVERRIDE_ENV;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct RemoveCommand {
     name: String,
     force: bool,
     quiet: bool,
     remove_local_branch: bool,
+    remove_remote_branch: bool,
     spawn_shell: bool,
+    confirmed: bool,
+    prompt: bool,
+    assume_yes: bool,
+    prune_branches: bool,
+    wip: bool,
+    keep_files: bool,
+    json_lines: bool,
+    /// How to get past the uncommitted-changes check, for callers without `--force`/`--wip`.
+    dirty_hint: Option<String>,
+    /// Asks the `[y/N]` questions; tests answer them without a terminal.
+    confirm: fn(&str) -> color_eyre::Result<bool>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,10 +56,29 @@ pub enum LocalBranchStatus {
     NotFound,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteBranchStatus {
+    Deleted,
+    NotFound,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RemoveOutcome {
     pub local_branch: Option<LocalBranchStatus>,
+    pub remote_branch: Option<RemoteBranchStatus>,
     pub repositioned: bool,
+    /// False when there was no such worktree to remove, or removing it was declined.
+    pub removed: bool,
+}
+
+impl RemoveOutcome {
+    fn batch_outcome(&self) -> BatchOutcome {
+        if self.removed {
+            BatchOutcome::Succeeded
+        } else {
+            BatchOutcome::Skipped
+        }
+    }
 }
 
 impl RemoveCommand {
@@ -38,10 +88,52 @@ impl RemoveCommand {
             force,
             quiet: false,
             remove_local_branch: false,
+            remove_remote_branch: false,
             spawn_shell: true,
+            confirmed: false,
+            prompt: false,
+            assume_yes: false,
+            prune_branches: false,
+            wip: false,
+            keep_files: false,
+            json_lines: false,
+            dirty_hint: None,
+            confirm,
         }
     }
 
+    /// Marks the removal as already confirmed, e.g. by a TUI dialog.
+    pub fn with_confirmed(mut self, confirmed: bool) -> Self {
+        self.confirmed = confirmed;
+        self
+    }
+
+    /// Allows asking on stdin before removing a worktree, and before removing the one the shell
+    /// is currently in.
+    pub fn with_prompt(mut self, prompt: bool) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Skips the `Remove worktree ...? [y/N]` question asked when prompting is allowed.
+    pub fn with_assume_yes(mut self, assume_yes: bool) -> Self {
+        self.assume_yes = assume_yes;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_confirm(mut self, confirm: fn(&str) -> color_eyre::Result<bool>) -> Self {
+        self.confirm = confirm;
+        self
+    }
+
+    /// After removal, offers to delete local branches whose upstream is gone and that have no
+    /// worktree.
+    pub fn with_prune_branches(mut self, prune: bool) -> Self {
+        self.prune_branches = prune;
+        self
+    }
+
     pub fn with_quiet(mut self, quiet: bool) -> Self {
         self.quiet = quiet;
         self
@@ -52,11 +144,42 @@ impl RemoveCommand {
         self
     }
 
+    /// Also delete the branch from `origin` with `git push origin --delete`.
+    pub fn with_remove_remote_branch(mut self, remove: bool) -> Self {
+        self.remove_remote_branch = remove;
+        self
+    }
+
     pub fn with_spawn_shell(mut self, spawn: bool) -> Self {
         self.spawn_shell = spawn;
         self
     }
 
+    /// Commits uncommitted changes to the worktree's branch before removing it.
+    pub fn with_wip(mut self, wip: bool) -> Self {
+        self.wip = wip;
+        self
+    }
+
+    /// Unregisters the worktree but leaves its files in place as a plain directory.
+    pub fn with_keep_files(mut self, keep_files: bool) -> Self {
+        self.keep_files = keep_files;
+        self
+    }
+
+    /// With several worktrees, print one JSON object per worktree on stdout instead of the usual
+    /// messages and summary.
+    pub fn with_json_lines(mut self, json_lines: bool) -> Self {
+        self.json_lines = json_lines;
+        self
+    }
+
+    /// Replaces the `--force`/`--wip` advice in the uncommitted-changes error with `hint`.
+    pub fn with_dirty_hint(mut self, hint: impl Into<String>) -> Self {
+        self.dirty_hint = Some(hint.into());
+        self
+    }
+
     pub fn execute(&self, repo: &Repo) -> color_eyre::Result<RemoveOutcome> {
         let worktrees_dir = repo.worktrees_dir();
         if !worktrees_dir.exists() {
@@ -76,13 +199,36 @@ impl RemoveCommand {
                 local_branch: self
                     .remove_local_branch
                     .then_some(LocalBranchStatus::NotFound),
+                remote_branch: None,
                 repositioned: false,
+                removed: false,
             });
         }
 
         let worktree_path = worktrees_dir.join(&self.name);
         let worktree_path = fs::canonicalize(&worktree_path).unwrap_or(worktree_path);
 
+        if !worktree_path.exists()
+            && let Some(matched) = match_worktree_name(&worktrees_dir, &self.name)?
+        {
+            // A partial match is only trusted when someone confirms the removal, so a typo in a
+            // script cannot remove an unrelated worktree.
+            let confirmable = self.assume_yes || self.confirmed || (self.prompt && !self.quiet);
+            if !matches_last_segments(&matched, &self.name) && !confirmable {
+                return Err(eyre::eyre!(
+                    "`{}` only partly matches worktree `{}`; pass its full name, or `--yes` to remove it anyway",
+                    self.name,
+                    matched
+                ));
+            }
+            if !self.quiet {
+                println!("Matched `{}` to worktree `{}`.", self.name, matched);
+            }
+            let mut command = self.clone();
+            command.name = matched;
+            return command.execute(repo);
+        }
+
         if !worktree_path.exists() {
             let name = format!(
                 "{}",
@@ -101,12 +247,14 @@ impl RemoveCommand {
                 local_branch: self
                     .remove_local_branch
                     .then_some(LocalBranchStatus::NotFound),
+                remote_branch: None,
                 repositioned: false,
+                removed: false,
             });
         }
 
         let git_repo = repo.git();
-        let worktree_name = match find_worktree_name(git_repo, &worktree_path)? {
+        let worktree_name = match find_worktree_metadata_name(git_repo, &worktree_path)? {
             Some(name) => name,
             None => {
                 let name = format!(
@@ -126,18 +274,78 @@ impl RemoveCommand {
                     local_branch: self
                         .remove_local_branch
                         .then_some(LocalBranchStatus::NotFound),
+                    remote_branch: None,
                     repositioned: false,
+                    removed: false,
                 });
             }
         };
 
+        let asked = self.prompt && !self.confirmed && !self.assume_yes && !self.quiet;
+        if asked && !self.confirm_removal()? {
+            println!("Kept worktree `{}`.", self.name);
+            return Ok(RemoveOutcome {
+                local_branch: None,
+                remote_branch: None,
+                repositioned: false,
+                removed: false,
+            });
+        }
+
+        let need_reposition = !self.keep_files
+            && match std::env::current_dir() {
+                Ok(dir) => {
+                    let canonical = fs::canonicalize(&dir).unwrap_or(dir.clone());
+                    canonical.starts_with(&worktree_path)
+                }
+                Err(_) => true,
+            };
+
+        // Someone who just answered `y` is not asked a second time about the current directory.
+        if need_reposition
+            && !self.force
+            && !self.confirmed
+            && !asked
+            && !self.confirm_current_removal()?
+        {
+            return Err(eyre::eyre!(
+                "refusing to remove worktree `{}` because the current directory is inside it; pass `--force` to remove it anyway",
+                self.name
+            ));
+        }
+
+        if self.wip {
+            self.commit_work_in_progress(&worktree_path)?;
+        }
+
+        if !self.force && !self.keep_files {
+            self.ensure_clean(&worktree_path)?;
+        }
+
+        // The directory name and branch differ for worktrees created with `create --branch`.
+        let branch = worktree_branch(&worktree_path).unwrap_or_else(|| self.name.clone());
+
+        let hooks = load_remove_hooks(repo)?;
+        let mut runner = SystemCommandRunner::default();
+        if let Some(hook) = hooks.pre_remove.as_deref() {
+            run_hook(
+                &mut runner,
+                "pre-remove",
+                hook,
+                &worktree_path,
+                &[],
+                self.quiet,
+            )
+            .wrap_err_with(|| eyre::eyre!("worktree `{}` was not removed", self.name))?;
+        }
+
         let worktree = git_repo.find_worktree(&worktree_name).wrap_err_with(|| {
             eyre::eyre!("failed to load git worktree metadata for `{}`", self.name)
         })?;
 
         let mut prune_opts = WorktreePruneOptions::new();
         prune_opts.valid(true);
-        prune_opts.working_tree(true);
+        prune_opts.working_tree(!self.keep_files);
         if self.force {
             prune_opts.locked(true);
         }
@@ -148,43 +356,80 @@ impl RemoveCommand {
 
         drop(worktree);
 
-        if worktree_path.exists() {
-            fs::remove_dir_all(&worktree_path).wrap_err_with(|| {
-                eyre::eyre!(
-                    "failed to clean worktree directory `{}`",
-                    worktree_path.display()
-                )
-            })?;
-        }
-
         let name = format!(
             "{}",
             self.name
                 .as_str()
                 .if_supports_color(Stream::Stdout, |text| format!("{}", text.red().bold()))
         );
-        if !self.quiet {
-            println!(
-                "Removed worktree `{}` from `{}`.",
-                name,
-                worktrees_dir.display()
-            );
-        }
 
-        let need_reposition = match std::env::current_dir() {
-            Ok(dir) => {
-                let canonical = fs::canonicalize(&dir).unwrap_or(dir.clone());
-                canonical.starts_with(&worktree_path)
+        if self.keep_files {
+            // The `.git` file points at the metadata pruned above; drop it so the directory is
+            // plain files rather than a broken worktree.
+            let git_file = worktree_path.join(".git");
+            if git_file.is_file() {
+                fs::remove_file(&git_file)
+                    .wrap_err_with(|| eyre::eyre!("failed to remove `{}`", git_file.display()))?;
             }
-            Err(_) => true,
-        };
+            if !self.quiet {
+                println!(
+                    "Unregistered worktree `{}`; its files remain at `{}` and are no longer a git worktree.",
+                    name,
+                    worktree_path.display()
+                );
+            }
+        } else {
+            if worktree_path.exists() {
+                fs::remove_dir_all(&worktree_path).wrap_err_with(|| {
+                    eyre::eyre!(
+                        "failed to clean worktree directory `{}`",
+                        worktree_path.display()
+                    )
+                })?;
+            }
+
+            if !self.quiet {
+                println!(
+                    "Removed worktree `{}` from `{}`.",
+                    name,
+                    worktrees_dir.display()
+                );
+            }
+        }
+
+        if let Some(hook) = hooks.post_remove.as_deref()
+            && let Err(err) = run_hook(
+                &mut runner,
+                "post-remove",
+                hook,
+                repo.root(),
+                &[],
+                self.quiet,
+            )
+        {
+            eprintln!("Warning: {err:#}");
+        }
+
+        if let Err(err) = forget_stack_worktree(repo, &self.name) {
+            eprintln!("Warning: {err:#}");
+        }
 
         let local_branch = if self.remove_local_branch {
-            Some(self.delete_local_branch(repo)?)
+            Some(self.delete_local_branch(repo, &branch)?)
         } else {
             None
         };
 
+        let remote_branch = if self.remove_remote_branch {
+            Some(self.delete_remote_branch(&mut runner, repo, &branch)?)
+        } else {
+            None
+        };
+
+        if self.prune_branches {
+            self.prune_gone_branches(repo)?;
+        }
+
         if need_reposition {
             std::env::set_current_dir(repo.root()).wrap_err_with(|| {
                 eyre::eyre!(
@@ -205,43 +450,286 @@ impl RemoveCommand {
             }
 
             if self.spawn_shell {
-                let (program, args) = shell_command();
-                let status = Command::new(&program)
-                    .args(args)
-                    .current_dir(repo.root())
-                    .env("PWD", logical_pwd(repo.root()))
-                    .status()
-                    .wrap_err("failed to spawn root shell")?;
-
-                if !status.success() {
-                    return Err(eyre::eyre!("subshell exited with a non-zero status"));
-                }
+                run_interactive_shell(repo.root(), &logical_pwd(repo.root()), &[], None)?;
             }
         }
 
         Ok(RemoveOutcome {
             local_branch,
+            remote_branch,
             repositioned: need_reposition,
+            removed: true,
         })
     }
 
-    fn delete_local_branch(&self, repo: &Repo) -> color_eyre::Result<LocalBranchStatus> {
+    /// Removes every worktree under `.rsworktree`. See [`RemoveCommand::execute_many`].
+    pub fn execute_all(&self, repo: &Repo) -> color_eyre::Result<()> {
+        let worktrees_dir = repo.worktrees_dir();
+        let names = if worktrees_dir.exists() {
+            find_worktrees(&worktrees_dir)?
+                .iter()
+                .map(|path| format_worktree(path))
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+        if names.is_empty() {
+            if !self.quiet && !self.json_lines {
+                println!("No worktrees to remove.");
+            }
+            return Ok(());
+        }
+        self.execute_many(repo, &names)
+    }
+
+    /// Removes each of `names` with this command's options, ignoring its own name, and prints a
+    /// summary. A failure does not stop the remaining removals. Returning to the root and
+    /// spawning a shell there, and `--prune-branches`, happen once at the end rather than per
+    /// worktree.
+    pub fn execute_many(&self, repo: &Repo, names: &[String]) -> color_eyre::Result<()> {
+        if self.json_lines && self.prompt && !self.assume_yes && !self.confirmed {
+            return Err(eyre::eyre!(
+                "`--json-lines` cannot prompt before removing worktrees; pass `--yes`"
+            ));
+        }
+
+        let mut reporter =
+            BatchReporter::new("worktree", "removed").with_json_lines(self.json_lines);
+        let mut failed = Vec::new();
+        let mut repositioned = false;
+        for name in names {
+            let mut command = self
+                .clone()
+                .with_spawn_shell(false)
+                .with_prune_branches(false);
+            command.quiet |= self.json_lines;
+            command.name = name.clone();
+            match command.execute(repo) {
+                Ok(outcome) => {
+                    repositioned |= outcome.repositioned;
+                    reporter.count(name, outcome.batch_outcome());
+                }
+                Err(err) => {
+                    reporter.record(
+                        name,
+                        BatchOutcome::Failed,
+                        format!("Failed to remove worktree `{name}`: {err:#}"),
+                    );
+                    failed.push(name.as_str());
+                }
+            }
+        }
+
+        if self.prune_branches {
+            self.prune_gone_branches(repo)?;
+        }
+        if !self.quiet {
+            reporter.print_summary("Worktrees");
+        }
+        if repositioned && self.spawn_shell {
+            run_interactive_shell(repo.root(), &logical_pwd(repo.root()), &[], None)?;
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre::eyre!(
+                "failed to remove {}: {}",
+                if failed.len() == 1 {
+                    "1 worktree".to_owned()
+                } else {
+                    format!("{} worktrees", failed.len())
+                },
+                failed.join(", ")
+            ))
+        }
+    }
+
+    /// Refuses to remove a worktree with staged, unstaged, untracked or conflicted files, since
+    /// pruning it would discard them.
+    fn ensure_clean(&self, worktree_path: &Path) -> color_eyre::Result<()> {
+        // A worktree git cannot open has no status to check; let the prune deal with it.
+        let Ok(worktree_repo) = git2::Repository::open(worktree_path) else {
+            return Ok(());
+        };
+        let summary = summarize_worktree(&worktree_repo)
+            .ok_or_else(|| eyre::eyre!("failed to read status of `{}`", self.name))?;
+        if summary.clean {
+            return Ok(());
+        }
+
+        let changes = [
+            (summary.staged, "staged"),
+            (summary.unstaged, "unstaged"),
+            (summary.untracked, "untracked"),
+            (summary.conflicts, "conflicted"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+        let hint = self
+            .dirty_hint
+            .as_deref()
+            .unwrap_or("pass `--force` to remove it anyway, or `--wip` to commit them first");
+        Err(eyre::eyre!(
+            "worktree `{}` has uncommitted changes ({changes}); {hint}",
+            self.name
+        ))
+    }
+
+    fn commit_work_in_progress(&self, worktree_path: &Path) -> color_eyre::Result<()> {
+        let worktree_repo = git2::Repository::open(worktree_path).wrap_err_with(|| {
+            eyre::eyre!("failed to open worktree `{}`", worktree_path.display())
+        })?;
+
+        let mut status_opts = git2::StatusOptions::new();
+        status_opts
+            .include_untracked(true)
+            .recurse_untracked_dirs(true);
+        let statuses = worktree_repo
+            .statuses(Some(&mut status_opts))
+            .wrap_err_with(|| eyre::eyre!("failed to read status of `{}`", self.name))?;
+        if statuses.is_empty() {
+            return Ok(());
+        }
+
+        let head = worktree_repo
+            .head()
+            .wrap_err_with(|| eyre::eyre!("failed to resolve HEAD of `{}`", self.name))?;
+        let branch = match head.is_branch().then(|| head.shorthand()).flatten() {
+            Some(branch) => branch.to_owned(),
+            None => {
+                return Err(eyre::eyre!(
+                    "cannot save work in progress for `{}` because its HEAD is detached",
+                    self.name
+                ));
+            }
+        };
+
+        run_git(worktree_path, &["add", "--all"])?;
+        run_git(
+            worktree_path,
+            &[
+                "commit",
+                "--no-verify",
+                "-m",
+                &format!("WIP: saved before removing worktree `{}`", self.name),
+            ],
+        )?;
+
+        if !self.quiet {
+            let branch = format!(
+                "{}",
+                branch
+                    .as_str()
+                    .if_supports_color(Stream::Stdout, |text| format!("{}", text.cyan()))
+            );
+            println!("Committed work in progress to branch `{}`.", branch);
+        }
+
+        Ok(())
+    }
+
+    fn confirm_removal(&self) -> color_eyre::Result<bool> {
+        let name = format!(
+            "{}",
+            self.name
+                .as_str()
+                .if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow().bold()))
+        );
+        (self.confirm)(&format!("Remove worktree `{name}`?"))
+    }
+
+    fn confirm_current_removal(&self) -> color_eyre::Result<bool> {
+        if !self.prompt {
+            return Ok(false);
+        }
+
+        let name = format!(
+            "{}",
+            self.name
+                .as_str()
+                .if_supports_color(Stream::Stdout, |text| format!("{}", text.yellow().bold()))
+        );
+        (self.confirm)(&format!(
+            "You are currently inside worktree `{name}`. Remove it anyway?"
+        ))
+    }
+
+    fn confirm_branch_pruning(&self, branches: &[String]) -> color_eyre::Result<bool> {
+        println!("Local branches whose upstream is gone:");
+        for name in branches {
+            println!("  {name}");
+        }
+        (self.confirm)(&format!("Delete {} branch(es)?", branches.len()))
+    }
+
+    fn prune_gone_branches(&self, repo: &Repo) -> color_eyre::Result<()> {
+        let gone = find_gone_branches(repo)?;
+        if gone.is_empty() {
+            if !self.quiet {
+                println!("No local branches with a gone upstream to prune.");
+            }
+            return Ok(());
+        }
+
+        let approved = self.assume_yes || self.confirmed;
+        if !approved && (!self.prompt || self.quiet) {
+            if !self.quiet {
+                println!(
+                    "Kept {} local branch(es) whose upstream is gone; pass `--yes` to delete them without asking.",
+                    gone.len()
+                );
+            }
+            return Ok(());
+        }
+        if !approved && !self.confirm_branch_pruning(&gone)? {
+            if !self.quiet {
+                println!("Kept {} local branch(es).", gone.len());
+            }
+            return Ok(());
+        }
+
+        let git_repo = repo.git();
+        for name in &gone {
+            Self::force_delete_reference(git_repo, name)?;
+            if !self.quiet {
+                let branch_label = format!(
+                    "{}",
+                    name.as_str().if_supports_color(Stream::Stdout, |text| {
+                        format!("{}", text.magenta().bold())
+                    })
+                );
+                println!("Deleted local branch `{}`.", branch_label);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn delete_local_branch(
+        &self,
+        repo: &Repo,
+        branch_name: &str,
+    ) -> color_eyre::Result<LocalBranchStatus> {
         let git_repo = repo.git();
-        match git_repo.find_branch(&self.name, BranchType::Local) {
+        match git_repo.find_branch(branch_name, BranchType::Local) {
             Ok(mut branch) => {
                 if self.force {
                     drop(branch);
-                    Self::force_delete_reference(git_repo, &self.name)?;
+                    Self::force_delete_reference(git_repo, branch_name)?;
                 } else {
                     match branch.delete() {
                         Ok(()) => {}
                         Err(err) => {
                             drop(branch);
-                            Self::force_delete_reference(git_repo, &self.name).wrap_err_with(
+                            Self::force_delete_reference(git_repo, branch_name).wrap_err_with(
                                 || {
                                     eyre::eyre!(
                                         "failed to delete local branch `{}` ({}).",
-                                        self.name,
+                                        branch_name,
                                         err
                                     )
                                 },
@@ -253,11 +741,9 @@ impl RemoveCommand {
                 if !self.quiet {
                     let branch_label = format!(
                         "{}",
-                        self.name
-                            .as_str()
-                            .if_supports_color(Stream::Stdout, |text| {
-                                format!("{}", text.magenta().bold())
-                            })
+                        branch_name.if_supports_color(Stream::Stdout, |text| {
+                            format!("{}", text.magenta().bold())
+                        })
                     );
                     println!("Deleted local branch `{}`.", branch_label);
                 }
@@ -267,11 +753,9 @@ impl RemoveCommand {
                 if !self.quiet {
                     let branch_label = format!(
                         "{}",
-                        self.name
-                            .as_str()
-                            .if_supports_color(Stream::Stdout, |text| {
-                                format!("{}", text.magenta())
-                            })
+                        branch_name.if_supports_color(Stream::Stdout, |text| {
+                            format!("{}", text.magenta())
+                        })
                     );
                     println!(
                         "Local branch `{}` not found; skipping removal.",
@@ -282,11 +766,58 @@ impl RemoveCommand {
             }
             Err(err) => Err(eyre::eyre!(
                 "failed to look up local branch `{}`: {err}",
-                self.name
+                branch_name
             )),
         }
     }
 
+    fn delete_remote_branch<R: CommandRunner>(
+        &self,
+        runner: &mut R,
+        repo: &Repo,
+        branch_name: &str,
+    ) -> color_eyre::Result<RemoteBranchStatus> {
+        let args = vec![
+            "push".to_owned(),
+            "origin".to_owned(),
+            "--delete".to_owned(),
+            branch_name.to_owned(),
+        ];
+        let output = runner
+            .run("git", repo.root(), &args)
+            .wrap_err("failed to delete remote branch with `git push`")?;
+
+        let branch_label = format!(
+            "{}",
+            branch_name.if_supports_color(Stream::Stdout, |text| {
+                format!("{}", text.magenta().bold())
+            })
+        );
+        if output.success {
+            if !self.quiet {
+                println!("Deleted remote branch `{}`.", branch_label);
+            }
+            return Ok(RemoteBranchStatus::Deleted);
+        }
+
+        let combined = format!("{}{}", output.stderr, output.stdout).to_lowercase();
+        if combined.contains("remote ref does not exist") {
+            if !self.quiet {
+                println!(
+                    "Remote branch `{}` not found; skipping removal.",
+                    branch_label
+                );
+            }
+            return Ok(RemoteBranchStatus::NotFound);
+        }
+
+        Err(eyre::eyre!(
+            "failed to delete remote branch `{}`: {}",
+            branch_name,
+            output.stderr.trim()
+        ))
+    }
+
     fn force_delete_reference(repo: &git2::Repository, name: &str) -> color_eyre::Result<()> {
         let full_ref = format!("refs/heads/{name}");
         match repo.find_reference(&full_ref) {
@@ -299,37 +830,112 @@ impl RemoveCommand {
     }
 }
 
-fn find_worktree_name(
-    repo: &git2::Repository,
-    worktree_path: &Path,
-) -> color_eyre::Result<Option<String>> {
-    let target = worktree_path
-        .canonicalize()
-        .unwrap_or_else(|_| worktree_path.to_path_buf());
-
-    let names = repo
-        .worktrees()
-        .wrap_err("failed to list repository worktrees")?;
-
-    for name in names.iter().flatten() {
-        let worktree = match repo.find_worktree(name) {
-            Ok(worktree) => worktree,
-            Err(err) if err.code() == ErrorCode::NotFound => continue,
+/// Local branches that track an upstream which no longer exists (`[gone]` in `git branch -vv`)
+/// and are not checked out in the main repository or any worktree.
+pub(crate) fn find_gone_branches(repo: &Repo) -> color_eyre::Result<Vec<String>> {
+    let git_repo = repo.git();
+    let config = git_repo
+        .config()
+        .wrap_err("failed to read repository config")?;
+    let branches = git_repo
+        .branches(Some(BranchType::Local))
+        .wrap_err("failed to list local branches")?;
+
+    let mut gone = Vec::new();
+    for entry in branches {
+        let (branch, _) = entry.wrap_err("failed to read local branch")?;
+        let Some(name) = branch.name().ok().flatten() else {
+            continue;
+        };
+
+        if config.get_string(&format!("branch.{name}.merge")).is_err() {
+            continue;
+        }
+
+        match branch.upstream() {
+            Ok(_) => continue,
+            Err(err) if err.code() == ErrorCode::NotFound => {}
             Err(err) => {
-                return Err(eyre::eyre!("failed to open git worktree `{name}`: {err}"));
+                return Err(eyre::eyre!(
+                    "failed to resolve upstream of branch `{name}`: {err}"
+                ));
             }
-        };
+        }
+
+        if find_branch_checkout(git_repo, repo.root(), name)?.is_some() {
+            continue;
+        }
+
+        gone.push(name.to_owned());
+    }
+
+    Ok(gone)
+}
+
+/// Which end of the worktrees ordered by last commit time `rm --oldest`/`--newest` picks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitAge {
+    Oldest,
+    Newest,
+}
 
-        let path = worktree
-            .path()
-            .canonicalize()
-            .unwrap_or_else(|_| worktree.path().to_path_buf());
-        if path == target {
-            return Ok(Some(name.to_owned()));
+impl CommitAge {
+    fn label(self) -> &'static str {
+        match self {
+            CommitAge::Oldest => "oldest",
+            CommitAge::Newest => "newest",
         }
     }
+}
+
+/// The managed worktree whose HEAD commit is the oldest or newest; ties go to the first name.
+/// Worktrees whose HEAD cannot be read are ignored.
+pub fn find_worktree_by_commit_age(
+    repo: &Repo,
+    age: CommitAge,
+) -> color_eyre::Result<Option<String>> {
+    let worktrees_dir = repo.worktrees_dir();
+    if !worktrees_dir.exists() {
+        return Ok(None);
+    }
 
-    Ok(None)
+    let dated = find_worktrees(&worktrees_dir)?
+        .into_iter()
+        .filter_map(|worktree| {
+            let time = last_commit_time(&worktrees_dir.join(&worktree))?;
+            Some((time, format_worktree(&worktree)))
+        });
+    let selected = match age {
+        CommitAge::Oldest => dated.min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1))),
+        CommitAge::Newest => dated.min_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1))),
+    };
+    Ok(selected.map(|(_, name)| name))
+}
+
+/// Asks before removing the worktree picked by `--oldest`/`--newest`.
+pub fn confirm_removal_by_age(name: &str, age: CommitAge) -> color_eyre::Result<bool> {
+    confirm(&format!(
+        "Remove worktree `{name}`, the one with the {} last commit?",
+        age.label()
+    ))
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> color_eyre::Result<()> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .wrap_err("failed to run `git`")?;
+
+    if !output.status.success() {
+        return Err(eyre::eyre!(
+            "`git {}` failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
 }
 
 fn logical_pwd(path: &Path) -> std::ffi::OsString {
@@ -353,27 +959,7 @@ mod tests {
 
     use tempfile::TempDir;
 
-    use crate::{Repo, commands::create::CreateCommand};
-
-    fn init_git_repo(dir: &TempDir) -> color_eyre::Result<()> {
-        run(dir, ["git", "init"])?;
-        fs::write(dir.path().join("README.md"), "test")?;
-        run(dir, ["git", "add", "README.md"])?;
-        run(
-            dir,
-            [
-                "git",
-                "-c",
-                "user.name=Test",
-                "-c",
-                "user.email=test@example.com",
-                "commit",
-                "-m",
-                "Initial commit",
-            ],
-        )?;
-        Ok(())
-    }
+    use crate::{Repo, commands::create::CreateCommand, test_support::init_repo};
 
     fn run(dir: &TempDir, cmd: impl IntoIterator<Item = &'static str>) -> color_eyre::Result<()> {
         let mut iter = cmd.into_iter();
@@ -407,11 +993,146 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn keep_files_unregisters_worktree_but_leaves_directory() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature/keep".into(), None).create_without_enter(&repo, true)?;
+        let worktree_path = repo.worktrees_dir().join("feature/keep");
+        fs::write(worktree_path.join("notes.txt"), "keep me")?;
+
+        let command = RemoveCommand::new("feature/keep".into(), false)
+            .with_keep_files(true)
+            .with_quiet(true);
+        let _ = command.execute(&repo)?;
+
+        assert_eq!(
+            fs::read_to_string(worktree_path.join("notes.txt"))?,
+            "keep me"
+        );
+        assert!(!worktree_path.join(".git").exists());
+        let registered = repo.git().worktrees()?;
+        assert!(
+            registered.iter().flatten().next().is_none(),
+            "worktree should be unregistered: {:?}",
+            registered.iter().collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_name_match_needs_confirmation() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("bugfix/api-crash".into(), None).create_without_enter(&repo, true)?;
+        let worktree_path = repo.worktrees_dir().join("bugfix/api-crash");
+
+        let err = RemoveCommand::new("a".into(), false)
+            .with_quiet(true)
+            .execute(&repo)
+            .expect_err("`a` is only a substring of the worktree name");
+        assert_eq!(
+            err.to_string(),
+            "`a` only partly matches worktree `bugfix/api-crash`; pass its full name, or `--yes` to remove it anyway"
+        );
+        assert!(worktree_path.exists());
+
+        let outcome = RemoveCommand::new("a".into(), false)
+            .with_quiet(true)
+            .with_assume_yes(true)
+            .execute(&repo)?;
+        assert!(outcome.removed);
+        assert!(!worktree_path.exists());
+
+        CreateCommand::new("bugfix/api-crash".into(), None).create_without_enter(&repo, true)?;
+        let outcome = RemoveCommand::new("api-crash".into(), false)
+            .with_quiet(true)
+            .execute(&repo)?;
+        assert!(outcome.removed, "a whole trailing segment is trusted");
+
+        Ok(())
+    }
+
+    #[test]
+    fn assume_yes_removes_without_asking() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature/yes".into(), None).create_without_enter(&repo, true)?;
+
+        let outcome = RemoveCommand::new("feature/yes".into(), false)
+            .with_prompt(true)
+            .with_assume_yes(true)
+            .with_confirm(|question| panic!("asked `{question}` despite --yes"))
+            .with_spawn_shell(false)
+            .execute(&repo)?;
+
+        assert!(outcome.removed);
+        assert!(!repo.worktrees_dir().join("feature/yes").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn declined_removals_are_kept_and_skipped() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        for name in ["feature/one", "feature/two"] {
+            CreateCommand::new(name.into(), None).create_without_enter(&repo, true)?;
+        }
+        let command = RemoveCommand::new(String::new(), false)
+            .with_prompt(true)
+            .with_confirm(|_| Ok(false))
+            .with_spawn_shell(false);
+
+        let mut single = command.clone();
+        single.name = "feature/one".into();
+        let outcome = single.execute(&repo)?;
+        assert!(!outcome.removed);
+        assert_eq!(outcome.batch_outcome(), BatchOutcome::Skipped);
+
+        command.execute_many(&repo, &["feature/one".into(), "feature/two".into()])?;
+        assert!(repo.worktrees_dir().join("feature/one").exists());
+        assert!(repo.worktrees_dir().join("feature/two").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_dirty_worktree_unless_forced() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        CreateCommand::new("feature/dirty".into(), None).create_without_enter(&repo, true)?;
+        let worktree_path = repo.worktrees_dir().join("feature/dirty");
+        fs::write(worktree_path.join("README.md"), "changed")?;
+        fs::write(worktree_path.join("scratch.txt"), "draft")?;
+        fs::write(worktree_path.join("staged.txt"), "staged")?;
+        run_in(&worktree_path, ["git", "add", "staged.txt"])?;
+
+        let err = RemoveCommand::new("feature/dirty".into(), false)
+            .with_quiet(true)
+            .execute(&repo)
+            .expect_err("dirty worktree should be kept");
+        assert_eq!(
+            err.to_string(),
+            "worktree `feature/dirty` has uncommitted changes (1 staged, 1 unstaged, 1 untracked); pass `--force` to remove it anyway, or `--wip` to commit them first"
+        );
+        assert_eq!(
+            fs::read_to_string(worktree_path.join("scratch.txt"))?,
+            "draft"
+        );
+
+        let outcome = RemoveCommand::new("feature/dirty".into(), true)
+            .with_quiet(true)
+            .execute(&repo)?;
+        assert!(outcome.removed);
+        assert!(!worktree_path.exists());
+
+        Ok(())
+    }
+
     #[test]
     fn reports_missing_worktree_directory() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
-        let repo = Repo::discover_from(dir.path())?;
+        let repo = init_repo(&dir)?;
 
         let command = RemoveCommand::new("feature/test".into(), false);
         let _ = command.execute(&repo)?;
@@ -430,8 +1151,7 @@ mod tests {
             }
         };
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
-        let repo = Repo::discover_from(dir.path())?;
+        let repo = init_repo(&dir)?;
 
         unsafe {
             std::env::set_var(SHELL_OVERRIDE_ENV, "env");
@@ -442,7 +1162,7 @@ mod tests {
         let worktree_path = repo.worktrees_dir().join("feature/local");
         std::env::set_current_dir(&worktree_path)?;
 
-        let command = RemoveCommand::new("feature/local".into(), false);
+        let command = RemoveCommand::new("feature/local".into(), false).with_confirmed(true);
         let outcome = command.execute(&repo)?;
         assert!(
             outcome.repositioned,
@@ -464,8 +1184,7 @@ mod tests {
     #[test]
     fn deletes_local_branch_when_requested() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
-        let repo = Repo::discover_from(dir.path())?;
+        let repo = init_repo(&dir)?;
 
         let create = CreateCommand::new("feature/local".into(), None);
         create.create_without_enter(&repo, true)?;
@@ -493,11 +1212,130 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn deletes_checked_out_branch_when_it_differs_from_worktree_name() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+
+        CreateCommand::new("review".into(), None).create_without_enter(&repo, true)?;
+        CreateCommand::new("other".into(), None)
+            .with_branch(Some("teammate/feature".into()))
+            .create_without_enter(&repo, true)?;
+
+        let outcome = RemoveCommand::new("other".into(), false)
+            .with_quiet(true)
+            .with_remove_local_branch(true)
+            .execute(&repo)?;
+
+        assert_eq!(outcome.local_branch, Some(LocalBranchStatus::Deleted));
+        let branch = repo
+            .git()
+            .find_branch("teammate/feature", BranchType::Local);
+        assert!(matches!(branch, Err(err) if err.code() == ErrorCode::NotFound));
+        assert!(
+            repo.git().find_branch("review", BranchType::Local).is_ok(),
+            "unrelated branches must be kept"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn finds_branches_whose_upstream_is_gone() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        init_repo(&dir)?;
+        let remote = TempDir::new()?;
+        run(&remote, ["git", "init", "--bare"])?;
+        let remote_path = remote.path().to_str().unwrap().to_owned();
+        let repo_path = dir.path();
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(["remote", "add", "origin", &remote_path])
+            .status()?;
+        assert!(status.success());
+
+        for branch in ["feature/gone", "feature/live", "feature/busy"] {
+            let status = Command::new("git")
+                .current_dir(repo_path)
+                .args(["branch", branch])
+                .status()?;
+            assert!(status.success());
+            let status = Command::new("git")
+                .current_dir(repo_path)
+                .args(["push", "-q", "-u", "origin", branch])
+                .status()?;
+            assert!(status.success());
+        }
+        run(&dir, ["git", "branch", "local-only"])?;
+
+        let repo = Repo::discover_from(repo_path)?;
+        CreateCommand::new("feature/busy".into(), None).create_without_enter(&repo, true)?;
+
+        for branch in ["feature/gone", "feature/busy"] {
+            let status = Command::new("git")
+                .current_dir(repo_path)
+                .args(["push", "-q", "origin", "--delete", branch])
+                .status()?;
+            assert!(status.success());
+        }
+        run(&dir, ["git", "fetch", "-q", "--prune", "origin"])?;
+
+        let repo = Repo::discover_from(repo_path)?;
+        assert_eq!(find_gone_branches(&repo)?, vec!["feature/gone".to_owned()]);
+
+        Ok(())
+    }
+
+    /// Adds `feature/gone`, tracking an `origin` branch that does not exist.
+    fn add_gone_branch(repo: &Repo) -> color_eyre::Result<()> {
+        let git = repo.git();
+        git.remote("origin", "https://example.invalid/repo.git")?;
+        let head = git.head()?.peel_to_commit()?;
+        git.branch("feature/gone", &head, false)?;
+        let mut config = git.config()?;
+        config.set_str("branch.feature/gone.remote", "origin")?;
+        config.set_str("branch.feature/gone.merge", "refs/heads/feature/gone")?;
+        assert_eq!(find_gone_branches(repo)?, vec!["feature/gone".to_owned()]);
+        Ok(())
+    }
+
+    #[test]
+    fn prune_branches_follows_yes_and_never_asks_without_prompt() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        add_gone_branch(&repo)?;
+        let gone_exists = || {
+            repo.git()
+                .find_branch("feature/gone", BranchType::Local)
+                .is_ok()
+        };
+
+        for name in ["one", "two"] {
+            CreateCommand::new(name.into(), None).create_without_enter(&repo, true)?;
+        }
+        RemoveCommand::new("one".into(), false)
+            .with_prune_branches(true)
+            .with_confirm(|question| panic!("asked `{question}` without a terminal"))
+            .with_spawn_shell(false)
+            .execute(&repo)?;
+        assert!(gone_exists(), "kept when nobody can confirm");
+
+        RemoveCommand::new("two".into(), false)
+            .with_prompt(true)
+            .with_assume_yes(true)
+            .with_prune_branches(true)
+            .with_confirm(|question| panic!("asked `{question}` despite --yes"))
+            .with_spawn_shell(false)
+            .execute(&repo)?;
+        assert!(!gone_exists());
+
+        Ok(())
+    }
+
     #[test]
     fn deletes_unmerged_local_branch_when_requested() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
-        let repo = Repo::discover_from(dir.path())?;
+        let repo = init_repo(&dir)?;
 
         let create = CreateCommand::new("feature/local".into(), None);
         create.create_without_enter(&repo, true)?;
@@ -536,10 +1374,62 @@ mod tests {
     }
 
     #[test]
-    fn keeps_local_branch_when_not_requested() -> color_eyre::Result<()> {
+    fn deletes_remote_branch_only_when_requested() -> color_eyre::Result<()> {
         let dir = TempDir::new()?;
-        init_git_repo(&dir)?;
+        init_repo(&dir)?;
+        let remote = TempDir::new()?;
+        run(&remote, ["git", "init", "--bare"])?;
+        let remote_path = remote.path().to_str().unwrap().to_owned();
+        let status = Command::new("git")
+            .current_dir(dir.path())
+            .args(["remote", "add", "origin", &remote_path])
+            .status()?;
+        assert!(status.success());
+
         let repo = Repo::discover_from(dir.path())?;
+        for name in ["feature/kept", "feature/gone"] {
+            CreateCommand::new(name.into(), None).create_without_enter(&repo, true)?;
+            let status = Command::new("git")
+                .current_dir(dir.path())
+                .args(["push", "-q", "origin", name])
+                .status()?;
+            assert!(status.success());
+        }
+        let remote_has = |branch: &str| -> color_eyre::Result<bool> {
+            let status = Command::new("git")
+                .current_dir(remote.path())
+                .args([
+                    "rev-parse",
+                    "--verify",
+                    "-q",
+                    &format!("refs/heads/{branch}"),
+                ])
+                .stdout(std::process::Stdio::null())
+                .status()?;
+            Ok(status.success())
+        };
+
+        let outcome = RemoveCommand::new("feature/kept".into(), false)
+            .with_quiet(true)
+            .execute(&repo)?;
+        assert_eq!(outcome.remote_branch, None);
+        assert!(remote_has("feature/kept")?);
+
+        let outcome = RemoveCommand::new("feature/gone".into(), false)
+            .with_quiet(true)
+            .with_remove_remote_branch(true)
+            .execute(&repo)?;
+        assert_eq!(outcome.remote_branch, Some(RemoteBranchStatus::Deleted));
+        assert!(!remote_has("feature/gone")?);
+        assert!(remote_has("feature/kept")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_local_branch_when_not_requested() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
 
         let create = CreateCommand::new("feature/local".into(), None);
         create.create_without_enter(&repo, true)?;
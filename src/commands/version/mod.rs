@@ -0,0 +1,136 @@
+use std::{env, path::Path};
+
+use color_eyre::eyre::WrapErr;
+
+use crate::commands::pr_github::{CommandRunner, SystemCommandRunner};
+
+#[derive(Debug)]
+pub struct VersionCommand<R = SystemCommandRunner> {
+    verbose: bool,
+    runner: R,
+}
+
+impl VersionCommand {
+    pub fn new() -> Self {
+        Self::with_runner(SystemCommandRunner::default())
+    }
+}
+
+impl<R> VersionCommand<R>
+where
+    R: CommandRunner,
+{
+    pub fn with_runner(runner: R) -> Self {
+        Self {
+            verbose: false,
+            runner,
+        }
+    }
+
+    /// Also report libgit2, the `git2` crate, and the `git`/`gh` found on `PATH`.
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    pub fn execute(&mut self) -> color_eyre::Result<()> {
+        let cwd = env::current_dir().wrap_err("failed to read current directory")?;
+        for line in self.lines(&cwd) {
+            println!("{line}");
+        }
+        Ok(())
+    }
+
+    fn lines(&mut self, cwd: &Path) -> Vec<String> {
+        let mut lines = vec![format!("rsworktree {}", env!("CARGO_PKG_VERSION"))];
+        if !self.verbose {
+            return lines;
+        }
+
+        let git2_version = git2::Version::get();
+        let (major, minor, patch) = git2_version.libgit2_version();
+        let vendored = if git2_version.vendored() {
+            " (vendored)"
+        } else {
+            ""
+        };
+        lines.push(format!("libgit2: {major}.{minor}.{patch}{vendored}"));
+        lines.push(format!("git2:    {}", git2_version.crate_version()));
+        lines.push(format!("git:     {}", self.tool_version("git", cwd)));
+        lines.push(format!("gh:      {}", self.tool_version("gh", cwd)));
+        lines
+    }
+
+    /// The version reported by `<program> --version`, or `not found` when it cannot be run.
+    fn tool_version(&mut self, program: &str, cwd: &Path) -> String {
+        let args = vec!["--version".to_owned()];
+        match self.runner.run(program, cwd, &args) {
+            Ok(output) if output.success => {
+                let first_line = output.stdout.lines().next().unwrap_or_default().trim();
+                let prefix = format!("{program} version ");
+                first_line
+                    .strip_prefix(&prefix)
+                    .unwrap_or(first_line)
+                    .to_owned()
+            }
+            _ => "not found".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use color_eyre::eyre;
+
+    use crate::commands::pr_github::CommandOutput;
+
+    struct StubRunner;
+
+    impl CommandRunner for StubRunner {
+        fn run(
+            &mut self,
+            program: &str,
+            _current_dir: &Path,
+            args: &[String],
+        ) -> color_eyre::Result<CommandOutput> {
+            assert_eq!(args, ["--version"]);
+            match program {
+                "git" => Ok(CommandOutput {
+                    stdout: "git version 2.45.1\n".into(),
+                    stderr: String::new(),
+                    success: true,
+                    status_code: Some(0),
+                }),
+                _ => Err(eyre::eyre!("failed to execute `{program}`")),
+            }
+        }
+    }
+
+    #[test]
+    fn prints_only_crate_version_by_default() {
+        let mut command = VersionCommand::with_runner(StubRunner);
+
+        assert_eq!(
+            command.lines(Path::new(".")),
+            vec![format!("rsworktree {}", env!("CARGO_PKG_VERSION"))]
+        );
+    }
+
+    #[test]
+    fn verbose_reports_libraries_and_tools() {
+        let mut command = VersionCommand::with_runner(StubRunner).with_verbose(true);
+        let lines = command.lines(Path::new("."));
+
+        let (major, minor, patch) = git2::Version::get().libgit2_version();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with(&format!("libgit2: {major}.{minor}.{patch}")));
+        assert_eq!(
+            lines[2],
+            format!("git2:    {}", git2::Version::get().crate_version())
+        );
+        assert_eq!(lines[3], "git:     2.45.1");
+        assert_eq!(lines[4], "gh:      not found");
+    }
+}
@@ -1,4 +1,9 @@
-use std::{ffi::OsStr, io, path::Path, process::{Command, Stdio}};
+use std::{
+    ffi::OsStr,
+    io,
+    path::Path,
+    process::{Command, Stdio},
+};
 
 use crate::telemetry::EditorLaunchStatus;
 
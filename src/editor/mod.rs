@@ -2,7 +2,7 @@ mod launch;
 mod preference;
 mod support;
 
-use std::path::Path;
+use std::{ffi::OsString, path::Path};
 
 use crate::{Repo, telemetry::EditorLaunchStatus};
 
@@ -12,22 +12,30 @@ pub use preference::{
     EditorPreferenceSource, PreferenceMissingReason, resolve_editor_preference,
 };
 
+pub(crate) use preference::FileEditorPreference;
 pub use support::{SupportedEditor, supported_editor_commands};
 
+/// Opens the worktree in the preferred editor. `extra_args` go after the preference's own args.
 pub fn launch_worktree(
     repo: &Repo,
     worktree_name: &str,
     worktree_path: &Path,
     wait_for_completion: bool,
+    extra_args: &[String],
 ) -> color_eyre::Result<LaunchOutcome> {
     let resolution = resolve_editor_preference(repo)?;
     let outcome = match resolution {
-        EditorPreferenceResolution::Found(preference) => launch_editor(LaunchRequest {
-            preference: &preference,
-            worktree_name,
-            worktree_path,
-            wait_for_completion,
-        }),
+        EditorPreferenceResolution::Found(mut preference) => {
+            preference
+                .args
+                .extend(extra_args.iter().map(OsString::from));
+            launch_editor(LaunchRequest {
+                preference: &preference,
+                worktree_name,
+                worktree_path,
+                wait_for_completion,
+            })
+        }
         EditorPreferenceResolution::Missing(reason) => missing_preference_outcome(reason),
     };
 
@@ -1,13 +1,12 @@
 use std::{
     env,
     ffi::OsString,
-    fs,
     path::{Path, PathBuf},
 };
 
 use serde::Deserialize;
 
-use crate::Repo;
+use crate::{Repo, repo::Preferences};
 
 pub const CONFIG_FILE_NAME: &str = "preferences.json";
 
@@ -58,21 +57,16 @@ pub enum PreferenceMissingReason {
     },
 }
 
+/// The `editor` section of the preferences file.
 #[derive(Debug, Deserialize)]
-struct FileFormat {
-    #[serde(default)]
-    editor: Option<FileEditorPreference>,
-}
-
-#[derive(Debug, Deserialize)]
-struct FileEditorPreference {
+pub(crate) struct FileEditorPreference {
     command: String,
     #[serde(default)]
     args: Vec<String>,
 }
 
 pub fn resolve_editor_preference(repo: &Repo) -> color_eyre::Result<EditorPreferenceResolution> {
-    let config_path = repo.worktrees_dir().join(CONFIG_FILE_NAME);
+    let config_path = repo.preferences_path();
 
     if config_path.exists() {
         match load_from_config(&config_path) {
@@ -108,23 +102,13 @@ pub fn resolve_editor_preference(repo: &Repo) -> color_eyre::Result<EditorPrefer
 }
 
 fn load_from_config(path: &Path) -> Result<Option<EditorPreference>, PreferenceMissingReason> {
-    let text = match fs::read_to_string(path) {
-        Ok(text) => text,
-        Err(error) => {
-            return Err(PreferenceMissingReason::ConfigInvalid {
-                path: path.to_path_buf(),
-                error: error.to_string(),
-            });
-        }
-    };
-
-    let parsed: FileFormat =
-        serde_json::from_str(&text).map_err(|error| PreferenceMissingReason::ConfigInvalid {
+    let preferences =
+        Preferences::load(path).map_err(|error| PreferenceMissingReason::ConfigInvalid {
             path: path.to_path_buf(),
-            error: error.to_string(),
+            error: error.root_cause().to_string(),
         })?;
 
-    let Some(editor) = parsed.editor else {
+    let Some(editor) = preferences.editor else {
         return Ok(None);
     };
 
@@ -313,7 +297,10 @@ mod tests {
         match result {
             Ok(Some(pref)) => {
                 assert_eq!(pref.command, OsString::from("vim"));
-                assert_eq!(pref.args, vec![OsString::from("-u"), OsString::from("NONE")]);
+                assert_eq!(
+                    pref.args,
+                    vec![OsString::from("-u"), OsString::from("NONE")]
+                );
             }
             other => panic!("expected Some preference, got: {other:?}"),
         }
@@ -372,10 +359,11 @@ mod tests {
             return Ok(None);
         }
 
-        let parts = shell_words::split(value).map_err(|error| PreferenceMissingReason::EnvInvalid {
-            variable,
-            error: error.to_string(),
-        })?;
+        let parts =
+            shell_words::split(value).map_err(|error| PreferenceMissingReason::EnvInvalid {
+                variable,
+                error: error.to_string(),
+            })?;
 
         if parts.is_empty() {
             return Ok(None);
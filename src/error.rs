@@ -0,0 +1,109 @@
+use std::{fmt, io, path::PathBuf};
+
+use color_eyre::eyre::Report;
+
+/// A managed worktree name that does not resolve under `.rsworktree`.
+#[derive(Debug)]
+pub struct WorktreeNotFound {
+    pub name: String,
+    pub worktrees_dir: PathBuf,
+}
+
+impl WorktreeNotFound {
+    pub fn new(name: impl Into<String>, worktrees_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            worktrees_dir: worktrees_dir.into(),
+        }
+    }
+}
+
+impl fmt::Display for WorktreeNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "worktree `{}` does not exist under `{}`",
+            self.name,
+            self.worktrees_dir.display()
+        )
+    }
+}
+
+impl std::error::Error for WorktreeNotFound {}
+
+/// A command run on the user's behalf (e.g. `rsworktree cd <name> -- <command>`) that exited
+/// non-zero; `rsworktree` exits with the same `code`.
+#[derive(Debug)]
+pub struct CommandExited {
+    pub program: String,
+    pub code: i32,
+}
+
+impl fmt::Display for CommandExited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` exited with status {}", self.program, self.code)
+    }
+}
+
+impl std::error::Error for CommandExited {}
+
+/// Stable machine-readable category for an error report.
+pub fn error_kind(report: &Report) -> &'static str {
+    for cause in report.chain() {
+        if cause.is::<WorktreeNotFound>() {
+            return "worktree_not_found";
+        }
+        if cause.is::<git2::Error>() {
+            return "git";
+        }
+        if cause.is::<io::Error>() {
+            return "io";
+        }
+    }
+    "other"
+}
+
+/// Renders `report` as a single-line JSON object for `--json-errors`.
+pub fn error_json(report: &Report) -> String {
+    serde_json::json!({
+        "error": format!("{report:#}"),
+        "kind": error_kind(report),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use color_eyre::eyre::{self, WrapErr};
+
+    #[test]
+    fn classifies_wrapped_worktree_not_found() {
+        let report = Err::<(), _>(WorktreeNotFound::new("feature/x", "/repo/.rsworktree"))
+            .wrap_err("failed to open worktree")
+            .unwrap_err();
+        assert_eq!(error_kind(&report), "worktree_not_found");
+    }
+
+    #[test]
+    fn classifies_git_and_io_errors() {
+        let git = Report::new(git2::Error::from_str("bad ref"));
+        assert_eq!(error_kind(&git), "git");
+
+        let io = Report::new(io::Error::other("disk full"));
+        assert_eq!(error_kind(&io), "io");
+
+        assert_eq!(error_kind(&eyre::eyre!("plain message")), "other");
+    }
+
+    #[test]
+    fn json_includes_full_error_chain() {
+        let report = Err::<(), _>(io::Error::other("disk full"))
+            .wrap_err("failed to write `.gitignore`")
+            .unwrap_err();
+        let value: serde_json::Value = serde_json::from_str(&error_json(&report)).unwrap();
+        assert_eq!(value["kind"], "io");
+        assert_eq!(value["error"], "failed to write `.gitignore`: disk full");
+    }
+}
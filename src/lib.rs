@@ -1,8 +1,11 @@
 pub mod cli;
 mod commands;
 pub mod editor;
+mod error;
 mod repo;
 pub mod telemetry;
+#[cfg(test)]
+mod test_support;
 
 pub use commands::create;
 pub use repo::Repo;
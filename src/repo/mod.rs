@@ -1,23 +1,35 @@
 use std::{
     fs::{self, OpenOptions},
-    io::Write,
-    path::{Path, PathBuf},
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 use color_eyre::eyre::{self, Context};
 use git2::Repository as GitRepository;
 
-const WORKTREE_IGNORE_ENTRY: &str = ".rsworktree/";
-const WORKTREE_IGNORE_ALT_ENTRY: &str = ".rsworktree";
+use crate::editor::CONFIG_FILE_NAME;
+
+mod preferences;
+
+pub(crate) use preferences::Preferences;
+
+/// Directory holding `preferences.json`, and the worktrees unless `worktrees_dir` says otherwise.
+pub(crate) const DEFAULT_WORKTREES_DIR: &str = ".rsworktree";
 
 pub struct Repo {
     git: GitRepository,
     root: PathBuf,
+    /// Worktrees directory relative to `root`.
+    worktrees_dir: PathBuf,
 }
 
 impl std::fmt::Debug for Repo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Repo").field("root", &self.root).finish()
+        f.debug_struct("Repo")
+            .field("root", &self.root)
+            .field("worktrees_dir", &self.worktrees_dir)
+            .finish()
     }
 }
 
@@ -50,7 +62,12 @@ impl Repo {
             discovered
         };
 
-        Ok(Self { git, root })
+        let worktrees_dir = configured_worktrees_dir(&root)?;
+        Ok(Self {
+            git,
+            root,
+            worktrees_dir,
+        })
     }
 
     pub fn root(&self) -> &Path {
@@ -62,17 +79,69 @@ impl Repo {
     }
 
     pub fn worktrees_dir(&self) -> PathBuf {
-        self.root.join(".rsworktree")
+        self.root.join(&self.worktrees_dir)
+    }
+
+    /// The preferences file, which stays in `.rsworktree` even when `worktrees_dir` moves the
+    /// worktrees elsewhere.
+    pub fn preferences_path(&self) -> PathBuf {
+        preferences_path(&self.root)
+    }
+
+    /// Every section of the preferences file, read fresh so edits made while running are seen.
+    pub(crate) fn preferences(&self) -> color_eyre::Result<Preferences> {
+        Preferences::load(&self.preferences_path())
+    }
+
+    /// Opens the repository of the managed worktree `name`.
+    pub fn open_worktree_repo(&self, name: &str) -> color_eyre::Result<GitRepository> {
+        open_worktree_at(name, &self.worktrees_dir().join(name))
+    }
+
+    /// Whether `path` lies inside one of this repository's managed worktrees.
+    pub fn is_inside_worktree(&self, path: &Path) -> bool {
+        let worktrees_dir = canonical_or_self(&self.worktrees_dir());
+        let path = canonical_or_self(path);
+        path != worktrees_dir && path.starts_with(&worktrees_dir)
     }
 
     pub fn ensure_worktrees_dir(&self) -> color_eyre::Result<PathBuf> {
-        self.ensure_gitignore_entry()?;
+        self.ensure_not_nested()?;
+        match self.ensure_gitignore_entry() {
+            Err(err) if is_permission_denied(&err) => warn_read_only_once(&err),
+            result => result?,
+        }
         let dir = self.worktrees_dir();
         fs::create_dir_all(&dir)
             .wrap_err_with(|| eyre::eyre!("failed to create `{}`", dir.display()))?;
         Ok(dir)
     }
 
+    /// Refuses to manage worktrees for a repository that itself sits inside another repository's
+    /// managed worktree, which would nest one worktrees directory inside another.
+    fn ensure_not_nested(&self) -> color_eyre::Result<()> {
+        let root = canonical_or_self(&self.root);
+        let enclosing = root
+            .ancestors()
+            .skip(1)
+            .filter(|ancestor| ancestor.join(".git").is_dir())
+            .find_map(|parent| {
+                Repo::discover_from(parent)
+                    .ok()
+                    .filter(|outer| outer.is_inside_worktree(&root))
+            });
+
+        match enclosing {
+            Some(outer) => Err(eyre::eyre!(
+                "repository `{}` is inside a worktree managed from `{}`; run rsworktree from `{}` instead of nesting worktrees directories",
+                root.display(),
+                outer.root().display(),
+                outer.root().display()
+            )),
+            None => Ok(()),
+        }
+    }
+
     fn ensure_gitignore_entry(&self) -> color_eyre::Result<()> {
         let gitignore_path = self.root.join(".gitignore");
 
@@ -80,7 +149,7 @@ impl Repo {
             let contents = fs::read_to_string(&gitignore_path)
                 .wrap_err_with(|| eyre::eyre!("failed to read `{}`", gitignore_path.display()))?;
 
-            if gitignore_has_entry(&contents) {
+            if gitignore_has_entry(&contents, &self.worktrees_dir) {
                 return Ok(());
             }
 
@@ -95,7 +164,7 @@ impl Repo {
                 })?;
             }
 
-            file.write_all(WORKTREE_IGNORE_ENTRY.as_bytes())
+            file.write_all(self.gitignore_entry().as_bytes())
                 .wrap_err_with(|| {
                     eyre::eyre!("failed to append to `{}`", gitignore_path.display())
                 })?;
@@ -103,19 +172,146 @@ impl Repo {
                 eyre::eyre!("failed to append newline to `{}`", gitignore_path.display())
             })?;
         } else {
-            fs::write(&gitignore_path, format!("{WORKTREE_IGNORE_ENTRY}\n"))
+            fs::write(&gitignore_path, format!("{}\n", self.gitignore_entry()))
                 .wrap_err_with(|| eyre::eyre!("failed to write `{}`", gitignore_path.display()))?;
         }
 
         Ok(())
     }
+
+    /// Root-anchored `.gitignore` line for the worktrees directory, e.g. `.rsworktree/`.
+    fn gitignore_entry(&self) -> String {
+        let entry = gitignore_path(&self.worktrees_dir);
+        if entry.contains('/') {
+            format!("/{entry}/")
+        } else {
+            format!("{entry}/")
+        }
+    }
+}
+
+/// Set once the read-only warning has been printed, so commands that reopen the repository
+/// (such as `exec` across worktrees) do not repeat it.
+static READ_ONLY_WARNED: AtomicBool = AtomicBool::new(false);
+
+fn warn_read_only_once(err: &eyre::Report) {
+    if !READ_ONLY_WARNED.swap(true, Ordering::Relaxed) {
+        eprintln!("Warning: {err:#}; continuing without updating it");
+    }
+}
+
+/// Whether `err` was caused by the filesystem refusing a write, as in a read-only checkout.
+fn is_permission_denied(err: &eyre::Report) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<io::Error>()
+            .is_some_and(|io_err| io_err.kind() == io::ErrorKind::PermissionDenied)
+    })
+}
+
+fn preferences_path(root: &Path) -> PathBuf {
+    root.join(DEFAULT_WORKTREES_DIR).join(CONFIG_FILE_NAME)
+}
+
+/// The `worktrees_dir` preference, falling back to `.rsworktree`. Only relative paths that stay
+/// inside the repository are accepted. Every command discovers the repository, so the rest of
+/// the file is left for the commands using it, and an unreadable file only warns.
+fn configured_worktrees_dir(root: &Path) -> color_eyre::Result<PathBuf> {
+    let path = preferences_path(root);
+    let configured = match read_worktrees_dir(&path) {
+        Ok(configured) => configured.filter(|dir| !dir.trim().is_empty()),
+        Err(err) => {
+            warn_unreadable_worktrees_dir_once(&err);
+            None
+        }
+    };
+    let Some(configured) = configured else {
+        return Ok(PathBuf::from(DEFAULT_WORKTREES_DIR));
+    };
+
+    let dir = PathBuf::from(configured.trim());
+    let inside_repo = dir
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    let normalized: PathBuf = dir
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    if !inside_repo || normalized.as_os_str().is_empty() || normalized.starts_with(".git") {
+        return Err(eyre::eyre!(
+            "invalid `worktrees_dir` `{}` in `{}`; expected a directory inside the repository such as `.worktrees`",
+            configured,
+            path.display()
+        ));
+    }
+
+    Ok(normalized)
+}
+
+fn read_worktrees_dir(path: &Path) -> color_eyre::Result<Option<String>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).wrap_err_with(|| eyre::eyre!("failed to read `{}`", path.display()));
+        }
+    };
+    let value: serde_json::Value = serde_json::from_str(&text)
+        .wrap_err_with(|| eyre::eyre!("failed to parse `{}`", path.display()))?;
+    match value.get("worktrees_dir") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(dir)) => Ok(Some(dir.clone())),
+        Some(_) => Err(eyre::eyre!(
+            "`worktrees_dir` in `{}` is not a string",
+            path.display()
+        )),
+    }
+}
+
+static WORKTREES_DIR_WARNED: AtomicBool = AtomicBool::new(false);
+
+fn warn_unreadable_worktrees_dir_once(err: &eyre::Report) {
+    if !WORKTREES_DIR_WARNED.swap(true, Ordering::Relaxed) {
+        eprintln!("Warning: {err:#}; using `{DEFAULT_WORKTREES_DIR}` as the worktrees directory");
+    }
+}
+
+fn gitignore_path(dir: &Path) -> String {
+    dir.components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub(crate) fn open_worktree_at(name: &str, path: &Path) -> color_eyre::Result<GitRepository> {
+    if !path.exists() {
+        return Err(eyre::eyre!(
+            "worktree `{}` not found at `{}`",
+            name,
+            path.display()
+        ));
+    }
+
+    GitRepository::open(path).map_err(|err| {
+        eyre::eyre!(
+            "worktree `{}` repository at `{}` is corrupt: {}",
+            name,
+            path.display(),
+            err.message()
+        )
+    })
+}
+
+fn canonical_or_self(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
 }
 
-fn gitignore_has_entry(contents: &str) -> bool {
+fn gitignore_has_entry(contents: &str, worktrees_dir: &Path) -> bool {
+    let entry = gitignore_path(worktrees_dir);
     contents
         .lines()
-        .map(|line| line.trim())
-        .any(|line| line == WORKTREE_IGNORE_ENTRY || line == WORKTREE_IGNORE_ALT_ENTRY)
+        .map(|line| line.trim().trim_start_matches('/').trim_end_matches('/'))
+        .any(|line| line == entry)
 }
 
 #[cfg(test)]
@@ -141,7 +337,7 @@ mod tests {
         repo.ensure_worktrees_dir()?;
 
         let contents = fs::read_to_string(&gitignore)?;
-        assert_eq!(contents, format!("{WORKTREE_IGNORE_ENTRY}\n"));
+        assert_eq!(contents, ".rsworktree/\n");
 
         Ok(())
     }
@@ -155,7 +351,7 @@ mod tests {
 
         repo.ensure_worktrees_dir()?;
         let contents = fs::read_to_string(&gitignore)?;
-        assert_eq!(contents, format!("target\n{WORKTREE_IGNORE_ENTRY}\n"));
+        assert_eq!(contents, "target\n.rsworktree/\n");
 
         repo.ensure_worktrees_dir()?;
         let contents_again = fs::read_to_string(&gitignore)?;
@@ -179,11 +375,106 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn is_inside_worktree_only_matches_paths_below_worktrees_dir() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let worktrees_dir = repo.ensure_worktrees_dir()?;
+        let nested = worktrees_dir.join("feature/test/src");
+        fs::create_dir_all(&nested)?;
+
+        assert!(repo.is_inside_worktree(&nested));
+        assert!(!repo.is_inside_worktree(&worktrees_dir));
+        assert!(!repo.is_inside_worktree(dir.path()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ensure_worktrees_dir_refuses_repo_nested_in_managed_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let outer = init_repo(&dir)?;
+        let inner_path = outer.ensure_worktrees_dir()?.join("feature/test/inner");
+        git2::Repository::init(&inner_path)?;
+        let inner = Repo::discover_from(&inner_path)?;
+
+        let err = inner
+            .ensure_worktrees_dir()
+            .expect_err("nested repository must be rejected");
+
+        assert!(
+            err.to_string()
+                .contains("is inside a worktree managed from")
+        );
+        assert!(!inner_path.join(".rsworktree").exists());
+        assert!(!inner_path.join(".gitignore").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn open_worktree_repo_opens_existing_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let worktree_path = repo.ensure_worktrees_dir()?.join("feature/test");
+        git2::Repository::init(&worktree_path)?;
+
+        let opened = repo.open_worktree_repo("feature/test")?;
+        assert_eq!(
+            opened.workdir().map(fs::canonicalize).transpose()?,
+            Some(fs::canonicalize(&worktree_path)?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn open_worktree_repo_reports_missing_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+
+        let Err(err) = repo.open_worktree_repo("missing") else {
+            panic!("expected missing worktree error");
+        };
+        assert!(
+            err.to_string().contains("worktree `missing` not found"),
+            "unexpected error: {err}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn open_worktree_repo_reports_corrupt_worktree() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let outside = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let worktree_path = repo.ensure_worktrees_dir()?.join("broken");
+        fs::create_dir_all(&worktree_path)?;
+        let bogus = outside.path().join("nowhere");
+        fs::write(
+            worktree_path.join(".git"),
+            format!("gitdir: {}\n", bogus.display()),
+        )?;
+
+        let Err(err) = repo.open_worktree_repo("broken") else {
+            panic!("expected corrupt worktree error");
+        };
+        assert!(
+            err.to_string().contains("worktree `broken` repository at"),
+            "unexpected error: {err}"
+        );
+        assert!(err.to_string().contains("is corrupt"));
+
+        Ok(())
+    }
+
     #[test]
     fn gitignore_has_entry_detects_alternate_form() {
-        assert!(gitignore_has_entry(".rsworktree\n"));
-        assert!(gitignore_has_entry("  .rsworktree/  \n"));
-        assert!(!gitignore_has_entry(".other"));
+        let dir = Path::new(".rsworktree");
+        assert!(gitignore_has_entry(".rsworktree\n", dir));
+        assert!(gitignore_has_entry("  .rsworktree/  \n", dir));
+        assert!(!gitignore_has_entry(".other", dir));
     }
 
     #[test]
@@ -191,13 +482,117 @@ mod tests {
         let dir = TempDir::new()?;
         let repo = init_repo(&dir)?;
         let gitignore = dir.path().join(".gitignore");
-        fs::write(&gitignore, format!("{WORKTREE_IGNORE_ALT_ENTRY}\n"))?;
+        fs::write(&gitignore, ".rsworktree\n")?;
 
         repo.ensure_worktrees_dir()?;
 
         let contents = fs::read_to_string(&gitignore)?;
-        assert_eq!(contents, format!("{WORKTREE_IGNORE_ALT_ENTRY}\n"));
+        assert_eq!(contents, ".rsworktree\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn permission_errors_are_detected_through_wrapped_reports() {
+        let denied: color_eyre::Result<()> =
+            Err(io::Error::from(io::ErrorKind::PermissionDenied)).wrap_err("failed to write");
+        let missing: color_eyre::Result<()> =
+            Err(io::Error::from(io::ErrorKind::NotFound)).wrap_err("failed to write");
+
+        assert!(is_permission_denied(&denied.unwrap_err()));
+        assert!(!is_permission_denied(&missing.unwrap_err()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ensure_worktrees_dir_tolerates_read_only_gitignore() -> color_eyre::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new()?;
+        let repo = init_repo(&dir)?;
+        let gitignore = dir.path().join(".gitignore");
+        fs::write(&gitignore, "target\n")?;
+        fs::set_permissions(&gitignore, fs::Permissions::from_mode(0o444))?;
+
+        let created = repo.ensure_worktrees_dir();
+
+        fs::set_permissions(&gitignore, fs::Permissions::from_mode(0o644))?;
+        assert!(created?.is_dir());
+        Ok(())
+    }
+
+    fn write_worktrees_dir_preference(dir: &TempDir, value: &str) -> color_eyre::Result<()> {
+        let preferences = dir.path().join(DEFAULT_WORKTREES_DIR);
+        fs::create_dir_all(&preferences)?;
+        fs::write(
+            preferences.join(CONFIG_FILE_NAME),
+            serde_json::json!({ "worktrees_dir": value }).to_string(),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn worktrees_dir_follows_preference_and_is_gitignored() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        git2::Repository::init(dir.path())?;
+        write_worktrees_dir_preference(&dir, "./tmp/wt/")?;
+        let repo = Repo::discover_from(dir.path())?;
+
+        assert_eq!(repo.worktrees_dir(), dir.path().join("tmp/wt"));
+        assert_eq!(
+            repo.preferences_path(),
+            dir.path().join(".rsworktree/preferences.json")
+        );
+
+        let created = repo.ensure_worktrees_dir()?;
+        assert!(created.is_dir());
+        let gitignore = dir.path().join(".gitignore");
+        assert_eq!(fs::read_to_string(&gitignore)?, "/tmp/wt/\n");
+
+        repo.ensure_worktrees_dir()?;
+        assert_eq!(fs::read_to_string(&gitignore)?, "/tmp/wt/\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn worktrees_dir_preference_must_stay_inside_repository() -> color_eyre::Result<()> {
+        for value in ["../elsewhere", "/tmp/worktrees", ".git/worktrees", "."] {
+            let dir = TempDir::new()?;
+            git2::Repository::init(dir.path())?;
+            write_worktrees_dir_preference(&dir, value)?;
+
+            let err = Repo::discover_from(dir.path()).expect_err(value);
+            assert!(
+                err.to_string().contains("invalid `worktrees_dir`"),
+                "unexpected error for `{value}`: {err}"
+            );
+        }
+
+        Ok(())
+    }
 
+    #[test]
+    fn malformed_preferences_fall_back_to_default_worktrees_dir() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        git2::Repository::init(dir.path())?;
+        let preferences = dir.path().join(DEFAULT_WORKTREES_DIR);
+        fs::create_dir_all(&preferences)?;
+
+        for text in [
+            "{ \"worktrees_dir\": ",
+            r#"{ "worktrees_dir": 7 }"#,
+            r#"{ "worktrees_dir": "wt", "editor": "vim" }"#,
+        ] {
+            fs::write(preferences.join(CONFIG_FILE_NAME), text)?;
+            let repo = Repo::discover_from(dir.path())?;
+            let expected = if text.contains("\"wt\"") {
+                dir.path().join("wt")
+            } else {
+                preferences.clone()
+            };
+            assert_eq!(repo.worktrees_dir(), expected, "{text}");
+        }
         Ok(())
     }
 }
@@ -0,0 +1,80 @@
+use std::{fs, io, path::Path};
+
+use color_eyre::eyre::{self, WrapErr};
+use serde::Deserialize;
+
+use crate::{
+    commands::{
+        cd::CdPreferences, create::CreatePreferences, interactive::TuiPreferences, rm::RemoveHooks,
+    },
+    editor::FileEditorPreference,
+};
+
+/// `preferences.json`, with a section per feature. Sections the file leaves out keep their
+/// defaults.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct Preferences {
+    #[serde(default)]
+    pub(crate) editor: Option<FileEditorPreference>,
+    #[serde(default)]
+    pub(crate) create: CreatePreferences,
+    #[serde(default)]
+    pub(crate) remove: RemoveHooks,
+    #[serde(default)]
+    pub(crate) cd: CdPreferences,
+    #[serde(default)]
+    pub(crate) tui: TuiPreferences,
+}
+
+impl Preferences {
+    /// Parses the preferences file at `path`; a missing file means every preference is unset.
+    pub(crate) fn load(path: &Path) -> color_eyre::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err)
+                    .wrap_err_with(|| eyre::eyre!("failed to read `{}`", path.display()));
+            }
+        };
+        serde_json::from_str(&text)
+            .wrap_err_with(|| eyre::eyre!("failed to parse `{}`", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn missing_file_and_sections_use_defaults() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("preferences.json");
+
+        let preferences = Preferences::load(&path)?;
+        assert!(preferences.editor.is_none());
+
+        fs::write(&path, r#"{ "remove": { "pre_remove": "make stop" } }"#)?;
+        let preferences = Preferences::load(&path)?;
+        assert_eq!(preferences.remove.pre_remove.as_deref(), Some("make stop"));
+        assert!(preferences.create.copy_untracked.is_empty());
+        assert!(preferences.cd.init.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn reports_parse_errors_with_the_path() -> color_eyre::Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("preferences.json");
+        fs::write(&path, "{ invalid json }")?;
+
+        let err = Preferences::load(&path).expect_err("malformed file");
+        assert_eq!(
+            err.to_string(),
+            format!("failed to parse `{}`", path.display())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+//! Fixtures shared by the unit tests.
+
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre;
+use tempfile::TempDir;
+
+use crate::{
+    Repo,
+    commands::pr_github::{CommandOutput, CommandRunner},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RecordedCall {
+    pub(crate) program: String,
+    pub(crate) dir: PathBuf,
+    pub(crate) args: Vec<String>,
+}
+
+/// Records every call and answers with the queued `responses`. Once those run out, calls get
+/// `fallback`, or fail as unexpected when there is none.
+#[derive(Debug, Default)]
+pub(crate) struct MockCommandRunner {
+    pub(crate) responses: VecDeque<color_eyre::Result<CommandOutput>>,
+    pub(crate) fallback: Option<CommandOutput>,
+    pub(crate) calls: Vec<RecordedCall>,
+    pub(crate) streamed_calls: usize,
+}
+
+impl MockCommandRunner {
+    /// A runner whose calls all succeed with no output.
+    pub(crate) fn succeeding() -> Self {
+        Self::answering(output("", true))
+    }
+
+    /// A runner answering every call with `output`.
+    pub(crate) fn answering(output: CommandOutput) -> Self {
+        Self {
+            fallback: Some(output),
+            ..Self::default()
+        }
+    }
+
+    /// A runner answering calls with `outputs` in order.
+    pub(crate) fn with_outputs(outputs: impl IntoIterator<Item = CommandOutput>) -> Self {
+        Self {
+            responses: outputs.into_iter().map(Ok).collect(),
+            ..Self::default()
+        }
+    }
+}
+
+// Queued errors are cloned by message, since reports are not `Clone`.
+impl Clone for MockCommandRunner {
+    fn clone(&self) -> Self {
+        Self {
+            responses: self
+                .responses
+                .iter()
+                .map(|response| match response {
+                    Ok(output) => Ok(output.clone()),
+                    Err(err) => Err(eyre::eyre!("{err:#}")),
+                })
+                .collect(),
+            fallback: self.fallback.clone(),
+            calls: self.calls.clone(),
+            streamed_calls: self.streamed_calls,
+        }
+    }
+}
+
+impl CommandRunner for MockCommandRunner {
+    fn run(
+        &mut self,
+        program: &str,
+        current_dir: &Path,
+        args: &[String],
+    ) -> color_eyre::Result<CommandOutput> {
+        self.calls.push(RecordedCall {
+            program: program.to_owned(),
+            dir: current_dir.to_path_buf(),
+            args: args.to_vec(),
+        });
+        match (self.responses.pop_front(), &self.fallback) {
+            (Some(response), _) => response,
+            (None, Some(fallback)) => Ok(fallback.clone()),
+            (None, None) => Err(eyre::eyre!("unexpected command invocation")),
+        }
+    }
+
+    fn run_streamed(
+        &mut self,
+        program: &str,
+        current_dir: &Path,
+        args: &[String],
+    ) -> color_eyre::Result<CommandOutput> {
+        self.streamed_calls += 1;
+        self.run(program, current_dir, args)
+    }
+}
+
+/// Output of a command that printed `stdout` and exited with 0, or with 1 unless `success`.
+pub(crate) fn output(stdout: &str, success: bool) -> CommandOutput {
+    CommandOutput {
+        stdout: stdout.into(),
+        stderr: String::new(),
+        success,
+        status_code: Some(if success { 0 } else { 1 }),
+    }
+}
+
+/// Creates a repository in `dir` whose only commit adds `README.md`.
+pub(crate) fn init_repo(dir: &TempDir) -> color_eyre::Result<Repo> {
+    let git = git2::Repository::init(dir.path())?;
+    fs::write(dir.path().join("README.md"), "test")?;
+    let mut index = git.index()?;
+    index.add_path(Path::new("README.md"))?;
+    index.write()?;
+    let tree = git.find_tree(index.write_tree()?)?;
+    let signature = git2::Signature::now("Test", "test@example.com")?;
+    git.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "Initial commit",
+        &tree,
+        &[],
+    )?;
+    Repo::discover_from(dir.path())
+}
+
+/// Replaces the preferences file of `repo` with `json`.
+pub(crate) fn write_preferences(repo: &Repo, json: serde_json::Value) {
+    repo.ensure_worktrees_dir().expect("worktrees dir");
+    fs::write(repo.preferences_path(), serde_json::to_vec(&json).unwrap()).expect("write config");
+}
@@ -1,7 +1,17 @@
 #[path = "commands/cd.rs"]
 mod cd;
+#[path = "commands/color.rs"]
+mod color;
+#[path = "commands/completions.rs"]
+mod completions;
+#[path = "commands/config.rs"]
+mod config;
 #[path = "commands/create.rs"]
 mod create;
+#[path = "commands/describe.rs"]
+mod describe;
+#[path = "commands/exec.rs"]
+mod exec;
 #[path = "commands/list.rs"]
 mod list;
 #[path = "commands/merge_pr_github.rs"]
@@ -10,5 +20,17 @@ mod merge_pr_github;
 mod open_editor;
 #[path = "commands/pr_github.rs"]
 mod pr_github;
+#[path = "commands/pr_gitlab.rs"]
+mod pr_gitlab;
+#[path = "commands/prune.rs"]
+mod prune;
+#[path = "commands/rename.rs"]
+mod rename;
+#[path = "commands/rename_branch.rs"]
+mod rename_branch;
+#[path = "commands/repair.rs"]
+mod repair;
 #[path = "commands/rm.rs"]
 mod rm;
+#[path = "commands/version.rs"]
+mod version;
@@ -113,3 +113,404 @@ fn cd_command_fails_for_missing_worktree() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn cd_command_create_creates_and_enters_missing_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    run(repo_dir.path(), ["git", "branch", "develop"])?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["cd", "feature/new", "--create", "--base", "develop"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created worktree"))
+        .stdout(predicate::str::contains("PWD="));
+
+    let worktree_path = repo_dir.path().join(".rsworktree").join("feature/new");
+    assert!(worktree_path.exists(), "worktree should have been created");
+
+    Ok(())
+}
+
+#[test]
+fn cd_command_create_enters_existing_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/test"])
+        .assert()
+        .success();
+
+    let worktree_path = repo_dir
+        .path()
+        .join(".rsworktree")
+        .join("feature/test")
+        .canonicalize()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "feature/test", "--create", "--print"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created worktree").not())
+        .stdout(predicate::str::contains(worktree_path.to_string_lossy()));
+
+    Ok(())
+}
+
+#[test]
+fn cd_json_errors_reports_missing_worktree_as_json() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["--json-errors", "cd", "missing"])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    let value: serde_json::Value = serde_json::from_str(stderr.trim())?;
+    assert_eq!(value["kind"], "worktree_not_found");
+    assert!(
+        value["error"]
+            .as_str()
+            .is_some_and(|message| message.contains("worktree `missing` does not exist"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cd_last_enters_most_recently_created_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "--last", "--print"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no worktree has been created"));
+
+    for name in ["feature/first", "feature/second"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+
+    let worktree_path = repo_dir
+        .path()
+        .join(".rsworktree")
+        .join("feature/second")
+        .canonicalize()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "--last", "--print"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(worktree_path.to_string_lossy()));
+
+    Ok(())
+}
+
+#[test]
+fn cd_last_records_next_to_preferences_with_custom_worktrees_dir() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    let preferences = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&preferences)?;
+    fs::write(
+        preferences.join("preferences.json"),
+        r#"{ "worktrees_dir": ".worktrees" }"#,
+    )?;
+
+    create_worktree(repo_dir.path(), "feature/custom")?;
+
+    assert_eq!(
+        fs::read_to_string(preferences.join("last-created"))?,
+        "feature/custom\n"
+    );
+    assert!(!repo_dir.path().join(".worktrees/last-created").exists());
+
+    let worktree_path = repo_dir
+        .path()
+        .join(".worktrees/feature/custom")
+        .canonicalize()?;
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "--last", "--print"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(worktree_path.to_string_lossy()));
+
+    Ok(())
+}
+
+fn create_worktree(repo: &Path, name: &str) -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo)
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", name])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn cd_command_ignores_non_zero_exit_of_interactive_shell() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/test")?;
+
+    // The last command typed into the shell failing is not an rsworktree failure.
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "false")
+        .args(["cd", "feature/test"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn cd_command_fails_when_shell_cannot_be_spawned() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/test")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "/nonexistent/shell")
+        .args(["cd", "feature/test"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "failed to spawn subshell `/nonexistent/shell`",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn cd_command_runs_command_and_propagates_its_exit_code() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/test")?;
+
+    let worktree_path = repo_dir
+        .path()
+        .join(".rsworktree")
+        .join("feature/test")
+        .canonicalize()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "feature/test", "--", "pwd"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", worktree_path.display()));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "feature/test", "--", "sh", "-c", "exit 7"])
+        .assert()
+        .code(7)
+        .stderr("");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "feature/test", "--", "/nonexistent/tool"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "failed to run `/nonexistent/tool`",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn cd_command_exports_variables_from_worktree_env_file() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/env")?;
+
+    let worktree = repo_dir.path().join(".rsworktree").join("feature/env");
+    fs::write(
+        worktree.join(".rsworktree.env"),
+        "# per-worktree settings\nAPP_PORT=4001\nexport APP_NAME=\"feature env\"\n",
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["cd", "feature/env"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("APP_PORT=4001\n"))
+        .stdout(predicate::str::contains("APP_NAME=feature env\n"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "feature/env", "--", "sh", "-c", "echo $APP_PORT"])
+        .assert()
+        .success()
+        .stdout("4001\n");
+
+    Ok(())
+}
+
+#[test]
+fn cd_command_runs_init_snippet_from_preferences() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/init")?;
+
+    fs::write(
+        repo_dir.path().join(".rsworktree/preferences.json"),
+        r#"{ "cd": { "init": "export INIT_MARKER=\"from $(basename \"$PWD\")\"" } }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "/bin/sh")
+        .args(["cd", "feature/init"])
+        .write_stdin("env\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("INIT_MARKER=from init\n"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "feature/init", "--", "env"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("INIT_MARKER=from init\n"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["cd", "feature/init"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "RSWORKTREE_CD_INIT=export INIT_MARKER=",
+        ))
+        .stdout(predicate::str::contains("INIT_MARKER=from").not());
+
+    Ok(())
+}
+
+#[test]
+fn cd_emit_cd_prints_quoted_cd_for_eval() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "it's here", "--branch", "feature/quoted"])
+        .assert()
+        .success();
+
+    let worktree_path = repo_dir
+        .path()
+        .join(".rsworktree")
+        .join("it's here")
+        .canonicalize()?;
+    let escaped = worktree_path.to_string_lossy().replace('\'', "'\\''");
+
+    // No RSWORKTREE_SHELL: `--emit-cd` must not spawn a shell.
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "--emit-cd", "it's here"])
+        .assert()
+        .success()
+        .stdout(format!("cd '{escaped}'\n"));
+
+    let binary = assert_cmd::cargo::cargo_bin("rsworktree");
+    let output = StdCommand::new("sh")
+        .current_dir(repo_dir.path())
+        .arg("-c")
+        .arg(r#"eval "$("$0" cd --emit-cd "it's here")" && pwd -P"#)
+        .arg(binary)
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        format!("{}\n", worktree_path.display())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cd_command_falls_back_to_partial_name_match() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/some-long-name")?;
+    create_worktree(repo_dir.path(), "bugfix/long-lines")?;
+
+    let worktree_path = repo_dir
+        .path()
+        .join(".rsworktree/feature/some-long-name")
+        .canonicalize()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "name", "--print"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", worktree_path.display()));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["cd", "long", "--print"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "worktree identifier `long` is ambiguous. Matches: bugfix/long-lines, feature/some-long-name",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn cd_without_name_uses_current_worktree_or_errors_without_terminal() -> Result<(), Box<dyn Error>>
+{
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/here")?;
+    create_worktree(repo_dir.path(), "feature/there")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .arg("cd")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no worktree name given; pass one, or run `rsworktree cd` in a terminal to pick one",
+        ));
+
+    let worktree = repo_dir.path().join(".rsworktree/feature/here");
+    Command::cargo_bin("rsworktree")?
+        .current_dir(&worktree)
+        .args(["cd", "--print"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".rsworktree/feature/here"));
+
+    Ok(())
+}
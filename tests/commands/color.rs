@@ -0,0 +1,65 @@
+use std::{error::Error, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let status = StdCommand::new("git")
+        .current_dir(dir)
+        .args(["init", "-q"])
+        .status()?;
+    if !status.success() {
+        return Err(format!("`git init` exited with status {status}").into());
+    }
+    Ok(())
+}
+
+fn ls_stdout(dir: &Path, color: &str, force: bool) -> Result<String, Box<dyn Error>> {
+    let mut command = Command::cargo_bin("rsworktree")?;
+    command
+        .current_dir(dir)
+        .env_remove("FORCE_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .env_remove("NO_COLOR")
+        .args(["ls", "--color", color]);
+    if force {
+        command.env("FORCE_COLOR", "1").env("CLICOLOR_FORCE", "1");
+    }
+    let output = command.assert().success().get_output().stdout.clone();
+    Ok(String::from_utf8(output)?)
+}
+
+#[test]
+fn color_auto_leaves_piped_output_plain() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let stdout = ls_stdout(repo_dir.path(), "auto", false)?;
+
+    assert!(stdout.contains("Worktrees under"));
+    assert!(!stdout.contains('\u{1b}'), "unexpected ANSI in {stdout:?}");
+    Ok(())
+}
+
+#[test]
+fn color_always_colors_piped_output() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let stdout = ls_stdout(repo_dir.path(), "always", false)?;
+
+    assert!(stdout.contains("\u{1b}["), "expected ANSI in {stdout:?}");
+    Ok(())
+}
+
+#[test]
+fn color_never_wins_over_forced_colors() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let stdout = ls_stdout(repo_dir.path(), "never", true)?;
+
+    assert!(stdout.contains("Worktrees under"));
+    assert!(!stdout.contains('\u{1b}'), "unexpected ANSI in {stdout:?}");
+    Ok(())
+}
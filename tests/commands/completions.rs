@@ -0,0 +1,45 @@
+use std::error::Error;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn completions_print_script_outside_a_repository() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(dir.path())
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("_rsworktree()")
+                .and(predicate::str::contains("complete -F _rsworktree"))
+                .and(predicate::str::contains("--oldest")),
+        );
+
+    Ok(())
+}
+
+#[test]
+fn completions_support_zsh_fish_and_powershell() -> Result<(), Box<dyn Error>> {
+    for (shell, marker) in [
+        ("zsh", "#compdef rsworktree"),
+        ("fish", "complete -c rsworktree"),
+        ("powershell", "Register-ArgumentCompleter"),
+    ] {
+        Command::cargo_bin("rsworktree")?
+            .args(["completions", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(marker));
+    }
+
+    Command::cargo_bin("rsworktree")?
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use std::{error::Error, fs, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run(dir, ["git", "add", "README.md"])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )?;
+    Ok(())
+}
+
+fn run(dir: &Path, cmd: impl IntoIterator<Item = &'static str>) -> Result<(), Box<dyn Error>> {
+    let mut iter = cmd.into_iter();
+    let program = iter.next().expect("command must not be empty");
+    let status = StdCommand::new(program)
+        .current_dir(dir)
+        .args(iter)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("`{program}` exited with status {status}").into());
+    }
+
+    Ok(())
+}
+
+fn config_list(repo: &Path, editor: Option<&str>) -> Result<serde_json::Value, Box<dyn Error>> {
+    let mut command = Command::cargo_bin("rsworktree")?;
+    command
+        .current_dir(repo)
+        .env_remove("VISUAL")
+        .env_remove("RSWORKTREE_CMD_TIMEOUT")
+        .args(["config", "list"]);
+    match editor {
+        Some(editor) => command.env("EDITOR", editor),
+        None => command.env_remove("EDITOR"),
+    };
+
+    let output = command.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&output)?)
+}
+
+#[test]
+fn config_list_annotates_overridden_and_default_values() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{
+            "editor": { "command": "code", "args": ["--wait"] },
+            "create": { "base_default": "develop" },
+            "tui": { "theme": { "highlight": "yellow" } }
+        }"#,
+    )?;
+
+    let config = config_list(repo_dir.path(), Some("vim"))?;
+    let settings = &config["settings"];
+
+    assert_eq!(settings["editor"]["value"], "code --wait");
+    assert_eq!(settings["editor"]["source"], "preferences");
+    assert_eq!(settings["create.base_default"]["value"], "develop");
+    assert_eq!(settings["create.base_default"]["source"], "preferences");
+    assert_eq!(settings["tui.theme.highlight"]["value"], "Yellow");
+    assert_eq!(settings["tui.theme.highlight"]["source"], "preferences");
+    assert_eq!(settings["tui.theme.muted"]["source"], "default");
+    assert_eq!(
+        settings["create.name_template"]["value"],
+        serde_json::Value::Null
+    );
+    assert_eq!(settings["create.name_template"]["source"], "default");
+
+    Ok(())
+}
+
+#[test]
+fn config_list_reports_environment_and_defaults_without_preferences() -> Result<(), Box<dyn Error>>
+{
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let config = config_list(repo_dir.path(), Some("nvim -p"))?;
+    let settings = &config["settings"];
+    assert_eq!(settings["editor"]["value"], "nvim -p");
+    assert_eq!(settings["editor"]["source"], "env:EDITOR");
+    assert_eq!(settings["create.base_default"]["value"], "HEAD");
+    assert_eq!(settings["create.base_default"]["source"], "default");
+    assert_eq!(settings["command_timeout_secs"]["source"], "default");
+
+    let config = config_list(repo_dir.path(), None)?;
+    assert_eq!(
+        config["settings"]["editor"]["value"],
+        serde_json::Value::Null
+    );
+    assert_eq!(config["settings"]["editor"]["source"], "default");
+
+    Ok(())
+}
+
+#[test]
+fn config_list_reports_hook_copy_and_cd_preferences() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let config = config_list(repo_dir.path(), None)?;
+    let settings = &config["settings"];
+    for key in [
+        "create.post_create",
+        "remove.pre_remove",
+        "remove.post_remove",
+        "cd.init",
+    ] {
+        assert_eq!(settings[key]["value"], serde_json::Value::Null, "{key}");
+        assert_eq!(settings[key]["source"], "default", "{key}");
+    }
+    assert_eq!(settings["create.post_create_when_quiet"]["value"], false);
+    assert_eq!(
+        settings["create.copy_untracked"]["value"],
+        serde_json::json!([])
+    );
+    assert_eq!(settings["cd.env_file"]["value"], ".rsworktree.env");
+    assert_eq!(settings["cd.env_file"]["source"], "default");
+    assert_eq!(settings["worktrees_dir"]["value"], ".rsworktree");
+    assert_eq!(settings["worktrees_dir"]["source"], "default");
+
+    let preferences_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&preferences_dir)?;
+    fs::write(
+        preferences_dir.join("preferences.json"),
+        r#"{
+            "worktrees_dir": ".worktrees",
+            "create": {
+                "post_create": "./setup.sh",
+                "post_create_when_quiet": true,
+                "copy_untracked": [".env"]
+            },
+            "remove": { "pre_remove": "make stop", "post_remove": "make clean" },
+            "cd": { "env_file": ".env.local", "init": "source .venv/bin/activate" }
+        }"#,
+    )?;
+
+    let config = config_list(repo_dir.path(), None)?;
+    let settings = &config["settings"];
+    for (key, value) in [
+        ("create.post_create", serde_json::json!("./setup.sh")),
+        ("create.post_create_when_quiet", serde_json::json!(true)),
+        ("create.copy_untracked", serde_json::json!([".env"])),
+        ("remove.pre_remove", serde_json::json!("make stop")),
+        ("remove.post_remove", serde_json::json!("make clean")),
+        ("cd.env_file", serde_json::json!(".env.local")),
+        ("cd.init", serde_json::json!("source .venv/bin/activate")),
+        ("worktrees_dir", serde_json::json!(".worktrees")),
+    ] {
+        assert_eq!(settings[key]["value"], value, "{key}");
+        assert_eq!(settings[key]["source"], "preferences", "{key}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn config_list_never_contains_ansi_escapes() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("FORCE_COLOR", "1")
+        .env("CLICOLOR_FORCE", "1")
+        .args(["config", "list"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert!(!output.contains(&0x1b), "stdout contains ANSI escapes");
+    serde_json::from_slice::<serde_json::Value>(&output)?;
+
+    Ok(())
+}
@@ -210,3 +210,671 @@ fn create_command_handles_names_with_reserved_characters() -> Result<(), Box<dyn
 
     Ok(())
 }
+
+#[test]
+fn create_command_rejects_branch_checked_out_in_main_repo() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "main"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "branch `main` is already checked out at",
+        ));
+
+    assert!(
+        !repo_dir.path().join(".rsworktree/main").exists(),
+        "no worktree directory should be created"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn create_command_applies_name_template_from_preferences() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{ "create": { "name_template": "{user}/{name}" } }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .env("USER", "alice")
+        .args(["create", "feat-x"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alice/feat-x"));
+
+    assert!(worktrees_dir.join("alice/feat-x").exists());
+    let branch = StdCommand::new("git")
+        .current_dir(repo_dir.path())
+        .args(["rev-parse", "--verify", "refs/heads/alice/feat-x"])
+        .status()?;
+    assert!(branch.success(), "branch should use the expanded name");
+
+    Ok(())
+}
+
+fn rev_parse(dir: &Path, spec: &str) -> Result<String, Box<dyn Error>> {
+    let output = StdCommand::new("git")
+        .current_dir(dir)
+        .args(["rev-parse", spec])
+        .output()?;
+    if !output.status.success() {
+        return Err(format!("`git rev-parse {spec}` failed").into());
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+}
+
+/// Creates `develop` at the initial commit, then advances `main` past it.
+fn diverge_develop_from_main(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "branch", "develop"])?;
+    fs::write(dir.join("CHANGELOG.md"), "notes")?;
+    run(dir, ["git", "add", "."])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Second commit",
+        ],
+    )?;
+    Ok(())
+}
+
+#[test]
+fn create_command_uses_base_default_from_preferences() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    diverge_develop_from_main(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{ "create": { "base_default": "develop" } }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/from-pref"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from `develop`"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/from-cli", "--base", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from `main`"));
+
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feature/from-pref")?,
+        rev_parse(repo_dir.path(), "develop")?
+    );
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feature/from-cli")?,
+        rev_parse(repo_dir.path(), "main")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn create_command_defaults_base_to_head_without_preference() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    diverge_develop_from_main(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/from-head"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feature/from-head")?,
+        rev_parse(repo_dir.path(), "HEAD")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn create_command_tracks_existing_remote_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    diverge_develop_from_main(repo_dir.path())?;
+    run(repo_dir.path(), ["git", "remote", "add", "origin", "."])?;
+    run(
+        repo_dir.path(),
+        [
+            "git",
+            "update-ref",
+            "refs/remotes/origin/feature/remote",
+            "develop",
+        ],
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/remote", "--checkout-existing-remote"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created tracking branch `feature/remote` from `origin/feature/remote`.",
+        ));
+
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feature/remote")?,
+        rev_parse(repo_dir.path(), "develop")?
+    );
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feature/remote@{upstream}")?,
+        rev_parse(repo_dir.path(), "origin/feature/remote")?
+    );
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/local-only", "--track"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created new branch `feature/local-only` from `HEAD`.",
+        ));
+
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feature/local-only")?,
+        rev_parse(repo_dir.path(), "HEAD")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn create_command_branch_option_separates_directory_from_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "review", "--branch", "teammate/feature"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Worktree directory `review` is checked out on branch `teammate/feature`.",
+        ));
+
+    let worktree = repo_dir.path().join(".rsworktree").join("review");
+    let head = StdCommand::new("git")
+        .current_dir(&worktree)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()?;
+    assert_eq!(String::from_utf8(head.stdout)?.trim(), "teammate/feature");
+    assert!(
+        StdCommand::new("git")
+            .current_dir(repo_dir.path())
+            .args(["rev-parse", "--verify", "refs/heads/review"])
+            .output()?
+            .stdout
+            .is_empty(),
+        "no branch should be named after the directory"
+    );
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "matched"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("checked out on branch").not());
+
+    let head = StdCommand::new("git")
+        .current_dir(repo_dir.path().join(".rsworktree").join("matched"))
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()?;
+    assert_eq!(String::from_utf8(head.stdout)?.trim(), "matched");
+
+    Ok(())
+}
+
+#[test]
+fn create_command_track_uses_non_origin_remote() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    diverge_develop_from_main(repo_dir.path())?;
+    run(repo_dir.path(), ["git", "remote", "add", "upstream", "."])?;
+    run(
+        repo_dir.path(),
+        [
+            "git",
+            "update-ref",
+            "refs/remotes/upstream/feature/shared",
+            "develop",
+        ],
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/shared", "--track"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created tracking branch `feature/shared` from `upstream/feature/shared`.",
+        ));
+
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feature/shared@{upstream}")?,
+        rev_parse(repo_dir.path(), "develop")?
+    );
+
+    Ok(())
+}
+
+fn local_branches(dir: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let output = StdCommand::new("git")
+        .current_dir(dir)
+        .args(["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+        .output()?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(str::to_owned)
+        .collect())
+}
+
+#[test]
+fn create_command_detach_checks_out_tag_without_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    run(repo_dir.path(), ["git", "tag", "v1.0"])?;
+    diverge_develop_from_main(repo_dir.path())?;
+    let branches_before = local_branches(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "release", "--detach", "--base", "v1.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("detached at `v1.0`"));
+
+    let worktree = repo_dir.path().join(".rsworktree").join("release");
+    let head = StdCommand::new("git")
+        .current_dir(&worktree)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()?;
+    assert_eq!(String::from_utf8(head.stdout)?.trim(), "HEAD");
+    assert_eq!(
+        rev_parse(&worktree, "HEAD")?,
+        rev_parse(repo_dir.path(), "v1.0^{commit}")?
+    );
+    assert_eq!(local_branches(repo_dir.path())?, branches_before);
+
+    let status = StdCommand::new("git")
+        .current_dir(&worktree)
+        .args(["status", "--porcelain"])
+        .output()?;
+    assert!(status.stdout.is_empty(), "worktree should be clean");
+
+    Ok(())
+}
+
+#[test]
+fn create_command_detach_defaults_to_head() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "scratch", "--detach"])
+        .assert()
+        .success();
+
+    let worktree = repo_dir.path().join(".rsworktree").join("scratch");
+    assert_eq!(
+        rev_parse(&worktree, "HEAD")?,
+        rev_parse(repo_dir.path(), "HEAD")?
+    );
+    assert_eq!(local_branches(repo_dir.path())?, ["main"]);
+
+    Ok(())
+}
+
+#[test]
+fn create_command_detach_uses_base_default_from_preferences() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    diverge_develop_from_main(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{ "create": { "base_default": "develop" } }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "scratch", "--detach"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("detached at `develop`"));
+
+    let worktree = worktrees_dir.join("scratch");
+    assert_eq!(
+        rev_parse(&worktree, "HEAD")?,
+        rev_parse(repo_dir.path(), "develop")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn create_command_uses_configured_worktrees_dir() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    let preferences = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&preferences)?;
+    fs::write(
+        preferences.join("preferences.json"),
+        r#"{ "worktrees_dir": ".worktrees" }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/elsewhere"])
+        .assert()
+        .success();
+
+    let worktree = repo_dir.path().join(".worktrees").join("feature/elsewhere");
+    assert!(worktree.join(".git").exists());
+    assert!(!preferences.join("feature").exists());
+    let gitignore = fs::read_to_string(repo_dir.path().join(".gitignore"))?;
+    assert_eq!(gitignore, ".worktrees/\n");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".worktrees"))
+        .stdout(predicate::str::contains("- feature/elsewhere\n"));
+
+    Ok(())
+}
+
+#[test]
+fn create_command_print_branch_outputs_only_resolved_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{ "create": { "name_template": "{user}/{name}" } }"#,
+    )?;
+
+    // No RSWORKTREE_SHELL: `--print-branch` must not spawn a shell.
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("USER", "alice")
+        .args(["create", "feat-x", "--print-branch"])
+        .assert()
+        .success()
+        .stdout("alice/feat-x\n");
+    assert!(worktrees_dir.join("alice/feat-x").exists());
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("USER", "alice")
+        .args([
+            "create",
+            "review",
+            "--branch",
+            "team/topic",
+            "--print-branch",
+        ])
+        .assert()
+        .success()
+        .stdout("team/topic\n");
+
+    Ok(())
+}
+
+#[test]
+fn create_command_runs_post_create_hook_in_new_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{ "create": { "post_create": "printf '%s' \"$RSWORKTREE_NAME\" > hook.out" } }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/hooked"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(worktrees_dir.join("feature/hooked/hook.out"))?,
+        "feature/hooked"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn create_command_warns_when_post_create_hook_fails() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{ "create": { "post_create": "exit 3" } }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/unhooked"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Warning: post-create hook `exit 3` failed with exit status 3",
+        ));
+
+    assert!(worktrees_dir.join("feature/unhooked").is_dir());
+
+    Ok(())
+}
+
+#[test]
+fn create_command_on_stacks_branch_and_records_parent() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feat/x-part1"])
+        .assert()
+        .success();
+
+    let parent_dir = repo_dir.path().join(".rsworktree/feat/x-part1");
+    fs::write(parent_dir.join("part1.txt"), "part1")?;
+    StdCommand::new("git")
+        .current_dir(&parent_dir)
+        .args(["add", "part1.txt"])
+        .status()?;
+    StdCommand::new("git")
+        .current_dir(&parent_dir)
+        .args([
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Part 1",
+        ])
+        .status()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feat/x-part2", "--on", "feat/x-part1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Stacked worktree `feat/x-part2` on `feat/x-part1`.",
+        ));
+
+    assert_eq!(
+        rev_parse(repo_dir.path(), "feat/x-part2")?,
+        rev_parse(repo_dir.path(), "feat/x-part1")?
+    );
+    let stacks = fs::read_to_string(repo_dir.path().join(".rsworktree/stacks.json"))?;
+    assert!(stacks.contains(r#""feat/x-part2": "feat/x-part1""#));
+
+    Ok(())
+}
+
+#[test]
+fn create_command_copies_untracked_files_unless_disabled() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&worktrees_dir)?;
+    fs::write(
+        worktrees_dir.join("preferences.json"),
+        r#"{ "create": { "copy_untracked": [".env", ".vscode", "config.local.toml"] } }"#,
+    )?;
+    fs::write(repo_dir.path().join(".env"), "TOKEN=abc")?;
+    fs::create_dir_all(repo_dir.path().join(".vscode"))?;
+    fs::write(repo_dir.path().join(".vscode/settings.json"), "{}")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/copied"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Skipping `config.local.toml` from `create.copy_untracked`",
+        ));
+
+    let copied = worktrees_dir.join("feature/copied");
+    assert_eq!(fs::read_to_string(copied.join(".env"))?, "TOKEN=abc");
+    assert!(copied.join(".vscode/settings.json").is_file());
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/plain", "--no-copy"])
+        .assert()
+        .success();
+
+    assert!(!worktrees_dir.join("feature/plain/.env").exists());
+
+    Ok(())
+}
+
+#[test]
+fn create_command_scaffolds_from_template_repo() -> Result<(), Box<dyn Error>> {
+    let template_dir = TempDir::new()?;
+    init_git_repo(template_dir.path())?;
+    fs::write(template_dir.path().join("Makefile"), "all:\n")?;
+    fs::write(template_dir.path().join("README.md"), "template")?;
+    run(template_dir.path(), ["git", "add", "Makefile", "README.md"])?;
+    run(
+        template_dir.path(),
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Scaffold",
+        ],
+    )?;
+    let bare_dir = TempDir::new()?;
+    let bare = bare_dir.path().join("template.git");
+    let status = StdCommand::new("git")
+        .args(["clone", "--quiet", "--bare"])
+        .arg(template_dir.path())
+        .arg(&bare)
+        .status()?;
+    assert!(status.success());
+
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/scaffolded", "--template-repo"])
+        .arg(&bare)
+        .assert()
+        .success();
+
+    let worktree = repo_dir.path().join(".rsworktree/feature/scaffolded");
+    assert_eq!(fs::read_to_string(worktree.join("Makefile"))?, "all:\n");
+    // Both repositories track README.md; the worktree keeps its own copy.
+    assert_eq!(fs::read_to_string(worktree.join("README.md"))?, "test");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/unscaffolded", "--template-repo"])
+        .arg(bare_dir.path().join("missing.git"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Warning: failed to clone template repository",
+        ));
+    assert!(
+        repo_dir
+            .path()
+            .join(".rsworktree/feature/unscaffolded")
+            .is_dir()
+    );
+
+    Ok(())
+}
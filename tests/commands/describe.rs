@@ -0,0 +1,184 @@
+use std::{error::Error, fs, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run(dir, ["git", "add", "README.md"])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )?;
+    Ok(())
+}
+
+fn run(dir: &Path, cmd: impl IntoIterator<Item = &'static str>) -> Result<(), Box<dyn Error>> {
+    let mut iter = cmd.into_iter();
+    let program = iter.next().expect("command must not be empty");
+    let status = StdCommand::new(program)
+        .current_dir(dir)
+        .args(iter)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("`{program}` exited with status {status}").into());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn describe_json_reports_worktree_state() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["create", "feature/describe"])
+        .assert()
+        .success();
+
+    let worktree = repo_dir.path().join(".rsworktree/feature/describe");
+    fs::write(worktree.join("README.md"), "changed")?;
+    fs::write(worktree.join("notes.txt"), "new")?;
+    fs::write(worktree.join("staged.txt"), "staged")?;
+    run(&worktree, ["git", "add", "staged.txt"])?;
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["describe", "feature/describe", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let mut details: serde_json::Value = serde_json::from_slice(&output)?;
+
+    let commit_id = details["head"]["commit"]["id"]
+        .as_str()
+        .ok_or("missing commit id")?
+        .to_owned();
+    assert_eq!(commit_id.len(), 40);
+    details["head"]["commit"]["id"] = json!("<id>");
+    details["path"] = json!("<path>");
+
+    assert_eq!(
+        details,
+        json!({
+            "name": "feature/describe",
+            "path": "<path>",
+            "exists": true,
+            "error": null,
+            "head": {
+                "kind": "branch",
+                "name": "feature/describe",
+                "error": null,
+                "tracking": {
+                    "upstream": null,
+                    "ahead": null,
+                    "behind": null,
+                    "error": null
+                },
+                "commit": {
+                    "id": "<id>",
+                    "summary": "Initial commit",
+                    "author_name": "Test",
+                    "author_email": "test@example.com"
+                },
+                "commit_error": null
+            },
+            "state": null,
+            "working_tree": {
+                "clean": false,
+                "staged": 1,
+                "unstaged": 1,
+                "untracked": 1,
+                "conflicts": 0
+            }
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn describe_prints_text_details() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["create", "clean"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["describe", "clean"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Branch:     clean"))
+        .stdout(predicate::str::contains("HEAD:"))
+        .stdout(predicate::str::contains("State:      Clean"));
+
+    Ok(())
+}
+
+#[test]
+fn describe_errors_for_missing_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["describe", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "worktree `missing` does not exist",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn describe_json_never_contains_ansi_escapes() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["create", "colored"])
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("FORCE_COLOR", "1")
+        .env("CLICOLOR_FORCE", "1")
+        .args(["describe", "colored", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert!(!output.contains(&0x1b), "stdout contains ANSI escapes");
+    let details: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(details["head"]["name"], "colored");
+
+    Ok(())
+}
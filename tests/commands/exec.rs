@@ -0,0 +1,191 @@
+use std::{error::Error, fs, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run(dir, ["git", "add", "README.md"])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )?;
+    Ok(())
+}
+
+fn run(dir: &Path, cmd: impl IntoIterator<Item = &'static str>) -> Result<(), Box<dyn Error>> {
+    let mut iter = cmd.into_iter();
+    let program = iter.next().expect("command must not be empty");
+    let status = StdCommand::new(program)
+        .current_dir(dir)
+        .args(iter)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("`{program}` exited with status {status}").into());
+    }
+
+    Ok(())
+}
+
+fn create_worktrees(repo: &Path, names: &[&str]) -> Result<(), Box<dyn Error>> {
+    for name in names {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo)
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+    Ok(())
+}
+
+#[test]
+fn exec_command_runs_in_each_worktree_with_headers() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktrees(repo_dir.path(), &["alpha", "feature/beta"])?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["exec", "--", "git", "branch", "--show-current"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("==> alpha\nalpha\n"))
+        .stdout(predicate::str::contains("==> feature/beta\nfeature/beta\n"))
+        .stdout(predicate::str::contains("Worktrees: 2 succeeded."));
+
+    Ok(())
+}
+
+#[test]
+fn exec_command_continues_past_failures_and_summarizes() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktrees(repo_dir.path(), &["alpha", "beta", "gamma"])?;
+    fs::write(repo_dir.path().join(".rsworktree/beta/broken"), "")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["exec", "--", "sh", "-c", "test ! -e broken"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("==> gamma"))
+        .stdout(predicate::str::contains(
+            "Worktrees: 2 succeeded, 1 failed.",
+        ))
+        .stderr(predicate::str::contains(
+            "`sh` exited with status 1 in `beta`.",
+        ))
+        .stderr(predicate::str::contains("`sh` failed in 1 worktree: beta"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["exec", "--fail-fast", "--", "sh", "-c", "test ! -e broken"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("==> gamma").not())
+        .stdout(predicate::str::contains(
+            "Worktrees: 1 succeeded, 1 failed.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn exec_command_max_parallel_groups_output_per_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktrees(repo_dir.path(), &["alpha", "beta", "gamma"])?;
+    fs::write(repo_dir.path().join(".rsworktree/beta/broken"), "")?;
+
+    let script = r#"name=$(basename "$PWD"); echo "start $name"; sleep 0.3; echo "end $name"; test ! -e broken"#;
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["exec", "--max-parallel", "3", "--", "sh", "-c", script])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "==> alpha\nstart alpha\nend alpha\n\n==> beta\nstart beta\nend beta\n==> gamma\nstart gamma\nend gamma\n",
+        ))
+        .stdout(predicate::str::contains(
+            "Worktrees: 2 succeeded, 1 failed.",
+        ))
+        .stderr(predicate::str::contains(
+            "`sh` exited with status 1 in `beta`.",
+        ));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["exec", "--max-parallel", "0", "--", "true"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn exec_command_json_lines_reports_every_worktree_with_exit_code() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktrees(repo_dir.path(), &["alpha", "beta", "nested/gamma"])?;
+    fs::write(repo_dir.path().join(".rsworktree/beta/broken"), "")?;
+
+    for max_parallel in ["1", "3"] {
+        let output = Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("FORCE_COLOR", "1")
+            .args(["exec", "--json-lines", "--max-parallel", max_parallel, "--"])
+            .args([
+                "sh",
+                "-c",
+                "echo noise; test ! -e broken && exit 0 || exit 3",
+            ])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("noise"))
+            .get_output()
+            .stdout
+            .clone();
+
+        let reported = String::from_utf8(output)?
+            .lines()
+            .map(|line| {
+                let event: serde_json::Value = serde_json::from_str(line)?;
+                Ok((
+                    event["worktree"]
+                        .as_str()
+                        .ok_or("missing `worktree` key")?
+                        .to_owned(),
+                    event["status"]
+                        .as_str()
+                        .ok_or("missing `status` key")?
+                        .to_owned(),
+                    event["exit"].as_i64().ok_or("missing `exit` key")?,
+                ))
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        assert_eq!(
+            reported,
+            [
+                ("alpha".to_owned(), "ok".to_owned(), 0),
+                ("beta".to_owned(), "failed".to_owned(), 3),
+                ("nested/gamma".to_owned(), "ok".to_owned(), 0),
+            ],
+            "--max-parallel {max_parallel}"
+        );
+    }
+
+    Ok(())
+}
@@ -105,3 +105,407 @@ fn ls_command_works_from_inside_worktree() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn ls_no_recurse_lists_only_top_level_worktrees() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["flat", "feature/nested"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--no-recurse"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("- flat")
+                .and(predicate::str::contains("feature/nested").not()),
+        );
+
+    Ok(())
+}
+
+#[test]
+fn ls_candidates_lists_branches_without_worktrees() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    run(repo_dir.path(), ["git", "branch", "feature/idle"])?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/active"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--candidates"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Branches without a worktree:")
+                .and(predicate::str::contains("- feature/idle"))
+                .and(predicate::str::contains("feature/active").not()),
+        );
+
+    Ok(())
+}
+
+#[test]
+fn ls_branch_finds_worktree_whose_directory_differs_from_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/old-name", "feature/other"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+
+    let worktree = repo_dir.path().join(".rsworktree").join("feature/old-name");
+    run(&worktree, ["git", "branch", "-m", "feature/new-name"])?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--branch", "feature/new-name"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("on branch `feature/new-name`")
+                .and(predicate::str::contains("- feature/old-name"))
+                .and(predicate::str::contains("feature/other").not()),
+        );
+
+    Ok(())
+}
+
+#[test]
+fn ls_absolute_prints_canonical_worktree_paths() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["alpha", "feature/nested"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--absolute"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    let expected = format!(
+        "{}\n{}\n",
+        worktrees_dir.join("alpha").canonicalize()?.display(),
+        worktrees_dir
+            .join("feature/nested")
+            .canonicalize()?
+            .display()
+    );
+    assert_eq!(String::from_utf8(output)?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn ls_since_base_shows_diff_stat_against_default_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/work", "feature/idle"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+
+    let worktree = repo_dir.path().join(".rsworktree/feature/work");
+    fs::write(worktree.join("notes.txt"), "one\ntwo\n")?;
+    run(&worktree, ["git", "add", "notes.txt"])?;
+    run(
+        &worktree,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Add notes",
+        ],
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--since-base"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- feature/work +2/-0 in 1 file\n"))
+        .stdout(predicate::str::contains(
+            "- feature/idle +0/-0 in 0 files\n",
+        ));
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--json", "--since-base"])
+        .output()?;
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let since_base = |name: &str| {
+        entries
+            .as_array()
+            .and_then(|entries| entries.iter().find(|entry| entry["name"] == name))
+            .map(|entry| entry["since_base"].clone())
+    };
+    assert_eq!(
+        since_base("feature/work"),
+        Some(serde_json::json!({ "files_changed": 1, "insertions": 2, "deletions": 0 }))
+    );
+    assert_eq!(
+        since_base("feature/idle"),
+        Some(serde_json::json!({ "files_changed": 0, "insertions": 0, "deletions": 0 }))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ls_with_pr_omits_pr_info_when_gh_is_missing() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/work"])
+        .assert()
+        .success();
+
+    let empty_path = TempDir::new()?;
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("PATH", empty_path.path())
+        .args(["ls", "--with-pr"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- feature/work\n"))
+        .stderr(predicate::str::contains(
+            "Note: could not list pull requests with `gh`; PR info is omitted.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn ls_json_lists_name_path_branch_and_head() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--json"])
+        .assert()
+        .success()
+        .stdout("[]\n");
+
+    for args in [
+        ["create", "feature/one", "--print-branch"].as_slice(),
+        ["create", "scratch", "--detach"].as_slice(),
+    ] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(args)
+            .assert()
+            .success();
+    }
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--json", "--color", "always"])
+        .output()?;
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+    let head = StdCommand::new("git")
+        .current_dir(repo_dir.path())
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()?;
+    let head = String::from_utf8(head.stdout)?.trim().to_owned();
+    let worktrees_dir = repo_dir.path().join(".rsworktree").canonicalize()?;
+    assert_eq!(
+        entries,
+        serde_json::json!([
+            {
+                "name": "feature/one",
+                "path": worktrees_dir.join("feature/one").display().to_string(),
+                "branch": "feature/one",
+                "head": head,
+            },
+            {
+                "name": "scratch",
+                "path": worktrees_dir.join("scratch").display().to_string(),
+                "branch": null,
+                "head": head,
+            },
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ls_status_shows_branch_and_dirty_marker() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/clean", "feature/dirty"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+    let dirty = repo_dir.path().join(".rsworktree/feature/dirty");
+    fs::write(dirty.join("README.md"), "edited")?;
+    fs::write(dirty.join("notes.txt"), "new")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- feature/clean [feature/clean ✓]\n",
+        ))
+        .stdout(predicate::str::contains(
+            "- feature/dirty [feature/dirty *2]\n",
+        ));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- feature/clean\n"));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn ls_and_cd_work_in_read_only_checkout() -> Result<(), Box<dyn Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/ro"])
+        .assert()
+        .success();
+
+    // Drop the ignore entry so rsworktree wants to write `.gitignore` again, then lock it down.
+    let gitignore = repo_dir.path().join(".gitignore");
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+    fs::write(&gitignore, "target\n")?;
+    fs::set_permissions(&gitignore, fs::Permissions::from_mode(0o444))?;
+    fs::set_permissions(&worktrees_dir, fs::Permissions::from_mode(0o555))?;
+
+    let ls = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .arg("ls")
+        .assert();
+    let cd = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["cd", "feature/ro"])
+        .assert();
+
+    fs::set_permissions(&worktrees_dir, fs::Permissions::from_mode(0o755))?;
+    fs::set_permissions(&gitignore, fs::Permissions::from_mode(0o644))?;
+    ls.success().stdout(predicate::str::contains("feature/ro"));
+    cd.success();
+
+    Ok(())
+}
+
+#[test]
+fn ls_json_object_wraps_worktrees_with_root_and_default_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/one", "--print-branch"])
+        .assert()
+        .success();
+
+    let ls_object = || -> Result<serde_json::Value, Box<dyn Error>> {
+        let output = Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .args(["ls", "--json", "--json-object"])
+            .output()?;
+        assert!(output.status.success());
+        Ok(serde_json::from_slice(&output.stdout)?)
+    };
+
+    let current = StdCommand::new("git")
+        .current_dir(repo_dir.path())
+        .args(["branch", "--show-current"])
+        .output()?;
+    let current = String::from_utf8(current.stdout)?.trim().to_owned();
+    let listing = ls_object()?;
+    assert_eq!(
+        listing["root"],
+        repo_dir.path().display().to_string().as_str()
+    );
+    assert_eq!(listing["default_branch"], current.as_str());
+    assert_eq!(listing["worktrees"][0]["name"], "feature/one");
+
+    // `origin/HEAD` names the default branch once a remote is set up.
+    run(
+        repo_dir.path(),
+        [
+            "git",
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/trunk",
+        ],
+    )?;
+    assert_eq!(ls_object()?["default_branch"], "trunk");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["ls", "--json-object"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
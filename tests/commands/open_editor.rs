@@ -187,6 +187,29 @@ fn open_editor_errors_when_path_does_not_exist() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn open_editor_errors_when_path_is_not_a_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    let plain_dir = TempDir::new()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("EDITOR", "/usr/bin/env true")
+        .args([
+            "worktree",
+            "open-editor",
+            "--path",
+            plain_dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Opened").not())
+        .stderr(predicate::str::contains("is not a git worktree"));
+
+    Ok(())
+}
+
 #[test]
 fn open_editor_matches_partial_name() -> Result<(), Box<dyn Error>> {
     let repo_dir = TempDir::new()?;
@@ -253,3 +276,160 @@ fn open_editor_uses_preferences_file() -> Result<(), Box<dyn Error>> {
     drop(guard_editor);
     Ok(())
 }
+
+#[test]
+fn invalid_editor_preference_only_affects_open_editor() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/prefs")?;
+    fs::write(
+        repo_dir.path().join(".rsworktree/preferences.json"),
+        r#"{"editor": "vim"}"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .arg("ls")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/prefs"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["worktree", "open-editor", "feature/prefs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Editor configuration `"))
+        .stderr(predicate::str::contains("is invalid"));
+
+    Ok(())
+}
+
+#[test]
+fn open_editor_create_opens_existing_worktree_without_recreating() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/existing")?;
+
+    let guard = EnvGuard::set("EDITOR", "/usr/bin/env true");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["worktree", "open-editor", "feature/existing", "--create"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Opened `feature/existing`")
+                .and(predicate::str::contains("Created worktree").not()),
+        );
+
+    drop(guard);
+    Ok(())
+}
+
+#[test]
+fn open_editor_create_creates_missing_worktree_then_opens() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    let guard = EnvGuard::set("EDITOR", "/usr/bin/env true");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["worktree", "open-editor", "feature/fresh", "--create"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Created worktree")
+                .and(predicate::str::contains("Opened `feature/fresh`")),
+        );
+
+    assert!(
+        repo_dir.path().join(".rsworktree/feature/fresh").exists(),
+        "worktree should be created before opening"
+    );
+
+    drop(guard);
+    Ok(())
+}
+
+#[test]
+fn open_editor_appends_json_editor_args_after_preference_args() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_worktree(repo_dir.path(), "feature/args")?;
+
+    let record = repo_dir.path().join("editor-args.txt");
+    let prefs_dir = repo_dir.path().join(".rsworktree");
+    fs::create_dir_all(&prefs_dir)?;
+    fs::write(
+        prefs_dir.join("preferences.json"),
+        serde_json::json!({
+            "editor": {
+                "command": "/bin/sh",
+                "args": [
+                    "-c",
+                    r#"printf '%s\n' "$@" > "$0.tmp" && mv "$0.tmp" "$0""#,
+                    record.display().to_string(),
+                    "--from-preferences"
+                ]
+            }
+        })
+        .to_string(),
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env_remove("EDITOR")
+        .env_remove("VISUAL")
+        .args([
+            "worktree",
+            "open-editor",
+            "feature/args",
+            "--editor-args",
+            r#"["--profile","work","--wait"]"#,
+        ])
+        .assert()
+        .success();
+
+    // The editor is spawned without waiting, so give it a moment to write the record.
+    for _ in 0..100 {
+        if record.exists() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    let recorded = fs::read_to_string(&record)?;
+    let args: Vec<&str> = recorded.lines().collect();
+    assert_eq!(
+        &args[..4],
+        ["--from-preferences", "--profile", "work", "--wait"]
+    );
+    assert!(
+        args[4].ends_with("feature/args"),
+        "path comes last: {args:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn open_editor_rejects_malformed_editor_args() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args([
+            "worktree",
+            "open-editor",
+            "feature/args",
+            "--editor-args",
+            "wait",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected a JSON array of strings"));
+
+    Ok(())
+}
@@ -84,6 +84,7 @@ fn pr_github_invokes_gh_with_expected_arguments() -> Result<(), Box<dyn Error>>
         .assert()
         .success();
 
+    let _remote = publish_branch(repo_dir.path(), "feature/test")?;
     let stub = install_stub_gh()?;
 
     Command::cargo_bin("rsworktree")?
@@ -142,6 +143,7 @@ fn pr_github_defaults_to_current_worktree() -> Result<(), Box<dyn Error>> {
         .join("feature/test")
         .canonicalize()?;
 
+    let _remote = publish_branch(repo_dir.path(), "feature/test")?;
     let stub = install_stub_gh()?;
 
     Command::cargo_bin("rsworktree")?
@@ -193,6 +195,7 @@ fn pr_github_defaults_to_fill_when_metadata_missing() -> Result<(), Box<dyn Erro
         .join("feature/test")
         .canonicalize()?;
 
+    let _remote = publish_branch(repo_dir.path(), "feature/test")?;
     let stub = install_stub_gh()?;
 
     Command::cargo_bin("rsworktree")?
@@ -209,6 +212,26 @@ fn pr_github_defaults_to_fill_when_metadata_missing() -> Result<(), Box<dyn Erro
     Ok(())
 }
 
+/// Pushes `branch` to a bare `origin` so `--no-push` finds it on the remote.
+fn publish_branch(repo: &Path, branch: &str) -> Result<TempDir, Box<dyn Error>> {
+    let remote = TempDir::new()?;
+    let remote_path = remote.path().to_str().ok_or("remote path is not utf-8")?;
+    for args in [
+        vec!["init", "-q", "--bare", remote_path],
+        vec!["remote", "add", "origin", remote_path],
+        vec!["push", "-q", "origin", branch],
+    ] {
+        let status = StdCommand::new("git")
+            .current_dir(repo)
+            .args(&args)
+            .status()?;
+        if !status.success() {
+            return Err(format!("`git {}` exited with status {status}", args.join(" ")).into());
+        }
+    }
+    Ok(remote)
+}
+
 fn install_stub_gh() -> Result<StubGh, Box<dyn Error>> {
     let stub_dir = TempDir::new()?;
     let gh_log = stub_dir.path().join("gh.log");
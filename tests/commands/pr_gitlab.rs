@@ -0,0 +1,147 @@
+#![cfg(unix)]
+
+use std::{
+    env,
+    error::Error,
+    ffi::OsString,
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::Command as StdCommand,
+};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+struct StubGlab {
+    _dir: TempDir,
+    path_value: OsString,
+    log_path: PathBuf,
+}
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run_git(dir, &["init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run_git(dir, &["add", "README.md"])?;
+    run_git(
+        dir,
+        &[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<(), Box<dyn Error>> {
+    let status = StdCommand::new("git")
+        .current_dir(dir)
+        .args(args)
+        .status()?;
+    if !status.success() {
+        return Err(format!("`git {}` exited with status {status}", args.join(" ")).into());
+    }
+    Ok(())
+}
+
+fn install_stub_glab() -> Result<StubGlab, Box<dyn Error>> {
+    let stub_dir = TempDir::new()?;
+    let log_path = stub_dir.path().join("glab.log");
+    let glab_path = stub_dir.path().join("glab");
+    fs::write(
+        &glab_path,
+        "#! /bin/sh\n\nprintf 'args:%s\n' \"$*\" > \"$GLAB_LOG\"\nprintf '%s\n' 'Creating merge request for feature into main' '' 'https://gitlab.example.com/acme/app/-/merge_requests/7'\n",
+    )?;
+    let mut perms = fs::metadata(&glab_path)?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&glab_path, perms)?;
+
+    let mut path_value = OsString::from(stub_dir.path());
+    if let Some(existing) = env::var_os("PATH") {
+        path_value.push(":");
+        path_value.push(existing);
+    }
+
+    Ok(StubGlab {
+        _dir: stub_dir,
+        path_value,
+        log_path,
+    })
+}
+
+#[test]
+fn pr_gitlab_pushes_and_invokes_glab() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    let remote = TempDir::new()?;
+    let remote_path = remote.path().to_str().ok_or("remote path is not utf-8")?;
+    run_git(remote.path(), &["init", "-q", "--bare"])?;
+    run_git(repo_dir.path(), &["remote", "add", "origin", remote_path])?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature"])
+        .assert()
+        .success();
+
+    let stub = install_stub_glab()?;
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("PATH", &stub.path_value)
+        .env("GLAB_LOG", &stub.log_path)
+        .args(["pr-gitlab", "feature", "--draft", "--reviewer", "alice"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("GitLab merge request created for `feature`.").and(
+                predicate::str::contains(
+                    "\nhttps://gitlab.example.com/acme/app/-/merge_requests/7",
+                ),
+            ),
+        );
+
+    let log_contents = fs::read_to_string(&stub.log_path)?;
+    assert!(
+        log_contents.contains(
+            "args:mr create --source-branch feature --draft --fill --yes --reviewer alice"
+        )
+    );
+
+    let status = StdCommand::new("git")
+        .current_dir(remote.path())
+        .args(["rev-parse", "--verify", "refs/heads/feature"])
+        .status()?;
+    assert!(status.success(), "branch should have been pushed");
+
+    Ok(())
+}
+
+#[test]
+fn pr_gitlab_dry_run_prints_commands() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["pr-gitlab", "feature", "--web", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git push -u origin feature").and(
+            predicate::str::contains("glab mr create --source-branch feature --web"),
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,381 @@
+use std::{error::Error, fs, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run(dir, ["git", "add", "README.md"])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )?;
+    Ok(())
+}
+
+fn run(dir: &Path, cmd: impl IntoIterator<Item = &'static str>) -> Result<(), Box<dyn Error>> {
+    let mut iter = cmd.into_iter();
+    let program = iter.next().expect("command must not be empty");
+    let status = StdCommand::new(program)
+        .current_dir(dir)
+        .args(iter)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("`{program}` exited with status {status}").into());
+    }
+
+    Ok(())
+}
+
+fn branch_exists(repo: &Path, branch: &str) -> Result<bool, Box<dyn Error>> {
+    let status = StdCommand::new("git")
+        .current_dir(repo)
+        .args([
+            "show-ref",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ])
+        .status()?;
+    Ok(status.success())
+}
+
+/// Creates `branch` with an empty commit dated 2001 on top of the current HEAD.
+fn create_old_branch(repo: &Path, branch: &str) -> Result<(), Box<dyn Error>> {
+    let date = "2001-01-01T00:00:00Z";
+    let tree = StdCommand::new("git")
+        .current_dir(repo)
+        .args(["rev-parse", "HEAD^{tree}"])
+        .output()?;
+    let tree = String::from_utf8(tree.stdout)?;
+    let commit = StdCommand::new("git")
+        .current_dir(repo)
+        .env("GIT_AUTHOR_NAME", "Test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .args(["commit-tree", tree.trim(), "-p", "HEAD", "-m", "Old work"])
+        .output()?;
+    assert!(commit.status.success(), "commit-tree failed");
+    let commit = String::from_utf8(commit.stdout)?;
+
+    let status = StdCommand::new("git")
+        .current_dir(repo)
+        .args(["branch", branch, commit.trim()])
+        .status()?;
+    assert!(status.success(), "failed to create branch `{branch}`");
+    Ok(())
+}
+
+#[test]
+fn prune_command_removes_worktrees_with_missing_directories() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/deleted"])
+        .assert()
+        .success();
+    fs::remove_dir_all(repo_dir.path().join(".rsworktree").join("feature/deleted"))?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Pruned worktree `feature/deleted`",
+        ))
+        .stdout(predicate::str::contains("Worktrees: 1 pruned."));
+
+    let list_output = StdCommand::new("git")
+        .current_dir(repo_dir.path())
+        .args(["worktree", "list", "--porcelain"])
+        .output()?;
+    assert!(!String::from_utf8_lossy(&list_output.stdout).contains("feature/deleted"));
+    assert!(
+        branch_exists(repo_dir.path(), "feature/deleted")?,
+        "plain prune should not delete branches"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn prune_command_expire_deletes_only_stale_branches() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_old_branch(repo_dir.path(), "feature/stale")?;
+    run(repo_dir.path(), ["git", "branch", "feature/recent"])?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--expire", "30d", "--include-unmerged"])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  feature/stale"))
+        .stdout(predicate::str::contains("feature/recent").not())
+        .stdout(predicate::str::contains("Kept 1 local branch(es)."));
+    assert!(branch_exists(repo_dir.path(), "feature/stale")?);
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--expire", "30d", "--include-unmerged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deleted local branch `feature/stale`.",
+        ))
+        .stdout(predicate::str::contains("Branches: 1 deleted."));
+
+    assert!(!branch_exists(repo_dir.path(), "feature/stale")?);
+    assert!(branch_exists(repo_dir.path(), "feature/recent")?);
+
+    Ok(())
+}
+
+#[test]
+fn prune_command_expire_keeps_default_and_unmerged_branches() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    run(repo_dir.path(), ["git", "branch", "-M", "main"])?;
+    create_old_branch(repo_dir.path(), "feature/merged")?;
+    run(repo_dir.path(), ["git", "switch", "-c", "dev"])?;
+    run(
+        repo_dir.path(),
+        ["git", "merge", "--ff-only", "feature/merged"],
+    )?;
+    create_old_branch(repo_dir.path(), "feature/unmerged")?;
+    // `main` is now older than the cutoff too, and nothing points `origin/HEAD` at it.
+    run(
+        repo_dir.path(),
+        ["git", "branch", "-f", "main", "feature/merged"],
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--expire", "30d", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deleted local branch `feature/merged`.",
+        ))
+        .stdout(predicate::str::contains("Branches: 1 deleted."));
+
+    assert!(branch_exists(repo_dir.path(), "main")?);
+    assert!(branch_exists(repo_dir.path(), "dev")?);
+    assert!(branch_exists(repo_dir.path(), "feature/unmerged")?);
+    assert!(!branch_exists(repo_dir.path(), "feature/merged")?);
+
+    Ok(())
+}
+
+#[test]
+fn prune_command_expire_keeps_stale_branches_with_a_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_old_branch(repo_dir.path(), "feature/stale")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/stale"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--expire", "30d", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No local branches"));
+
+    assert!(branch_exists(repo_dir.path(), "feature/stale")?);
+
+    Ok(())
+}
+
+#[test]
+fn prune_command_json_lines_reports_each_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/one", "feature/two"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+        fs::remove_dir_all(repo_dir.path().join(".rsworktree").join(name))?;
+    }
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("FORCE_COLOR", "1")
+        .args(["prune", "--json-lines"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let mut reported = String::from_utf8(output)?
+        .lines()
+        .map(|line| {
+            let event: serde_json::Value = serde_json::from_str(line)?;
+            assert_eq!(event["status"], "ok");
+            Ok(event["worktree"]
+                .as_str()
+                .ok_or("missing `worktree` key")?
+                .to_owned())
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    reported.sort();
+    assert_eq!(reported, ["feature/one", "feature/two"]);
+
+    Ok(())
+}
+
+#[test]
+fn prune_command_json_lines_requires_yes_with_expire() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--expire", "30d", "--json-lines"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass `--yes`"));
+
+    Ok(())
+}
+
+fn commit_file(dir: &Path, file: &str) -> Result<(), Box<dyn Error>> {
+    fs::write(dir.join(file), file)?;
+    let status = StdCommand::new("git")
+        .current_dir(dir)
+        .args(["add", file])
+        .status()?;
+    assert!(status.success());
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Work",
+        ],
+    )
+}
+
+/// Creates `merged` (fully merged into HEAD), `dirty` (merged but with local changes) and
+/// `unmerged` (one commit ahead) worktrees.
+fn setup_merge_candidates(repo: &Path) -> Result<(), Box<dyn Error>> {
+    for name in ["merged", "dirty", "unmerged"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo)
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+    let worktrees = repo.join(".rsworktree");
+    fs::write(worktrees.join("dirty").join("notes.txt"), "draft")?;
+    commit_file(&worktrees.join("unmerged"), "feature.txt")
+}
+
+#[test]
+fn prune_command_merged_dry_run_only_reports() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    setup_merge_candidates(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--merged", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would remove worktree `merged`; branch `merged` is merged.",
+        ))
+        .stdout(predicate::str::contains(
+            "Skipped worktree `dirty`; it has uncommitted changes.",
+        ))
+        .stdout(predicate::str::contains("unmerged").not())
+        .stdout(predicate::str::contains(
+            "Merged worktrees: 1 would be removed, 1 skipped.",
+        ));
+
+    assert!(repo_dir.path().join(".rsworktree/merged").exists());
+
+    Ok(())
+}
+
+#[test]
+fn prune_command_merged_removes_clean_merged_worktrees() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    setup_merge_candidates(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--merged"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed worktree `merged`; branch `merged` is merged.",
+        ))
+        .stdout(predicate::str::contains(
+            "Merged worktrees: 1 removed, 1 skipped.",
+        ));
+
+    let worktrees = repo_dir.path().join(".rsworktree");
+    assert!(!worktrees.join("merged").exists());
+    assert!(worktrees.join("dirty").exists());
+    assert!(worktrees.join("unmerged").exists());
+
+    Ok(())
+}
+
+#[test]
+fn prune_command_merged_checks_against_into_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    setup_merge_candidates(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["prune", "--merged", "--into", "unmerged", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would remove worktree `merged`"))
+        .stdout(predicate::str::contains("Would remove worktree `unmerged`"))
+        .stdout(predicate::str::contains(
+            "Merged worktrees: 2 would be removed, 1 skipped.",
+        ));
+
+    Ok(())
+}
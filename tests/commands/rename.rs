@@ -0,0 +1,108 @@
+use std::{error::Error, fs, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run(dir, ["git", "add", "README.md"])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )?;
+    Ok(())
+}
+
+fn run(dir: &Path, cmd: impl IntoIterator<Item = &'static str>) -> Result<(), Box<dyn Error>> {
+    let mut iter = cmd.into_iter();
+    let program = iter.next().expect("command must not be empty");
+    let status = StdCommand::new(program)
+        .current_dir(dir)
+        .args(iter)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("`{program}` exited with status {status}").into());
+    }
+
+    Ok(())
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = StdCommand::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()?;
+    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+}
+
+#[test]
+fn rename_moves_worktree_and_branch() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["create", "feature/typo"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rename", "feature/typo", "feature/fixed"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Renamed worktree `feature/typo` to `feature/fixed`").and(
+                predicate::str::contains("Renamed branch `feature/typo` to `feature/fixed`."),
+            ),
+        );
+
+    let worktree = repo_dir.path().join(".rsworktree/feature/fixed");
+    assert!(!repo_dir.path().join(".rsworktree/feature/typo").exists());
+    assert_eq!(
+        git_output(&worktree, &["rev-parse", "--abbrev-ref", "HEAD"])?,
+        "feature/fixed"
+    );
+    assert!(
+        git_output(repo_dir.path(), &["worktree", "list"])?.contains(".rsworktree/feature/fixed")
+    );
+    Ok(())
+}
+
+#[test]
+fn rename_fails_when_target_exists() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["one", "two"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rename", "one", "two"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cannot rename worktree `one` to `two`",
+        ));
+
+    assert!(repo_dir.path().join(".rsworktree/one").exists());
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use std::{error::Error, fs, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run(dir, ["git", "add", "README.md"])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )?;
+    Ok(())
+}
+
+fn run(dir: &Path, cmd: impl IntoIterator<Item = &'static str>) -> Result<(), Box<dyn Error>> {
+    let mut iter = cmd.into_iter();
+    let program = iter.next().expect("command must not be empty");
+    let status = StdCommand::new(program)
+        .current_dir(dir)
+        .args(iter)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("`{program}` exited with status {status}").into());
+    }
+
+    Ok(())
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = StdCommand::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()?;
+    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+}
+
+#[test]
+fn rename_branch_keeps_worktree_path_and_upstream() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["create", "feature"])
+        .assert()
+        .success();
+
+    let worktree = repo_dir.path().join(".rsworktree/feature");
+    run(
+        repo_dir.path(),
+        ["git", "config", "branch.feature.remote", "origin"],
+    )?;
+    run(
+        repo_dir.path(),
+        [
+            "git",
+            "config",
+            "branch.feature.merge",
+            "refs/heads/feature",
+        ],
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rename-branch", "feature", "feature-renamed"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed branch `feature` to `feature-renamed` in worktree `feature`.",
+        ));
+
+    assert!(worktree.is_dir(), "worktree directory should stay in place");
+    assert_eq!(
+        git_output(&worktree, &["rev-parse", "--abbrev-ref", "HEAD"])?,
+        "feature-renamed"
+    );
+    assert_eq!(
+        git_output(&worktree, &["config", "branch.feature-renamed.remote"])?,
+        "origin"
+    );
+    assert!(git_output(repo_dir.path(), &["branch", "--list", "feature"])?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn rename_branch_refuses_existing_branch_name() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    run(repo_dir.path(), ["git", "branch", "taken"])?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["create", "feature"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rename-branch", "feature", "taken"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "a local branch named `taken` already exists",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::{error::Error, fs, path::Path, process::Command as StdCommand};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_git_repo(dir: &Path) -> Result<(), Box<dyn Error>> {
+    run(dir, ["git", "init"])?;
+    fs::write(dir.join("README.md"), "test")?;
+    run(dir, ["git", "add", "README.md"])?;
+    run(
+        dir,
+        [
+            "git",
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "Initial commit",
+        ],
+    )?;
+    Ok(())
+}
+
+fn run(dir: &Path, cmd: impl IntoIterator<Item = &'static str>) -> Result<(), Box<dyn Error>> {
+    let mut iter = cmd.into_iter();
+    let program = iter.next().expect("command must not be empty");
+    let status = StdCommand::new(program)
+        .current_dir(dir)
+        .args(iter)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("`{program}` exited with status {status}").into());
+    }
+
+    Ok(())
+}
+
+fn worktree_list(repo: &Path) -> Result<String, Box<dyn Error>> {
+    let output = StdCommand::new("git")
+        .current_dir(repo)
+        .args(["worktree", "list", "--porcelain"])
+        .output()?;
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[test]
+fn repair_command_fixes_links_of_moved_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/old"])
+        .assert()
+        .success();
+
+    let worktrees = repo_dir.path().join(".rsworktree").join("feature");
+    fs::rename(worktrees.join("old"), worktrees.join("new"))?;
+    assert!(!worktree_list(repo_dir.path())?.contains("feature/new"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .arg("repair")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(gitdir incorrect)"))
+        .stdout(predicate::str::contains("Repaired 1 worktree link."));
+
+    assert!(worktree_list(repo_dir.path())?.contains("feature/new"));
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .arg("repair")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No broken worktree links found."));
+
+    Ok(())
+}
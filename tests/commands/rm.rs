@@ -111,14 +111,12 @@ fn rm_command_spawns_root_shell_when_called_inside_worktree() -> Result<(), Box<
         .path()
         .join(".rsworktree")
         .join("feature/move-back");
-    let repo_root = repo_dir.path().canonicalize()?;
-
     let repo_root = repo_dir.path();
 
     Command::cargo_bin("rsworktree")?
         .current_dir(&worktree_path)
         .env("RSWORKTREE_SHELL", "env")
-        .args(["rm", "feature/move-back"])
+        .args(["rm", "feature/move-back", "--force"])
         .assert()
         .success()
         .stdout(predicate::str::contains(format!(
@@ -129,6 +127,35 @@ fn rm_command_spawns_root_shell_when_called_inside_worktree() -> Result<(), Box<
     Ok(())
 }
 
+#[test]
+fn rm_command_refuses_current_worktree_without_confirmation() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/busy"])
+        .assert()
+        .success();
+
+    let worktree_path = repo_dir.path().join(".rsworktree").join("feature/busy");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(&worktree_path)
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["rm", "feature/busy"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "refusing to remove worktree `feature/busy` because the current directory is inside it",
+        ));
+
+    assert!(worktree_path.exists(), "worktree should be kept");
+
+    Ok(())
+}
+
 #[test]
 fn rm_command_refuses_locked_worktree_without_force() -> Result<(), Box<dyn Error>> {
     let repo_dir = TempDir::new()?;
@@ -196,3 +223,514 @@ fn rm_command_force_removes_locked_worktree() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+fn setup_gone_branch(repo: &Path, remote: &Path, branch: &str) -> Result<(), Box<dyn Error>> {
+    let remote_path = remote.to_str().ok_or("remote path is not utf-8")?;
+    for args in [
+        vec!["init", "-q", "--bare", remote_path],
+        vec!["remote", "add", "origin", remote_path],
+        vec!["branch", branch],
+        vec!["push", "-q", "-u", "origin", branch],
+        vec!["push", "-q", "origin", "--delete", branch],
+        vec!["fetch", "-q", "--prune", "origin"],
+    ] {
+        let status = StdCommand::new("git")
+            .current_dir(repo)
+            .args(&args)
+            .status()?;
+        if !status.success() {
+            return Err(format!("`git {}` exited with status {status}", args.join(" ")).into());
+        }
+    }
+    Ok(())
+}
+
+fn branch_exists(repo: &Path, branch: &str) -> Result<bool, Box<dyn Error>> {
+    let status = StdCommand::new("git")
+        .current_dir(repo)
+        .args([
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ])
+        .status()?;
+    Ok(status.success())
+}
+
+#[test]
+fn rm_command_prunes_gone_branches_with_yes() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    let remote_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    setup_gone_branch(repo_dir.path(), remote_dir.path(), "feature/stale")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/done"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "feature/done", "--prune-branches", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deleted local branch `feature/stale`.",
+        ));
+
+    assert!(!branch_exists(repo_dir.path(), "feature/stale")?);
+    assert!(
+        branch_exists(repo_dir.path(), "feature/done")?,
+        "branch without an upstream should be kept"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_keeps_gone_branches_without_a_terminal() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    let remote_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    setup_gone_branch(repo_dir.path(), remote_dir.path(), "feature/stale")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/done"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "feature/done", "--prune-branches"])
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Kept 1 local branch(es) whose upstream is gone; pass `--yes`",
+        ));
+
+    assert!(branch_exists(repo_dir.path(), "feature/stale")?);
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_wip_commits_changes_before_removal() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/wip"])
+        .assert()
+        .success();
+
+    let worktree_path = repo_dir.path().join(".rsworktree").join("feature/wip");
+    fs::write(worktree_path.join("README.md"), "edited")?;
+    fs::write(worktree_path.join("notes.txt"), "untracked")?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("GIT_AUTHOR_NAME", "Test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .args(["rm", "feature/wip", "--wip"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Committed work in progress to branch `feature/wip`.",
+        ))
+        .stdout(predicate::str::contains("Removed worktree"));
+
+    assert!(!worktree_path.exists(), "worktree directory should be gone");
+
+    let show = |spec: &str| -> Result<String, Box<dyn Error>> {
+        let output = StdCommand::new("git")
+            .current_dir(repo_dir.path())
+            .args(["show", spec])
+            .output()?;
+        assert!(output.status.success(), "`git show {spec}` failed");
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    assert_eq!(show("feature/wip:README.md")?, "edited");
+    assert_eq!(show("feature/wip:notes.txt")?, "untracked");
+
+    Ok(())
+}
+
+fn write_remove_hooks(repo: &Path, hooks: &str) -> Result<(), Box<dyn Error>> {
+    let dir = repo.join(".rsworktree");
+    fs::create_dir_all(&dir)?;
+    fs::write(
+        dir.join("preferences.json"),
+        format!(r#"{{ "remove": {hooks} }}"#),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn rm_command_runs_remove_hooks_around_removal() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/hooks"])
+        .assert()
+        .success();
+
+    write_remove_hooks(
+        repo_dir.path(),
+        r#"{ "pre_remove": "pwd > ../../pre.txt", "post_remove": "test ! -d .rsworktree/feature/hooks && touch post.txt" }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "feature/hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree"));
+
+    let worktree_path = repo_dir.path().join(".rsworktree").join("feature/hooks");
+    assert!(!worktree_path.exists(), "worktree directory should be gone");
+    let pre = fs::read_to_string(repo_dir.path().join(".rsworktree/pre.txt"))?;
+    assert!(
+        pre.trim_end().ends_with(".rsworktree/feature/hooks"),
+        "pre-remove hook should run inside the worktree, ran in {pre}"
+    );
+    assert!(repo_dir.path().join("post.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_aborts_when_pre_remove_hook_fails() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/keep"])
+        .assert()
+        .success();
+
+    write_remove_hooks(
+        repo_dir.path(),
+        r#"{ "pre_remove": "exit 4", "post_remove": "touch post.txt" }"#,
+    )?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "feature/keep"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "worktree `feature/keep` was not removed",
+        ))
+        .stderr(predicate::str::contains(
+            "pre-remove hook `exit 4` failed with exit status 4",
+        ));
+
+    assert!(
+        repo_dir
+            .path()
+            .join(".rsworktree")
+            .join("feature/keep")
+            .exists()
+    );
+    assert!(!repo_dir.path().join("post.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_warns_when_post_remove_hook_fails() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["create", "feature/warn"])
+        .assert()
+        .success();
+
+    write_remove_hooks(repo_dir.path(), r#"{ "post_remove": "exit 2" }"#)?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "feature/warn"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree"))
+        .stderr(predicate::str::contains(
+            "Warning: post-remove hook `exit 2` failed with exit status 2",
+        ));
+
+    assert!(
+        !repo_dir
+            .path()
+            .join(".rsworktree")
+            .join("feature/warn")
+            .exists()
+    );
+
+    Ok(())
+}
+
+/// Creates worktrees whose last commits are dated 2001, 2011 and 2021 respectively.
+fn create_dated_worktrees(repo: &Path) -> Result<(), Box<dyn Error>> {
+    for (name, year) in [("middle", 2011), ("oldest", 2001), ("newest", 2021)] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo)
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+        let date = format!("{year}-01-01T00:00:00Z");
+        let status = StdCommand::new("git")
+            .current_dir(repo.join(".rsworktree").join(name))
+            .env("GIT_COMMITTER_DATE", &date)
+            .env("GIT_AUTHOR_DATE", &date)
+            .args([
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--allow-empty",
+                "-m",
+                name,
+            ])
+            .status()?;
+        assert!(status.success());
+    }
+    Ok(())
+}
+
+#[test]
+fn rm_command_oldest_removes_worktree_with_oldest_commit() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_dated_worktrees(repo_dir.path())?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "--oldest", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree"));
+
+    let worktrees = repo_dir.path().join(".rsworktree");
+    assert!(!worktrees.join("oldest").exists());
+    assert!(worktrees.join("middle").exists());
+    assert!(worktrees.join("newest").exists());
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_newest_asks_before_removing() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+    create_dated_worktrees(repo_dir.path())?;
+    let newest = repo_dir.path().join(".rsworktree").join("newest");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "--newest"])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Remove worktree `newest`, the one with the newest last commit? [y/N]",
+        ))
+        .stdout(predicate::str::contains("Kept worktree `newest`."));
+    assert!(newest.exists());
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "--newest"])
+        .write_stdin("y\n")
+        .assert()
+        .success();
+    assert!(!newest.exists());
+    assert!(repo_dir.path().join(".rsworktree/middle").exists());
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_falls_back_to_partial_name_match() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/some-long-name", "bugfix/long-lines"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "long"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "worktree identifier `long` is ambiguous. Matches: bugfix/long-lines, feature/some-long-name",
+        ));
+    assert!(worktrees_dir.join("feature/some-long-name").exists());
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "some-long-name"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(
+                "Matched `some-long-name` to worktree `feature/some-long-name`.",
+            )
+            .and(predicate::str::contains("Removed worktree")),
+        );
+    assert!(!worktrees_dir.join("feature/some-long-name").exists());
+    assert!(worktrees_dir.join("bugfix/long-lines").exists());
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_removes_several_named_worktrees_with_summary() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/one", "feature/two", "keep"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .args(["rm", "feature/one", "missing", "feature/two"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Worktrees: 2 removed, 1 skipped."));
+
+    assert!(!worktrees_dir.join("feature/one").exists());
+    assert!(!worktrees_dir.join("feature/two").exists());
+    assert!(worktrees_dir.join("keep").exists());
+
+    Ok(())
+}
+
+#[test]
+fn rm_command_json_lines_reports_every_named_worktree() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/one", "feature/two"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(repo_dir.path())
+        .env("FORCE_COLOR", "1")
+        .args([
+            "rm",
+            "--json-lines",
+            "feature/one",
+            "missing",
+            "feature/two",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let reported = String::from_utf8(output)?
+        .lines()
+        .map(|line| {
+            let event: serde_json::Value = serde_json::from_str(line)?;
+            Ok(format!(
+                "{} {}",
+                event["worktree"].as_str().ok_or("missing `worktree` key")?,
+                event["status"].as_str().ok_or("missing `status` key")?
+            ))
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    assert_eq!(
+        reported,
+        ["feature/one ok", "missing skipped", "feature/two ok"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rm_all_removes_every_worktree_and_spawns_one_root_shell() -> Result<(), Box<dyn Error>> {
+    let repo_dir = TempDir::new()?;
+    init_git_repo(repo_dir.path())?;
+
+    for name in ["feature/one", "feature/two", "three"] {
+        Command::cargo_bin("rsworktree")?
+            .current_dir(repo_dir.path())
+            .env("RSWORKTREE_SHELL", "env")
+            .args(["create", name])
+            .assert()
+            .success();
+    }
+    let worktrees_dir = repo_dir.path().join(".rsworktree");
+
+    let output = Command::cargo_bin("rsworktree")?
+        .current_dir(worktrees_dir.join("feature/two"))
+        .env("RSWORKTREE_SHELL", "env")
+        .args(["rm", "--all", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Worktrees: 3 removed."))
+        .get_output()
+        .stdout
+        .clone();
+
+    let stdout = String::from_utf8(output)?;
+    let root_shell = format!("PWD={}\n", repo_dir.path().display());
+    assert_eq!(stdout.matches(&root_shell).count(), 1);
+    assert!(find_worktrees_left(&worktrees_dir)?.is_empty());
+
+    Ok(())
+}
+
+fn find_worktrees_left(worktrees_dir: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let output = StdCommand::new("git")
+        .current_dir(worktrees_dir.parent().expect("worktrees dir has a parent"))
+        .args(["worktree", "list", "--porcelain"])
+        .output()?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .filter(|line| line.contains(".rsworktree/"))
+        .map(str::to_owned)
+        .collect())
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn version_verbose_reports_libgit2_outside_a_repository() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+
+    Command::cargo_bin("rsworktree")?
+        .current_dir(dir.path())
+        .args(["version", "--verbose"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with(format!("rsworktree {}\n", env!("CARGO_PKG_VERSION")))
+                .and(predicate::str::is_match(r"(?m)^libgit2: \d+\.\d+\.\d+")?)
+                .and(predicate::str::contains("\ngit2:    "))
+                .and(predicate::str::contains("\ngh:      ")),
+        );
+
+    Ok(())
+}
+
+#[test]
+fn version_without_verbose_prints_only_crate_version() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("rsworktree")?
+        .arg("version")
+        .assert()
+        .success()
+        .stdout(format!("rsworktree {}\n", env!("CARGO_PKG_VERSION")));
+
+    Ok(())
+}